@@ -8,7 +8,7 @@ use syn::{
     braced,
     parse::{Parse, ParseStream},
     spanned::Spanned,
-    Field, FieldValue, Token, Type,
+    Attribute, Field, FieldValue, Token, Type,
 };
 
 pub(crate) mod attribute;
@@ -96,7 +96,31 @@ impl ToTokens for Api {
         let name = &self.metadata.name.value();
         let path = &self.metadata.path;
         let rate_limited = &self.metadata.rate_limited;
-        let requires_authentication = &self.metadata.requires_authentication;
+        let authentication = &self.metadata.authentication;
+        let requires_authentication = *authentication != "None";
+
+        let history = {
+            let version = |version: &Option<_>| match version {
+                Some(version) => quote! { Some(#ruma_api_import::MatrixVersion::#version) },
+                None => quote! { None },
+            };
+
+            let paths = self.metadata.paths.iter().map(|(version, path)| {
+                quote! { (#ruma_api_import::MatrixVersion::#version, #path) }
+            });
+            let added = version(&self.metadata.added);
+            let deprecated = version(&self.metadata.deprecated);
+            let removed = version(&self.metadata.removed);
+
+            quote! {
+                #ruma_api_import::VersionHistory {
+                    paths: &[ #( #paths ),* ],
+                    added: #added,
+                    deprecated: #deprecated,
+                    removed: #removed,
+                }
+            }
+        };
 
         let request_type = &self.request;
         let response_type = &self.response;
@@ -121,7 +145,10 @@ impl ToTokens for Api {
 
         let request_query_string = util::build_query_string(&self.request, &ruma_api_import);
 
-        let extract_request_query = util::extract_request_query(&self.request, &ruma_api_import);
+        let query_access_token = *authentication == "QueryOnlyAccessToken";
+
+        let extract_request_query =
+            util::extract_request_query(&self.request, query_access_token, &ruma_api_import);
 
         let parse_request_query = if let Some(field) = self.request.query_map_field() {
             let field_name = field.ident.as_ref().expect("expected field to have an identifier");
@@ -134,20 +161,37 @@ impl ToTokens for Api {
         };
 
         let mut header_kvs = self.request.append_header_kvs();
-        if requires_authentication.value {
+        if *authentication == "AccessToken" {
             header_kvs.push(quote! {
-                #ruma_api_import::exports::http::header::AUTHORIZATION,
-                #ruma_api_import::exports::http::header::HeaderValue::from_str(
-                    &::std::format!(
-                        "Bearer {}",
-                        access_token.ok_or(
-                            #ruma_api_import::error::IntoHttpError::NeedsAuthentication
-                        )?
-                    )
-                )?
+                req_headers.insert(
+                    #ruma_api_import::exports::http::header::AUTHORIZATION,
+                    #ruma_api_import::exports::http::header::HeaderValue::from_str(
+                        &::std::format!(
+                            "Bearer {}",
+                            access_token.ok_or(
+                                #ruma_api_import::error::IntoHttpError::NeedsAuthentication
+                            )?
+                        )
+                    )?
+                );
             });
         }
 
+        let append_access_token_query = if query_access_token {
+            quote! {
+                if !request_url.ends_with('?') {
+                    request_url.push(if request_url.contains('?') { '&' } else { '?' });
+                }
+                request_url.push_str("access_token=");
+                request_url.extend(#ruma_api_import::exports::percent_encoding::utf8_percent_encode(
+                    access_token.ok_or(#ruma_api_import::error::IntoHttpError::NeedsAuthentication)?,
+                    #ruma_api_import::exports::percent_encoding::NON_ALPHANUMERIC,
+                ));
+            }
+        } else {
+            TokenStream::new()
+        };
+
         let extract_request_headers = if self.request.has_header_fields() {
             quote! {
                 let headers = request.headers();
@@ -171,7 +215,7 @@ impl ToTokens for Api {
                 let request_body: <RequestBody #body_lifetimes as #ruma_api_import::Outgoing>::Incoming =
                     #ruma_api_import::try_deserialize!(
                         request,
-                        #ruma_api_import::exports::serde_json::from_slice(request.body().as_slice())
+                        #ruma_api_import::exports::serde_json::from_slice(request.body().as_ref())
                     );
             }
         } else {
@@ -203,7 +247,7 @@ impl ToTokens for Api {
                 let response_body: <ResponseBody as #ruma_api_import::Outgoing>::Incoming =
                     #ruma_api_import::try_deserialize!(
                         response,
-                        #ruma_api_import::exports::serde_json::from_slice(response.body().as_slice()),
+                        #ruma_api_import::exports::serde_json::from_slice(response.body().as_ref()),
                     );
             }
         } else {
@@ -214,6 +258,20 @@ impl ToTokens for Api {
 
         let serialize_response_headers = self.response.apply_header_fields();
 
+        // Raw bodies are sent as-is, their content type has to be set through a header field.
+        let set_response_content_type = if self.response.newtype_raw_body_field().is_some() {
+            TokenStream::new()
+        } else {
+            quote! {
+                resp_headers.insert(
+                    #ruma_api_import::exports::http::header::CONTENT_TYPE,
+                    #ruma_api_import::exports::http::header::HeaderValue::from_static(
+                        "application/json",
+                    ),
+                );
+            }
+        };
+
         let body = self.response.to_body();
 
         let request_doc = format!(
@@ -227,7 +285,7 @@ impl ToTokens for Api {
 
         let request_lifetimes = self.request.combine_lifetimes();
 
-        let non_auth_endpoint_impls = if requires_authentication.value {
+        let non_auth_endpoint_impls = if requires_authentication {
             TokenStream::new()
         } else {
             quote! {
@@ -243,55 +301,46 @@ impl ToTokens for Api {
             #[doc = #request_doc]
             #request_type
 
-            impl ::std::convert::TryFrom<#ruma_api_import::exports::http::Request<Vec<u8>>>
-                for #incoming_request_type
-            {
-                type Error = #ruma_api_import::error::FromHttpRequestError;
-
-                #[allow(unused_variables)]
-                fn try_from(
-                    request: #ruma_api_import::exports::http::Request<Vec<u8>>
-                ) -> ::std::result::Result<Self, Self::Error> {
-                    #extract_request_path
-                    #extract_request_query
-                    #extract_request_headers
-                    #extract_request_body
-
-                    Ok(Self {
-                        #parse_request_path
-                        #parse_request_query
-                        #parse_request_headers
-                        #parse_request_body
-                    })
-                }
-            }
-
             #[doc = #response_doc]
             #response_type
 
-            impl ::std::convert::TryFrom<Response> for #ruma_api_import::exports::http::Response<Vec<u8>> {
-                type Error = #ruma_api_import::error::IntoHttpError;
-
+            impl #ruma_api_import::OutgoingResponse for Response {
                 #[allow(unused_variables)]
-                fn try_from(response: Response) -> ::std::result::Result<Self, Self::Error> {
-                    let response = #ruma_api_import::exports::http::Response::builder()
-                        .header(#ruma_api_import::exports::http::header::CONTENT_TYPE, "application/json")
+                fn try_into_http_response<T>(
+                    self,
+                ) -> ::std::result::Result<
+                    #ruma_api_import::exports::http::Response<T>,
+                    #ruma_api_import::error::IntoHttpError,
+                >
+                where
+                    T: ::std::default::Default + #ruma_api_import::exports::bytes::BufMut,
+                {
+                    let response = self;
+                    let mut resp_builder = #ruma_api_import::exports::http::Response::builder();
+
+                    // Since we require header names to come from the `http::header` module and
+                    // nothing else was set on the builder yet, this is always `Some`.
+                    if let Some(resp_headers) = resp_builder.headers_mut() {
+                        #set_response_content_type
                         #serialize_response_headers
-                        .body(#body)
-                        // Since we require header names to come from the `http::header` module,
-                        // this cannot fail.
-                        .unwrap();
+                    }
+
+                    // Same as above, this cannot fail.
+                    let response = resp_builder.body(#body).unwrap();
                     Ok(response)
                 }
             }
 
-            impl ::std::convert::TryFrom<#ruma_api_import::exports::http::Response<Vec<u8>>> for Response {
-                type Error = #ruma_api_import::error::FromHttpResponseError<#error>;
+            impl #ruma_api_import::IncomingResponse for Response {
+                type EndpointError = #error;
 
                 #[allow(unused_variables)]
-                fn try_from(
-                    response: #ruma_api_import::exports::http::Response<Vec<u8>>,
-                ) -> ::std::result::Result<Self, Self::Error> {
+                fn try_from_http_response<T: ::std::convert::AsRef<[::std::primitive::u8]>>(
+                    response: #ruma_api_import::exports::http::Response<T>,
+                ) -> ::std::result::Result<
+                    Self,
+                    #ruma_api_import::error::FromHttpResponseError<#error>,
+                > {
                     if response.status().as_u16() < 400 {
                         #extract_response_headers
 
@@ -317,7 +366,8 @@ impl ToTokens for Api {
                 name: #name,
                 path: #path,
                 rate_limited: #rate_limited,
-                requires_authentication: #requires_authentication,
+                authentication: #ruma_api_import::AuthScheme::#authentication,
+                history: #history,
             };
 
             impl #request_lifetimes #ruma_api_import::OutgoingRequest
@@ -330,27 +380,63 @@ impl ToTokens for Api {
                 /// Metadata for the `#name` endpoint.
                 const METADATA: #ruma_api_import::Metadata = __METADATA;
 
+                fn try_into_http_request<T>(
+                    self,
+                    base_url: &::std::primitive::str,
+                    access_token: ::std::option::Option<&str>,
+                ) -> ::std::result::Result<
+                    #ruma_api_import::exports::http::Request<T>,
+                    #ruma_api_import::error::IntoHttpError,
+                >
+                where
+                    T: ::std::default::Default + #ruma_api_import::exports::bytes::BufMut,
+                {
+                    #ruma_api_import::OutgoingRequest::try_into_http_request_for_versions(
+                        self,
+                        base_url,
+                        access_token,
+                        &[],
+                    )
+                }
+
                 #[allow(unused_mut, unused_variables)]
-                fn try_into_http_request(
+                fn try_into_http_request_for_versions<T>(
                     self,
                     base_url: &::std::primitive::str,
                     access_token: ::std::option::Option<&str>,
+                    considering_versions: &[#ruma_api_import::MatrixVersion],
                 ) -> ::std::result::Result<
-                    #ruma_api_import::exports::http::Request<Vec<u8>>,
+                    #ruma_api_import::exports::http::Request<T>,
                     #ruma_api_import::error::IntoHttpError,
-                > {
+                >
+                where
+                    T: ::std::default::Default + #ruma_api_import::exports::bytes::BufMut,
+                {
                     let metadata = <Self as #ruma_api_import::OutgoingRequest>::METADATA;
+                    let path = metadata
+                        .history
+                        .select_path(considering_versions)
+                        .unwrap_or(metadata.path);
+
+                    let mut request_url = ::std::format!(
+                        "{}{}{}",
+                        base_url.strip_suffix("/").unwrap_or(base_url),
+                        #request_path_string,
+                        #request_query_string,
+                    );
+                    #append_access_token_query
 
-                    let http_request = #ruma_api_import::exports::http::Request::builder()
+                    let mut req_builder = #ruma_api_import::exports::http::Request::builder()
                         .method(#ruma_api_import::exports::http::Method::#method)
-                        .uri(::std::format!(
-                            "{}{}{}",
-                            base_url.strip_suffix("/").unwrap_or(base_url),
-                            #request_path_string,
-                            #request_query_string,
-                        ))
-                        #( .header(#header_kvs) )*
-                        .body(#request_body)?;
+                        .uri(request_url);
+
+                    // `headers_mut` only returns `None` if the builder already failed, in which
+                    // case the error is returned by `body` below.
+                    if let Some(req_headers) = req_builder.headers_mut() {
+                        #( #header_kvs )*
+                    }
+
+                    let http_request = req_builder.body(#request_body)?;
 
                     Ok(http_request)
                 }
@@ -363,6 +449,23 @@ impl ToTokens for Api {
                 // FIXME: Doc string interpolation
                 /// Metadata for the `#name` endpoint.
                 const METADATA: #ruma_api_import::Metadata = __METADATA;
+
+                #[allow(unused_variables)]
+                fn try_from_http_request<T: ::std::convert::AsRef<[::std::primitive::u8]>>(
+                    request: #ruma_api_import::exports::http::Request<T>,
+                ) -> ::std::result::Result<Self, #ruma_api_import::error::FromHttpRequestError> {
+                    #extract_request_path
+                    #extract_request_query
+                    #extract_request_headers
+                    #extract_request_body
+
+                    Ok(Self {
+                        #parse_request_path
+                        #parse_request_query
+                        #parse_request_headers
+                        #parse_request_body
+                    })
+                }
             }
 
             #non_auth_endpoint_impls
@@ -428,18 +531,21 @@ impl Parse for RawMetadata {
 }
 
 pub struct RawRequest {
+    pub attributes: Vec<Attribute>,
     pub request_kw: kw::request,
     pub fields: Vec<Field>,
 }
 
 impl Parse for RawRequest {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let attributes = input.call(Attribute::parse_outer)?;
         let request_kw = input.parse::<kw::request>()?;
         input.parse::<Token![:]>()?;
         let fields;
         braced!(fields in input);
 
         Ok(Self {
+            attributes,
             request_kw,
             fields: fields
                 .parse_terminated::<Field, Token![,]>(Field::parse_named)?
@@ -450,18 +556,21 @@ impl Parse for RawRequest {
 }
 
 pub struct RawResponse {
+    pub attributes: Vec<Attribute>,
     pub response_kw: kw::response,
     pub fields: Vec<Field>,
 }
 
 impl Parse for RawResponse {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let attributes = input.call(Attribute::parse_outer)?;
         let response_kw = input.parse::<kw::response>()?;
         input.parse::<Token![:]>()?;
         let fields;
         braced!(fields in input);
 
         Ok(Self {
+            attributes,
             response_kw,
             fields: fields
                 .parse_terminated::<Field, Token![,]>(Field::parse_named)?
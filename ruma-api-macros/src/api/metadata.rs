@@ -2,7 +2,10 @@
 
 use std::convert::TryFrom;
 
-use syn::{Expr, ExprLit, ExprPath, Ident, Lit, LitBool, LitStr, Member};
+use proc_macro2::Span;
+use syn::{
+    Expr, ExprArray, ExprLit, ExprPath, ExprTuple, Ident, Lit, LitBool, LitFloat, LitStr, Member,
+};
 
 use crate::{api::RawMetadata, util};
 
@@ -14,12 +17,22 @@ pub struct Metadata {
     pub method: Ident,
     /// The name field.
     pub name: LitStr,
-    /// The path field.
+    /// The path field, or the oldest of the versioned paths.
     pub path: LitStr,
+    /// The versioned paths, oldest first, as `MatrixVersion` variants and paths.
+    pub paths: Vec<(Ident, LitStr)>,
     /// The rate_limited field.
     pub rate_limited: LitBool,
-    /// The description field.
-    pub requires_authentication: LitBool,
+    /// The authentication field, as an `AuthScheme` variant.
+    ///
+    /// `requires_authentication: true` / `false` is turned into `AccessToken` / `None`.
+    pub authentication: Ident,
+    /// The added field, as a `MatrixVersion` variant.
+    pub added: Option<Ident>,
+    /// The deprecated field, as a `MatrixVersion` variant.
+    pub deprecated: Option<Ident>,
+    /// The removed field, as a `MatrixVersion` variant.
+    pub removed: Option<Ident>,
 }
 
 impl TryFrom<RawMetadata> for Metadata {
@@ -30,8 +43,12 @@ impl TryFrom<RawMetadata> for Metadata {
         let mut method = None;
         let mut name = None;
         let mut path = None;
+        let mut paths = Vec::new();
         let mut rate_limited = None;
-        let mut requires_authentication = None;
+        let mut authentication = None;
+        let mut added = None;
+        let mut deprecated = None;
+        let mut removed = None;
 
         for field_value in raw.field_values {
             let identifier = match field_value.member.clone() {
@@ -61,29 +78,52 @@ impl TryFrom<RawMetadata> for Metadata {
                 },
                 "path" => match expr {
                     Expr::Lit(ExprLit { lit: Lit::Str(literal), .. }) => {
-                        let path_str = literal.value();
-                        if !util::is_valid_endpoint_path(&path_str) {
-                            return Err(syn::Error::new_spanned(
-                                literal,
-                                "path may only contain printable ASCII characters with no spaces",
-                            ));
-                        }
-                        path = Some(literal);
+                        path = Some(check_path(literal)?);
+                    }
+                    Expr::Array(array) => {
+                        paths = parse_versioned_paths(array)?;
+                        path = Some(paths[0].1.clone());
+                    }
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            expr,
+                            "expected a string literal or an array of (version, path) tuples",
+                        ))
                     }
-                    _ => return Err(syn::Error::new_spanned(expr, "expected a string literal")),
                 },
+                "added" => added = Some(parse_version_expr(&expr)?),
+                "deprecated" => deprecated = Some(parse_version_expr(&expr)?),
+                "removed" => removed = Some(parse_version_expr(&expr)?),
                 "rate_limited" => match expr {
                     Expr::Lit(ExprLit { lit: Lit::Bool(literal), .. }) => {
                         rate_limited = Some(literal);
                     }
                     _ => return Err(syn::Error::new_spanned(expr, "expected a bool literal")),
                 },
+                "requires_authentication" | "authentication" if authentication.is_some() => {
+                    return Err(syn::Error::new_spanned(
+                        field_value,
+                        "only one of `requires_authentication` and `authentication` may be given",
+                    ));
+                }
                 "requires_authentication" => match expr {
                     Expr::Lit(ExprLit { lit: Lit::Bool(literal), .. }) => {
-                        requires_authentication = Some(literal);
+                        let scheme = if literal.value { "AccessToken" } else { "None" };
+                        authentication = Some(Ident::new(scheme, literal.span()));
                     }
                     _ => return Err(syn::Error::new_spanned(expr, "expected a bool literal")),
                 },
+                "authentication" => match expr {
+                    Expr::Path(ExprPath { ref path, .. }) if path.segments.len() == 1 => {
+                        authentication = Some(path.segments[0].ident.clone());
+                    }
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            expr,
+                            "expected an `AuthScheme` variant, e.g. `AccessToken`",
+                        ))
+                    }
+                },
                 _ => return Err(syn::Error::new_spanned(field_value, "unexpected field")),
             }
         }
@@ -97,9 +137,104 @@ impl TryFrom<RawMetadata> for Metadata {
             method: method.ok_or_else(|| missing_field("method"))?,
             name: name.ok_or_else(|| missing_field("name"))?,
             path: path.ok_or_else(|| missing_field("path"))?,
+            paths,
             rate_limited: rate_limited.ok_or_else(|| missing_field("rate_limited"))?,
-            requires_authentication: requires_authentication
+            authentication: authentication
                 .ok_or_else(|| missing_field("requires_authentication"))?,
+            added,
+            deprecated,
+            removed,
         })
     }
 }
+
+fn check_path(literal: LitStr) -> syn::Result<LitStr> {
+    if !util::is_valid_endpoint_path(&literal.value()) {
+        return Err(syn::Error::new_spanned(
+            literal,
+            "path may only contain printable ASCII characters with no spaces",
+        ));
+    }
+
+    Ok(literal)
+}
+
+/// Parses `[(1.0, "/path/r0/:param"), (1.1, "/path/v3/:param")]`.
+///
+/// The versions have to be in ascending order, and all paths need the same path parameters in the
+/// same segments, so incoming requests can be parsed the same way for each of them.
+fn parse_versioned_paths(array: ExprArray) -> syn::Result<Vec<(Ident, LitStr)>> {
+    let mut paths: Vec<(Ident, LitStr, LitFloat)> = Vec::new();
+
+    for elem in &array.elems {
+        let invalid = || syn::Error::new_spanned(elem, "expected a (version, path) tuple");
+
+        let (version, path) = match elem {
+            Expr::Tuple(ExprTuple { elems, .. }) if elems.len() == 2 => {
+                match (&elems[0], &elems[1]) {
+                    (
+                        Expr::Lit(ExprLit { lit: Lit::Float(version), .. }),
+                        Expr::Lit(ExprLit { lit: Lit::Str(path), .. }),
+                    ) => (version.clone(), check_path(path.clone())?),
+                    _ => return Err(invalid()),
+                }
+            }
+            _ => return Err(invalid()),
+        };
+
+        if let (Some((_, first_path, _)), Some((_, _, previous_version))) =
+            (paths.first(), paths.last())
+        {
+            if version_number(&version)? <= version_number(previous_version)? {
+                return Err(syn::Error::new_spanned(
+                    version,
+                    "versions of paths need to be in ascending order",
+                ));
+            }
+
+            if path_params(&path.value()) != path_params(&first_path.value()) {
+                return Err(syn::Error::new_spanned(
+                    path,
+                    "all paths need the same path parameters in the same segments",
+                ));
+            }
+        }
+
+        paths.push((parse_version(&version)?, path, version));
+    }
+
+    if paths.is_empty() {
+        return Err(syn::Error::new_spanned(array, "expected at least one path"));
+    }
+
+    Ok(paths.into_iter().map(|(ident, path, _)| (ident, path)).collect())
+}
+
+fn path_params(path: &str) -> Vec<(usize, &str)> {
+    path.split('/').enumerate().filter(|(_, segment)| segment.starts_with(':')).collect()
+}
+
+fn parse_version_expr(expr: &Expr) -> syn::Result<Ident> {
+    match expr {
+        Expr::Lit(ExprLit { lit: Lit::Float(version), .. }) => parse_version(version),
+        _ => Err(syn::Error::new_spanned(expr, "expected a Matrix version like `1.1`")),
+    }
+}
+
+fn version_number(version: &LitFloat) -> syn::Result<(u8, u8)> {
+    let digits = version.base10_digits();
+    let mut parts = digits.splitn(2, '.').map(str::parse::<u8>);
+
+    match (parts.next(), parts.next(), version.suffix()) {
+        (Some(Ok(major)), Some(Ok(minor)), "") => Ok((major, minor)),
+        _ => Err(syn::Error::new_spanned(version, "expected a Matrix version like `1.1`")),
+    }
+}
+
+/// Converts a version literal like `1.1` to the name of the `MatrixVersion` variant.
+fn parse_version(version: &LitFloat) -> syn::Result<Ident> {
+    match version_number(version)? {
+        (1, minor @ 0..=2) => Ok(Ident::new(&format!("V1_{}", minor), Span::call_site())),
+        _ => Err(syn::Error::new_spanned(version, "unknown Matrix version")),
+    }
+}
@@ -4,7 +4,7 @@ use std::{collections::BTreeSet, convert::TryFrom, mem};
 
 use proc_macro2::TokenStream;
 use quote::{quote, quote_spanned, ToTokens};
-use syn::{spanned::Spanned, Field, Ident, Lifetime};
+use syn::{spanned::Spanned, Attribute, Field, Ident, Lifetime};
 
 use crate::{
     api::{
@@ -24,6 +24,9 @@ pub struct RequestLifetimes {
 
 /// The result of processing the `request` section of the macro.
 pub struct Request {
+    /// The attributes that will be applied to the struct definition.
+    attributes: Vec<Attribute>,
+
     /// The fields of the request.
     fields: Vec<RequestField>,
 
@@ -36,6 +39,9 @@ pub struct Request {
 
 impl Request {
     /// Produces code to add necessary HTTP headers to an `http::Request`.
+    ///
+    /// The generated statements insert into a `HeaderMap` called `req_headers`. Headers of
+    /// `Option` fields are only inserted if the field is `Some`.
     pub fn append_header_kvs(&self) -> Vec<TokenStream> {
         let import_path = &self.ruma_api_import;
         self.header_fields().map(|request_field| {
@@ -46,9 +52,24 @@ impl Request {
 
             let field_name = &field.ident;
 
-            quote! {
-                #import_path::exports::http::header::#header_name,
-                #import_path::exports::http::header::HeaderValue::from_str(self.#field_name.as_ref())?
+            if util::is_option(&field.ty) {
+                quote! {
+                    if let Some(header_val) = self.#field_name.as_ref() {
+                        req_headers.insert(
+                            #import_path::exports::http::header::#header_name,
+                            #import_path::exports::http::header::HeaderValue::from_str(header_val)?,
+                        );
+                    }
+                }
+            } else {
+                quote! {
+                    req_headers.insert(
+                        #import_path::exports::http::header::#header_name,
+                        #import_path::exports::http::header::HeaderValue::from_str(
+                            self.#field_name.as_ref(),
+                        )?,
+                    );
+                }
             }
         }).collect()
     }
@@ -65,6 +86,17 @@ impl Request {
             let field_name = &field.ident;
             let header_name_string = header_name.to_string();
 
+            if util::is_option(&field.ty) {
+                return quote! {
+                    #field_name: match headers.get(#import_path::exports::http::header::#header_name) {
+                        Some(header) => Some(
+                            #import_path::try_deserialize!(request, header.to_str()).to_owned()
+                        ),
+                        None => None,
+                    }
+                };
+            }
+
             quote! {
                 #field_name: match headers
                     .get(#import_path::exports::http::header::#header_name)
@@ -382,7 +414,12 @@ impl TryFrom<RawRequest> for Request {
             ));
         }
 
-        Ok(Self { fields, lifetimes, ruma_api_import: util::import_ruma_api() })
+        Ok(Self {
+            attributes: raw.attributes,
+            fields,
+            lifetimes,
+            ruma_api_import: util::import_ruma_api(),
+        })
     }
 }
 
@@ -481,9 +518,12 @@ impl ToTokens for Request {
             TokenStream::new()
         };
 
+        let struct_attributes = &self.attributes;
+
         let request = quote! {
             #[derive(Debug, Clone, #import_path::Outgoing)]
             #[incoming_no_deserialize]
+            #( #struct_attributes )*
             pub struct Request #request_generics #request_def
 
             #request_body_struct
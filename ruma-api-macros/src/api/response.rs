@@ -4,7 +4,7 @@ use std::{convert::TryFrom, mem};
 
 use proc_macro2::TokenStream;
 use quote::{quote, quote_spanned, ToTokens};
-use syn::{spanned::Spanned, Field, Ident};
+use syn::{spanned::Spanned, Attribute, Field, Ident};
 
 use crate::{
     api::{
@@ -16,6 +16,9 @@ use crate::{
 
 /// The result of processing the `response` section of the macro.
 pub struct Response {
+    /// The attributes that will be applied to the struct definition.
+    attributes: Vec<Attribute>,
+
     /// The fields of the response.
     fields: Vec<ResponseField>,
 
@@ -54,6 +57,19 @@ impl Response {
                         #field_name: response_body.#field_name
                     }
                 }
+                ResponseField::Header(_, header_name) if util::is_option(&field.ty) => {
+                    quote_spanned! {span=>
+                        #field_name: match headers.remove(
+                            #import_path::exports::http::header::#header_name
+                        ) {
+                            Some(header) => Some(
+                                #import_path::try_deserialize!(response, header.to_str())
+                                    .to_owned()
+                            ),
+                            None => None,
+                        }
+                    }
+                }
                 ResponseField::Header(_, header_name) => {
                     quote_spanned! {span=>
                         #field_name: #import_path::try_deserialize!(
@@ -74,7 +90,7 @@ impl Response {
                 // We are guaranteed only one new body field because of a check in `try_from`.
                 ResponseField::NewtypeRawBody(_) => {
                     new_type_raw_body = Some(quote_spanned! {span=>
-                        #field_name: response.into_body()
+                        #field_name: response.into_body().as_ref().to_owned()
                     });
                     // skip adding to the vec
                     continue;
@@ -90,6 +106,9 @@ impl Response {
     }
 
     /// Produces code to add necessary HTTP headers to an `http::Response`.
+    ///
+    /// The generated statements insert into a `HeaderMap` called `resp_headers`. Headers of
+    /// `Option` fields are only inserted if the field is `Some`.
     pub fn apply_header_fields(&self) -> TokenStream {
         let import_path = &self.ruma_api_import;
 
@@ -99,8 +118,24 @@ impl Response {
                     field.ident.as_ref().expect("expected field to have an identifier");
                 let span = field.span();
 
-                Some(quote_spanned! {span=>
-                    .header(#import_path::exports::http::header::#header_name, response.#field_name)
+                let header_value = quote! { #import_path::exports::http::header::HeaderValue };
+
+                Some(if util::is_option(&field.ty) {
+                    quote_spanned! {span=>
+                        if let Some(header_val) = response.#field_name.as_ref() {
+                            resp_headers.insert(
+                                #import_path::exports::http::header::#header_name,
+                                #header_value::from_str(header_val)?,
+                            );
+                        }
+                    }
+                } else {
+                    quote_spanned! {span=>
+                        resp_headers.insert(
+                            #import_path::exports::http::header::#header_name,
+                            #header_value::from_str(response.#field_name.as_ref())?,
+                        );
+                    }
                 })
             } else {
                 None
@@ -117,13 +152,24 @@ impl Response {
         if let Some(field) = self.newtype_raw_body_field() {
             let field_name = field.ident.as_ref().expect("expected field to have an identifier");
             let span = field.span();
-            return quote_spanned!(span=> response.#field_name);
+            return quote_spanned! {span=>
+                {
+                    let mut body = T::default();
+                    #import_path::exports::bytes::BufMut::put_slice(
+                        &mut body,
+                        &response.#field_name,
+                    );
+                    body
+                }
+            };
         }
 
         let body = if let Some(field) = self.newtype_body_field() {
             let field_name = field.ident.as_ref().expect("expected field to have an identifier");
             let span = field.span();
-            quote_spanned!(span=> response.#field_name)
+            // Serialize through `ResponseBody` so serde attributes on the field, like
+            // `#[serde(with = "...")]`, apply in both directions.
+            quote_spanned!(span=> ResponseBody(response.#field_name))
         } else {
             let fields = self.fields.iter().filter_map(|response_field| {
                 if let ResponseField::Body(ref field) = *response_field {
@@ -147,7 +193,7 @@ impl Response {
             }
         };
 
-        quote!(#import_path::exports::serde_json::to_vec(&#body)?)
+        quote!(#import_path::serialize_json_body(&#body)?)
     }
 
     /// Gets the newtype body field, if this response has one.
@@ -234,7 +280,7 @@ impl TryFrom<RawResponse> for Response {
             ));
         }
 
-        Ok(Self { fields, ruma_api_import: util::import_ruma_api() })
+        Ok(Self { attributes: raw.attributes, fields, ruma_api_import: util::import_ruma_api() })
     }
 }
 
@@ -276,9 +322,12 @@ impl ToTokens for Response {
             struct ResponseBody #def
         };
 
+        let struct_attributes = &self.attributes;
+
         let response = quote! {
             #[derive(Debug, Clone, #import_path::Outgoing)]
             #[incoming_no_deserialize]
+            #( #struct_attributes )*
             pub struct Response #response_def
 
             #response_body_struct
@@ -2,7 +2,7 @@ use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote};
 use syn::{
     parse_quote, AngleBracketedGenericArguments, Attribute, Data, DeriveInput, Field, Fields,
-    GenericArgument, GenericParam, Generics, ImplGenerics, ParenthesizedGenericArguments,
+    GenericArgument, GenericParam, Generics, Ident, ImplGenerics, ParenthesizedGenericArguments,
     PathArguments, Type, TypeGenerics, TypePath, TypeReference, TypeSlice, Variant,
 };
 
@@ -64,13 +64,15 @@ pub fn expand_derive_outgoing(input: DeriveInput) -> syn::Result<TokenStream> {
                 return Ok(impl_outgoing_with_incoming_self(&input, &import_path));
             }
 
-            let vis = input.vis;
+            let vis = &input.vis;
             let doc = format!("'Incoming' variant of [{ty}](enum.{ty}.html).", ty = &input.ident);
             let incoming_ident =
                 format_ident!("Incoming{}", original_ident, span = Span::call_site());
             let mut gen_copy = input.generics.clone();
             let (impl_gen, ty_gen) = split_for_impl_lifetime_less(&mut gen_copy);
 
+            let from_impl = incoming_from_enum_impl(&input, &incoming_ident, &ty_gen);
+
             Ok(quote! {
                 #[doc = #doc]
                 #[derive(Debug, #derive_deserialize)]
@@ -80,6 +82,8 @@ pub fn expand_derive_outgoing(input: DeriveInput) -> syn::Result<TokenStream> {
                 impl #original_impl_gen #import_path::Outgoing for #original_ident #original_ty_gen {
                     type Incoming = #incoming_ident #impl_gen;
                 }
+
+                #from_impl
             })
         }
         DataKind::Struct(mut fields, struct_kind) => {
@@ -97,7 +101,7 @@ pub fn expand_derive_outgoing(input: DeriveInput) -> syn::Result<TokenStream> {
                 return Ok(impl_outgoing_with_incoming_self(&input, &import_path));
             }
 
-            let vis = input.vis;
+            let vis = &input.vis;
             let doc = format!("'Incoming' variant of [{ty}](struct.{ty}.html).", ty = &input.ident);
             let incoming_ident =
                 format_ident!("Incoming{}", original_ident, span = Span::call_site());
@@ -109,6 +113,8 @@ pub fn expand_derive_outgoing(input: DeriveInput) -> syn::Result<TokenStream> {
                 StructKind::Tuple => quote! { ( #(#fields,)* ); },
             };
 
+            let from_impl = incoming_from_struct_impl(&input, &incoming_ident, &ty_gen);
+
             Ok(quote! {
                 #[doc = #doc]
                 #[derive(Debug, #derive_deserialize)]
@@ -118,15 +124,208 @@ pub fn expand_derive_outgoing(input: DeriveInput) -> syn::Result<TokenStream> {
                 impl #original_impl_gen #import_path::Outgoing for #original_ident #original_ty_gen {
                     type Incoming = #incoming_ident #impl_gen;
                 }
+
+                #from_impl
             })
         }
     }
 }
 
-/// Keep any `serde` or `non_exhaustive` attributes found and
+/// Generates `From<T<'a>> for IncomingT` for a struct, converting every borrowed field into its
+/// owned counterpart.
+///
+/// Nothing is generated for types with type parameters, since converting their fields would need
+/// bounds the user didn't write, or if one of the fields has a type `incoming_conversion` can't
+/// convert.
+fn incoming_from_struct_impl(
+    input: &DeriveInput,
+    incoming_ident: &Ident,
+    incoming_ty_gen: &TypeGenerics<'_>,
+) -> TokenStream {
+    if input.generics.type_params().next().is_some() {
+        return TokenStream::new();
+    }
+
+    let fields = match &input.data {
+        Data::Struct(s) => &s.fields,
+        _ => return TokenStream::new(),
+    };
+
+    let (bindings, conversions) = match field_conversions(fields) {
+        Some(c) => c,
+        None => return TokenStream::new(),
+    };
+
+    let construct = match fields {
+        Fields::Named(_) => quote! { Self { #( #conversions, )* } },
+        Fields::Unnamed(_) => quote! { Self ( #( #conversions, )* ) },
+        Fields::Unit => quote! { Self },
+    };
+    let destructure = match fields {
+        Fields::Named(_) => quote! { { #( #bindings, )* } },
+        Fields::Unnamed(_) => quote! { ( #( #bindings, )* ) },
+        Fields::Unit => TokenStream::new(),
+    };
+
+    let original_ident = &input.ident;
+    let (impl_gen, ty_gen, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_gen ::std::convert::From<#original_ident #ty_gen>
+            for #incoming_ident #incoming_ty_gen #where_clause
+        {
+            fn from(original: #original_ident #ty_gen) -> Self {
+                let #original_ident #destructure = original;
+                #construct
+            }
+        }
+    }
+}
+
+/// Like `incoming_from_struct_impl`, for an enum.
+fn incoming_from_enum_impl(
+    input: &DeriveInput,
+    incoming_ident: &Ident,
+    incoming_ty_gen: &TypeGenerics<'_>,
+) -> TokenStream {
+    if input.generics.type_params().next().is_some() {
+        return TokenStream::new();
+    }
+
+    let variants = match &input.data {
+        Data::Enum(e) => &e.variants,
+        _ => return TokenStream::new(),
+    };
+
+    let original_ident = &input.ident;
+    let mut arms = Vec::new();
+    for variant in variants {
+        let variant_ident = &variant.ident;
+        let (bindings, conversions) = match field_conversions(&variant.fields) {
+            Some(c) => c,
+            None => return TokenStream::new(),
+        };
+
+        arms.push(match &variant.fields {
+            Fields::Named(_) => quote! {
+                #original_ident::#variant_ident { #( #bindings, )* } =>
+                    Self::#variant_ident { #( #conversions, )* }
+            },
+            Fields::Unnamed(_) => quote! {
+                #original_ident::#variant_ident ( #( #bindings, )* ) =>
+                    Self::#variant_ident ( #( #conversions, )* )
+            },
+            Fields::Unit => quote! {
+                #original_ident::#variant_ident => Self::#variant_ident
+            },
+        });
+    }
+
+    let (impl_gen, ty_gen, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_gen ::std::convert::From<#original_ident #ty_gen>
+            for #incoming_ident #incoming_ty_gen #where_clause
+        {
+            fn from(original: #original_ident #ty_gen) -> Self {
+                match original {
+                    #( #arms, )*
+                }
+            }
+        }
+    }
+}
+
+/// Returns the patterns binding the given fields and the expressions converting them, or `None`
+/// if one of them can't be converted.
+///
+/// Unnamed fields are bound to `field0`, `field1` and so on. `#[cfg]` attributes are kept on both.
+fn field_conversions(fields: &Fields) -> Option<(Vec<TokenStream>, Vec<TokenStream>)> {
+    let mut bindings = Vec::new();
+    let mut conversions = Vec::new();
+
+    for (i, field) in fields.iter().enumerate() {
+        let cfg_attrs =
+            field.attrs.iter().filter(|attr| attr.path.is_ident("cfg")).collect::<Vec<_>>();
+        let binding = match &field.ident {
+            Some(ident) => ident.clone(),
+            None => format_ident!("field{}", i),
+        };
+        let conversion = incoming_conversion(&field.ty, quote! { #binding })?;
+
+        bindings.push(quote! { #( #cfg_attrs )* #binding });
+        conversions.push(match &field.ident {
+            Some(ident) => quote! { #( #cfg_attrs )* #ident: #conversion },
+            None => quote! { #( #cfg_attrs )* #conversion },
+        });
+    }
+
+    Some((bindings, conversions))
+}
+
+/// Builds an expression converting `expr`, a value of the borrowed type `ty`, into a value of the
+/// type `strip_lifetimes` turns `ty` into.
+///
+/// Returns `None` for types whose lifetimes `strip_lifetimes` removes but that can't be converted
+/// here, like maps with borrowed keys.
+fn incoming_conversion(ty: &Type, expr: TokenStream) -> Option<TokenStream> {
+    if !strip_lifetimes(&mut ty.clone()) {
+        return Some(expr);
+    }
+
+    match ty {
+        // &str -> String, &DeviceId -> Box<DeviceId>, &T -> T
+        Type::Reference(TypeReference { elem, .. }) => match &**elem {
+            Type::Path(_) => Some(quote! { ::std::borrow::ToOwned::to_owned(#expr) }),
+            // &[T] -> Vec<T>
+            Type::Slice(TypeSlice { elem, .. }) => {
+                if !strip_lifetimes(&mut (**elem).clone()) {
+                    return Some(quote! { #expr.to_vec() });
+                }
+
+                let conversion = incoming_conversion(elem, quote! { elem })?;
+                Some(quote! {
+                    #expr.iter().cloned().map(|elem| #conversion).collect::<Vec<_>>()
+                })
+            }
+            _ => None,
+        },
+        Type::Path(TypePath { path, .. }) => {
+            let segment = path.segments.last()?;
+            let args = match &segment.arguments {
+                PathArguments::AngleBracketed(AngleBracketedGenericArguments { args, .. }) => args,
+                _ => return None,
+            };
+
+            // T<'a> -> IncomingT, which gets a `From` implementation from this derive
+            if args.iter().any(|arg| matches!(arg, GenericArgument::Lifetime(_))) {
+                return Some(quote! { ::std::convert::From::from(#expr) });
+            }
+
+            let elem = match args.iter().collect::<Vec<_>>().as_slice() {
+                [GenericArgument::Type(elem)] => elem,
+                _ => return None,
+            };
+            let conversion = incoming_conversion(elem, quote! { elem })?;
+
+            match segment.ident.to_string().as_str() {
+                "Option" => Some(quote! { #expr.map(|elem| #conversion) }),
+                "Vec" => Some(quote! {
+                    #expr.into_iter().map(|elem| #conversion).collect::<Vec<_>>()
+                }),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Keep any `serde`, `non_exhaustive` or `allow` attributes found and
 /// pass them to the Incoming variant.
 fn filter_input_attrs(attr: &Attribute) -> bool {
-    attr.path.is_ident("serde") || attr.path.is_ident("non_exhaustive")
+    attr.path.is_ident("serde")
+        || attr.path.is_ident("non_exhaustive")
+        || attr.path.is_ident("allow")
 }
 
 fn no_deserialize_in_attrs(attrs: &[Attribute]) -> bool {
@@ -81,6 +81,16 @@ pub fn unique_lifetimes_to_tokens<'a, I: Iterator<Item = &'a Lifetime>>(
     }
 }
 
+/// Whether the given type is an `Option`, judging by the last path segment.
+pub fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(TypePath { path, .. }) => {
+            path.segments.last().map_or(false, |seg| seg.ident == "Option")
+        }
+        _ => false,
+    }
+}
+
 pub fn has_lifetime(ty: &Type) -> bool {
     match ty {
         Type::Path(TypePath { path, .. }) => {
@@ -146,8 +156,8 @@ pub fn has_lifetime(ty: &Type) -> bool {
 /// of the path that start with ":".
 ///
 /// The first `TokenStream` returned is the constructed url path. The second `TokenStream` is
-/// used for implementing `TryFrom<http::Request<Vec<u8>>>`, from path strings deserialized to Ruma
-/// types.
+/// used for implementing `IncomingRequest::try_from_http_request`, from path strings deserialized
+/// to Ruma types.
 pub(crate) fn request_path_string_and_parse(
     request: &Request,
     metadata: &Metadata,
@@ -162,8 +172,8 @@ pub(crate) fn request_path_string_and_parse(
             "number of declared path parameters needs to match amount of placeholders in path"
         );
 
-        let format_call = {
-            let mut format_string = path_string.clone();
+        let format_call = |path_string: &str| {
+            let mut format_string = path_string.to_owned();
             let mut format_args = Vec::new();
 
             while let Some(start_of_segment) = format_string.find(':') {
@@ -193,6 +203,24 @@ pub(crate) fn request_path_string_and_parse(
             }
         };
 
+        // For endpoints with several paths, the one to use is chosen at runtime and stored in
+        // `path`. They all have the same path parameters, see `metadata::parse_versioned_paths`.
+        let path_call = if metadata.paths.is_empty() {
+            format_call(&path_string)
+        } else {
+            let arms = metadata.paths.iter().map(|(_, path)| {
+                let format_call = format_call(&path.value());
+                quote! { #path => ::std::string::ToString::to_string(&#format_call), }
+            });
+
+            quote! {
+                match path {
+                    #( #arms )*
+                    _ => ::std::unreachable!("path is one of the endpoint's paths"),
+                }
+            }
+        };
+
         let path_fields =
             path_string[1..].split('/').enumerate().filter(|(_, s)| s.starts_with(':')).map(
                 |(i, segment)| {
@@ -218,9 +246,9 @@ pub(crate) fn request_path_string_and_parse(
                 },
             );
 
-        (format_call, quote! { #(#path_fields,)* })
+        (path_call, quote! { #(#path_fields,)* })
     } else {
-        (quote! { metadata.path.to_owned() }, TokenStream::new())
+        (quote! { path.to_owned() }, TokenStream::new())
     }
 }
 
@@ -273,8 +301,28 @@ pub(crate) fn build_query_string(request: &Request, import_path: &TokenStream) -
 }
 
 /// Deserialize the query string.
-pub(crate) fn extract_request_query(request: &Request, import_path: &TokenStream) -> TokenStream {
-    if request.query_map_field().is_some() {
+///
+/// If `query_access_token` is set, the `access_token` parameter is left out of query maps.
+pub(crate) fn extract_request_query(
+    request: &Request,
+    query_access_token: bool,
+    import_path: &TokenStream,
+) -> TokenStream {
+    if request.query_map_field().is_some() && query_access_token {
+        quote! {
+            let request_query = {
+                let pairs: ::std::vec::Vec<(::std::string::String, ::std::string::String)> =
+                    #import_path::try_deserialize!(
+                        request,
+                        #import_path::exports::ruma_serde::urlencoded::from_str(
+                            &request.uri().query().unwrap_or("")
+                        ),
+                    );
+
+                pairs.into_iter().filter(|(key, _)| key != "access_token").collect()
+            };
+        }
+    } else if request.query_map_field().is_some() {
         quote! {
             let request_query = #import_path::try_deserialize!(
                 request,
@@ -304,7 +352,13 @@ pub(crate) fn extract_request_query(request: &Request, import_path: &TokenStream
 pub(crate) fn build_request_body(request: &Request, import_path: &TokenStream) -> TokenStream {
     if let Some(field) = request.newtype_raw_body_field() {
         let field_name = field.ident.as_ref().expect("expected field to have an identifier");
-        quote!(self.#field_name)
+        quote! {
+            {
+                let mut body = T::default();
+                #import_path::exports::bytes::BufMut::put_slice(&mut body, &self.#field_name);
+                body
+            }
+        }
     } else if request.has_body_fields() || request.newtype_body_field().is_some() {
         let request_body_initializers = if let Some(field) = request.newtype_body_field() {
             let field_name = field.ident.as_ref().expect("expected field to have an identifier");
@@ -317,11 +371,11 @@ pub(crate) fn build_request_body(request: &Request, import_path: &TokenStream) -
         quote! {
             {
                 let request_body = RequestBody #request_body_initializers;
-                #import_path::exports::serde_json::to_vec(&request_body)?
+                #import_path::serialize_json_body(&request_body)?
             }
         }
     } else {
-        quote!(Vec::new())
+        quote!(T::default())
     }
 }
 
@@ -334,7 +388,7 @@ pub(crate) fn parse_request_body(request: &Request) -> TokenStream {
     } else if let Some(field) = request.newtype_raw_body_field() {
         let field_name = field.ident.as_ref().expect("expected field to have an identifier");
         quote! {
-            #field_name: request.into_body(),
+            #field_name: request.into_body().as_ref().to_owned(),
         }
     } else {
         request.request_init_body_fields()
@@ -12,8 +12,8 @@ use crate::EndpointError;
 pub enum Void {}
 
 impl EndpointError for Void {
-    fn try_from_response(
-        response: http::Response<Vec<u8>>,
+    fn try_from_response<T: AsRef<[u8]>>(
+        response: http::Response<T>,
     ) -> Result<Self, ResponseDeserializationError> {
         Err(ResponseDeserializationError::from_response(response))
     }
@@ -121,11 +121,26 @@ pub struct RequestDeserializationError {
 impl RequestDeserializationError {
     /// Creates a new `RequestDeserializationError` from the given deserialization error and http
     /// request.
-    pub fn new(
+    ///
+    /// The body of the request is copied into the error.
+    pub fn new<T: AsRef<[u8]>>(
         inner: impl Into<DeserializationError>,
-        http_request: http::Request<Vec<u8>>,
+        http_request: http::Request<T>,
     ) -> Self {
-        Self { inner: inner.into(), http_request }
+        Self {
+            inner: inner.into(),
+            http_request: http_request.map(|body| body.as_ref().to_owned()),
+        }
+    }
+
+    /// The http request that could not be deserialized.
+    pub fn http_request(&self) -> &http::Request<Vec<u8>> {
+        &self.http_request
+    }
+
+    /// Consumes this error and returns the http request that could not be deserialized.
+    pub fn into_http_request(self) -> http::Request<Vec<u8>> {
+        self.http_request
     }
 }
 
@@ -181,16 +196,36 @@ pub struct ResponseDeserializationError {
 impl ResponseDeserializationError {
     /// Creates a new `ResponseDeserializationError` from the given deserialization error and http
     /// response.
-    pub fn new(
+    ///
+    /// The body of the response is copied into the error.
+    pub fn new<T: AsRef<[u8]>>(
         inner: impl Into<DeserializationError>,
-        http_response: http::Response<Vec<u8>>,
+        http_response: http::Response<T>,
     ) -> Self {
-        Self { inner: Some(inner.into()), http_response }
+        Self {
+            inner: Some(inner.into()),
+            http_response: http_response.map(|body| body.as_ref().to_owned()),
+        }
     }
 
     /// Creates a new `ResponseDeserializationError` without an inner deserialization error.
-    pub fn from_response(http_response: http::Response<Vec<u8>>) -> Self {
-        Self { http_response, inner: None }
+    ///
+    /// The body of the response is copied into the error.
+    pub fn from_response<T: AsRef<[u8]>>(http_response: http::Response<T>) -> Self {
+        Self { http_response: http_response.map(|body| body.as_ref().to_owned()), inner: None }
+    }
+
+    /// The http response that could not be deserialized.
+    ///
+    /// This includes the raw body, which can be useful for debugging since error responses are
+    /// not always JSON (e.g. HTML error pages from reverse proxies).
+    pub fn http_response(&self) -> &http::Response<Vec<u8>> {
+        &self.http_response
+    }
+
+    /// Consumes this error and returns the http response that could not be deserialized.
+    pub fn into_http_response(self) -> http::Response<Vec<u8>> {
+        self.http_response
     }
 }
 
@@ -3,18 +3,52 @@
 //! These types can be shared by client and server code for all Matrix APIs.
 //!
 //! When implementing a new Matrix API, each endpoint has a request type which implements
-//! `Endpoint`, and a response type connected via an associated type.
+//! `OutgoingRequest` and `IncomingRequest`, and a response type connected via associated types.
 //!
-//! An implementation of `Endpoint` contains all the information about the HTTP method, the path and
-//! input parameters for requests, and the structure of a successful response.
+//! These implementations contain all the information about the HTTP method, the path and input
+//! parameters for requests, and the structure of a successful response.
 //! Such types can then be used by client code to make requests, and by server code to fulfill
 //! those requests.
+//!
+//! # Converting to and from `http` types
+//!
+//! Requests and responses are not tied to a particular HTTP client or server library. Instead,
+//! they are converted to and from the types of the [`http`](https://docs.rs/http) crate, which
+//! most libraries can work with:
+//!
+//! * `OutgoingRequest::try_into_http_request(self, base_url, access_token)` creates an
+//!   `http::Request` to send to the server
+//! * `IncomingRequest::try_from_http_request(request)` parses an `http::Request` received by a
+//!   server
+//! * `OutgoingResponse::try_into_http_response(self)` creates an `http::Response` to send back to
+//!   the client
+//! * `IncomingResponse::try_from_http_response(response)` parses an `http::Response` received by
+//!   a client
+//!
+//! These conversions are part of the stable API of this crate. They are generic over the body
+//! type, so the body can be written directly into the buffer type of the HTTP library in use:
+//! bodies are created in any `T: Default + BufMut`, e.g. `Vec<u8>` or `bytes::BytesMut`, and read
+//! from any `T: AsRef<[u8]>`, e.g. `Vec<u8>` or `bytes::Bytes`. `#[ruma_api(raw_body)]` fields
+//! are always `Vec<u8>`, so their contents are copied into and out of that buffer once.
+//!
+//! # Borrowed and owned requests
+//!
+//! Many request types borrow their fields, e.g. `get_devices::Request<'a>` contains a
+//! `&'a UserId`, so clients can send requests without cloning anything. For every such type,
+//! `ruma_api!` also generates an `IncomingRequest` struct with the same fields as owned types
+//! (`String` instead of `&str`, `UserId` instead of `&UserId` and so on). This is the type that
+//! implements `IncomingRequest` and that `try_from_http_request` returns.
+//!
+//! Servers should therefore name `<Request as Outgoing>::Incoming` in their handler signatures,
+//! or `IncomingRequest` directly: it is `IncomingRequest` for requests with lifetimes and
+//! `Request` itself for all others. Since it doesn't borrow from anything, it can be stored in
+//! queues or passed between threads freely. A borrowed request can be turned into the owned one
+//! with `From`, e.g. to build incoming requests in tests.
 #![doc(html_favicon_url = "https://www.ruma.io/favicon.ico")]
 #![warn(rust_2018_idioms)]
 #![deny(missing_copy_implementations, missing_debug_implementations, missing_docs)]
 
-use std::convert::{TryFrom, TryInto};
-
+use bytes::BufMut;
 use http::Method;
 
 /// Generates a `ruma_api::Endpoint` from a concise definition.
@@ -29,7 +63,11 @@ use http::Method;
 ///         name: &'static str,
 ///         path: &'static str,
 ///         rate_limited: bool,
-///         requires_authentication: bool,
+///         authentication: AuthScheme, // or `requires_authentication: bool`
+///         // Optional:
+///         added: MatrixVersion,
+///         deprecated: MatrixVersion,
+///         removed: MatrixVersion,
 ///     }
 ///
 ///     request: {
@@ -67,8 +105,20 @@ use http::Method;
 ///     identifier prefixed with a colon, e.g. `/foo/:some_parameter`.
 ///     A corresponding query string parameter will be expected in the request struct (see below
 ///     for details).
+///     Endpoints that changed their path across Matrix versions can instead list each path with
+///     the version it is available from, oldest first, e.g.
+///     `[(1.0, "/_matrix/client/r0/foo/:bar"), (1.1, "/_matrix/client/v3/foo/:bar")]`. All of
+///     these paths need the same parameters in the same segments, and incoming requests are
+///     accepted on any of them. Outgoing requests use the newest path supported by the server,
+///     see `OutgoingRequest::try_into_http_request_for_versions`.
 /// *   `rate_limited`: Whether or not the endpoint enforces rate limiting on requests.
-/// *   `requires_authentication`: Whether or not the endpoint requires a valid access token.
+/// *   `authentication`: The `AuthScheme` of the endpoint, written without the enum name, e.g.
+///     `AccessToken` or `QueryOnlyAccessToken`.
+/// *   `requires_authentication`: Instead of `authentication`, whether or not the endpoint
+///     requires a valid access token in the `Authorization` header. `true` and `false` are short
+///     for `authentication: AccessToken` and `authentication: None`.
+/// *   `added`, `deprecated`, `removed`: The Matrix versions, written like `1.1`, in which the
+///     endpoint was added, deprecated and removed. These are optional.
 ///
 /// ## Request
 ///
@@ -81,6 +131,7 @@ use http::Method;
 ///     headers on the request.
 ///     The value must implement `AsRef<str>`.
 ///     Generally this is a `String`.
+///     If the field is an `Option`, the header is only sent when it is `Some` and may be missing.
 ///     The attribute value shown above as `HEADER_NAME` must be a header name constant from
 ///     `http::header`, e.g. `CONTENT_TYPE`.
 /// *   `#[ruma_api(path)]`: Fields with this attribute will be inserted into the matching path
@@ -95,6 +146,13 @@ use http::Method;
 /// Any field that does not include one of these attributes will be part of the request's JSON
 /// body.
 ///
+/// Attributes written before the `request` keyword, such as `#[derive(PartialEq)]`, are applied to
+/// the generated `Request` struct. They are not copied to the `IncomingRequest` type generated for
+/// requests with lifetimes.
+///
+/// For requests with lifetimes, `IncomingRequest` also implements `From<Request<'_>>`, unless a
+/// field has a type the conversion can't be generated for, like a map with borrowed keys.
+///
 /// ## Response
 ///
 /// Like the request block, the response block consists of normal struct field definitions.
@@ -106,12 +164,16 @@ use http::Method;
 ///     headers on the response.
 ///     The value must implement `AsRef<str>`.
 ///     Generally this is a `String`.
+///     If the field is an `Option`, the header is only sent when it is `Some` and may be missing.
 ///     The attribute value shown above as `HEADER_NAME` must be a header name constant from
 ///     `http::header`, e.g. `CONTENT_TYPE`.
 ///
 /// Any field that does not include the above attribute will be expected in the response's JSON
 /// body.
 ///
+/// As with the request, attributes written before the `response` keyword are applied to the
+/// generated `Response` struct.
+///
 /// ## Newtype bodies
 ///
 /// Both the request and response block also support "newtype bodies" by using the
@@ -123,7 +185,8 @@ use http::Method;
 ///
 /// There is another kind of newtype body that is enabled with `#[ruma_api(raw_body)]`. It is used
 /// for endpoints in which the request or response body can be arbitrary bytes instead of a JSON
-/// objects. A field with `#[ruma_api(raw_body)]` needs to have the type `Vec<u8>`.
+/// objects. A field with `#[ruma_api(raw_body)]` needs to have the type `Vec<u8>`. Its content is
+/// copied into and out of the body buffers of the `http` types, since these are generic.
 ///
 /// # Examples
 ///
@@ -210,6 +273,7 @@ pub mod error;
 /// It is not considered part of ruma-api's public API.
 #[doc(hidden)]
 pub mod exports {
+    pub use bytes;
     pub use http;
     pub use percent_encoding;
     pub use ruma_serde;
@@ -225,6 +289,9 @@ use error::{FromHttpRequestError, FromHttpResponseError, IntoHttpError};
 /// ruma_events' `EventResult` type. For more details, see the [derive macro's documentation][doc].
 ///
 /// [doc]: derive.Outgoing.html
+///
+/// If `Self` has lifetimes and no type parameters, the derive macro also implements
+/// `From<Self>` for the 'Incoming' type, converting every borrowed field into an owned one.
 // TODO: Better explain how this trait relates to serde's traits
 pub trait Outgoing {
     /// The 'Incoming' variant of `Self`.
@@ -237,21 +304,18 @@ pub trait EndpointError: std::error::Error + Sized {
     ///
     /// This will always return `Err` variant when no `error` field is defined in
     /// the `ruma_api` macro.
-    fn try_from_response(
-        response: http::Response<Vec<u8>>,
+    fn try_from_response<T: AsRef<[u8]>>(
+        response: http::Response<T>,
     ) -> Result<Self, error::ResponseDeserializationError>;
 }
 
 /// A request type for a Matrix API endpoint. (trait used for sending requests)
-pub trait OutgoingRequest {
+pub trait OutgoingRequest: Sized {
     /// A type capturing the expected error conditions the server can return.
     type EndpointError: EndpointError;
 
     /// Response type returned when the request is successful.
-    type IncomingResponse: TryFrom<
-        http::Response<Vec<u8>>,
-        Error = FromHttpResponseError<Self::EndpointError>,
-    >;
+    type IncomingResponse: IncomingResponse<EndpointError = Self::EndpointError>;
 
     /// Metadata about the endpoint.
     const METADATA: Metadata;
@@ -264,23 +328,71 @@ pub trait OutgoingRequest {
     /// The endpoints path will be appended to the given `base_url`, for example
     /// `https://matrix.org`. Since all paths begin with a slash, it is not necessary for the
     /// `base_url` to have a trailing slash. If it has one however, it will be ignored.
-    fn try_into_http_request(
+    ///
+    /// The body is written into a new `T`, for example a `Vec<u8>` or a `bytes::BytesMut`.
+    ///
+    /// Endpoints with several paths use `Metadata::path`, which is the one supported by the most
+    /// servers. Use `try_into_http_request_for_versions` to pick the newest path a server supports.
+    fn try_into_http_request<T: Default + BufMut>(
+        self,
+        base_url: &str,
+        access_token: Option<&str>,
+    ) -> Result<http::Request<T>, IntoHttpError>;
+
+    /// Like `try_into_http_request`, but uses the newest path of the endpoint that is available in
+    /// one of `considering_versions`, the Matrix versions supported by the server.
+    ///
+    /// Falls back to `Metadata::path` if the endpoint has a single path or none of its paths are
+    /// available in the given versions.
+    fn try_into_http_request_for_versions<T: Default + BufMut>(
         self,
         base_url: &str,
         access_token: Option<&str>,
-    ) -> Result<http::Request<Vec<u8>>, IntoHttpError>;
+        considering_versions: &[MatrixVersion],
+    ) -> Result<http::Request<T>, IntoHttpError> {
+        let _ = considering_versions;
+        self.try_into_http_request(base_url, access_token)
+    }
+}
+
+/// A response type for a Matrix API endpoint. (trait used for receiving responses)
+pub trait IncomingResponse: Sized {
+    /// A type capturing the expected error conditions the server can return.
+    type EndpointError: EndpointError;
+
+    /// Tries to convert the given `http::Response` into this response type.
+    ///
+    /// Responses with a status code of 400 or above are converted into an `EndpointError`.
+    fn try_from_http_response<T: AsRef<[u8]>>(
+        response: http::Response<T>,
+    ) -> Result<Self, FromHttpResponseError<Self::EndpointError>>;
 }
 
 /// A request type for a Matrix API endpoint. (trait used for receiving requests)
-pub trait IncomingRequest: TryFrom<http::Request<Vec<u8>>, Error = FromHttpRequestError> {
+pub trait IncomingRequest: Sized {
     /// A type capturing the error conditions that can be returned in the response.
     type EndpointError: EndpointError;
 
     /// Response type to return when the request is successful.
-    type OutgoingResponse: TryInto<http::Response<Vec<u8>>, Error = IntoHttpError>;
+    type OutgoingResponse: OutgoingResponse;
 
     /// Metadata about the endpoint.
     const METADATA: Metadata;
+
+    /// Tries to turn the given `http::Request` into this request type.
+    fn try_from_http_request<T: AsRef<[u8]>>(
+        req: http::Request<T>,
+    ) -> Result<Self, FromHttpRequestError>;
+}
+
+/// A response type for a Matrix API endpoint. (trait used for sending responses)
+pub trait OutgoingResponse {
+    /// Tries to convert this response into an `http::Response`.
+    ///
+    /// The body is written into a new `T`, for example a `Vec<u8>` or a `bytes::BytesMut`.
+    fn try_into_http_response<T: Default + BufMut>(
+        self,
+    ) -> Result<http::Response<T>, IntoHttpError>;
 }
 
 /// Marker trait for requests that don't require authentication. (for the client side)
@@ -303,13 +415,166 @@ pub struct Metadata {
 
     /// The path of this endpoint's URL, with variable names where path parameters should be filled
     /// in during a request.
+    ///
+    /// For endpoints with several paths, this is the oldest one in `history`.
     pub path: &'static str,
 
     /// Whether or not this endpoint is rate limited by the server.
     pub rate_limited: bool,
 
-    /// Whether or not the server requires an authenticated user for this endpoint.
-    pub requires_authentication: bool,
+    /// How the access token is sent for this endpoint.
+    ///
+    /// Use `AuthScheme::requires_authentication` to find out whether the endpoint needs an access
+    /// token at all.
+    pub authentication: AuthScheme,
+
+    /// The paths of this endpoint in different Matrix versions, and the versions it was added,
+    /// deprecated and removed in.
+    pub history: VersionHistory,
+}
+
+/// The way an endpoint authenticates requests.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AuthScheme {
+    /// No authentication is performed.
+    None,
+
+    /// The access token is sent in the `Authorization` header, as
+    /// `Authorization: Bearer <access_token>`.
+    AccessToken,
+
+    /// The access token is sent in the `access_token` query parameter only.
+    ///
+    /// This is how homeservers authenticate to application services, using their `hs_token`.
+    QueryOnlyAccessToken,
+}
+
+impl AuthScheme {
+    /// Whether the server requires an access token for endpoints with this scheme.
+    pub fn requires_authentication(self) -> bool {
+        self != Self::None
+    }
+}
+
+/// A version of the Matrix specification.
+///
+/// `V1_0` stands for the releases before the specification was versioned as a whole, like
+/// `r0.6.1` of the client-server API.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum MatrixVersion {
+    /// Matrix 1.0, including the `r0.x` releases of the individual APIs.
+    V1_0,
+
+    /// Matrix 1.1.
+    V1_1,
+
+    /// Matrix 1.2.
+    V1_2,
+
+    /// A version newer than all of the above, e.g. one released after this crate.
+    ///
+    /// It is ordered after every known version, so the newest known path of an endpoint is used
+    /// for servers that only advertise newer versions.
+    Newer {
+        /// The major version number.
+        major: u32,
+
+        /// The minor version number.
+        minor: u32,
+    },
+}
+
+impl MatrixVersion {
+    /// Parses a version string as found in the `versions` field of the `/_matrix/client/versions`
+    /// response, e.g. `r0.6.1` or `v1.1`.
+    ///
+    /// Versions newer than the known ones, like `v1.99`, are parsed as `Newer`. Returns `None` for
+    /// strings that aren't versions of the specification.
+    pub fn from_versions_str(version: &str) -> Option<Self> {
+        if version.starts_with("r0.") {
+            return Some(Self::V1_0);
+        }
+
+        match version {
+            "v1.1" => Some(Self::V1_1),
+            "v1.2" => Some(Self::V1_2),
+            _ if version.starts_with('v') => {
+                let mut parts = version[1..].splitn(2, '.');
+                let major = parts.next()?.parse().ok()?;
+                let minor = parts.next()?.parse().ok()?;
+
+                // There are no unknown versions older than the known ones.
+                if (major, minor) > (1, 2) {
+                    Some(Self::Newer { major, minor })
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The history of an endpoint across Matrix versions.
+#[derive(Clone, Copy, Debug)]
+pub struct VersionHistory {
+    /// The paths of the endpoint with the version they are available from, oldest first.
+    ///
+    /// Empty for endpoints with a single path that isn't tied to a Matrix version.
+    pub paths: &'static [(MatrixVersion, &'static str)],
+
+    /// The Matrix version that added this endpoint.
+    pub added: Option<MatrixVersion>,
+
+    /// The Matrix version that deprecated this endpoint.
+    pub deprecated: Option<MatrixVersion>,
+
+    /// The Matrix version that removed this endpoint.
+    pub removed: Option<MatrixVersion>,
+}
+
+impl VersionHistory {
+    /// The history of an endpoint with a single path and no version information.
+    pub const UNVERSIONED: Self = Self { paths: &[], added: None, deprecated: None, removed: None };
+
+    /// Returns the newest path that is available in one of the given versions, if any.
+    pub fn select_path(&self, versions: &[MatrixVersion]) -> Option<&'static str> {
+        let newest_version = versions.iter().max()?;
+
+        self.paths.iter().rev().find(|(added, _)| added <= newest_version).map(|(_, path)| *path)
+    }
+
+    /// Whether this endpoint is deprecated in all of the given versions.
+    ///
+    /// Returns `false` if `versions` is empty.
+    pub fn is_deprecated_in(&self, versions: &[MatrixVersion]) -> bool {
+        match (self.deprecated, versions.iter().min()) {
+            (Some(deprecated), Some(oldest)) => deprecated <= *oldest,
+            _ => false,
+        }
+    }
+
+    /// Whether this endpoint is removed in all of the given versions.
+    ///
+    /// Returns `false` if `versions` is empty.
+    pub fn is_removed_in(&self, versions: &[MatrixVersion]) -> bool {
+        match (self.removed, versions.iter().min()) {
+            (Some(removed), Some(oldest)) => removed <= *oldest,
+            _ => false,
+        }
+    }
+}
+
+/// Serializes the given value as JSON into a new `T`.
+#[doc(hidden)]
+pub fn serialize_json_body<T: Default + BufMut>(
+    value: &impl serde::Serialize,
+) -> Result<T, serde_json::Error> {
+    let mut body = T::default();
+    serde_json::to_writer(bytes::buf::BufMutExt::writer(&mut body), value)?;
+    Ok(body)
 }
 
 #[doc(hidden)]
@@ -0,0 +1,99 @@
+use std::collections::BTreeMap;
+
+use ruma_api::{error::IntoHttpError, AuthScheme, IncomingRequest as _, OutgoingRequest};
+
+mod query_token {
+    use ruma_api::ruma_api;
+
+    ruma_api! {
+        metadata: {
+            description: "Does something.",
+            method: GET,
+            name: "query_token",
+            path: "/_matrix/app/v1/users/:user_id",
+            rate_limited: false,
+            authentication: QueryOnlyAccessToken,
+        }
+
+        request: {
+            #[ruma_api(path)]
+            pub user_id: String,
+        }
+
+        response: {}
+    }
+}
+
+mod query_token_with_query_map {
+    use std::collections::BTreeMap;
+
+    use ruma_api::ruma_api;
+
+    ruma_api! {
+        metadata: {
+            description: "Does something.",
+            method: GET,
+            name: "query_token_with_query_map",
+            path: "/_matrix/app/v1/thirdparty/user/:protocol",
+            rate_limited: false,
+            authentication: QueryOnlyAccessToken,
+        }
+
+        request: {
+            #[ruma_api(path)]
+            pub protocol: String,
+
+            #[ruma_api(query_map)]
+            pub fields: BTreeMap<String, String>,
+        }
+
+        response: {}
+    }
+}
+
+#[test]
+fn metadata() {
+    assert_eq!(
+        <query_token::Request as OutgoingRequest>::METADATA.authentication,
+        AuthScheme::QueryOnlyAccessToken
+    );
+    assert!(<query_token::Request as OutgoingRequest>::METADATA
+        .authentication
+        .requires_authentication());
+}
+
+#[test]
+fn access_token_in_query() {
+    let req = query_token::Request { user_id: "@bot:example.org".into() };
+    let http_req =
+        req.try_into_http_request::<Vec<u8>>("https://appservice.tld", Some("hs_token")).unwrap();
+
+    assert_eq!(http_req.uri().path(), "/_matrix/app/v1/users/%40bot%3Aexample%2Eorg");
+    assert_eq!(http_req.uri().query(), Some("access_token=hs%5Ftoken"));
+    assert!(http_req.headers().get(http::header::AUTHORIZATION).is_none());
+}
+
+#[test]
+fn access_token_required() {
+    let req = query_token::Request { user_id: "@bot:example.org".into() };
+
+    match req.try_into_http_request::<Vec<u8>>("https://appservice.tld", None) {
+        Err(IntoHttpError::NeedsAuthentication) => {}
+        res => panic!("expected `NeedsAuthentication`, got {:?}", res),
+    }
+}
+
+#[test]
+fn access_token_appended_to_query_map() {
+    let mut fields = BTreeMap::new();
+    fields.insert("network".to_owned(), "irc".to_owned());
+    let req = query_token_with_query_map::Request { protocol: "irc".into(), fields };
+
+    let http_req =
+        req.try_into_http_request::<Vec<u8>>("https://appservice.tld", Some("hs_token")).unwrap();
+    assert_eq!(http_req.uri().query(), Some("network=irc&access_token=hs%5Ftoken"));
+
+    let req = query_token_with_query_map::Request::try_from_http_request(http_req).unwrap();
+    assert_eq!(req.fields.len(), 1);
+    assert_eq!(req.fields["network"], "irc");
+}
@@ -0,0 +1,55 @@
+use ruma_api::{
+    error::{FromHttpResponseError, ServerError},
+    ruma_api, IncomingResponse as _,
+};
+
+ruma_api! {
+    metadata: {
+        description: "Does something.",
+        method: GET,
+        name: "my_endpoint",
+        path: "/_matrix/foo",
+        rate_limited: false,
+        requires_authentication: false,
+    }
+
+    request: {}
+
+    response: {
+        pub hello: String,
+    }
+}
+
+const HTML_BODY: &str = "<html><body><h1>500 Internal Server Error</h1></body></html>";
+
+#[test]
+fn html_error_page_is_unknown_server_error() {
+    let http_response = http::Response::builder()
+        .status(http::StatusCode::INTERNAL_SERVER_ERROR)
+        .header(http::header::CONTENT_TYPE, "text/html")
+        .body(HTML_BODY.as_bytes().to_vec())
+        .unwrap();
+
+    match Response::try_from_http_response(http_response) {
+        Err(FromHttpResponseError::Http(ServerError::Unknown(err))) => {
+            let response = err.http_response();
+            assert_eq!(response.status(), http::StatusCode::INTERNAL_SERVER_ERROR);
+            assert_eq!(response.body().as_slice(), HTML_BODY.as_bytes());
+        }
+        other => panic!("unexpected result: {:?}", other.map(|res| res.hello)),
+    }
+}
+
+#[test]
+fn invalid_success_body_keeps_raw_body() {
+    let http_response =
+        http::Response::builder().status(http::StatusCode::OK).body(b"{}".to_vec()).unwrap();
+
+    match Response::try_from_http_response(http_response) {
+        Err(FromHttpResponseError::Deserialization(err)) => {
+            assert_eq!(err.http_response().body().as_slice(), b"{}");
+            assert!(err.to_string().contains("hello"));
+        }
+        other => panic!("unexpected result: {:?}", other.map(|res| res.hello)),
+    }
+}
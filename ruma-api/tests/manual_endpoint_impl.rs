@@ -1,6 +1,7 @@
 //! PUT /_matrix/client/r0/directory/room/:room_alias
-use std::{convert::TryFrom, ops::Deref};
+use std::ops::Deref;
 
+use bytes::{buf::BufMutExt, BufMut};
 use http::{header::CONTENT_TYPE, method::Method};
 use ruma_identifiers::{RoomAliasId, RoomId};
 use serde::{Deserialize, Serialize};
@@ -10,7 +11,8 @@ use ruma_api::{
         FromHttpRequestError, FromHttpResponseError, IntoHttpError, RequestDeserializationError,
         ResponseDeserializationError, ServerError, Void,
     },
-    IncomingRequest, Metadata, Outgoing, OutgoingRequest,
+    AuthScheme, IncomingRequest, IncomingResponse, Metadata, Outgoing, OutgoingRequest,
+    OutgoingResponse, VersionHistory,
 };
 
 /// A request to create a new room alias.
@@ -30,7 +32,8 @@ const METADATA: Metadata = Metadata {
     name: "create_alias",
     path: "/_matrix/client/r0/directory/room/:room_alias",
     rate_limited: false,
-    requires_authentication: false,
+    authentication: AuthScheme::None,
+    history: VersionHistory::UNVERSIONED,
 };
 
 impl OutgoingRequest for Request {
@@ -39,20 +42,22 @@ impl OutgoingRequest for Request {
 
     const METADATA: Metadata = METADATA;
 
-    fn try_into_http_request(
+    fn try_into_http_request<T: Default + BufMut>(
         self,
         base_url: &str,
         _access_token: Option<&str>,
-    ) -> Result<http::Request<Vec<u8>>, IntoHttpError> {
+    ) -> Result<http::Request<T>, IntoHttpError> {
         let url = (base_url.to_owned() + METADATA.path)
             .replace(":room_alias", &self.room_alias.to_string());
 
         let request_body = RequestBody { room_id: self.room_id };
+        let mut body = T::default();
+        serde_json::to_writer((&mut body).writer(), &request_body)?;
 
         let http_request = http::Request::builder()
             .method(METADATA.method)
             .uri(url)
-            .body(serde_json::to_vec(&request_body)?)
+            .body(body)
             // this cannot fail because we don't give user-supplied data to any of the
             // builder methods
             .unwrap();
@@ -66,13 +71,11 @@ impl IncomingRequest for Request {
     type OutgoingResponse = Response;
 
     const METADATA: Metadata = METADATA;
-}
-
-impl TryFrom<http::Request<Vec<u8>>> for Request {
-    type Error = FromHttpRequestError;
 
-    fn try_from(request: http::Request<Vec<u8>>) -> Result<Self, Self::Error> {
-        let request_body: RequestBody = match serde_json::from_slice(request.body().as_slice()) {
+    fn try_from_http_request<T: AsRef<[u8]>>(
+        request: http::Request<T>,
+    ) -> Result<Self, FromHttpRequestError> {
+        let request_body: RequestBody = match serde_json::from_slice(request.body().as_ref()) {
             Ok(body) => body,
             Err(err) => {
                 return Err(RequestDeserializationError::new(err, request).into());
@@ -109,10 +112,12 @@ impl Outgoing for Response {
     type Incoming = Self;
 }
 
-impl TryFrom<http::Response<Vec<u8>>> for Response {
-    type Error = FromHttpResponseError<Void>;
+impl IncomingResponse for Response {
+    type EndpointError = Void;
 
-    fn try_from(http_response: http::Response<Vec<u8>>) -> Result<Response, Self::Error> {
+    fn try_from_http_response<T: AsRef<[u8]>>(
+        http_response: http::Response<T>,
+    ) -> Result<Self, FromHttpResponseError<Void>> {
         if http_response.status().as_u16() < 400 {
             Ok(Response)
         } else {
@@ -123,14 +128,15 @@ impl TryFrom<http::Response<Vec<u8>>> for Response {
     }
 }
 
-impl TryFrom<Response> for http::Response<Vec<u8>> {
-    type Error = IntoHttpError;
+impl OutgoingResponse for Response {
+    fn try_into_http_response<T: Default + BufMut>(
+        self,
+    ) -> Result<http::Response<T>, IntoHttpError> {
+        let mut body = T::default();
+        body.put_slice(b"{}");
 
-    fn try_from(_: Response) -> Result<http::Response<Vec<u8>>, Self::Error> {
-        let response = http::Response::builder()
-            .header(CONTENT_TYPE, "application/json")
-            .body(b"{}".to_vec())
-            .unwrap();
+        let response =
+            http::Response::builder().header(CONTENT_TYPE, "application/json").body(body).unwrap();
 
         Ok(response)
     }
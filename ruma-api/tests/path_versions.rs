@@ -0,0 +1,137 @@
+use ruma_api::{IncomingRequest as _, MatrixVersion, OutgoingRequest};
+
+mod versioned {
+    use ruma_api::ruma_api;
+
+    ruma_api! {
+        metadata: {
+            description: "Does something.",
+            method: GET,
+            name: "versioned",
+            path: [
+                (1.0, "/_matrix/client/r0/rooms/:room_id/thing"),
+                (1.1, "/_matrix/client/v3/rooms/:room_id/thing"),
+            ],
+            rate_limited: false,
+            requires_authentication: false,
+            added: 1.0,
+            deprecated: 1.2,
+        }
+
+        request: {
+            #[ruma_api(path)]
+            pub room_id: String,
+        }
+
+        response: {}
+    }
+}
+
+mod unversioned {
+    use ruma_api::ruma_api;
+
+    ruma_api! {
+        metadata: {
+            description: "Does something.",
+            method: GET,
+            name: "unversioned",
+            path: "/_matrix/custom/endpoint",
+            rate_limited: false,
+            requires_authentication: false,
+        }
+
+        request: {}
+        response: {}
+    }
+}
+
+fn versioned_request() -> versioned::Request {
+    versioned::Request { room_id: "!room".into() }
+}
+
+#[test]
+fn newest_supported_path() {
+    let http_req = versioned_request()
+        .try_into_http_request_for_versions::<Vec<u8>>(
+            "https://homeserver.tld",
+            None,
+            &[MatrixVersion::V1_0, MatrixVersion::V1_1],
+        )
+        .unwrap();
+    assert_eq!(http_req.uri().path(), "/_matrix/client/v3/rooms/%21room/thing");
+
+    let http_req = versioned_request()
+        .try_into_http_request_for_versions::<Vec<u8>>(
+            "https://homeserver.tld",
+            None,
+            &[MatrixVersion::V1_0],
+        )
+        .unwrap();
+    assert_eq!(http_req.uri().path(), "/_matrix/client/r0/rooms/%21room/thing");
+
+    // Servers that only support versions released after this crate get the newest known path.
+    let http_req = versioned_request()
+        .try_into_http_request_for_versions::<Vec<u8>>(
+            "https://homeserver.tld",
+            None,
+            &[MatrixVersion::Newer { major: 1, minor: 3 }],
+        )
+        .unwrap();
+    assert_eq!(http_req.uri().path(), "/_matrix/client/v3/rooms/%21room/thing");
+
+    // Without version information, the oldest path is used.
+    let http_req = versioned_request()
+        .try_into_http_request::<Vec<u8>>("https://homeserver.tld", None)
+        .unwrap();
+    assert_eq!(http_req.uri().path(), "/_matrix/client/r0/rooms/%21room/thing");
+}
+
+#[test]
+fn incoming_on_any_path() {
+    for path in
+        &["/_matrix/client/r0/rooms/%21room/thing", "/_matrix/client/v3/rooms/%21room/thing"]
+    {
+        let http_req = http::Request::builder().uri(*path).body(Vec::new()).unwrap();
+        let req = versioned::Request::try_from_http_request(http_req).unwrap();
+        assert_eq!(req.room_id, "!room");
+    }
+}
+
+#[test]
+fn history() {
+    let history = <versioned::Request as OutgoingRequest>::METADATA.history;
+    assert_eq!(history.added, Some(MatrixVersion::V1_0));
+    assert!(!history.is_deprecated_in(&[MatrixVersion::V1_1]));
+    assert!(history.is_deprecated_in(&[MatrixVersion::V1_2]));
+    assert!(!history.is_removed_in(&[MatrixVersion::V1_2]));
+
+    assert_eq!(MatrixVersion::from_versions_str("r0.6.1"), Some(MatrixVersion::V1_0));
+    assert_eq!(MatrixVersion::from_versions_str("v1.1"), Some(MatrixVersion::V1_1));
+    assert_eq!(
+        MatrixVersion::from_versions_str("v1.99"),
+        Some(MatrixVersion::Newer { major: 1, minor: 99 })
+    );
+    assert_eq!(
+        MatrixVersion::from_versions_str("v2.0"),
+        Some(MatrixVersion::Newer { major: 2, minor: 0 })
+    );
+    assert!(MatrixVersion::Newer { major: 1, minor: 3 } > MatrixVersion::V1_2);
+    assert_eq!(MatrixVersion::from_versions_str("v1.0"), None);
+    assert_eq!(MatrixVersion::from_versions_str("v1"), None);
+    assert_eq!(MatrixVersion::from_versions_str("not a version"), None);
+}
+
+#[test]
+fn unversioned_path() {
+    let history = <unversioned::Request as OutgoingRequest>::METADATA.history;
+    assert!(history.paths.is_empty());
+
+    let http_req = unversioned::Request {}
+        .try_into_http_request_for_versions::<Vec<u8>>(
+            "https://homeserver.tld",
+            None,
+            &[MatrixVersion::V1_1],
+        )
+        .unwrap();
+    assert_eq!(http_req.uri().path(), "/_matrix/custom/endpoint");
+}
@@ -0,0 +1,56 @@
+//! The conversions to and from `http` types work with any body buffer, e.g. `bytes::BytesMut` and
+//! `bytes::Bytes`. `raw_body` fields are still `Vec<u8>` though: they are copied into the body
+//! buffer once when sending and out of it when receiving. Passing byte streams or `hyper` bodies
+//! through without copying them would need a generic `raw_body` field type, which is out of scope
+//! for now.
+
+use bytes::{Bytes, BytesMut};
+use ruma_api::{
+    ruma_api, IncomingRequest as _, IncomingResponse as _, OutgoingRequest as _,
+    OutgoingResponse as _,
+};
+
+ruma_api! {
+    metadata: {
+        description: "Uploads or downloads a file.",
+        method: PUT,
+        name: "raw_body",
+        path: "/_matrix/media/r0/file",
+        rate_limited: false,
+        requires_authentication: false,
+    }
+
+    request: {
+        #[ruma_api(raw_body)]
+        pub file: Vec<u8>,
+    }
+
+    response: {
+        #[ruma_api(raw_body)]
+        pub file: Vec<u8>,
+    }
+}
+
+fn file() -> Vec<u8> {
+    vec![0xff; 1024]
+}
+
+#[test]
+fn request_body_in_bytes_mut() {
+    let http_req = Request { file: file() }
+        .try_into_http_request::<BytesMut>("https://homeserver.tld", None)
+        .unwrap();
+    assert_eq!(http_req.body().as_ref(), file().as_slice());
+
+    let req = Request::try_from_http_request(http_req.map(BytesMut::freeze)).unwrap();
+    assert_eq!(req.file, file());
+}
+
+#[test]
+fn response_body_in_bytes_mut() {
+    let http_res = Response { file: file() }.try_into_http_response::<BytesMut>().unwrap();
+    assert_eq!(http_res.body().as_ref(), file().as_slice());
+
+    let res = Response::try_from_http_response::<Bytes>(http_res.map(BytesMut::freeze)).unwrap();
+    assert_eq!(res.file, file());
+}
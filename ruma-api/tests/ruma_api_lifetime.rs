@@ -1,3 +1,8 @@
+use std::any::Any;
+
+use ruma_api::{IncomingRequest as _, OutgoingRequest as _};
+use ruma_identifiers::{room_alias_id, room_id, DeviceId};
+
 #[allow(unused)]
 #[derive(Copy, Clone, Debug, ruma_api::Outgoing, serde::Serialize)]
 pub struct OtherThing<'t> {
@@ -159,3 +164,72 @@ mod query_fields {
         response: { }
     }
 }
+
+#[test]
+fn borrowed_requests_convert_to_owned_ones() {
+    let room_alias = room_alias_id!("#alias:example.org");
+    let room_id = room_id!("!room:example.org");
+    let req: empty_response::IncomingRequest =
+        empty_response::Request { room_alias: &room_alias, room_id: &room_id }.into();
+    assert_eq!(req.room_alias, room_alias);
+    assert_eq!(req.room_id, room_id);
+
+    let device_id = <&DeviceId>::from("DEVICE");
+    let req = nested_types::IncomingRequest::from(nested_types::Request {
+        room_alias: &[Some(&room_alias), None],
+        room_id: &[Some(Some(device_id)), Some(None)],
+    });
+    assert_eq!(req.room_alias, vec![Some(room_alias), None]);
+    assert_eq!(req.room_id, vec![Some(Some(device_id.to_owned())), Some(None)]);
+
+    let req = full_request_response::IncomingRequest::from(full_request_response::Request {
+        abc: "abc",
+        thing: "thing",
+        stuff: "text/plain",
+        more: OtherThing { some: "some", t: &[1, 2] },
+    });
+    assert_eq!(req.abc, "abc");
+    assert_eq!(req.more.some, "some");
+    assert_eq!(req.more.t, vec![1, 2]);
+}
+
+#[test]
+fn incoming_requests_can_be_stored() {
+    let room_alias = room_alias_id!("#alias:example.org");
+    let room_id = room_id!("!room:example.org");
+    let mut registry: Vec<Box<dyn Any + Send>> = Vec::new();
+
+    for i in 0..5 {
+        let since = format!("token_{}", i);
+
+        let http_req = empty_response::Request { room_alias: &room_alias, room_id: &room_id }
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+        registry.push(Box::new(
+            empty_response::IncomingRequest::try_from_http_request(http_req).unwrap(),
+        ));
+
+        let http_req = query_fields::Request { limit: Some(i), since: Some(&since), server: None }
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", None)
+            .unwrap();
+        registry.push(Box::new(
+            query_fields::IncomingRequest::try_from_http_request(http_req).unwrap(),
+        ));
+    }
+
+    // Nothing in the registry borrows from the data the requests were created from.
+    drop(room_alias);
+    drop(room_id);
+
+    assert_eq!(registry.len(), 10);
+    for (i, pair) in registry.chunks(2).enumerate() {
+        let req = pair[0].downcast_ref::<empty_response::IncomingRequest>().unwrap();
+        assert_eq!(req.room_alias.as_str(), "#alias:example.org");
+        assert_eq!(req.room_id.as_str(), "!room:example.org");
+
+        let req = pair[1].downcast_ref::<query_fields::IncomingRequest>().unwrap();
+        assert_eq!(req.limit, Some(i));
+        assert_eq!(req.since, Some(format!("token_{}", i)));
+        assert_eq!(req.server, None);
+    }
+}
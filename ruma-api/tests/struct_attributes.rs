@@ -0,0 +1,34 @@
+use ruma_api::ruma_api;
+
+ruma_api! {
+    metadata: {
+        description: "Does something.",
+        method: POST,
+        name: "struct_attributes",
+        path: "/_matrix/foo/:bar/",
+        rate_limited: false,
+        requires_authentication: false,
+    }
+
+    #[derive(PartialEq, Eq)]
+    request: {
+        #[ruma_api(path)]
+        pub bar: String,
+
+        pub baz: u32,
+    }
+
+    #[derive(PartialEq, Eq)]
+    response: {
+        pub value: String,
+    }
+}
+
+#[test]
+fn derives_are_applied() {
+    let req = Request { bar: "bar".into(), baz: 1 };
+    assert_eq!(req.clone(), Request { bar: "bar".into(), baz: 1 });
+    assert_ne!(req, Request { bar: "bar".into(), baz: 2 });
+
+    assert_eq!(Response { value: "value".into() }, Response { value: "value".into() });
+}
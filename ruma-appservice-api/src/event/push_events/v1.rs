@@ -11,7 +11,7 @@ ruma_api! {
         name: "push_events",
         path: "/_matrix/app/v1/transactions/:txn_id",
         rate_limited: false,
-        requires_authentication: true,
+        authentication: QueryOnlyAccessToken,
     }
 
     request: {
@@ -22,10 +22,10 @@ ruma_api! {
         pub txn_id: &'a str,
 
         /// A list of events.
-        #[ruma_api(body)]
         pub events: &'a [Raw<AnyEvent>],
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 }
 
@@ -42,3 +42,89 @@ impl Response {
         Self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::{IncomingRequest as _, OutgoingRequest as _};
+    use ruma_common::Raw;
+    use ruma_events::{AnyEvent, AnyMessageEvent, AnyStateEvent};
+    use ruma_identifiers::room_id;
+    use serde_json::{
+        from_slice as from_json_slice, json, to_vec as to_json_vec, Value as JsonValue,
+    };
+
+    use super::{IncomingRequest, Request};
+
+    #[test]
+    fn incoming_transaction() {
+        let body = json!({
+            "events": [
+                {
+                    "content": {
+                        "body": "hello",
+                        "msgtype": "m.text"
+                    },
+                    "event_id": "$message:example.org",
+                    "origin_server_ts": 1,
+                    "room_id": "!room:example.org",
+                    "sender": "@alice:example.org",
+                    "type": "m.room.message"
+                },
+                {
+                    "content": {
+                        "membership": "join"
+                    },
+                    "event_id": "$member:example.org",
+                    "origin_server_ts": 2,
+                    "room_id": "!room:example.org",
+                    "sender": "@_bridge_bob:example.org",
+                    "state_key": "@_bridge_bob:example.org",
+                    "type": "m.room.member"
+                }
+            ]
+        });
+        let http_req = http::Request::builder()
+            .method(http::Method::PUT)
+            .uri("https://appservice.example.org/_matrix/app/v1/transactions/txn1?access_token=hs_token")
+            .body(to_json_vec(&body).unwrap())
+            .unwrap();
+
+        let req = IncomingRequest::try_from_http_request(http_req).unwrap();
+        assert_eq!(req.txn_id, "txn1");
+        assert_eq!(req.events.len(), 2);
+
+        match req.events[0].deserialize().unwrap() {
+            AnyEvent::Message(AnyMessageEvent::RoomMessage(ev)) => {
+                assert_eq!(ev.room_id, room_id!("!room:example.org"));
+            }
+            ev => panic!("unexpected event: {:?}", ev),
+        }
+        match req.events[1].deserialize().unwrap() {
+            AnyEvent::State(AnyStateEvent::RoomMember(ev)) => {
+                assert_eq!(ev.state_key, "@_bridge_bob:example.org");
+            }
+            ev => panic!("unexpected event: {:?}", ev),
+        }
+    }
+
+    #[test]
+    fn outgoing_transaction() {
+        let events: Vec<Raw<AnyEvent>> = vec![serde_json::from_value(json!({
+            "content": {},
+            "type": "m.dummy"
+        }))
+        .unwrap()];
+
+        let http_req = Request::new("txn1", &events)
+            .try_into_http_request::<Vec<u8>>("https://appservice.example.org", Some("hs_token"))
+            .unwrap();
+        assert_eq!(
+            http_req.uri(),
+            "https://appservice.example.org/_matrix/app/v1/transactions/txn1?access_token=hs%5Ftoken"
+        );
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({ "events": [{ "content": {}, "type": "m.dummy" }] })
+        );
+    }
+}
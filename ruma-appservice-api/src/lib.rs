@@ -7,3 +7,169 @@
 pub mod event;
 pub mod query;
 pub mod thirdparty;
+
+use serde::{Deserialize, Serialize};
+
+/// A namespace defined by an application service.
+///
+/// Used for [appservice registration](https://matrix.org/docs/spec/application_service/r0.1.2#registration).
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Namespace {
+    /// Whether this application service has exclusive access to events within this namespace.
+    pub exclusive: bool,
+
+    /// A regular expression defining which values this namespace includes.
+    pub regex: String,
+}
+
+impl Namespace {
+    /// Creates a new `Namespace` with the given exclusivity and regex pattern.
+    pub fn new(exclusive: bool, regex: String) -> Self {
+        Self { exclusive, regex }
+    }
+}
+
+/// Namespaces defined by an application service.
+///
+/// Used for [appservice registration](https://matrix.org/docs/spec/application_service/r0.1.2#registration).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Namespaces {
+    /// Events which are sent from certain users.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub users: Vec<Namespace>,
+
+    /// Events which are sent in rooms with certain room aliases.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<Namespace>,
+
+    /// Events which are sent in rooms with certain room IDs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rooms: Vec<Namespace>,
+}
+
+impl Namespaces {
+    /// Creates a new `Namespaces` without any namespaces.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Information required in the registration yaml file that a homeserver needs.
+///
+/// To create an instance of this type, first create a `RegistrationInit` and convert it via
+/// `Registration::from` / `.into()`.
+///
+/// Used for [appservice registration](https://matrix.org/docs/spec/application_service/r0.1.2#registration).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Registration {
+    /// A unique, user-defined ID of the application service which will never change.
+    pub id: String,
+
+    /// The URL for the application service.
+    pub url: String,
+
+    /// A unique token for application services to use to authenticate requests to homeservers.
+    pub as_token: String,
+
+    /// A unique token for homeservers to use to authenticate requests to application services.
+    pub hs_token: String,
+
+    /// The localpart of the user associated with the application service.
+    pub sender_localpart: String,
+
+    /// A list of users, aliases and rooms namespaces that the application service controls.
+    pub namespaces: Namespaces,
+
+    /// Whether requests from masqueraded users are rate-limited.
+    ///
+    /// The sender is excluded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limited: Option<bool>,
+
+    /// The external protocols which the application service provides (e.g. IRC).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocols: Option<Vec<String>>,
+}
+
+/// Initial set of fields of `Registration`.
+///
+/// This struct will not be updated even if additional fields are added to `Registration` in a new
+/// (non-breaking) release of the Matrix specification.
+#[derive(Debug)]
+pub struct RegistrationInit {
+    /// A unique, user-defined ID of the application service which will never change.
+    pub id: String,
+
+    /// The URL for the application service.
+    pub url: String,
+
+    /// A unique token for application services to use to authenticate requests to homeservers.
+    pub as_token: String,
+
+    /// A unique token for homeservers to use to authenticate requests to application services.
+    pub hs_token: String,
+
+    /// The localpart of the user associated with the application service.
+    pub sender_localpart: String,
+
+    /// A list of users, aliases and rooms namespaces that the application service controls.
+    pub namespaces: Namespaces,
+}
+
+impl From<RegistrationInit> for Registration {
+    fn from(init: RegistrationInit) -> Self {
+        let RegistrationInit { id, url, as_token, hs_token, sender_localpart, namespaces } = init;
+        Self {
+            id,
+            url,
+            as_token,
+            hs_token,
+            sender_localpart,
+            namespaces,
+            rate_limited: None,
+            protocols: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::{Namespace, Namespaces, Registration};
+
+    #[test]
+    fn registration_serde() {
+        let json = json!({
+            "id": "irc_bridge",
+            "url": "http://127.0.0.1:1234",
+            "as_token": "30c05ae90a248a4188e620216fa72e349803310ec83e2a77b34fe90be6081f46",
+            "hs_token": "312df522183efd404ec1cd22d2ffa4bbc76a8c1ccf541dd692eef281356bb74e",
+            "sender_localpart": "_irc_bot",
+            "namespaces": {
+                "users": [
+                    { "exclusive": true, "regex": "@_irc_bridge_.*" }
+                ],
+                "aliases": [
+                    { "exclusive": false, "regex": "#_irc_bridge_.*" }
+                ]
+            },
+            "protocols": ["irc"]
+        });
+
+        let registration = from_json_value::<Registration>(json.clone()).unwrap();
+        assert_eq!(registration.sender_localpart, "_irc_bot");
+        assert_eq!(
+            registration.namespaces,
+            Namespaces {
+                users: vec![Namespace::new(true, "@_irc_bridge_.*".into())],
+                aliases: vec![Namespace::new(false, "#_irc_bridge_.*".into())],
+                rooms: Vec::new(),
+            }
+        );
+        assert_eq!(registration.rate_limited, None);
+        assert_eq!(registration.protocols, Some(vec!["irc".to_owned()]));
+
+        assert_eq!(to_json_value(registration).unwrap(), json);
+    }
+}
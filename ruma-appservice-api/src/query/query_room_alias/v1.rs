@@ -10,15 +10,17 @@ ruma_api! {
         name: "query_room_alias",
         path: "/_matrix/app/v1/rooms/:room_alias",
         rate_limited: false,
-        requires_authentication: true,
+        authentication: QueryOnlyAccessToken,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The room alias being queried.
         #[ruma_api(path)]
         pub room_alias: &'a RoomAliasId,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 }
 
@@ -10,15 +10,17 @@ ruma_api! {
         name: "query_user_id",
         path: "/_matrix/app/v1/users/:user_id",
         rate_limited: false,
-        requires_authentication: true,
+        authentication: QueryOnlyAccessToken,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The user ID being queried.
         #[ruma_api(path)]
         pub user_id: &'a UserId,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 }
 
@@ -11,9 +11,10 @@ ruma_api! {
         name: "get_location_for_room_alias",
         path: "/_matrix/app/v1/thirdparty/location",
         rate_limited: false,
-        requires_authentication: true,
+        authentication: QueryOnlyAccessToken,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The Matrix room alias to look up.
         #[ruma_api(query)]
@@ -10,9 +10,10 @@ ruma_api! {
         name: "get_protocol",
         path: "/_matrix/app/v1/thirdparty/protocol/:protocol",
         rate_limited: false,
-        requires_authentication: true,
+        authentication: QueryOnlyAccessToken,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The name of the protocol.
         #[ruma_api(path)]
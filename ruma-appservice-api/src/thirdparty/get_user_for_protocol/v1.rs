@@ -12,9 +12,10 @@ ruma_api! {
         name: "get_user_for_protocol",
         path: "/_matrix/app/v1/thirdparty/user/:protocol",
         rate_limited: false,
-        requires_authentication: true,
+        authentication: QueryOnlyAccessToken,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The protocol used to communicate to the third party network.
         #[ruma_api(path)]
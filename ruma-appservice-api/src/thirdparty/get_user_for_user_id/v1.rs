@@ -11,9 +11,10 @@ ruma_api! {
         name: "get_user_for_user_id",
         path: "/_matrix/app/v1/thirdparty/user",
         rate_limited: false,
-        requires_authentication: true,
+        authentication: QueryOnlyAccessToken,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The Matrix User ID to look up.
         #[ruma_api(query)]
@@ -190,10 +190,10 @@ pub struct Error {
 }
 
 impl EndpointError for Error {
-    fn try_from_response(
-        response: http::Response<Vec<u8>>,
+    fn try_from_response<T: AsRef<[u8]>>(
+        response: http::Response<T>,
     ) -> Result<Self, ResponseDeserializationError> {
-        match from_json_slice::<ErrorBody>(response.body()) {
+        match from_json_slice::<ErrorBody>(response.body().as_ref()) {
             Ok(error_body) => Ok(error_body.into_error(response.status())),
             Err(de_error) => Err(ResponseDeserializationError::new(de_error, response)),
         }
@@ -230,3 +230,39 @@ impl From<Error> for http::Response<Vec<u8>> {
             .unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::EndpointError;
+
+    use super::{Error, ErrorKind};
+
+    #[test]
+    fn deserialize_json_error() {
+        let response = http::Response::builder()
+            .status(http::StatusCode::FORBIDDEN)
+            .body(
+                br#"{ "errcode": "M_FORBIDDEN", "error": "You are not invited to this room." }"#
+                    .to_vec(),
+            )
+            .unwrap();
+
+        let error = Error::try_from_response(response).unwrap();
+        assert_eq!(error.kind, ErrorKind::Forbidden);
+        assert_eq!(error.message, "You are not invited to this room.");
+        assert_eq!(error.status_code, http::StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn html_error_keeps_raw_response() {
+        let body = b"<html><body>502 Bad Gateway</body></html>".to_vec();
+        let response = http::Response::builder()
+            .status(http::StatusCode::BAD_GATEWAY)
+            .body(body.clone())
+            .unwrap();
+
+        let error = Error::try_from_response(response).unwrap_err();
+        assert_eq!(error.http_response().status(), http::StatusCode::BAD_GATEWAY);
+        assert_eq!(*error.http_response().body(), body);
+    }
+}
@@ -12,6 +12,8 @@ pub mod device;
 pub mod directory;
 pub mod filter;
 pub mod keys;
+#[cfg(feature = "unstable-pre-spec")]
+pub mod knock;
 pub mod media;
 pub mod membership;
 pub mod message;
@@ -25,6 +27,7 @@ pub mod room;
 pub mod search;
 pub mod server;
 pub mod session;
+pub mod space;
 pub mod state;
 pub mod sync;
 pub mod tag;
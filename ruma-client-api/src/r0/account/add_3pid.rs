@@ -14,6 +14,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// Additional information for the User-Interactive Authentication API.
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -26,6 +27,7 @@ ruma_api! {
         pub sid: &'a str,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: UiaaResponse
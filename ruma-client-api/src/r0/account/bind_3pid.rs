@@ -27,6 +27,7 @@ ruma_api! {
         pub sid: String,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
@@ -32,6 +32,7 @@ ruma_api! {
         pub auth: Option<AuthData>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: UiaaResponse
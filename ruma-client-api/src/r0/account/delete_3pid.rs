@@ -15,6 +15,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// Identity server to delete from.
         #[serde(skip_serializing_if = "Option::is_none")]
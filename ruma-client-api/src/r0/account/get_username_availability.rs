@@ -12,12 +12,14 @@ ruma_api! {
         requires_authentication: false,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The username to check the availability of.
         #[ruma_api(query)]
         pub username: String,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// A flag to indicate that the username is available.
         /// This should always be true when the server replies with 200 OK.
@@ -65,6 +65,8 @@ ruma_api! {
         pub inhibit_login: bool,
     }
 
+    #[derive(PartialEq, Eq)]
+    #[non_exhaustive]
     response: {
         /// An access token for the account.
         ///
@@ -84,6 +86,28 @@ ruma_api! {
     error: UiaaResponse
 }
 
+impl Request {
+    /// Creates a new `Request` with all parameters defaulted.
+    pub fn new() -> Self {
+        Self {
+            password: None,
+            username: None,
+            device_id: None,
+            initial_device_display_name: None,
+            auth: None,
+            kind: None,
+            inhibit_login: false,
+        }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given user ID.
+    pub fn new(user_id: UserId) -> Self {
+        Self { access_token: None, user_id, device_id: None }
+    }
+}
+
 /// The kind of account being registered.
 #[derive(Copy, Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -96,3 +120,27 @@ pub enum RegistrationKind {
     /// A regular user account
     User,
 }
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::{IncomingResponse as _, OutgoingResponse as _};
+    use ruma_identifiers::user_id;
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Response;
+
+    #[test]
+    fn response_constructor() {
+        let res: http::Response<Vec<u8>> =
+            Response::new(user_id!("@carl:example.org")).try_into_http_response().unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(res.body()).unwrap(),
+            json!({ "user_id": "@carl:example.org", "device_id": null })
+        );
+
+        assert_eq!(
+            Response::try_from_http_response(res).unwrap(),
+            Response::new(user_id!("@carl:example.org"))
+        );
+    }
+}
@@ -35,6 +35,7 @@ ruma_api! {
         pub identity_server_info: Option<IdentityServerInfo>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The session identifier given by the identity server.
         pub sid: String,
@@ -46,3 +47,49 @@ ruma_api! {
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given client secret, email address and send attempt
+    /// counter.
+    pub fn new(client_secret: String, email: String, send_attempt: UInt) -> Self {
+        Self { client_secret, email, send_attempt, next_link: None, identity_server_info: None }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given session identifier.
+    pub fn new(sid: String) -> Self {
+        Self { sid, submit_url: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::uint;
+    use ruma_api::OutgoingRequest as _;
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::{IdentityServerInfo, Request};
+
+    #[test]
+    fn serialize_request_with_identity_server_info() {
+        let mut req = Request::new("secret".into(), "alice@example.org".into(), uint!(1));
+        req.identity_server_info = Some(IdentityServerInfo {
+            id_server: "id.example.org".into(),
+            id_access_token: "id_token".into(),
+        });
+
+        let http_req =
+            req.try_into_http_request::<Vec<u8>>("https://homeserver.tld", None).unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({
+                "client_secret": "secret",
+                "email": "alice@example.org",
+                "send_attempt": 1,
+                "id_server": "id.example.org",
+                "id_access_token": "id_token",
+            })
+        );
+    }
+}
@@ -38,6 +38,7 @@ ruma_api! {
         pub identity_server_info: Option<IdentityServerInfo>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The session identifier given by the identity server.
         pub sid: String,
@@ -49,3 +50,56 @@ ruma_api! {
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given client secret, country code, phone number and send
+    /// attempt counter.
+    pub fn new(
+        client_secret: String,
+        country: String,
+        phone_number: String,
+        send_attempt: UInt,
+    ) -> Self {
+        Self {
+            client_secret,
+            country,
+            phone_number,
+            send_attempt,
+            next_link: None,
+            identity_server_info: None,
+        }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given session identifier.
+    pub fn new(sid: String) -> Self {
+        Self { sid, submit_url: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::uint;
+    use ruma_api::OutgoingRequest as _;
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+
+    #[test]
+    fn serialize_request_without_identity_server_info() {
+        let req = Request::new("secret".into(), "GB".into(), "07700900001".into(), uint!(2));
+
+        let http_req =
+            req.try_into_http_request::<Vec<u8>>("https://homeserver.tld", None).unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({
+                "client_secret": "secret",
+                "country": "GB",
+                "phone_number": "07700900001",
+                "send_attempt": 2,
+            })
+        );
+    }
+}
@@ -16,12 +16,14 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// User ID of authenticated user.
         #[ruma_api(path)]
         pub user_id: UserId,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// Access token for verifying user's identity.
         pub access_token: String,
@@ -40,9 +42,72 @@ ruma_api! {
     error: crate::Error
 }
 
+impl Request {
+    /// Creates a new `Request` with the given user ID.
+    pub fn new(user_id: UserId) -> Self {
+        Self { user_id }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given access token, token type, server name and
+    /// expiration duration.
+    pub fn new(
+        access_token: String,
+        token_type: TokenType,
+        matrix_server_name: Box<ServerName>,
+        expires_in: Duration,
+    ) -> Self {
+        Self { access_token, token_type, matrix_server_name, expires_in }
+    }
+}
+
 /// Access token types.
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum TokenType {
     /// Bearer token type
     Bearer,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{convert::TryFrom, time::Duration};
+
+    use ruma_api::{OutgoingRequest as _, OutgoingResponse as _};
+    use ruma_identifiers::{user_id, ServerName};
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::{Request, Response, TokenType};
+
+    #[test]
+    fn serialize_request_path() {
+        let http_req = Request::new(user_id!("@alice:example.org"))
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+        assert_eq!(
+            http_req.uri().path(),
+            "/_matrix/client/r0/user/%40alice%3Aexample%2Eorg/openid/request_token"
+        );
+    }
+
+    #[test]
+    fn serialize_expires_in_as_seconds() {
+        let res = Response::new(
+            "SomeT0kenHere".into(),
+            TokenType::Bearer,
+            <Box<ServerName>>::try_from("example.org").unwrap(),
+            Duration::from_secs(3600),
+        );
+
+        let http_res = res.try_into_http_response::<Vec<u8>>().unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_res.body()).unwrap(),
+            json!({
+                "access_token": "SomeT0kenHere",
+                "token_type": "Bearer",
+                "matrix_server_name": "example.org",
+                "expires_in": 3600,
+            })
+        );
+    }
+}
@@ -13,6 +13,7 @@ ruma_api! {
         requires_authentication: false,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// Client-generated secret string used to protect this session.
         pub client_secret: String,
@@ -31,6 +32,7 @@ ruma_api! {
         pub next_link: Option<String>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The session identifier given by the identity server.
         pub sid: String,
@@ -35,6 +35,7 @@ ruma_api! {
         pub identity_server_info: Option<IdentityServerInfo>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The session identifier given by the identity server.
         pub sid: String,
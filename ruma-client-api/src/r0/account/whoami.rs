@@ -1,6 +1,8 @@
 //! [GET /_matrix/client/r0/account/whoami](https://matrix.org/docs/spec/client_server/r0.6.0#get-matrix-client-r0-account-whoami)
 
 use ruma_api::ruma_api;
+#[cfg(feature = "unstable-pre-spec")]
+use ruma_identifiers::DeviceId;
 use ruma_identifiers::UserId;
 
 ruma_api! {
@@ -13,12 +15,94 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {}
 
+    #[derive(PartialEq, Eq)]
+    #[non_exhaustive]
     response: {
         /// The id of the user that owns the access token.
         pub user_id: UserId,
+
+        /// The device ID associated with the access token, if any.
+        #[cfg(feature = "unstable-pre-spec")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub device_id: Option<Box<DeviceId>>,
+
+        /// Whether the user is a guest, if known.
+        #[cfg(feature = "unstable-pre-spec")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub is_guest: Option<bool>,
     }
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates an empty `Request`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given user ID.
+    pub fn new(user_id: UserId) -> Self {
+        Self {
+            user_id,
+            #[cfg(feature = "unstable-pre-spec")]
+            device_id: None,
+            #[cfg(feature = "unstable-pre-spec")]
+            is_guest: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::{IncomingResponse as _, OutgoingResponse as _};
+    use ruma_identifiers::user_id;
+    use serde_json::{
+        from_slice as from_json_slice, json, to_vec as to_json_vec, Value as JsonValue,
+    };
+
+    use super::Response;
+
+    fn response(body: serde_json::Value) -> Response {
+        Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap())).unwrap()
+    }
+
+    #[test]
+    fn response_constructor() {
+        let res: http::Response<Vec<u8>> =
+            Response::new(user_id!("@carl:example.org")).try_into_http_response().unwrap();
+        let body = from_json_slice::<JsonValue>(res.body()).unwrap();
+        assert_eq!(body, json!({ "user_id": "@carl:example.org" }));
+
+        assert_eq!(response(body), Response::new(user_id!("@carl:example.org")));
+    }
+
+    #[cfg(feature = "unstable-pre-spec")]
+    #[test]
+    fn deserialize_response_without_device_id() {
+        let res = response(json!({ "user_id": "@carl:example.org" }));
+
+        assert_eq!(res.user_id, user_id!("@carl:example.org"));
+        assert_eq!(res.device_id, None);
+        assert_eq!(res.is_guest, None);
+    }
+
+    #[cfg(feature = "unstable-pre-spec")]
+    #[test]
+    fn deserialize_response_with_device_id() {
+        let res = response(json!({
+            "user_id": "@carl:example.org",
+            "device_id": "ABCDEFG",
+            "is_guest": false,
+        }));
+
+        assert_eq!(res.user_id, user_id!("@carl:example.org"));
+        assert_eq!(res.device_id.as_deref().map(|id| id.as_str()), Some("ABCDEFG"));
+        assert_eq!(res.is_guest, Some(false));
+    }
+}
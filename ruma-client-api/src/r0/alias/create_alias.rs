@@ -13,6 +13,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The room alias to set.
         #[ruma_api(path)]
@@ -22,7 +23,50 @@ ruma_api! {
         pub room_id: &'a RoomId,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
 }
+
+impl<'a> Request<'a> {
+    /// Creates a new `Request` with the given room alias and room ID.
+    pub fn new(room_alias: &'a RoomAliasId, room_id: &'a RoomId) -> Self {
+        Self { room_alias, room_id }
+    }
+}
+
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::OutgoingRequest as _;
+    use ruma_identifiers::{room_alias_id, room_id};
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+
+    #[test]
+    fn serialize_request() {
+        let room_alias = room_alias_id!("#test:server.org");
+        let room_id = room_id!("!room:server.org");
+        let http_req = Request::new(&room_alias, &room_id)
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+
+        assert_eq!(http_req.method(), http::Method::PUT);
+        assert_eq!(
+            http_req.uri().path(),
+            "/_matrix/client/r0/directory/room/%23test%3Aserver%2Eorg"
+        );
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({ "room_id": "!room:server.org" })
+        );
+    }
+}
@@ -13,13 +13,54 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The room alias to remove.
         #[ruma_api(path)]
         pub room_alias: &'a RoomAliasId,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
 }
+
+impl<'a> Request<'a> {
+    /// Creates a new `Request` with the given room alias.
+    pub fn new(room_alias: &'a RoomAliasId) -> Self {
+        Self { room_alias }
+    }
+}
+
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::{IncomingRequest as _, OutgoingRequest as _};
+    use ruma_identifiers::room_alias_id;
+
+    use super::{IncomingRequest, Request};
+
+    #[test]
+    fn request_roundtrip() {
+        let room_alias = room_alias_id!("#test:server.org");
+        let http_req = Request::new(&room_alias)
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+
+        assert_eq!(http_req.method(), http::Method::DELETE);
+        assert_eq!(
+            http_req.uri().path(),
+            "/_matrix/client/r0/directory/room/%23test%3Aserver%2Eorg"
+        );
+
+        let req = IncomingRequest::try_from_http_request(http_req).unwrap();
+        assert_eq!(req.room_alias, room_alias);
+    }
+}
@@ -13,12 +13,14 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The room alias.
         #[ruma_api(path)]
         pub room_alias: &'a RoomAliasId,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The room ID for this room alias.
         pub room_id: RoomId,
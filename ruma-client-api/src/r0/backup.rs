@@ -1,25 +1,25 @@
 //! Endpoints for server-side key backups.
 
+pub mod add_backup_key_session;
+pub mod add_backup_key_sessions;
 pub mod add_backup_keys;
 pub mod create_backup;
+pub mod delete_backup;
+pub mod delete_backup_key_session;
+pub mod delete_backup_key_sessions;
+pub mod delete_backup_keys;
 pub mod get_backup;
+pub mod get_backup_key_session;
+pub mod get_backup_key_sessions;
 pub mod get_backup_keys;
 pub mod get_latest_backup;
 pub mod update_backup;
 
+use std::collections::BTreeMap;
+
 use js_int::UInt;
 use ruma_identifiers::{DeviceKeyId, UserId};
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
-
-// TODO: remove
-/// A wrapper around a mapping of session IDs to key data.
-#[cfg(feature = "unstable-synapse-quirks")]
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct Sessions {
-    ///  A map of session IDs to key data.
-    pub sessions: BTreeMap<String, KeyData>,
-}
 
 /// The algorithm used for storing backups.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -35,26 +35,64 @@ pub enum BackupAlgorithm {
     },
 }
 
-/// The key data.
+/// The backed up keys of a single room.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RoomKeyBackup {
+    /// A map of session IDs to key data.
+    pub sessions: BTreeMap<String, KeyBackupData>,
+}
+
+impl RoomKeyBackup {
+    /// Creates a new `RoomKeyBackup` with the given sessions.
+    pub fn new(sessions: BTreeMap<String, KeyBackupData>) -> Self {
+        Self { sessions }
+    }
+}
+
+/// The key backup data of a single session.
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct KeyData {
+pub struct KeyBackupData {
     /// The index of the first message in the session that the key can decrypt.
-    first_message_index: UInt,
+    pub first_message_index: UInt,
+
     /// The number of times this key has been forwarded via key-sharing between devices.
-    forwarded_count: UInt,
+    pub forwarded_count: UInt,
+
     /// Whether the device backing up the key verified the device that the key is from.
-    is_verified: bool,
+    pub is_verified: bool,
+
     /// Data about the session.
-    session_data: SessionData,
+    pub session_data: SessionData,
 }
 
-/// The algorithm used for storing backups.
+impl KeyBackupData {
+    /// Creates a new `KeyBackupData` with the given values.
+    pub fn new(
+        first_message_index: UInt,
+        forwarded_count: UInt,
+        is_verified: bool,
+        session_data: SessionData,
+    ) -> Self {
+        Self { first_message_index, forwarded_count, is_verified, session_data }
+    }
+}
+
+/// The encrypted algorithm-dependent data for backups.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SessionData {
     /// Unpadded base64-encoded public half of the ephemeral key.
-    ephemeral: String,
+    pub ephemeral: String,
+
     /// Ciphertext, encrypted using AES-CBC-256 with PKCS#7 padding, encoded in base64.
-    ciphertext: String,
+    pub ciphertext: String,
+
     /// First 8 bytes of MAC key, encoded in base64.
-    mac: String,
+    pub mac: String,
+}
+
+impl SessionData {
+    /// Creates a new `SessionData` with the given ephemeral key, ciphertext and MAC.
+    pub fn new(ephemeral: String, ciphertext: String, mac: String) -> Self {
+        Self { ephemeral, ciphertext, mac }
+    }
 }
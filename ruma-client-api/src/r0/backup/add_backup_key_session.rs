@@ -0,0 +1,67 @@
+//! [PUT /_matrix/client/r0/room_keys/keys/{roomId}/{sessionId}](https://matrix.org/docs/spec/client_server/r0.6.1#put-matrix-client-r0-room-keys-keys-roomid-sessionid)
+
+use js_int::UInt;
+use ruma_api::ruma_api;
+use ruma_identifiers::RoomId;
+
+use super::KeyBackupData;
+
+ruma_api! {
+    metadata: {
+        description: "Store a single session in the backup.",
+        method: PUT,
+        name: "add_backup_key_session",
+        path: "/_matrix/client/r0/room_keys/keys/:room_id/:session_id",
+        rate_limited: true,
+        requires_authentication: true,
+    }
+
+    request: {
+        /// The backup version. Must be the current backup.
+        #[ruma_api(query)]
+        pub version: String,
+
+        /// The ID of the room that the requested key is for.
+        #[ruma_api(path)]
+        pub room_id: RoomId,
+
+        /// The ID of the megolm session whose key is requested.
+        #[ruma_api(path)]
+        pub session_id: String,
+
+        /// The key information to backup.
+        #[ruma_api(body)]
+        pub session_data: KeyBackupData,
+    }
+
+    #[derive(PartialEq, Eq)]
+    response: {
+        /// An opaque string representing stored keys in the backup. Clients can compare it with
+        /// the etag value they received in the request of their last key storage request.
+        pub etag: String,
+
+        /// The number of keys stored in the backup.
+        pub count: UInt,
+    }
+
+    error: crate::Error
+}
+
+impl Request {
+    /// Creates a new `Request` with the given backup version, room ID, session ID and key data.
+    pub fn new(
+        version: String,
+        room_id: RoomId,
+        session_id: String,
+        session_data: KeyBackupData,
+    ) -> Self {
+        Self { version, room_id, session_id, session_data }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given etag and key count.
+    pub fn new(etag: String, count: UInt) -> Self {
+        Self { etag, count }
+    }
+}
@@ -0,0 +1,63 @@
+//! [PUT /_matrix/client/r0/room_keys/keys/{roomId}](https://matrix.org/docs/spec/client_server/r0.6.1#put-matrix-client-r0-room-keys-keys-roomid)
+
+use std::collections::BTreeMap;
+
+use js_int::UInt;
+use ruma_api::ruma_api;
+use ruma_identifiers::RoomId;
+
+use super::KeyBackupData;
+
+ruma_api! {
+    metadata: {
+        description: "Store several sessions in the backup.",
+        method: PUT,
+        name: "add_backup_key_sessions",
+        path: "/_matrix/client/r0/room_keys/keys/:room_id",
+        rate_limited: true,
+        requires_authentication: true,
+    }
+
+    request: {
+        /// The backup version. Must be the current backup.
+        #[ruma_api(query)]
+        pub version: String,
+
+        /// The ID of the room that the requested key is for.
+        #[ruma_api(path)]
+        pub room_id: RoomId,
+
+        /// A map from session IDs to key data.
+        pub sessions: BTreeMap<String, KeyBackupData>,
+    }
+
+    #[derive(PartialEq, Eq)]
+    response: {
+        /// An opaque string representing stored keys in the backup. Clients can compare it with
+        /// the etag value they received in the request of their last key storage request.
+        pub etag: String,
+
+        /// The number of keys stored in the backup.
+        pub count: UInt,
+    }
+
+    error: crate::Error
+}
+
+impl Request {
+    /// Creates a new `Request` with the given backup version, room ID and sessions.
+    pub fn new(
+        version: String,
+        room_id: RoomId,
+        sessions: BTreeMap<String, KeyBackupData>,
+    ) -> Self {
+        Self { version, room_id, sessions }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given etag and key count.
+    pub fn new(etag: String, count: UInt) -> Self {
+        Self { etag, count }
+    }
+}
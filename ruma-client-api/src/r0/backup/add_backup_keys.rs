@@ -1,4 +1,4 @@
-//! [PUT /_matrix/client/r0/room_keys/keys](https://matrix.org/docs/spec/client_server/unstable#put-matrix-client-r0-room-keys-keys)
+//! [PUT /_matrix/client/r0/room_keys/keys](https://matrix.org/docs/spec/client_server/r0.6.1#put-matrix-client-r0-room-keys-keys)
 
 use std::collections::BTreeMap;
 
@@ -6,6 +6,8 @@ use js_int::UInt;
 use ruma_api::ruma_api;
 use ruma_identifiers::RoomId;
 
+use super::RoomKeyBackup;
+
 ruma_api! {
     metadata: {
         description: "Store several keys in the backup.",
@@ -22,16 +24,10 @@ ruma_api! {
         pub version: String,
 
         /// A map from room IDs to session IDs to key data.
-        ///
-        /// Note: synapse has the `sessions: {}` wrapper, the Matrix spec does not.
-        #[cfg(feature = "unstable-synapse-quirks")]
-        pub rooms: BTreeMap<RoomId, super::Sessions>,
-
-        /// A map from room IDs to session IDs to key data.
-        #[cfg(not(feature = "unstable-synapse-quirks"))]
-        pub rooms: BTreeMap<RoomId, BTreeMap<String, super::KeyData>>,
+        pub rooms: BTreeMap<RoomId, RoomKeyBackup>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// An opaque string representing stored keys in the backup. Clients can compare it with
         /// the etag value they received in the request of their last key storage request.
@@ -43,3 +39,73 @@ ruma_api! {
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given backup version and room key backups.
+    pub fn new(version: String, rooms: BTreeMap<RoomId, RoomKeyBackup>) -> Self {
+        Self { version, rooms }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given etag and key count.
+    pub fn new(etag: String, count: UInt) -> Self {
+        Self { etag, count }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::uint;
+    use maplit::btreemap;
+    use ruma_api::OutgoingRequest as _;
+    use ruma_identifiers::room_id;
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+    use crate::r0::backup::{KeyBackupData, RoomKeyBackup, SessionData};
+
+    #[test]
+    fn serialize_request() {
+        let key_data = KeyBackupData::new(
+            uint!(1),
+            uint!(0),
+            true,
+            SessionData::new("ephemeral".into(), "ciphertext".into(), "mac".into()),
+        );
+        let req = Request::new(
+            "1".into(),
+            btreemap! {
+                room_id!("!roomid:example.org") => RoomKeyBackup::new(btreemap! {
+                    "sessionid".to_owned() => key_data,
+                }),
+            },
+        );
+
+        let http_req = req
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+        assert_eq!(http_req.uri().query(), Some("version=1"));
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({
+                "rooms": {
+                    "!roomid:example.org": {
+                        "sessions": {
+                            "sessionid": {
+                                "first_message_index": 1,
+                                "forwarded_count": 0,
+                                "is_verified": true,
+                                "session_data": {
+                                    "ephemeral": "ephemeral",
+                                    "ciphertext": "ciphertext",
+                                    "mac": "mac"
+                                }
+                            }
+                        }
+                    }
+                }
+            })
+        );
+    }
+}
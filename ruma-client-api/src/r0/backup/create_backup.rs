@@ -1,4 +1,4 @@
-//! [POST /_matrix/client/r0/room_keys/version](https://matrix.org/docs/spec/client_server/unstable#post-matrix-client-r0-room-keys-version)
+//! [POST /_matrix/client/r0/room_keys/version](https://matrix.org/docs/spec/client_server/r0.6.1#post-matrix-client-r0-room-keys-version)
 
 use ruma_api::ruma_api;
 
@@ -18,6 +18,7 @@ ruma_api! {
         pub algorithm: super::BackupAlgorithm,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The backup version. This is an opaque string.
         pub version: String,
@@ -25,3 +26,92 @@ ruma_api! {
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given backup algorithm.
+    pub fn new(algorithm: super::BackupAlgorithm) -> Self {
+        Self { algorithm }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given backup version.
+    pub fn new(version: String) -> Self {
+        Self { version }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use js_int::uint;
+    use maplit::btreemap;
+    use ruma_api::{IncomingResponse as _, OutgoingRequest as _, OutgoingResponse as _};
+    use ruma_identifiers::{user_id, DeviceKeyId};
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::{Request, Response};
+    use crate::r0::backup::{get_backup, BackupAlgorithm};
+
+    #[test]
+    fn create_backup_and_read_it_back() {
+        let key_id = DeviceKeyId::try_from("ed25519:ABCDEFG").unwrap();
+        let algorithm = BackupAlgorithm::MegolmBackupV1Curve25519AesSha2 {
+            public_key: "hSDwCYkwp1R0i33ctD73Wg2/Og0mOBr066SpjqqbTmo".into(),
+            signatures: btreemap! {
+                user_id!("@alice:example.org") => btreemap! { key_id => "signature".into() },
+            },
+        };
+
+        let http_req = Request::new(algorithm)
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+        assert_eq!(http_req.method(), http::Method::POST);
+        assert_eq!(http_req.uri().path(), "/_matrix/client/r0/room_keys/version");
+
+        let auth_data = json!({
+            "public_key": "hSDwCYkwp1R0i33ctD73Wg2/Og0mOBr066SpjqqbTmo",
+            "signatures": {
+                "@alice:example.org": {
+                    "ed25519:ABCDEFG": "signature"
+                }
+            }
+        });
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({
+                "algorithm": "m.megolm_backup.v1.curve25519-aes-sha2",
+                "auth_data": auth_data
+            })
+        );
+
+        let created = Response::try_from_http_response(
+            Response::new("1".into()).try_into_http_response::<Vec<u8>>().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(created.version, "1");
+
+        // The homeserver reports the stored algorithm back, along with the key count and etag.
+        let body = json!({
+            "algorithm": "m.megolm_backup.v1.curve25519-aes-sha2",
+            "auth_data": auth_data,
+            "count": 0,
+            "etag": "0",
+            "version": created.version
+        });
+        let backup = get_backup::Response::try_from_http_response(http::Response::new(
+            serde_json::to_vec(&body).unwrap(),
+        ))
+        .unwrap();
+
+        assert_eq!(backup.version, "1");
+        assert_eq!(backup.count, uint!(0));
+        match backup.algorithm {
+            BackupAlgorithm::MegolmBackupV1Curve25519AesSha2 { public_key, signatures } => {
+                assert_eq!(public_key, "hSDwCYkwp1R0i33ctD73Wg2/Og0mOBr066SpjqqbTmo");
+                assert_eq!(signatures.len(), 1);
+            }
+        }
+    }
+}
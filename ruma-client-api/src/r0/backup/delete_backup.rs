@@ -0,0 +1,40 @@
+//! [DELETE /_matrix/client/r0/room_keys/version/{version}](https://matrix.org/docs/spec/client_server/r0.6.1#delete-matrix-client-r0-room-keys-version-version)
+
+use ruma_api::ruma_api;
+
+ruma_api! {
+    metadata: {
+        description: "Delete an existing backup.",
+        method: DELETE,
+        name: "delete_backup",
+        path: "/_matrix/client/r0/room_keys/version/:version",
+        rate_limited: true,
+        requires_authentication: true,
+    }
+
+    #[derive(PartialEq, Eq)]
+    request: {
+        /// The backup version.
+        #[ruma_api(path)]
+        pub version: String,
+    }
+
+    #[derive(PartialEq, Eq)]
+    response: {}
+
+    error: crate::Error
+}
+
+impl Request {
+    /// Creates a new `Request` with the given backup version.
+    pub fn new(version: String) -> Self {
+        Self { version }
+    }
+}
+
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self
+    }
+}
@@ -0,0 +1,57 @@
+//! [DELETE /_matrix/client/r0/room_keys/keys/{roomId}/{sessionId}](https://matrix.org/docs/spec/client_server/r0.6.1#delete-matrix-client-r0-room-keys-keys-roomid-sessionid)
+
+use js_int::UInt;
+use ruma_api::ruma_api;
+use ruma_identifiers::RoomId;
+
+ruma_api! {
+    metadata: {
+        description: "Delete a key from a backup.",
+        method: DELETE,
+        name: "delete_backup_key_session",
+        path: "/_matrix/client/r0/room_keys/keys/:room_id/:session_id",
+        rate_limited: true,
+        requires_authentication: true,
+    }
+
+    #[derive(PartialEq, Eq)]
+    request: {
+        /// The backup version. Must be the current backup.
+        #[ruma_api(query)]
+        pub version: String,
+
+        /// The ID of the room that the requested key is for.
+        #[ruma_api(path)]
+        pub room_id: RoomId,
+
+        /// The ID of the megolm session whose key is to be deleted.
+        #[ruma_api(path)]
+        pub session_id: String,
+    }
+
+    #[derive(PartialEq, Eq)]
+    response: {
+        /// An opaque string representing stored keys in the backup. Clients can compare it with
+        /// the etag value they received in the request of their last key storage request.
+        pub etag: String,
+
+        /// The number of keys stored in the backup.
+        pub count: UInt,
+    }
+
+    error: crate::Error
+}
+
+impl Request {
+    /// Creates a new `Request` with the given backup version, room ID and session ID.
+    pub fn new(version: String, room_id: RoomId, session_id: String) -> Self {
+        Self { version, room_id, session_id }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given etag and key count.
+    pub fn new(etag: String, count: UInt) -> Self {
+        Self { etag, count }
+    }
+}
@@ -0,0 +1,53 @@
+//! [DELETE /_matrix/client/r0/room_keys/keys/{roomId}](https://matrix.org/docs/spec/client_server/r0.6.1#delete-matrix-client-r0-room-keys-keys-roomid)
+
+use js_int::UInt;
+use ruma_api::ruma_api;
+use ruma_identifiers::RoomId;
+
+ruma_api! {
+    metadata: {
+        description: "Delete all keys for a room from a backup.",
+        method: DELETE,
+        name: "delete_backup_key_sessions",
+        path: "/_matrix/client/r0/room_keys/keys/:room_id",
+        rate_limited: true,
+        requires_authentication: true,
+    }
+
+    #[derive(PartialEq, Eq)]
+    request: {
+        /// The backup version. Must be the current backup.
+        #[ruma_api(query)]
+        pub version: String,
+
+        /// The ID of the room to delete keys from.
+        #[ruma_api(path)]
+        pub room_id: RoomId,
+    }
+
+    #[derive(PartialEq, Eq)]
+    response: {
+        /// An opaque string representing stored keys in the backup. Clients can compare it with
+        /// the etag value they received in the request of their last key storage request.
+        pub etag: String,
+
+        /// The number of keys stored in the backup.
+        pub count: UInt,
+    }
+
+    error: crate::Error
+}
+
+impl Request {
+    /// Creates a new `Request` with the given backup version and room ID.
+    pub fn new(version: String, room_id: RoomId) -> Self {
+        Self { version, room_id }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given etag and key count.
+    pub fn new(etag: String, count: UInt) -> Self {
+        Self { etag, count }
+    }
+}
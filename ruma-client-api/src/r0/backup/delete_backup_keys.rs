@@ -0,0 +1,48 @@
+//! [DELETE /_matrix/client/r0/room_keys/keys](https://matrix.org/docs/spec/client_server/r0.6.1#delete-matrix-client-r0-room-keys-keys)
+
+use js_int::UInt;
+use ruma_api::ruma_api;
+
+ruma_api! {
+    metadata: {
+        description: "Delete all keys from a backup.",
+        method: DELETE,
+        name: "delete_backup_keys",
+        path: "/_matrix/client/r0/room_keys/keys",
+        rate_limited: true,
+        requires_authentication: true,
+    }
+
+    #[derive(PartialEq, Eq)]
+    request: {
+        /// The backup version. Must be the current backup.
+        #[ruma_api(query)]
+        pub version: String,
+    }
+
+    #[derive(PartialEq, Eq)]
+    response: {
+        /// An opaque string representing stored keys in the backup. Clients can compare it with
+        /// the etag value they received in the request of their last key storage request.
+        pub etag: String,
+
+        /// The number of keys stored in the backup.
+        pub count: UInt,
+    }
+
+    error: crate::Error
+}
+
+impl Request {
+    /// Creates a new `Request` with the given backup version.
+    pub fn new(version: String) -> Self {
+        Self { version }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given etag and key count.
+    pub fn new(etag: String, count: UInt) -> Self {
+        Self { etag, count }
+    }
+}
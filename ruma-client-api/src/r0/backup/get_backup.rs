@@ -1,4 +1,4 @@
-//! [GET /_matrix/client/r0/room_keys/version](https://matrix.org/docs/spec/client_server/unstable#post-matrix-client-r0-room-keys-version)
+//! [GET /_matrix/client/r0/room_keys/version/{version}](https://matrix.org/docs/spec/client_server/r0.6.1#get-matrix-client-r0-room-keys-version-version)
 
 use js_int::UInt;
 use ruma_api::ruma_api;
@@ -13,6 +13,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The backup version.
         #[ruma_api(path)]
@@ -37,3 +38,57 @@ ruma_api! {
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given backup version.
+    pub fn new(version: String) -> Self {
+        Self { version }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given algorithm, key count, etag and version.
+    pub fn new(
+        algorithm: super::BackupAlgorithm,
+        count: UInt,
+        etag: String,
+        version: String,
+    ) -> Self {
+        Self { algorithm, count, etag, version }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::uint;
+    use maplit::btreemap;
+    use ruma_api::OutgoingResponse as _;
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Response;
+    use crate::r0::backup::BackupAlgorithm;
+
+    #[test]
+    fn serialize_response() {
+        let algorithm = BackupAlgorithm::MegolmBackupV1Curve25519AesSha2 {
+            public_key: "abcdefg".into(),
+            signatures: btreemap! {},
+        };
+        let http_res = Response::new(algorithm, uint!(3), "etag".into(), "1".into())
+            .try_into_http_response::<Vec<u8>>()
+            .unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_res.body()).unwrap(),
+            json!({
+                "algorithm": "m.megolm_backup.v1.curve25519-aes-sha2",
+                "auth_data": {
+                    "public_key": "abcdefg",
+                    "signatures": {}
+                },
+                "count": 3,
+                "etag": "etag",
+                "version": "1"
+            })
+        );
+    }
+}
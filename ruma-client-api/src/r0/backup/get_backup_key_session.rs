@@ -0,0 +1,81 @@
+//! [GET /_matrix/client/r0/room_keys/keys/{roomId}/{sessionId}](https://matrix.org/docs/spec/client_server/r0.6.1#get-matrix-client-r0-room-keys-keys-roomid-sessionid)
+
+use ruma_api::ruma_api;
+use ruma_identifiers::RoomId;
+
+use super::KeyBackupData;
+
+ruma_api! {
+    metadata: {
+        description: "Retrieve a key from the backup",
+        method: GET,
+        name: "get_backup_key_session",
+        path: "/_matrix/client/r0/room_keys/keys/:room_id/:session_id",
+        rate_limited: true,
+        requires_authentication: true,
+    }
+
+    #[derive(PartialEq, Eq)]
+    request: {
+        /// The backup version. Must be the current backup.
+        #[ruma_api(query)]
+        pub version: String,
+
+        /// The ID of the room that the requested key is for.
+        #[ruma_api(path)]
+        pub room_id: RoomId,
+
+        /// The ID of the megolm session whose key is requested.
+        #[ruma_api(path)]
+        pub session_id: String,
+    }
+
+    response: {
+        /// Information about the requested backup key.
+        #[ruma_api(body)]
+        pub key_data: KeyBackupData,
+    }
+
+    error: crate::Error
+}
+
+impl Request {
+    /// Creates a new `Request` with the given backup version, room ID and session ID.
+    pub fn new(version: String, room_id: RoomId, session_id: String) -> Self {
+        Self { version, room_id, session_id }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given key data.
+    pub fn new(key_data: KeyBackupData) -> Self {
+        Self { key_data }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::IncomingResponse as _;
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::Response;
+
+    #[test]
+    fn deserialize_flattened_response() {
+        let body = json!({
+            "first_message_index": 1,
+            "forwarded_count": 0,
+            "is_verified": true,
+            "session_data": {
+                "ephemeral": "ephemeral",
+                "ciphertext": "ciphertext",
+                "mac": "mac"
+            }
+        });
+        let res =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+        assert!(res.key_data.is_verified);
+        assert_eq!(res.key_data.session_data.mac, "mac");
+    }
+}
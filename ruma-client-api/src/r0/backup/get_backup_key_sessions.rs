@@ -0,0 +1,83 @@
+//! [GET /_matrix/client/r0/room_keys/keys/{roomId}](https://matrix.org/docs/spec/client_server/r0.6.1#get-matrix-client-r0-room-keys-keys-roomid)
+
+use std::collections::BTreeMap;
+
+use ruma_api::ruma_api;
+use ruma_identifiers::RoomId;
+
+use super::KeyBackupData;
+
+ruma_api! {
+    metadata: {
+        description: "Retrieve all keys from a backup for a given room.",
+        method: GET,
+        name: "get_backup_key_sessions",
+        path: "/_matrix/client/r0/room_keys/keys/:room_id",
+        rate_limited: true,
+        requires_authentication: true,
+    }
+
+    #[derive(PartialEq, Eq)]
+    request: {
+        /// The backup version. Must be the current backup.
+        #[ruma_api(query)]
+        pub version: String,
+
+        /// The ID of the room that the requested key is for.
+        #[ruma_api(path)]
+        pub room_id: RoomId,
+    }
+
+    response: {
+        /// A map of session IDs to key data.
+        pub sessions: BTreeMap<String, KeyBackupData>,
+    }
+
+    error: crate::Error
+}
+
+impl Request {
+    /// Creates a new `Request` with the given backup version and room ID.
+    pub fn new(version: String, room_id: RoomId) -> Self {
+        Self { version, room_id }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given sessions.
+    pub fn new(sessions: BTreeMap<String, KeyBackupData>) -> Self {
+        Self { sessions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::uint;
+    use ruma_api::IncomingResponse as _;
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::Response;
+
+    #[test]
+    fn deserialize_response() {
+        let body = json!({
+            "sessions": {
+                "sessionid": {
+                    "first_message_index": 1,
+                    "forwarded_count": 0,
+                    "is_verified": true,
+                    "session_data": {
+                        "ephemeral": "ephemeral",
+                        "ciphertext": "ciphertext",
+                        "mac": "mac"
+                    }
+                }
+            }
+        });
+        let res =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+        assert_eq!(res.sessions.len(), 1);
+        assert_eq!(res.sessions["sessionid"].first_message_index, uint!(1));
+    }
+}
@@ -1,10 +1,12 @@
-//! [GET /_matrix/client/r0/room_keys/keys](https://matrix.org/docs/spec/client_server/unstable#get-matrix-client-r0-room-keys-keys)
+//! [GET /_matrix/client/r0/room_keys/keys](https://matrix.org/docs/spec/client_server/r0.6.1#get-matrix-client-r0-room-keys-keys)
 
 use std::collections::BTreeMap;
 
 use ruma_api::ruma_api;
 use ruma_identifiers::RoomId;
 
+use super::RoomKeyBackup;
+
 ruma_api! {
     metadata: {
         description: "Retrieve all keys from a backup.",
@@ -15,6 +17,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The backup version. Must be the current backup.
         #[ruma_api(query)]
@@ -23,15 +26,72 @@ ruma_api! {
 
     response: {
         /// A map from room IDs to session IDs to key data.
-        ///
-        /// Note: synapse has the `sessions: {}` wrapper, the Matrix spec does not.
-        #[cfg(feature = "unstable-synapse-quirks")]
-        pub rooms: BTreeMap<RoomId, super::Sessions>,
-
-        /// A map from room IDs to session IDs to key data.
-        #[cfg(not(feature = "unstable-synapse-quirks"))]
-        pub rooms: BTreeMap<RoomId, BTreeMap<String, super::KeyData>>,
+        pub rooms: BTreeMap<RoomId, RoomKeyBackup>,
     }
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given backup version.
+    pub fn new(version: String) -> Self {
+        Self { version }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given room key backups.
+    pub fn new(rooms: BTreeMap<RoomId, RoomKeyBackup>) -> Self {
+        Self { rooms }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::uint;
+    use maplit::btreemap;
+    use ruma_api::OutgoingResponse as _;
+    use ruma_identifiers::room_id;
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Response;
+    use crate::r0::backup::{KeyBackupData, RoomKeyBackup, SessionData};
+
+    #[test]
+    fn serialize_response() {
+        let key_data = KeyBackupData::new(
+            uint!(0),
+            uint!(2),
+            false,
+            SessionData::new("ephemeral".into(), "ciphertext".into(), "mac".into()),
+        );
+        let http_res = Response::new(btreemap! {
+            room_id!("!roomid:example.org") => RoomKeyBackup::new(btreemap! {
+                "sessionid".to_owned() => key_data,
+            }),
+        })
+        .try_into_http_response::<Vec<u8>>()
+        .unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_res.body()).unwrap(),
+            json!({
+                "rooms": {
+                    "!roomid:example.org": {
+                        "sessions": {
+                            "sessionid": {
+                                "first_message_index": 0,
+                                "forwarded_count": 2,
+                                "is_verified": false,
+                                "session_data": {
+                                    "ephemeral": "ephemeral",
+                                    "ciphertext": "ciphertext",
+                                    "mac": "mac"
+                                }
+                            }
+                        }
+                    }
+                }
+            })
+        );
+    }
+}
@@ -1,4 +1,4 @@
-//! [GET /_matrix/client/r0/room_keys/version](https://matrix.org/docs/spec/client_server/unstable#post-matrix-client-r0-room-keys-version)
+//! [GET /_matrix/client/r0/room_keys/version](https://matrix.org/docs/spec/client_server/r0.6.1#get-matrix-client-r0-room-keys-version)
 
 use js_int::UInt;
 use ruma_api::ruma_api;
@@ -13,6 +13,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {}
 
     response: {
@@ -23,7 +24,7 @@ ruma_api! {
         /// The number of keys stored in the backup.
         pub count: UInt,
 
-        /// An opaque string represetning stored keys in the backup. Clients can compare it with
+        /// An opaque string representing stored keys in the backup. Clients can compare it with
         /// the etag value they received in the request of their last key storage request.
         pub etag: String,
 
@@ -33,3 +34,22 @@ ruma_api! {
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates an empty `Request`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given algorithm, key count, etag and version.
+    pub fn new(
+        algorithm: super::BackupAlgorithm,
+        count: UInt,
+        etag: String,
+        version: String,
+    ) -> Self {
+        Self { algorithm, count, etag, version }
+    }
+}
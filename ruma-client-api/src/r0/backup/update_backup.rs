@@ -1,11 +1,11 @@
-//! [POST /_matrix/client/r0/room_keys/version](https://matrix.org/docs/spec/client_server/unstable#post-matrix-client-r0-room-keys-version)
+//! [PUT /_matrix/client/r0/room_keys/version/{version}](https://matrix.org/docs/spec/client_server/r0.6.1#put-matrix-client-r0-room-keys-version-version)
 
 use ruma_api::ruma_api;
 
 ruma_api! {
     metadata: {
         description: "Update information about an existing backup.",
-        method: POST,
+        method: PUT,
         name: "update_backup",
         path: "/_matrix/client/r0/room_keys/version/:version",
         rate_limited: true,
@@ -22,7 +22,22 @@ ruma_api! {
         pub algorithm: super::BackupAlgorithm,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given backup version and algorithm.
+    pub fn new(version: String, algorithm: super::BackupAlgorithm) -> Self {
+        Self { version, algorithm }
+    }
+}
+
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self
+    }
+}
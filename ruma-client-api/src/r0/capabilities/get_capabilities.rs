@@ -1,10 +1,11 @@
 //! [GET /_matrix/client/r0/capabilities](https://matrix.org/docs/spec/client_server/r0.6.1#get-matrix-client-r0-capabilities)
 
+use std::collections::BTreeMap;
+
 use ruma_api::ruma_api;
 use ruma_identifiers::RoomVersionId;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use std::collections::BTreeMap;
 
 ruma_api! {
     metadata: {
@@ -16,8 +17,10 @@ ruma_api! {
         requires_authentication: true
     }
 
+    #[derive(PartialEq, Eq)]
     request: {}
 
+    #[non_exhaustive]
     response: {
         /// The capabilities the server supports
         pub capabilities: Capabilities,
@@ -26,8 +29,22 @@ ruma_api! {
     error: crate::Error
 }
 
+impl Request {
+    /// Creates an empty `Request`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given capabilities.
+    pub fn new(capabilities: Capabilities) -> Self {
+        Self { capabilities }
+    }
+}
+
 /// Contains information about all the capabilities that the server supports.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Capabilities {
     /// Capability to indicate if the user can change their password.
     #[serde(rename = "m.change_password", skip_serializing_if = "Option::is_none")]
@@ -54,14 +71,33 @@ pub struct ChangePasswordCapability {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RoomVersionsCapability {
     /// The default room version the server is using for new rooms.
-    pub default: String,
+    pub default: RoomVersionId,
 
     /// A detailed description of the room versions the server supports.
     pub available: BTreeMap<RoomVersionId, RoomVersionStability>,
 }
 
+impl RoomVersionsCapability {
+    /// Creates a new `RoomVersionsCapability` with the given default room version ID and room
+    /// version descriptions.
+    pub fn new(
+        default: RoomVersionId,
+        available: BTreeMap<RoomVersionId, RoomVersionStability>,
+    ) -> Self {
+        Self { default, available }
+    }
+
+    /// Returns an iterator over the room versions the server considers stable.
+    pub fn stable_versions(&self) -> impl Iterator<Item = &RoomVersionId> {
+        self.available
+            .iter()
+            .filter(|(_, stability)| **stability == RoomVersionStability::Stable)
+            .map(|(version, _)| version)
+    }
+}
+
 /// The stability of a room version
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RoomVersionStability {
     /// Support for the given version is stable.
     #[serde(rename = "stable")]
@@ -71,3 +107,56 @@ pub enum RoomVersionStability {
     #[serde(rename = "unstable")]
     Unstable,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ruma_api::IncomingResponse as _;
+    use ruma_identifiers::RoomVersionId;
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::{Response, RoomVersionStability};
+
+    #[test]
+    fn deserialize_room_versions() {
+        let body = json!({
+            "capabilities": {
+                "m.change_password": { "enabled": false },
+                "m.room_versions": {
+                    "default": "6",
+                    "available": {
+                        "1": "stable",
+                        "2": "stable",
+                        "3": "stable",
+                        "4": "stable",
+                        "5": "stable",
+                        "6": "stable",
+                        "7": "unstable",
+                        "8": "unstable",
+                        "9": "unstable",
+                        "10": "unstable"
+                    }
+                },
+                "com.example.custom_ratelimit": { "max_requests_per_hour": 600 }
+            }
+        });
+        let capabilities =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap()
+                .capabilities;
+
+        assert!(!capabilities.change_password.unwrap().enabled);
+        assert!(capabilities.custom_capabilities.contains_key("com.example.custom_ratelimit"));
+
+        let room_versions = capabilities.room_versions.unwrap();
+        assert_eq!(room_versions.default, RoomVersionId::Version6);
+        assert_eq!(room_versions.available.len(), 10);
+        assert_eq!(
+            room_versions.available[&RoomVersionId::try_from("10").unwrap()],
+            RoomVersionStability::Unstable
+        );
+        assert_eq!(room_versions.stable_versions().count(), 6);
+        assert!(room_versions.stable_versions().all(RoomVersionId::is_official));
+    }
+}
@@ -1,9 +1,9 @@
 //! [GET /_matrix/client/r0/user/{userId}/account_data/{type}](https://matrix.org/docs/spec/client_server/r0.6.0#get-matrix-client-r0-user-userid-account-data-type)
 
 use ruma_api::ruma_api;
-use ruma_common::Raw;
-use ruma_events::AnyBasicEvent;
+use ruma_events::EventType;
 use ruma_identifiers::UserId;
+use serde_json::value::RawValue as RawJsonValue;
 
 ruma_api! {
     metadata: {
@@ -15,6 +15,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// User ID of user for whom to retrieve data.
         #[ruma_api(path)]
@@ -22,14 +23,61 @@ ruma_api! {
 
         /// Type of data to retrieve.
         #[ruma_api(path)]
-        pub event_type: String,
+        pub event_type: EventType,
     }
 
     response: {
         /// Account data content for the given type.
         #[ruma_api(body)]
-        pub account_data: Raw<AnyBasicEvent>,
+        pub account_data: Box<RawJsonValue>,
     }
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given user ID and event type.
+    pub fn new(user_id: UserId, event_type: EventType) -> Self {
+        Self { user_id, event_type }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given account data.
+    pub fn new(account_data: Box<RawJsonValue>) -> Self {
+        Self { account_data }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::{IncomingResponse as _, OutgoingRequest as _};
+    use ruma_events::EventType;
+    use ruma_identifiers::user_id;
+    use serde_json::{from_str as from_json_str, json, to_vec as to_json_vec, Value as JsonValue};
+
+    use super::{Request, Response};
+
+    #[test]
+    fn serialize_request_with_custom_type() {
+        let req = Request::new(user_id!("@alice:example.org"), "org.example.custom".into());
+        assert_eq!(req.event_type, EventType::Custom("org.example.custom".into()));
+
+        let http_req: http::Request<Vec<u8>> =
+            req.try_into_http_request("https://homeserver.tld", Some("auth_tok")).unwrap();
+        assert_eq!(
+            http_req.uri().path(),
+            "/_matrix/client/r0/user/%40alice%3Aexample%2Eorg/account_data/org%2Eexample%2Ecustom"
+        );
+    }
+
+    #[test]
+    fn deserialize_response() {
+        let body = json!({ "ignored_users": { "@bob:example.org": {} } });
+        let response =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert_eq!(from_json_str::<JsonValue>(response.account_data.get()).unwrap(), body);
+    }
+}
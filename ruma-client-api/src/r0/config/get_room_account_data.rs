@@ -1,9 +1,9 @@
 //! [GET /_matrix/client/r0/user/{userId}/rooms/{roomId}/account_data/{type}](https://matrix.org/docs/spec/client_server/r0.6.0#get-matrix-client-r0-user-userid-rooms-roomid-account-data-type)
 
 use ruma_api::ruma_api;
-use ruma_common::Raw;
-use ruma_events::AnyBasicEvent;
+use ruma_events::EventType;
 use ruma_identifiers::{RoomId, UserId};
+use serde_json::value::RawValue as RawJsonValue;
 
 ruma_api! {
     metadata: {
@@ -15,6 +15,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// User ID of user for whom to retrieve data.
         #[ruma_api(path)]
@@ -26,14 +27,53 @@ ruma_api! {
 
         /// Type of data to retrieve.
         #[ruma_api(path)]
-        pub event_type: String,
+        pub event_type: EventType,
     }
 
     response: {
         /// Account data content for the given type.
         #[ruma_api(body)]
-        pub account_data: Raw<AnyBasicEvent>,
+        pub account_data: Box<RawJsonValue>,
     }
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given user ID, room ID and event type.
+    pub fn new(user_id: UserId, room_id: RoomId, event_type: EventType) -> Self {
+        Self { user_id, room_id, event_type }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given account data.
+    pub fn new(account_data: Box<RawJsonValue>) -> Self {
+        Self { account_data }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::OutgoingRequest as _;
+    use ruma_identifiers::{room_id, user_id};
+
+    use super::Request;
+
+    #[test]
+    fn serialize_request_with_custom_type() {
+        let req = Request::new(
+            user_id!("@alice:example.org"),
+            room_id!("!roomid:example.org"),
+            "org.example.custom".into(),
+        );
+
+        let http_req: http::Request<Vec<u8>> =
+            req.try_into_http_request("https://homeserver.tld", Some("auth_tok")).unwrap();
+        assert_eq!(
+            http_req.uri().path(),
+            "/_matrix/client/r0/user/%40alice%3Aexample%2Eorg/rooms/%21roomid%3Aexample%2Eorg\
+             /account_data/org%2Eexample%2Ecustom"
+        );
+    }
+}
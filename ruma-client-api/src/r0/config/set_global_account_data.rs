@@ -1,6 +1,7 @@
 //! [PUT /_matrix/client/r0/user/{userId}/account_data/{type}](https://matrix.org/docs/spec/client_server/r0.6.0#put-matrix-client-r0-user-userid-account-data-type)
 
 use ruma_api::ruma_api;
+use ruma_events::EventType;
 use ruma_identifiers::UserId;
 use serde_json::value::RawValue as RawJsonValue;
 
@@ -25,7 +26,7 @@ ruma_api! {
         ///
         /// Custom types should be namespaced to avoid clashes.
         #[ruma_api(path)]
-        pub event_type: String,
+        pub event_type: EventType,
 
         /// The ID of the user to set account_data for.
         ///
@@ -34,7 +35,54 @@ ruma_api! {
         pub user_id: UserId,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given data, event type and user ID.
+    pub fn new(data: Box<RawJsonValue>, event_type: EventType, user_id: UserId) -> Self {
+        Self { data, event_type, user_id }
+    }
+}
+
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::OutgoingRequest as _;
+    use ruma_identifiers::user_id;
+    use serde_json::{
+        from_slice as from_json_slice, json, value::to_raw_value as to_raw_json_value,
+        Value as JsonValue,
+    };
+
+    use super::Request;
+
+    #[test]
+    fn serialize_request_with_custom_type() {
+        let req = Request::new(
+            to_raw_json_value(&json!({ "ignored_users": {} })).unwrap(),
+            "org.example.custom".into(),
+            user_id!("@alice:example.org"),
+        );
+
+        let http_req: http::Request<Vec<u8>> =
+            req.try_into_http_request("https://homeserver.tld", Some("auth_tok")).unwrap();
+        assert_eq!(
+            http_req.uri().path(),
+            "/_matrix/client/r0/user/%40alice%3Aexample%2Eorg/account_data/org%2Eexample%2Ecustom"
+        );
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({ "ignored_users": {} })
+        );
+    }
+}
@@ -13,6 +13,7 @@ ruma_api! {
         requires_authentication: false,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// Client-generated secret string used to protect this session.
         pub client_secret: String,
@@ -41,6 +42,7 @@ ruma_api! {
         pub id_access_token: Option<String>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
@@ -31,3 +31,10 @@ pub struct Device {
     )]
     pub last_seen_ts: Option<SystemTime>,
 }
+
+impl Device {
+    /// Creates a new `Device` with the given device ID.
+    pub fn new(device_id: Box<DeviceId>) -> Self {
+        Self { device_id, display_name: None, last_seen_ip: None, last_seen_ts: None }
+    }
+}
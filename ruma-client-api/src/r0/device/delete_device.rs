@@ -25,7 +25,73 @@ ruma_api! {
         pub auth: Option<AuthData>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: UiaaResponse
 }
+
+impl Request {
+    /// Creates a new `Request` with the given device ID.
+    pub fn new(device_id: Box<DeviceId>) -> Self {
+        Self { device_id, auth: None }
+    }
+}
+
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use matches::assert_matches;
+    use ruma_api::{
+        error::{FromHttpResponseError, ServerError},
+        IncomingResponse as _, OutgoingRequest as _,
+    };
+    use serde_json::{
+        from_slice as from_json_slice, json, to_vec as to_json_vec, Value as JsonValue,
+    };
+
+    use super::{Request, Response};
+    use crate::r0::uiaa::{AuthData, UiaaResponse};
+
+    #[test]
+    fn serialize_request_with_auth() {
+        let req = Request {
+            auth: Some(AuthData::FallbackAcknowledgement { session: "xxxxxx".into() }),
+            ..Request::new("QBUAZIFURK".into())
+        }
+        .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+        .unwrap();
+
+        assert_eq!(req.method(), http::Method::DELETE);
+        assert_eq!(req.uri().path(), "/_matrix/client/r0/devices/QBUAZIFURK");
+        assert_eq!(
+            from_json_slice::<JsonValue>(req.body()).unwrap(),
+            json!({ "auth": { "session": "xxxxxx" } })
+        );
+    }
+
+    #[test]
+    fn deserialize_uiaa_response() {
+        let body = json!({
+            "flows": [{ "stages": ["m.login.password"] }],
+            "params": {},
+            "session": "xxxxxx"
+        });
+        let http_response = http::Response::builder()
+            .status(http::StatusCode::UNAUTHORIZED)
+            .body(to_json_vec(&body).unwrap())
+            .unwrap();
+
+        assert_matches!(
+            Response::try_from_http_response(http_response),
+            Err(FromHttpResponseError::Http(ServerError::Known(UiaaResponse::AuthResponse(info))))
+                if info.session.as_deref() == Some("xxxxxx")
+        );
+    }
+}
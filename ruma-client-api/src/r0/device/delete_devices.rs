@@ -24,7 +24,22 @@ ruma_api! {
         pub auth: Option<AuthData>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: UiaaResponse
 }
+
+impl Request {
+    /// Creates a new `Request` with the given devices.
+    pub fn new(devices: Vec<Box<DeviceId>>) -> Self {
+        Self { devices, auth: None }
+    }
+}
+
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self
+    }
+}
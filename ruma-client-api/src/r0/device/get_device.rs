@@ -14,12 +14,14 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The device to retrieve.
         #[ruma_api(path)]
         pub device_id: Box<DeviceId>,
     }
 
+    #[derive(PartialEq)]
     response: {
         /// Information about the device.
         #[ruma_api(body)]
@@ -28,3 +30,17 @@ ruma_api! {
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given device ID.
+    pub fn new(device_id: Box<DeviceId>) -> Self {
+        Self { device_id }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given device.
+    pub fn new(device: Device) -> Self {
+        Self { device }
+    }
+}
@@ -13,8 +13,10 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {}
 
+    #[derive(PartialEq)]
     response: {
         /// A list of all registered devices for this user
         pub devices: Vec<Device>,
@@ -22,3 +24,59 @@ ruma_api! {
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates an empty `Request`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given devices.
+    pub fn new(devices: Vec<Device>) -> Self {
+        Self { devices }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use ruma_api::IncomingResponse as _;
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::Response;
+    use crate::r0::device::Device;
+
+    #[test]
+    fn deserialize_response() {
+        let body = json!({
+            "devices": [
+                {
+                    "device_id": "QBUAZIFURK",
+                    "display_name": "android",
+                    "last_seen_ip": "1.2.3.4",
+                    "last_seen_ts": 1_474_491_775_024_u64
+                },
+                { "device_id": "JLAFKJWSCS" }
+            ]
+        });
+        let res =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert_eq!(
+            res.devices,
+            vec![
+                Device {
+                    display_name: Some("android".into()),
+                    last_seen_ip: Some("1.2.3.4".into()),
+                    last_seen_ts: Some(UNIX_EPOCH + Duration::from_millis(1_474_491_775_024)),
+                    ..Device::new("QBUAZIFURK".into())
+                },
+                Device::new("JLAFKJWSCS".into()),
+            ]
+        );
+    }
+}
@@ -13,6 +13,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The device to update.
         #[ruma_api(path)]
@@ -24,7 +25,45 @@ ruma_api! {
         pub display_name: Option<String>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given device ID.
+    pub fn new(device_id: Box<DeviceId>) -> Self {
+        Self { device_id, display_name: None }
+    }
+}
+
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::OutgoingRequest as _;
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+
+    #[test]
+    fn serialize_rename() {
+        let req =
+            Request { display_name: Some("My laptop".into()), ..Request::new("QBUAZIFURK".into()) }
+                .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+                .unwrap();
+
+        assert_eq!(req.method(), http::Method::PUT);
+        assert_eq!(req.uri().path(), "/_matrix/client/r0/devices/QBUAZIFURK");
+        assert_eq!(
+            from_json_slice::<JsonValue>(req.body()).unwrap(),
+            json!({ "display_name": "My laptop" })
+        );
+    }
+}
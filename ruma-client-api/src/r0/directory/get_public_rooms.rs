@@ -15,6 +15,7 @@ ruma_api! {
         requires_authentication: false,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// Limit for the number of results to return.
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -53,10 +54,8 @@ ruma_api! {
 
 #[cfg(test)]
 mod tests {
-    use std::convert::TryInto;
-
     use js_int::uint;
-    use ruma_api::OutgoingRequest as _;
+    use ruma_api::{OutgoingRequest as _, OutgoingResponse as _};
 
     use super::{Request, Response};
 
@@ -64,7 +63,7 @@ mod tests {
     fn construct_request_from_refs() {
         let req: http::Request<Vec<u8>> =
             Request { limit: Some(uint!(10)), since: Some("hello"), server: Some("address") }
-                .try_into_http_request("https://homeserver.tld", Some("auth_tok"))
+                .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
                 .unwrap();
 
         let uri = req.uri();
@@ -84,7 +83,7 @@ mod tests {
             prev_batch: Some("prev_batch_token".into()),
             total_room_count_estimate: Some(uint!(10)),
         }
-        .try_into()
+        .try_into_http_response()
         .unwrap();
 
         assert_eq!(
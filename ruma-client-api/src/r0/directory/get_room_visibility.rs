@@ -15,6 +15,7 @@ ruma_api! {
         requires_authentication: false,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The ID of the room of which to request the visibility.
         #[ruma_api(path)]
@@ -24,6 +24,7 @@ ruma_api! {
         pub visibility: Visibility,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
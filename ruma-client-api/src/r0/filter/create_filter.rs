@@ -27,6 +27,7 @@ ruma_api! {
         pub filter: FilterDefinition,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The ID of the filter that was created.
         pub filter_id: String,
@@ -34,3 +35,44 @@ ruma_api! {
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given user ID and filter definition.
+    pub fn new(user_id: UserId, filter: FilterDefinition) -> Self {
+        Self { user_id, filter }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given filter ID.
+    pub fn new(filter_id: String) -> Self {
+        Self { filter_id }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::OutgoingRequest as _;
+    use ruma_identifiers::user_id;
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+    use crate::r0::filter::FilterDefinition;
+
+    #[test]
+    fn serialize_request_body_as_filter() {
+        let req = Request::new(user_id!("@alice:example.org"), FilterDefinition::ignore_all());
+
+        let http_req = req
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({
+                "account_data": { "types": [] },
+                "room": { "rooms": [] },
+                "presence": { "types": [] },
+            })
+        );
+    }
+}
@@ -15,6 +15,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The user ID to download a filter for.
         #[ruma_api(path)]
@@ -33,3 +34,41 @@ ruma_api! {
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given user ID and filter ID.
+    pub fn new(user_id: UserId, filter_id: String) -> Self {
+        Self { user_id, filter_id }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given filter definition.
+    pub fn new(filter: FilterDefinition) -> Self {
+        Self { filter }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::OutgoingResponse as _;
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Response;
+    use crate::r0::filter::FilterDefinition;
+
+    #[test]
+    fn serialize_response_body_as_filter() {
+        let http_res = Response::new(FilterDefinition::ignore_all())
+            .try_into_http_response::<Vec<u8>>()
+            .unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_res.body()).unwrap(),
+            json!({
+                "account_data": { "types": [] },
+                "room": { "rooms": [] },
+                "presence": { "types": [] },
+            })
+        );
+    }
+}
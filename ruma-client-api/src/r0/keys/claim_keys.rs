@@ -5,10 +5,10 @@ use std::collections::BTreeMap;
 use std::time::Duration;
 
 use ruma_api::ruma_api;
-use ruma_identifiers::{DeviceId, DeviceKeyAlgorithm, DeviceKeyId, UserId};
+use ruma_identifiers::{DeviceId, DeviceKeyAlgorithm, UserId};
 use serde_json::Value as JsonValue;
 
-use super::OneTimeKey;
+pub use ruma_common::encryption::OneTimeKeys;
 
 ruma_api! {
     metadata: {
@@ -20,6 +20,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The time (in milliseconds) to wait when downloading keys from remote servers.
         /// 10 seconds is the recommended default.
@@ -45,6 +46,3 @@ ruma_api! {
 
     error: crate::Error
 }
-
-/// The one-time keys for a given device.
-pub type OneTimeKeys = BTreeMap<Box<DeviceId>, BTreeMap<DeviceKeyId, OneTimeKey>>;
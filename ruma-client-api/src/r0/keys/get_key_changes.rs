@@ -13,6 +13,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The desired start point of the list.
         ///
@@ -28,6 +29,7 @@ ruma_api! {
         pub to: &'a str,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The Matrix User IDs of all users who updated their device identity keys.
         pub changed: Vec<UserId>,
@@ -39,3 +41,50 @@ ruma_api! {
 
     error: crate::Error
 }
+
+impl<'a> Request<'a> {
+    /// Creates a new `Request` with the given start and end points.
+    pub fn new(from: &'a str, to: &'a str) -> Self {
+        Self { from, to }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given changed and left users.
+    pub fn new(changed: Vec<UserId>, left: Vec<UserId>) -> Self {
+        Self { changed, left }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::{IncomingResponse as _, OutgoingRequest as _};
+    use ruma_identifiers::user_id;
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::{Request, Response};
+
+    #[test]
+    fn serialize_request() {
+        let req = Request::new("s72594_4483_1934", "s75689_5632_2435")
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+
+        assert_eq!(req.uri().path(), "/_matrix/client/r0/keys/changes");
+        assert_eq!(req.uri().query(), Some("from=s72594_4483_1934&to=s75689_5632_2435"));
+    }
+
+    #[test]
+    fn deserialize_response() {
+        let body = json!({
+            "changed": ["@alice:example.org"],
+            "left": ["@bob:example.org"]
+        });
+        let res =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert_eq!(res.changed, vec![user_id!("@alice:example.org")]);
+        assert_eq!(res.left, vec![user_id!("@bob:example.org")]);
+    }
+}
@@ -20,6 +20,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The time (in milliseconds) to wait when downloading keys from remote
         /// servers. 10 seconds is the recommended default.
@@ -29,6 +29,7 @@ ruma_api! {
         pub one_time_keys: Option<BTreeMap<DeviceKeyId, OneTimeKey>>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// For each key algorithm, the number of unclaimed one-time keys of that
         /// type currently held on the server for this device.
@@ -37,3 +38,89 @@ ruma_api! {
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates an empty `Request`.
+    pub fn new() -> Self {
+        Self { device_keys: None, one_time_keys: None }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given one time key counts.
+    pub fn new(one_time_key_counts: BTreeMap<DeviceKeyAlgorithm, UInt>) -> Self {
+        Self { one_time_key_counts }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, convert::TryFrom};
+
+    use js_int::uint;
+    use ruma_api::{IncomingResponse as _, OutgoingRequest as _};
+    use ruma_common::encryption::DeviceKeys;
+    use ruma_identifiers::{user_id, DeviceKeyAlgorithm, DeviceKeyId, EventEncryptionAlgorithm};
+    use serde_json::{
+        from_slice as from_json_slice, json, to_vec as to_json_vec, Value as JsonValue,
+    };
+
+    use super::{Request, Response};
+    use crate::r0::keys::OneTimeKey;
+
+    #[test]
+    fn serialize_request() {
+        let mut keys = BTreeMap::new();
+        keys.insert(DeviceKeyId::try_from("ed25519:JLAFKJWSCS").unwrap(), "ed25519_key".to_owned());
+        let device_keys = DeviceKeys {
+            user_id: user_id!("@alice:example.org"),
+            device_id: "JLAFKJWSCS".into(),
+            algorithms: vec![
+                EventEncryptionAlgorithm::OlmV1Curve25519AesSha2,
+                EventEncryptionAlgorithm::MegolmV1AesSha2,
+            ],
+            keys,
+            signatures: BTreeMap::new(),
+            unsigned: None,
+        };
+
+        let mut one_time_keys = BTreeMap::new();
+        one_time_keys.insert(
+            DeviceKeyId::try_from("curve25519:AAAAAQ").unwrap(),
+            OneTimeKey::Key("curve25519_key".into()),
+        );
+
+        let req: http::Request<Vec<u8>> =
+            Request { device_keys: Some(device_keys), one_time_keys: Some(one_time_keys) }
+                .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+                .unwrap();
+
+        assert_eq!(
+            from_json_slice::<JsonValue>(req.body()).unwrap(),
+            json!({
+                "device_keys": {
+                    "user_id": "@alice:example.org",
+                    "device_id": "JLAFKJWSCS",
+                    "algorithms": ["m.olm.v1.curve25519-aes-sha2", "m.megolm.v1.aes-sha2"],
+                    "keys": { "ed25519:JLAFKJWSCS": "ed25519_key" },
+                    "signatures": {}
+                },
+                "one_time_keys": { "curve25519:AAAAAQ": "curve25519_key" }
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_response() {
+        let body = json!({
+            "one_time_key_counts": { "curve25519": 10, "signed_curve25519": 20 }
+        });
+        let res =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert_eq!(res.one_time_key_counts.len(), 2);
+        assert_eq!(res.one_time_key_counts[&DeviceKeyAlgorithm::Curve25519], uint!(10));
+        assert_eq!(res.one_time_key_counts[&DeviceKeyAlgorithm::SignedCurve25519], uint!(20));
+    }
+}
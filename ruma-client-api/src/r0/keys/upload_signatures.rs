@@ -15,12 +15,14 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// Signed keys.
         #[ruma_api(body)]
         pub signed_keys: BTreeMap<UserId, BTreeMap<String, serde_json::Value>>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
@@ -3,7 +3,7 @@
 use ruma_api::ruma_api;
 
 use super::CrossSigningKey;
-use crate::r0::uiaa::AuthData;
+use crate::r0::uiaa::{AuthData, UiaaResponse};
 
 ruma_api! {
     metadata: {
@@ -35,7 +35,73 @@ ruma_api! {
         pub user_signing_key: Option<CrossSigningKey>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
-    error: crate::Error
+    error: UiaaResponse
+}
+
+impl Request {
+    /// Creates an empty `Request`.
+    pub fn new() -> Self {
+        Self { auth: None, master_key: None, self_signing_key: None, user_signing_key: None }
+    }
+}
+
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use ruma_api::OutgoingRequest as _;
+    use ruma_common::encryption::{CrossSigningKey, KeyUsage};
+    use ruma_identifiers::user_id;
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+    use crate::r0::uiaa::AuthData;
+
+    #[test]
+    fn serialize_master_key_upload() {
+        let mut keys = BTreeMap::new();
+        keys.insert(
+            "ed25519:base64+master+public+key".to_owned(),
+            "base64+master+public+key".to_owned(),
+        );
+        let master_key = CrossSigningKey::new(
+            user_id!("@alice:example.com"),
+            vec![KeyUsage::Master],
+            keys,
+            BTreeMap::new(),
+        );
+
+        let req = Request {
+            auth: Some(AuthData::FallbackAcknowledgement { session: "xxxxxx".into() }),
+            master_key: Some(master_key),
+            ..Request::new()
+        }
+        .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+        .unwrap();
+
+        assert_eq!(req.uri().path(), "/_matrix/client/r0/keys/device_signing/upload");
+        assert_eq!(
+            from_json_slice::<JsonValue>(req.body()).unwrap(),
+            json!({
+                "auth": { "session": "xxxxxx" },
+                "master_key": {
+                    "user_id": "@alice:example.com",
+                    "usage": ["master"],
+                    "keys": {
+                        "ed25519:base64+master+public+key": "base64+master+public+key"
+                    }
+                }
+            })
+        );
+    }
 }
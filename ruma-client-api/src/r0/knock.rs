@@ -0,0 +1,3 @@
+//! Endpoints for knocking on rooms.
+
+pub mod knock_room;
@@ -0,0 +1,83 @@
+//! [POST /_matrix/client/r0/knock/{roomIdOrAlias}](https://github.com/matrix-org/matrix-doc/pull/2403)
+
+use ruma_api::ruma_api;
+use ruma_identifiers::{RoomId, RoomIdOrAliasId, ServerName};
+
+ruma_api! {
+    metadata: {
+        description: "Knock on a room.",
+        method: POST,
+        name: "knock_room",
+        path: "/_matrix/client/r0/knock/:room_id_or_alias",
+        rate_limited: true,
+        requires_authentication: true,
+    }
+
+    #[derive(PartialEq, Eq)]
+    request: {
+        /// The room the user should knock on.
+        #[ruma_api(path)]
+        pub room_id_or_alias: RoomIdOrAliasId,
+
+        /// The reason for knocking on a room.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub reason: Option<String>,
+
+        /// The servers to attempt to knock on the room through.
+        ///
+        /// One of the servers must be participating in the room.
+        #[ruma_api(query)]
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub server_name: Vec<Box<ServerName>>,
+    }
+
+    #[derive(PartialEq, Eq)]
+    response: {
+        /// The room that the user knocked on.
+        pub room_id: RoomId,
+    }
+
+    error: crate::Error
+}
+
+impl Request {
+    /// Creates a new `Request` with the given room ID or alias.
+    pub fn new(room_id_or_alias: RoomIdOrAliasId) -> Self {
+        Self { room_id_or_alias, reason: None, server_name: Vec::new() }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given room ID.
+    pub fn new(room_id: RoomId) -> Self {
+        Self { room_id }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ruma_api::OutgoingRequest;
+    use ruma_identifiers::{server_name, RoomIdOrAliasId};
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+
+    #[test]
+    fn serialize_knock_with_reason_and_servers() {
+        let mut req = Request::new(RoomIdOrAliasId::try_from("#room:example.org").unwrap());
+        req.reason = Some("Let me in".into());
+        req.server_name = vec![server_name!("example.org"), server_name!("matrix.org")];
+
+        let request: http::Request<Vec<u8>> =
+            req.try_into_http_request("https://homeserver.tld", Some("auth_tok")).unwrap();
+
+        assert_eq!(request.uri().path(), "/_matrix/client/r0/knock/%23room%3Aexample%2Eorg");
+        assert_eq!(request.uri().query(), Some("server_name=example.org&server_name=matrix.org"));
+        assert_eq!(
+            from_json_slice::<JsonValue>(request.body()).unwrap(),
+            json!({ "reason": "Let me in" })
+        );
+    }
+}
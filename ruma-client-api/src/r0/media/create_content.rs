@@ -1,6 +1,7 @@
 //! [POST /_matrix/media/r0/upload](https://matrix.org/docs/spec/client_server/r0.6.0#post-matrix-media-r0-upload)
 
 use ruma_api::ruma_api;
+use ruma_identifiers::MxcUri;
 
 ruma_api! {
     metadata: {
@@ -12,6 +13,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The name of the file being uploaded.
         #[ruma_api(query)]
@@ -19,19 +21,106 @@ ruma_api! {
         pub filename: Option<String>,
 
         /// The content type of the file being uploaded.
-        // TODO: This should be optional.
         #[ruma_api(header = CONTENT_TYPE)]
-        pub content_type: String,
+        pub content_type: Option<String>,
 
         /// The file contents to upload.
         #[ruma_api(raw_body)]
         pub file: Vec<u8>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The MXC URI for the uploaded content.
-        pub content_uri: String,
+        pub content_uri: MxcUri,
     }
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given file contents.
+    pub fn new(file: Vec<u8>) -> Self {
+        Self { filename: None, content_type: None, file }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given MXC URI.
+    pub fn new(content_uri: MxcUri) -> Self {
+        Self { content_uri }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ruma_api::{IncomingRequest as _, IncomingResponse as _, OutgoingRequest};
+    use ruma_identifiers::MxcUri;
+
+    use super::{Request, Response};
+
+    #[test]
+    fn serialize_upload_request() {
+        let mut req = Request::new(b"hello world".to_vec());
+        req.filename = Some("hello.txt".into());
+        req.content_type = Some("text/plain".into());
+
+        let request: http::Request<Vec<u8>> =
+            req.try_into_http_request("https://homeserver.tld", Some("auth_tok")).unwrap();
+
+        assert_eq!(request.uri().path(), "/_matrix/media/r0/upload");
+        assert_eq!(request.uri().query(), Some("filename=hello.txt"));
+        assert_eq!(request.headers()[http::header::CONTENT_TYPE], "text/plain");
+        assert_eq!(request.body(), b"hello world");
+    }
+
+    #[test]
+    fn serialize_upload_request_without_content_type() {
+        let request: http::Request<Vec<u8>> = Request::new(vec![0, 1, 2])
+            .try_into_http_request("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+
+        assert!(request.headers().get(http::header::CONTENT_TYPE).is_none());
+        assert_eq!(request.body(), &[0_u8, 1, 2]);
+    }
+
+    #[test]
+    fn deserialize_upload_request() {
+        let request = http::Request::builder()
+            .method(http::Method::POST)
+            .uri("https://homeserver.tld/_matrix/media/r0/upload")
+            .header(http::header::CONTENT_TYPE, "text/plain")
+            .body(b"hello world".to_vec())
+            .unwrap();
+
+        let req = Request::try_from_http_request(request).unwrap();
+        assert_eq!(req.content_type.as_deref(), Some("text/plain"));
+        assert_eq!(req.file, b"hello world");
+    }
+
+    #[test]
+    fn deserialize_upload_request_with_invalid_content_type() {
+        let request = http::Request::builder()
+            .method(http::Method::POST)
+            .uri("https://homeserver.tld/_matrix/media/r0/upload")
+            .header(http::header::CONTENT_TYPE, &b"text/\xff"[..])
+            .body(vec![])
+            .unwrap();
+
+        assert!(Request::try_from_http_request(request).is_err());
+    }
+
+    #[test]
+    fn deserialize_upload_response() {
+        let response = http::Response::new(
+            br#"{ "content_uri": "mxc://example.org/AQwafuaFswefuhsfAFAgsw" }"#.to_vec(),
+        );
+
+        assert_eq!(
+            Response::try_from_http_response(response).unwrap().content_uri,
+            MxcUri::try_from("mxc://example.org/AQwafuaFswefuhsfAFAgsw").unwrap()
+        );
+    }
+}
@@ -13,6 +13,7 @@ ruma_api! {
         requires_authentication: false,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The media ID from the mxc:// URI (the path component).
         #[ruma_api(path)]
@@ -28,6 +29,7 @@ ruma_api! {
         pub allow_remote: Option<bool>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The content that was previously uploaded.
         #[ruma_api(raw_body)]
@@ -39,8 +41,53 @@ ruma_api! {
 
         /// The name of the file that was previously uploaded, if set.
         #[ruma_api(header = CONTENT_DISPOSITION)]
-        pub content_disposition: String,
+        pub content_disposition: Option<String>,
     }
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given media ID and server name.
+    pub fn new(media_id: String, server_name: Box<ServerName>) -> Self {
+        Self { media_id, server_name, allow_remote: None }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given file contents and content type.
+    pub fn new(file: Vec<u8>, content_type: String) -> Self {
+        Self { file, content_type, content_disposition: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::IncomingResponse as _;
+
+    use super::Response;
+
+    #[test]
+    fn deserialize_download_response() {
+        let response = http::Response::builder()
+            .header(http::header::CONTENT_TYPE, "image/png")
+            .header(http::header::CONTENT_DISPOSITION, "inline; filename=\"cat.png\"")
+            .body(vec![0x89, b'P', b'N', b'G'])
+            .unwrap();
+        let response = Response::try_from_http_response(response).unwrap();
+
+        assert_eq!(response.file, vec![0x89, b'P', b'N', b'G']);
+        assert_eq!(response.content_type, "image/png");
+        assert_eq!(response.content_disposition.as_deref(), Some("inline; filename=\"cat.png\""));
+    }
+
+    #[test]
+    fn deserialize_download_response_without_filename() {
+        let response =
+            http::Response::builder().header(http::header::CONTENT_TYPE, "text/plain").body(vec![]);
+        let response = Response::try_from_http_response(response.unwrap()).unwrap();
+
+        assert!(response.file.is_empty());
+        assert_eq!(response.content_disposition, None);
+    }
+}
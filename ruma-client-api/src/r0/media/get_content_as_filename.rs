@@ -13,6 +13,7 @@ ruma_api! {
         requires_authentication: false,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The media ID from the mxc:// URI (the path component).
         #[ruma_api(path)]
@@ -32,6 +33,7 @@ ruma_api! {
         pub allow_remote: Option<bool>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The content that was previously uploaded.
         #[ruma_api(raw_body)]
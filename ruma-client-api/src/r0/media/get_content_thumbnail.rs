@@ -58,6 +58,7 @@ ruma_api! {
         pub width: UInt,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The content type of the thumbnail.
         #[ruma_api(header = CONTENT_TYPE)]
@@ -70,3 +71,67 @@ ruma_api! {
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given media ID, server name, desired thumbnail width and
+    /// desired thumbnail height.
+    pub fn new(media_id: String, server_name: Box<ServerName>, width: UInt, height: UInt) -> Self {
+        Self { media_id, server_name, method: None, width, height, allow_remote: None }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given thumbnail content type and contents.
+    pub fn new(content_type: String, file: Vec<u8>) -> Self {
+        Self { content_type, file }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::uint;
+    use ruma_api::OutgoingRequest;
+    use ruma_identifiers::server_name;
+
+    use super::{Method, Request};
+
+    #[test]
+    fn serialize_thumbnail_request() {
+        let mut req = Request::new(
+            "AQwafuaFswefuhsfAFAgsw".into(),
+            server_name!("example.org"),
+            uint!(800),
+            uint!(600),
+        );
+        req.method = Some(Method::Crop);
+
+        let request: http::Request<Vec<u8>> =
+            req.try_into_http_request("https://homeserver.tld", None).unwrap();
+
+        assert_eq!(
+            request.uri().path(),
+            "/_matrix/media/r0/thumbnail/example%2Eorg/AQwafuaFswefuhsfAFAgsw"
+        );
+        assert_eq!(request.uri().query(), Some("height=600&method=crop&width=800"));
+    }
+
+    #[test]
+    fn serialize_thumbnail_request_scale_without_remote() {
+        let mut req = Request::new(
+            "AQwafuaFswefuhsfAFAgsw".into(),
+            server_name!("example.org"),
+            uint!(32),
+            uint!(32),
+        );
+        req.method = Some(Method::Scale);
+        req.allow_remote = Some(false);
+
+        let request: http::Request<Vec<u8>> =
+            req.try_into_http_request("https://homeserver.tld", None).unwrap();
+
+        assert_eq!(
+            request.uri().query(),
+            Some("allow_remote=false&height=32&method=scale&width=32")
+        );
+    }
+}
@@ -13,8 +13,10 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {}
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// Maximum size of upload in bytes.
         #[serde(rename = "m.upload.size")]
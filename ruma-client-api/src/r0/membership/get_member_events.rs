@@ -2,9 +2,8 @@
 
 use ruma_api::ruma_api;
 use ruma_common::Raw;
-use ruma_events::room::member::MemberEvent;
+use ruma_events::room::member::{MemberEvent, MembershipState};
 use ruma_identifiers::RoomId;
-use serde::{Deserialize, Serialize};
 
 ruma_api! {
     metadata: {
@@ -16,6 +15,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The room to get the member events for.
         #[ruma_api(path)]
@@ -32,13 +32,13 @@ ruma_api! {
         /// the membership is the same as membership or is not the same as not_membership.
         #[serde(skip_serializing_if = "Option::is_none")]
         #[ruma_api(query)]
-        pub membership: Option<MembershipEventFilter>,
+        pub membership: Option<MembershipState>,
 
         /// The kind of memberships to *exclude* from the results. Defaults to no filtering if
         /// unspecified.
         #[serde(skip_serializing_if = "Option::is_none")]
         #[ruma_api(query)]
-        pub not_membership: Option<MembershipEventFilter>,
+        pub not_membership: Option<MembershipState>,
     }
 
     response: {
@@ -49,30 +49,28 @@ ruma_api! {
     error: crate::Error
 }
 
-/// The kind of membership events to filter for.
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
-#[serde(rename_all = "lowercase")]
-pub enum MembershipEventFilter {
-    /// The user has joined.
-    Join,
-
-    /// The user has been invited.
-    Invite,
-
-    /// The user has left.
-    Leave,
+impl Request {
+    /// Creates a new `Request` with the given room ID.
+    pub fn new(room_id: RoomId) -> Self {
+        Self { room_id, at: None, membership: None, not_membership: None }
+    }
+}
 
-    /// The user has been banned.
-    Ban,
+impl Response {
+    /// Creates a new `Response` with the given member event chunk.
+    pub fn new(chunk: Vec<Raw<MemberEvent>>) -> Self {
+        Self { chunk }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::convert::TryInto;
-
     use matches::assert_matches;
+    use ruma_api::{IncomingRequest as _, OutgoingRequest as _};
+    use ruma_events::room::member::MembershipState;
+    use ruma_identifiers::room_id;
 
-    use super::{MembershipEventFilter, Request};
+    use super::Request;
 
     #[test]
     fn deserialization() {
@@ -87,8 +85,9 @@ mod tests {
             .build()
             .unwrap();
 
-        let req: Result<Request, _> =
-            http::Request::builder().uri(uri).body(Vec::<u8>::new()).unwrap().try_into();
+        let req = Request::try_from_http_request(
+            http::Request::builder().uri(uri).body(Vec::<u8>::new()).unwrap(),
+        );
 
         assert_matches!(
             req,
@@ -96,8 +95,35 @@ mod tests {
                 room_id,
                 at: Some(at),
                 membership: None,
-                not_membership: Some(MembershipEventFilter::Leave),
+                not_membership: Some(MembershipState::Leave),
             }) if room_id == "!dummy:example.org" && at == "1026"
         );
     }
+
+    #[test]
+    fn serialize_membership_filters() {
+        let req = Request {
+            at: Some("s72595_4483_1934".into()),
+            membership: Some(MembershipState::Join),
+            not_membership: Some(MembershipState::Ban),
+            ..Request::new(room_id!("!dummy:example.org"))
+        }
+        .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+        .unwrap();
+
+        assert_eq!(req.uri().path(), "/_matrix/client/r0/rooms/%21dummy%3Aexample%2Eorg/members");
+        assert_eq!(
+            req.uri().query(),
+            Some("at=s72595_4483_1934&membership=join&not_membership=ban")
+        );
+    }
+
+    #[test]
+    fn serialize_without_filters() {
+        let req = Request::new(room_id!("!dummy:example.org"))
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+
+        assert!(req.uri().query().unwrap_or("").is_empty());
+    }
 }
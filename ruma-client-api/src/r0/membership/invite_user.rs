@@ -22,6 +22,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq)]
     request: {
         /// The room where the user should be invited.
         #[ruma_api(path)]
@@ -32,6 +33,7 @@ ruma_api! {
         pub recipient: InvitationRecipient,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
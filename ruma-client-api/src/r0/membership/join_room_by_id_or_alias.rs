@@ -32,6 +32,7 @@ ruma_api! {
         pub third_party_signed: Option<ThirdPartySigned>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The room that the user joined.
         pub room_id: RoomId,
@@ -16,23 +16,38 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The room to get the members of.
         #[ruma_api(path)]
         pub room_id: RoomId,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
-        /// A list of the rooms the user is in, i.e.
-        /// the ID of each room in which the user has joined membership.
+        /// A map from the user ID of each joined member to information about them.
         pub joined: BTreeMap<UserId, RoomMember>,
     }
 
     error: crate::Error
 }
 
+impl Request {
+    /// Creates a new `Request` with the given room ID.
+    pub fn new(room_id: RoomId) -> Self {
+        Self { room_id }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given joined members.
+    pub fn new(joined: BTreeMap<UserId, RoomMember>) -> Self {
+        Self { joined }
+    }
+}
+
 /// Information about a room member.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
 pub struct RoomMember {
     /// The display name of the user.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -42,3 +57,45 @@ pub struct RoomMember {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub avatar_url: Option<String>,
 }
+
+impl RoomMember {
+    /// Creates an empty `RoomMember`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::IncomingResponse as _;
+    use ruma_identifiers::user_id;
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::{Response, RoomMember};
+
+    #[test]
+    fn deserialize_response() {
+        let body = json!({
+            "joined": {
+                "@alice:example.org": {
+                    "display_name": "Alice",
+                    "avatar_url": "mxc://example.org/SEsfnsuifSDFSSEF"
+                },
+                "@bob:example.org": {}
+            }
+        });
+        let res =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert_eq!(res.joined.len(), 2);
+        assert_eq!(
+            res.joined[&user_id!("@alice:example.org")],
+            RoomMember {
+                display_name: Some("Alice".into()),
+                avatar_url: Some("mxc://example.org/SEsfnsuifSDFSSEF".into()),
+            }
+        );
+        assert_eq!(res.joined[&user_id!("@bob:example.org")], RoomMember::new());
+    }
+}
@@ -13,8 +13,10 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {}
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// A list of the rooms the user is in, i.e. the ID of each room in
         /// which the user has joined membership.
@@ -23,3 +25,41 @@ ruma_api! {
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates an empty `Request`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given joined rooms.
+    pub fn new(joined_rooms: Vec<RoomId>) -> Self {
+        Self { joined_rooms }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::IncomingResponse as _;
+    use ruma_identifiers::room_id;
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::Response;
+
+    #[test]
+    fn deserialize_response() {
+        let body = json!({
+            "joined_rooms": ["!first:example.org", "!second:example.org"]
+        });
+        let res =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert_eq!(
+            res.joined_rooms,
+            vec![room_id!("!first:example.org"), room_id!("!second:example.org")]
+        );
+    }
+}
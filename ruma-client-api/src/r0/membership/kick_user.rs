@@ -13,6 +13,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The room to kick the user from.
         #[ruma_api(path)]
@@ -26,7 +27,47 @@ ruma_api! {
         pub reason: Option<String>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given room id and user id.
+    pub fn new(room_id: RoomId, user_id: UserId) -> Self {
+        Self { room_id, user_id, reason: None }
+    }
+}
+
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::OutgoingRequest as _;
+    use ruma_identifiers::{room_id, user_id};
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+
+    #[test]
+    fn serialize_kick_with_reason() {
+        let req = Request {
+            reason: Some("Spamming".into()),
+            ..Request::new(room_id!("!room:example.org"), user_id!("@spammer:example.org"))
+        }
+        .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+        .unwrap();
+
+        assert_eq!(req.uri().path(), "/_matrix/client/r0/rooms/%21room%3Aexample%2Eorg/kick");
+        assert_eq!(
+            from_json_slice::<JsonValue>(req.body()).unwrap(),
+            json!({ "user_id": "@spammer:example.org", "reason": "Spamming" })
+        );
+    }
+}
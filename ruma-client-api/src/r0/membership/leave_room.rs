@@ -13,12 +13,14 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The room to leave.
         #[ruma_api(path)]
         pub room_id: &'a RoomId,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
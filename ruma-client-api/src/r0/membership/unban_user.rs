@@ -13,6 +13,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The room to unban the user from.
         #[ruma_api(path)]
@@ -22,7 +23,22 @@ ruma_api! {
         pub user_id: UserId,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given room id and user id.
+    pub fn new(room_id: RoomId, user_id: UserId) -> Self {
+        Self { room_id, user_id }
+    }
+}
+
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self
+    }
+}
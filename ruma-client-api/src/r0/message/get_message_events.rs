@@ -168,8 +168,9 @@ mod tests {
             filter: None,
         };
 
-        let request =
-            req.try_into_http_request("https://homeserver.tld", Some("auth_tok")).unwrap();
+        let request = req
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
         assert_eq!("from=token&to=token2&dir=b&limit=0", request.uri().query().unwrap(),);
     }
 
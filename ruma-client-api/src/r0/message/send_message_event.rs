@@ -1,9 +1,9 @@
 //! [PUT /_matrix/client/r0/rooms/{roomId}/send/{eventType}/{txnId}](https://matrix.org/docs/spec/client_server/r0.6.1#put-matrix-client-r0-rooms-roomid-send-eventtype-txnid)
 
 use ruma_api::ruma_api;
-use ruma_events::EventType;
+use ruma_events::{EventType, MessageEventContent};
 use ruma_identifiers::{EventId, RoomId};
-use serde_json::value::RawValue as RawJsonValue;
+use serde_json::value::{to_raw_value as to_raw_json_value, RawValue as RawJsonValue};
 
 ruma_api! {
     metadata: {
@@ -32,13 +32,15 @@ ruma_api! {
         #[ruma_api(path)]
         pub txn_id: &'a str,
 
-        /// The event's content. The type for this field will be updated in a
-        /// future release, until then you can create a value using
-        /// `serde_json::value::to_raw_value`.
+        /// The event's content.
+        ///
+        /// Use `Request::new` to create this from a typed event content, or `Request::new_raw` to
+        /// send arbitrary JSON.
         #[ruma_api(body)]
         pub data: Box<RawJsonValue>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// A unique identifier for the event.
         pub event_id: EventId,
@@ -46,3 +48,100 @@ ruma_api! {
 
     error: crate::Error
 }
+
+impl<'a> Request<'a> {
+    /// Creates a new `Request` with the given room id, transaction id and event content.
+    ///
+    /// The event type is taken from the content.
+    ///
+    /// # Errors
+    ///
+    /// Since `Request` stores the request body in serialized form, this function can fail if `T`s
+    /// `Serialize` implementation can fail.
+    pub fn new<T: MessageEventContent>(
+        room_id: &'a RoomId,
+        txn_id: &'a str,
+        content: &T,
+    ) -> serde_json::Result<Self> {
+        Ok(Self {
+            room_id,
+            event_type: content.event_type().into(),
+            txn_id,
+            data: to_raw_json_value(content)?,
+        })
+    }
+
+    /// Creates a new `Request` with the given room id, event type, transaction id and raw event
+    /// content.
+    pub fn new_raw(
+        room_id: &'a RoomId,
+        event_type: EventType,
+        txn_id: &'a str,
+        data: Box<RawJsonValue>,
+    ) -> Self {
+        Self { room_id, event_type, txn_id, data }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given event id.
+    pub fn new(event_id: EventId) -> Self {
+        Self { event_id }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::OutgoingRequest as _;
+    use ruma_events::{
+        room::message::{MessageEventContent, TextMessageEventContent},
+        EventType,
+    };
+    use ruma_identifiers::room_id;
+    use serde_json::{
+        from_slice as from_json_slice, json, value::to_raw_value as to_raw_json_value,
+        Value as JsonValue,
+    };
+
+    use super::Request;
+
+    #[test]
+    fn serialize_raw_request() {
+        let room_id = room_id!("!roomid:example.org");
+        let data = to_raw_json_value(&json!({ "custom": true })).unwrap();
+        let req =
+            Request::new_raw(&room_id, EventType::Custom("org.example.event".into()), "txn1", data);
+
+        let http_req = req
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+        assert_eq!(
+            http_req.uri().path(),
+            "/_matrix/client/r0/rooms/%21roomid%3Aexample%2Eorg/send/org%2Eexample%2Eevent/txn1"
+        );
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({ "custom": true })
+        );
+    }
+
+    #[test]
+    fn request_from_typed_content() {
+        let room_id = room_id!("!roomid:example.org");
+        let content = MessageEventContent::Text(TextMessageEventContent::plain("Hello"));
+        let req = Request::new(&room_id, "txn1", &content).unwrap();
+        assert_eq!(req.event_type, EventType::RoomMessage);
+
+        let http_req = req
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+        assert_eq!(
+            http_req.uri().path(),
+            "/_matrix/client/r0/rooms/%21roomid%3Aexample%2Eorg/send/m%2Eroom%2Emessage/txn1"
+        );
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({ "msgtype": "m.text", "body": "Hello" })
+        );
+    }
+}
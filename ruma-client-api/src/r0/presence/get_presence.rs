@@ -1,4 +1,4 @@
-//! [GET /_matrix/client/r0/presence/{userId}/status](https://matrix.org/docs/spec/client_server/r0.6.0#get-matrix-client-r0-presence-userid-status)
+//! [GET /_matrix/client/r0/presence/{userId}/status](https://matrix.org/docs/spec/client_server/r0.6.1#get-matrix-client-r0-presence-userid-status)
 
 use std::time::Duration;
 
@@ -16,12 +16,14 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The user whose presence state will be retrieved.
         #[ruma_api(path)]
         pub user_id: UserId,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The state message for this user if one was set.
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -45,3 +47,69 @@ ruma_api! {
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given user ID.
+    pub fn new(user_id: UserId) -> Self {
+        Self { user_id }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given presence state.
+    pub fn new(presence: PresenceState) -> Self {
+        Self { presence, status_msg: None, currently_active: None, last_active_ago: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use ruma_api::{IncomingResponse as _, OutgoingRequest as _, OutgoingResponse as _};
+    use ruma_common::presence::PresenceState;
+    use ruma_identifiers::user_id;
+    use serde_json::{
+        from_slice as from_json_slice, json, to_vec as to_json_vec, Value as JsonValue,
+    };
+
+    use super::{Request, Response};
+
+    #[test]
+    fn request_path() {
+        let req = Request::new(user_id!("@alice:example.org"))
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+        assert_eq!(req.uri().path(), "/_matrix/client/r0/presence/%40alice%3Aexample%2Eorg/status");
+    }
+
+    #[test]
+    fn deserialize_online_response() {
+        let body = json!({
+            "presence": "online",
+            "last_active_ago": 420_845,
+            "status_msg": "Making cupcakes",
+            "currently_active": true
+        });
+        let response =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert_eq!(response.presence, PresenceState::Online);
+        assert_eq!(response.last_active_ago, Some(Duration::from_millis(420_845)));
+        assert_eq!(response.status_msg.as_deref(), Some("Making cupcakes"));
+        assert_eq!(response.currently_active, Some(true));
+    }
+
+    #[test]
+    fn serialize_offline_response() {
+        let mut response = Response::new(PresenceState::Offline);
+        response.last_active_ago = Some(Duration::from_secs(3600));
+
+        let http_res = response.try_into_http_response::<Vec<u8>>().unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_res.body()).unwrap(),
+            json!({ "presence": "offline", "last_active_ago": 3_600_000 })
+        );
+    }
+}
@@ -1,4 +1,4 @@
-//! [PUT /_matrix/client/r0/presence/{userId}/status](https://matrix.org/docs/spec/client_server/r0.6.0#put-matrix-client-r0-presence-userid-status)
+//! [PUT /_matrix/client/r0/presence/{userId}/status](https://matrix.org/docs/spec/client_server/r0.6.1#put-matrix-client-r0-presence-userid-status)
 
 use ruma_api::ruma_api;
 use ruma_common::presence::PresenceState;
@@ -14,6 +14,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The user whose presence state will be updated.
         #[ruma_api(path)]
@@ -27,7 +28,57 @@ ruma_api! {
         pub status_msg: Option<String>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given user ID and presence state.
+    pub fn new(user_id: UserId, presence: PresenceState) -> Self {
+        Self { user_id, presence, status_msg: None }
+    }
+}
+
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::OutgoingRequest as _;
+    use ruma_common::presence::PresenceState;
+    use ruma_identifiers::user_id;
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+
+    #[test]
+    fn serialize_request_with_status_msg() {
+        let mut req = Request::new(user_id!("@alice:example.org"), PresenceState::Unavailable);
+        req.status_msg = Some("Away from keyboard".into());
+
+        let http_req = req
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({ "presence": "unavailable", "status_msg": "Away from keyboard" })
+        );
+    }
+
+    #[test]
+    fn status_msg_is_optional() {
+        let http_req = Request::new(user_id!("@alice:example.org"), PresenceState::Online)
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({ "presence": "online" })
+        );
+    }
+}
@@ -13,12 +13,14 @@ ruma_api! {
         requires_authentication: false,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The user whose display name will be retrieved.
         #[ruma_api(path)]
         pub user_id: UserId
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The user's display name, if set.
         #[serde(skip_serializing_if = "Option::is_none")]
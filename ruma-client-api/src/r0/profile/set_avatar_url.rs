@@ -13,6 +13,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The user whose avatar URL will be set.
         #[ruma_api(path)]
@@ -24,6 +25,7 @@ ruma_api! {
         pub avatar_url: Option<String>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
@@ -13,6 +13,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The user whose display name will be set.
         #[ruma_api(path)]
@@ -23,6 +24,7 @@ ruma_api! {
         pub displayname: Option<String>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
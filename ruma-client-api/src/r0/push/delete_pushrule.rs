@@ -14,6 +14,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The scope to delete from. 'global' to specify global rules.
         #[ruma_api(path)]
@@ -28,6 +29,7 @@ ruma_api! {
         pub rule_id: String,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
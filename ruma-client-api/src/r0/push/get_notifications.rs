@@ -5,8 +5,9 @@ use std::time::SystemTime;
 use js_int::UInt;
 use ruma_api::ruma_api;
 use ruma_common::{push::Action, Raw};
-use ruma_events::AnyEvent;
+use ruma_events::AnyRoomEvent;
 use ruma_identifiers::RoomId;
+use ruma_serde::string_enum;
 use serde::{Deserialize, Serialize};
 
 ruma_api! {
@@ -19,6 +20,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// Pagination token given to retrieve the next set of events.
         #[ruma_api(query)]
@@ -30,11 +32,13 @@ ruma_api! {
         #[serde(skip_serializing_if = "Option::is_none")]
         pub limit: Option<UInt>,
 
-        /// Allows basic filtering of events returned. Supply "highlight" to return only events
-        /// where the notification had the 'highlight' tweak set.
+        /// Allows basic filtering of events returned.
+        ///
+        /// Use `NotificationFilter::Highlight` to return only events where the notification had
+        /// the 'highlight' tweak set.
         #[ruma_api(query)]
         #[serde(skip_serializing_if = "Option::is_none")]
-        pub only: Option<String>
+        pub only: Option<NotificationFilter>,
     }
 
     response: {
@@ -43,22 +47,45 @@ ruma_api! {
         #[serde(skip_serializing_if = "Option::is_none")]
         pub next_token: Option<String>,
 
-
         /// The list of events that triggered notifications.
-        pub notifications: Vec<Raw<Notification>>,
+        pub notifications: Vec<Notification>,
     }
 
     error: crate::Error
 }
 
-/// Represents a notification
+impl Request {
+    /// Creates an empty `Request`.
+    pub fn new() -> Self {
+        Self { from: None, limit: None, only: None }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given notifications.
+    pub fn new(notifications: Vec<Notification>) -> Self {
+        Self { next_token: None, notifications }
+    }
+}
+
+string_enum! {
+    /// A filter for the notifications returned by `get_notifications`.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum NotificationFilter {
+        /// Only return notifications that had the `highlight` tweak set.
+        Highlight => "highlight",
+    }
+}
+
+/// Represents a notification.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Notification {
     /// The actions to perform when the conditions for this rule are met.
     pub actions: Vec<Action>,
 
     /// The event that triggered the notification.
-    pub event: Raw<AnyEvent>,
+    pub event: Raw<AnyRoomEvent>,
 
     /// The profile tag of the rule that matched this event.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -75,3 +102,116 @@ pub struct Notification {
     #[serde(with = "ruma_serde::time::ms_since_unix_epoch")]
     pub ts: SystemTime,
 }
+
+impl Notification {
+    /// Creates a new `Notification` with the given actions, event, read flag, room ID and
+    /// timestamp.
+    pub fn new(
+        actions: Vec<Action>,
+        event: Raw<AnyRoomEvent>,
+        read: bool,
+        room_id: RoomId,
+        ts: SystemTime,
+    ) -> Self {
+        Self { actions, event, profile_tag: None, read, room_id, ts }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use js_int::uint;
+    use matches::assert_matches;
+    use ruma_api::{IncomingResponse as _, OutgoingRequest as _};
+    use ruma_common::push::{Action, Tweak};
+    use ruma_events::AnyRoomEvent;
+    use ruma_identifiers::room_id;
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::{NotificationFilter, Request, Response};
+
+    #[test]
+    fn serialize_request() {
+        let req = Request {
+            from: Some("token".into()),
+            limit: Some(uint!(10)),
+            only: Some(NotificationFilter::Highlight),
+        }
+        .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+        .unwrap();
+
+        assert_eq!(req.uri().query(), Some("from=token&limit=10&only=highlight"));
+    }
+
+    #[test]
+    fn deserialize_response() {
+        let body = json!({
+            "next_token": "abcdef",
+            "notifications": [
+                {
+                    "actions": [
+                        "notify",
+                        { "set_tweak": "sound", "value": "default" },
+                        { "set_tweak": "highlight" }
+                    ],
+                    "event": {
+                        "content": { "body": "Hey @alice!", "msgtype": "m.text" },
+                        "event_id": "$143273582443PhrSn:example.org",
+                        "origin_server_ts": 1_432_735_824_653_u64,
+                        "room_id": "!jEsUZKDJdhlrceRyVU:example.org",
+                        "sender": "@example:example.org",
+                        "type": "m.room.message"
+                    },
+                    "profile_tag": "hcbvkzxhcvb",
+                    "read": true,
+                    "room_id": "!jEsUZKDJdhlrceRyVU:example.org",
+                    "ts": 1_475_508_881_945_u64
+                },
+                {
+                    "actions": ["notify", { "set_tweak": "highlight", "value": false }],
+                    "event": {
+                        "content": { "body": "Hello", "msgtype": "m.text" },
+                        "event_id": "$143273582443PhrSo:example.org",
+                        "origin_server_ts": 1_432_735_824_654_u64,
+                        "room_id": "!jEsUZKDJdhlrceRyVU:example.org",
+                        "sender": "@example:example.org",
+                        "type": "m.room.message"
+                    },
+                    "read": false,
+                    "room_id": "!jEsUZKDJdhlrceRyVU:example.org",
+                    "ts": 1_475_508_881_946_u64
+                }
+            ]
+        });
+        let res =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert_eq!(res.next_token.as_deref(), Some("abcdef"));
+        assert_eq!(res.notifications.len(), 2);
+
+        let highlight = &res.notifications[0];
+        assert_matches!(
+            highlight.actions.as_slice(),
+            [
+                Action::Notify,
+                Action::SetTweak(Tweak::Sound(sound)),
+                Action::SetTweak(Tweak::Highlight(true)),
+            ] if sound == "default"
+        );
+        assert_matches!(highlight.event.deserialize().unwrap(), AnyRoomEvent::Message(_));
+        assert_eq!(highlight.profile_tag.as_deref(), Some("hcbvkzxhcvb"));
+        assert!(highlight.read);
+        assert_eq!(highlight.room_id, room_id!("!jEsUZKDJdhlrceRyVU:example.org"));
+        assert_eq!(highlight.ts, UNIX_EPOCH + Duration::from_millis(1_475_508_881_945));
+
+        let plain = &res.notifications[1];
+        assert_matches!(
+            plain.actions.as_slice(),
+            [Action::Notify, Action::SetTweak(Tweak::Highlight(false))]
+        );
+        assert_eq!(plain.profile_tag, None);
+        assert!(!plain.read);
+    }
+}
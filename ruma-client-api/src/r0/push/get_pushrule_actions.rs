@@ -15,6 +15,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The scope to fetch a rule from. 'global' to specify global rules.
         #[ruma_api(path)]
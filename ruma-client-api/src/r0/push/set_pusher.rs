@@ -26,6 +26,7 @@ ruma_api! {
 
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
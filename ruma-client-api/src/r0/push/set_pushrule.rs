@@ -52,6 +52,7 @@ ruma_api! {
         pub pattern: Option<String>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
@@ -32,6 +32,7 @@ ruma_api! {
         pub actions: Vec<Action>
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
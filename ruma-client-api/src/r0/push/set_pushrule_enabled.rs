@@ -14,6 +14,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The scope to fetch a rule from. 'global' to specify global rules.
         #[ruma_api(path)]
@@ -31,6 +32,7 @@ ruma_api! {
         pub enabled: bool
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
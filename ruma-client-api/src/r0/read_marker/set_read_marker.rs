@@ -13,6 +13,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The room ID to set the read marker in for the user.
         #[ruma_api(path)]
@@ -29,9 +30,67 @@ ruma_api! {
         #[serde(rename = "m.read", skip_serializing_if = "Option::is_none")]
         pub read_receipt: Option<EventId>,
 
+        /// The event ID to set the private read receipt location at.
+        ///
+        /// Private read receipts are not sent to other users of the room.
+        #[cfg(feature = "unstable-pre-spec")]
+        #[serde(rename = "m.read.private", skip_serializing_if = "Option::is_none")]
+        pub private_read_receipt: Option<EventId>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given room ID and fully read marker.
+    pub fn new(room_id: RoomId, fully_read: EventId) -> Self {
+        Self {
+            room_id,
+            fully_read,
+            read_receipt: None,
+            #[cfg(feature = "unstable-pre-spec")]
+            private_read_receipt: None,
+        }
+    }
+}
+
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::OutgoingRequest;
+    use ruma_identifiers::{event_id, room_id};
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+
+    #[test]
+    fn serialize_fully_read_and_read_markers() {
+        let mut req =
+            Request::new(room_id!("!room:example.org"), event_id!("$fully_read:example.org"));
+        req.read_receipt = Some(event_id!("$read:example.org"));
+
+        let request: http::Request<Vec<u8>> =
+            req.try_into_http_request("https://homeserver.tld", Some("auth_tok")).unwrap();
+
+        assert_eq!(
+            request.uri().path(),
+            "/_matrix/client/r0/rooms/%21room%3Aexample%2Eorg/read_markers"
+        );
+        assert_eq!(
+            from_json_slice::<JsonValue>(request.body()).unwrap(),
+            json!({
+                "m.fully_read": "$fully_read:example.org",
+                "m.read": "$read:example.org",
+            })
+        );
+    }
+}
@@ -1,10 +1,9 @@
 //! [POST /_matrix/client/r0/rooms/{roomId}/receipt/{receiptType}/{eventId}](https://matrix.org/docs/spec/client_server/r0.6.0#post-matrix-client-r0-rooms-roomid-receipt-receipttype-eventid)
 
-use std::convert::TryFrom;
-
 use ruma_api::ruma_api;
 use ruma_identifiers::{EventId, RoomId};
-use strum::{Display, EnumString};
+
+pub use ruma_common::receipt::ReceiptType;
 
 ruma_api! {
     metadata: {
@@ -16,6 +15,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The room in which to send the event.
         #[ruma_api(path)]
@@ -30,23 +30,47 @@ ruma_api! {
         pub event_id: EventId,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
 }
 
-/// The type of receipt.
-#[derive(Clone, Copy, Debug, Display, EnumString)]
-pub enum ReceiptType {
-    /// m.read
-    #[strum(serialize = "m.read")]
-    Read,
+impl Request {
+    /// Creates a new `Request` with the given room ID, receipt type and event ID.
+    pub fn new(room_id: RoomId, receipt_type: ReceiptType, event_id: EventId) -> Self {
+        Self { room_id, receipt_type, event_id }
+    }
 }
 
-impl TryFrom<&'_ str> for ReceiptType {
-    type Error = strum::ParseError;
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::OutgoingRequest;
+    use ruma_identifiers::{event_id, room_id};
+
+    use super::{ReceiptType, Request};
+
+    #[test]
+    fn read_receipt_path() {
+        let req = Request::new(
+            room_id!("!room:example.org"),
+            ReceiptType::Read,
+            event_id!("$event:example.org"),
+        );
+
+        let request: http::Request<Vec<u8>> =
+            req.try_into_http_request("https://homeserver.tld", Some("auth_tok")).unwrap();
 
-    fn try_from(s: &str) -> Result<Self, Self::Error> {
-        s.parse()
+        assert_eq!(
+            request.uri().path(),
+            "/_matrix/client/r0/rooms/%21room%3Aexample%2Eorg/receipt/m%2Eread/%24event%3Aexample%2Eorg"
+        );
     }
 }
@@ -13,6 +13,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The ID of the room of the event to redact.
         #[ruma_api(path)]
@@ -34,6 +35,7 @@ ruma_api! {
         pub reason: Option<String>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The ID of the redacted event.
         pub event_id: EventId,
@@ -41,3 +43,56 @@ ruma_api! {
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given room ID, event ID and transaction ID.
+    pub fn new(room_id: RoomId, event_id: EventId, txn_id: String) -> Self {
+        Self { room_id, event_id, txn_id, reason: None }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given event ID.
+    pub fn new(event_id: EventId) -> Self {
+        Self { event_id }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::OutgoingRequest;
+    use ruma_identifiers::{event_id, room_id};
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+
+    fn request() -> Request {
+        Request::new(room_id!("!room:example.org"), event_id!("$event:example.org"), "1".into())
+    }
+
+    #[test]
+    fn redaction_without_reason() {
+        let request: http::Request<Vec<u8>> =
+            request().try_into_http_request("https://homeserver.tld", Some("auth_tok")).unwrap();
+
+        assert_eq!(
+            request.uri().path(),
+            "/_matrix/client/r0/rooms/%21room%3Aexample%2Eorg/redact/%24event%3Aexample%2Eorg/1"
+        );
+        assert_eq!(from_json_slice::<JsonValue>(request.body()).unwrap(), json!({}));
+    }
+
+    #[test]
+    fn redaction_with_reason() {
+        let mut req = request();
+        req.reason = Some("Spam".into());
+
+        let request: http::Request<Vec<u8>> =
+            req.try_into_http_request("https://homeserver.tld", Some("auth_tok")).unwrap();
+
+        assert_eq!(
+            from_json_slice::<JsonValue>(request.body()).unwrap(),
+            json!({ "reason": "Spam" })
+        );
+    }
+}
@@ -1,4 +1,4 @@
-//! [PUT /_matrix/client/r0/directory/room/{roomAlias}](https://matrix.org/docs/spec/client_server/r0.6.0#put-matrix-client-r0-directory-room-roomalias)
+//! [GET /_matrix/client/r0/rooms/{roomId}/aliases](https://matrix.org/docs/spec/client_server/r0.6.1#get-matrix-client-r0-rooms-roomid-aliases)
 
 use ruma_api::ruma_api;
 use ruma_identifiers::{RoomAliasId, RoomId};
@@ -6,22 +6,75 @@ use ruma_identifiers::{RoomAliasId, RoomId};
 ruma_api! {
     metadata: {
         description: "Get a list of local aliases on a given room.",
-        method: PUT,
-        name: "create_alias",
-        path: "/_matrix/client/r0/directory/room/:room_id",
-        rate_limited: false,
+        method: GET,
+        name: "aliases",
+        path: "/_matrix/client/r0/rooms/:room_id/aliases",
+        rate_limited: true,
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The room ID to get aliases of.
         #[ruma_api(path)]
         pub room_id: &'a RoomId,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
-        aliases: Vec<RoomAliasId>,
+        /// The server's local aliases on the room.
+        pub aliases: Vec<RoomAliasId>,
     }
 
     error: crate::Error
 }
+
+impl<'a> Request<'a> {
+    /// Creates a new `Request` with the given room ID.
+    pub fn new(room_id: &'a RoomId) -> Self {
+        Self { room_id }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given aliases.
+    pub fn new(aliases: Vec<RoomAliasId>) -> Self {
+        Self { aliases }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::{IncomingResponse as _, OutgoingRequest as _};
+    use ruma_identifiers::{room_alias_id, room_id};
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::{Request, Response};
+
+    #[test]
+    fn serialize_request() {
+        let room_id = room_id!("!room:server.org");
+        let http_req = Request::new(&room_id)
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+
+        assert_eq!(http_req.method(), http::Method::GET);
+        assert_eq!(
+            http_req.uri().path(),
+            "/_matrix/client/r0/rooms/%21room%3Aserver%2Eorg/aliases"
+        );
+    }
+
+    #[test]
+    fn deserialize_response() {
+        let body = json!({ "aliases": ["#test:server.org", "#other:server.org"] });
+        let res =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert_eq!(
+            res.aliases,
+            vec![room_alias_id!("#test:server.org"), room_alias_id!("#other:server.org")]
+        );
+    }
+}
@@ -86,6 +86,7 @@ ruma_api! {
         pub visibility: Option<Visibility>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The created room's ID.
         pub room_id: RoomId,
@@ -15,6 +15,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The ID of the room the event is in.
         #[ruma_api(path)]
@@ -33,3 +34,87 @@ ruma_api! {
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given room ID and event ID.
+    pub fn new(room_id: RoomId, event_id: EventId) -> Self {
+        Self { room_id, event_id }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given event.
+    pub fn new(event: Raw<AnyRoomEvent>) -> Self {
+        Self { event }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use matches::assert_matches;
+    use ruma_api::{IncomingResponse as _, OutgoingRequest as _};
+    use ruma_events::{
+        room::message::MessageEventContent, AnyMessageEvent, AnyRoomEvent, MessageEvent,
+    };
+    use ruma_identifiers::{event_id, room_id};
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::{Request, Response};
+
+    #[test]
+    fn serialize_request() {
+        let req = Request::new(room_id!("!roomid:example.org"), event_id!("$event:example.org"));
+
+        let http_req: http::Request<Vec<u8>> =
+            req.try_into_http_request("https://homeserver.tld", Some("auth_tok")).unwrap();
+
+        assert_eq!(
+            http_req.uri().path(),
+            "/_matrix/client/r0/rooms/%21roomid%3Aexample%2Eorg/event/%24event%3Aexample%2Eorg"
+        );
+    }
+
+    #[test]
+    fn deserialize_message_response() {
+        let body = json!({
+            "content": {
+                "body": "Hello",
+                "msgtype": "m.text"
+            },
+            "event_id": "$event:example.org",
+            "origin_server_ts": 1,
+            "room_id": "!roomid:example.org",
+            "sender": "@alice:example.org",
+            "type": "m.room.message"
+        });
+        let response =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert_matches!(
+            response.event.deserialize().unwrap(),
+            AnyRoomEvent::Message(AnyMessageEvent::RoomMessage(MessageEvent {
+                content: MessageEventContent::Text(text),
+                event_id,
+                ..
+            })) if text.body == "Hello" && event_id == event_id!("$event:example.org")
+        );
+    }
+
+    #[test]
+    fn deserialize_unknown_event_response() {
+        let body = json!({
+            "content": { "foo": "bar" },
+            "event_id": "$event:example.org",
+            "origin_server_ts": 1,
+            "room_id": "!roomid:example.org",
+            "sender": "@alice:example.org",
+            "type": "org.example.custom"
+        });
+        let response =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert_eq!(response.event.json().get(), body.to_string());
+    }
+}
@@ -1,19 +1,26 @@
 //! [POST /_matrix/client/r0/rooms/{roomId}/report/{eventId}](https://matrix.org/docs/spec/client_server/r0.6.0#post-matrix-client-r0-rooms-roomid-report-eventid)
 
-use js_int::Int;
+use std::{
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+};
+
+use js_int::{int, Int};
 use ruma_api::ruma_api;
 use ruma_identifiers::{EventId, RoomId};
+use serde::{Deserialize, Serialize};
 
 ruma_api! {
     metadata: {
         description: "Report content as inappropriate.",
         method: POST,
         name: "report_content",
-        path: "/rooms/:room_id/report/:event_id",
-        rate_limited:  false,
+        path: "/_matrix/client/r0/rooms/:room_id/report/:event_id",
+        rate_limited: false,
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// Room in which the event to be reported is located.
         #[ruma_api(path)]
@@ -23,14 +30,129 @@ ruma_api! {
         #[ruma_api(path)]
         pub event_id: EventId,
 
-        /// Integer between -100 and 0 rating offensivness.
-        pub score: Int,
+        /// How offensive the content is.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub score: Option<Score>,
 
         /// Reason to report content. May be blank.
-        pub reason: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub reason: Option<String>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given room ID, event ID, score and reason.
+    pub fn new(
+        room_id: RoomId,
+        event_id: EventId,
+        score: Option<Score>,
+        reason: Option<String>,
+    ) -> Self {
+        Self { room_id, event_id, score, reason }
+    }
+}
+
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// An integer between -100 and 0 rating the offensiveness of reported content.
+///
+/// -100 is "most offensive" and 0 is "inoffensive". Use `TryFrom<Int>` to create one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(try_from = "Int", into = "Int")]
+pub struct Score(Int);
+
+impl TryFrom<Int> for Score {
+    type Error = InvalidScore;
+
+    fn try_from(score: Int) -> Result<Self, InvalidScore> {
+        if score < int!(-100) || score > int!(0) {
+            Err(InvalidScore(score))
+        } else {
+            Ok(Self(score))
+        }
+    }
+}
+
+impl From<Score> for Int {
+    fn from(score: Score) -> Self {
+        score.0
+    }
+}
+
+/// An error returned when creating a `Score` outside of the range -100 to 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidScore(pub Int);
+
+impl Display for InvalidScore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "report score {} is not between -100 and 0", self.0)
+    }
+}
+
+impl std::error::Error for InvalidScore {}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use js_int::int;
+    use ruma_api::{IncomingRequest as _, OutgoingRequest as _};
+    use ruma_identifiers::{event_id, room_id};
+    use serde_json::{
+        from_slice as from_json_slice, json, to_vec as to_json_vec, Value as JsonValue,
+    };
+
+    use super::{InvalidScore, Request, Score};
+
+    #[test]
+    fn score_out_of_range() {
+        assert_eq!(Score::try_from(int!(-150)).unwrap_err(), InvalidScore(int!(-150)));
+        assert_eq!(Score::try_from(int!(1)).unwrap_err(), InvalidScore(int!(1)));
+        assert!(Score::try_from(int!(-100)).is_ok());
+        assert!(Score::try_from(int!(0)).is_ok());
+    }
+
+    #[test]
+    fn serialize_request() {
+        let req = Request::new(
+            room_id!("!roomid:example.org"),
+            event_id!("$event:example.org"),
+            Some(Score::try_from(int!(-100)).unwrap()),
+            Some("spam".into()),
+        );
+
+        let http_req = req
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+
+        assert_eq!(
+            http_req.uri().path(),
+            "/_matrix/client/r0/rooms/%21roomid%3Aexample%2Eorg/report/%24event%3Aexample%2Eorg"
+        );
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({ "score": -100, "reason": "spam" })
+        );
+    }
+
+    #[test]
+    fn deserialize_request_with_invalid_score() {
+        let http_req = http::Request::builder()
+            .method("POST")
+            .uri("/_matrix/client/r0/rooms/%21roomid%3Aexample%2Eorg/report/%24event%3Aexample%2Eorg")
+            .body(to_json_vec(&json!({ "score": 5 })).unwrap())
+            .unwrap();
+
+        assert!(Request::try_from_http_request(http_req).is_err());
+    }
+}
@@ -13,6 +13,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// ID of the room to be upgraded.
         #[ruma_api(path)]
@@ -22,6 +23,7 @@ ruma_api! {
         pub new_version: String,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// ID of the new room.
         pub replacement_room: RoomId,
@@ -40,14 +40,35 @@ ruma_api! {
     error: crate::Error
 }
 
+impl Request {
+    /// Creates a new `Request` with the given categories.
+    pub fn new(search_categories: Categories) -> Self {
+        Self { next_batch: None, search_categories }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given search results.
+    pub fn new(search_categories: ResultCategories) -> Self {
+        Self { search_categories }
+    }
+}
+
 /// Categories of events that can be searched for.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Categories {
     /// Criteria for searching a category of events.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub room_events: Option<Criteria>,
 }
 
+impl Categories {
+    /// Creates an empty `Categories`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 /// Criteria for searching a category of events.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Criteria {
@@ -79,6 +100,21 @@ pub struct Criteria {
     pub groupings: Option<Groupings>,
 }
 
+impl Criteria {
+    /// Creates a new `Criteria` with the given search term.
+    pub fn new(search_term: String) -> Self {
+        Self {
+            search_term,
+            keys: None,
+            filter: None,
+            order_by: None,
+            event_context: None,
+            include_state: None,
+            groupings: None,
+        }
+    }
+}
+
 /// Configures whether any context for the events returned are included in the response.
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct EventContext {
@@ -102,6 +138,23 @@ pub struct EventContext {
     pub include_profile: bool,
 }
 
+impl EventContext {
+    /// Creates a new `EventContext` with the default limits and without profile information.
+    pub fn new() -> Self {
+        Self {
+            before_limit: default_event_context_limit(),
+            after_limit: default_event_context_limit(),
+            include_profile: false,
+        }
+    }
+}
+
+impl Default for EventContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 fn default_event_context_limit() -> UInt {
     uint!(5)
 }
@@ -190,20 +243,28 @@ pub enum OrderBy {
 }
 
 /// Categories of events that can be searched for.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct ResultCategories {
     /// Room event results.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub room_events: Option<ResultRoomEvents>,
 }
 
+impl ResultCategories {
+    /// Creates an empty `ResultCategories`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 /// Categories of events that can be searched for.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct ResultRoomEvents {
     /// An approximate count of the total number of results found.
     pub count: UInt,
 
     /// Any groups that were requested.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub groups: BTreeMap<GroupingKey, BTreeMap<RoomIdOrUserId, ResultGroup>>,
 
     /// Token that can be used to get the next batch of results, by passing as the `next_batch`
@@ -225,6 +286,13 @@ pub struct ResultRoomEvents {
     pub highlights: Vec<String>,
 }
 
+impl ResultRoomEvents {
+    /// Creates an empty `ResultRoomEvents`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
 /// A grouping of results, if requested.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ResultGroup {
@@ -250,7 +318,7 @@ pub struct SearchResult {
 
     /// A number that describes how closely this result matches the search. Higher is closer.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub rank: Option<UInt>,
+    pub rank: Option<f64>,
 
     /// The event that matched.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -271,6 +339,7 @@ pub struct UserProfile {
 
 /// Represents either a room or user ID for returning grouped search results.
 #[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(untagged)]
 pub enum RoomIdOrUserId {
     /// Represents a room ID.
     RoomId(RoomId),
@@ -278,3 +347,114 @@ pub enum RoomIdOrUserId {
     /// Represents a user ID.
     UserId(UserId),
 }
+
+#[cfg(test)]
+mod tests {
+    use js_int::uint;
+    use matches::assert_matches;
+    use ruma_api::{IncomingResponse as _, OutgoingRequest as _};
+    use ruma_events::{
+        room::message::MessageEventContent, AnyEvent, AnyMessageEvent, MessageEvent,
+    };
+    use ruma_identifiers::event_id;
+    use serde_json::{
+        from_slice as from_json_slice, json, to_vec as to_json_vec, Value as JsonValue,
+    };
+
+    use super::{Categories, Criteria, OrderBy, Request, Response, SearchResult};
+
+    #[test]
+    fn serialize_request() {
+        let mut criteria = Criteria::new("cats".into());
+        criteria.order_by = Some(OrderBy::Recent);
+        let mut categories = Categories::new();
+        categories.room_events = Some(criteria);
+
+        let http_req: http::Request<Vec<u8>> = Request::new(categories)
+            .try_into_http_request("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+
+        assert_eq!(http_req.uri().path(), "/_matrix/client/r0/search");
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({
+                "search_categories": {
+                    "room_events": {
+                        "search_term": "cats",
+                        "order_by": "recent"
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_response_with_context() {
+        let body = json!({
+            "search_categories": {
+                "room_events": {
+                    "count": 1,
+                    "highlights": ["cats"],
+                    "next_batch": "next1",
+                    "results": [
+                        {
+                            "context": {
+                                "start": "start1",
+                                "end": "end1",
+                                "events_before": [
+                                    {
+                                        "content": { "body": "Do you like pets?", "msgtype": "m.text" },
+                                        "event_id": "$before:example.org",
+                                        "origin_server_ts": 1,
+                                        "room_id": "!roomid:example.org",
+                                        "sender": "@bob:example.org",
+                                        "type": "m.room.message"
+                                    }
+                                ],
+                                "profile_info": {
+                                    "@alice:example.org": { "displayname": "Alice" }
+                                }
+                            },
+                            "rank": 0.004_248_66,
+                            "result": {
+                                "content": { "body": "I like cats", "msgtype": "m.text" },
+                                "event_id": "$hit:example.org",
+                                "origin_server_ts": 2,
+                                "room_id": "!roomid:example.org",
+                                "sender": "@alice:example.org",
+                                "type": "m.room.message"
+                            }
+                        }
+                    ]
+                }
+            }
+        });
+        let response =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        let room_events = response.search_categories.room_events.unwrap();
+        assert_eq!(room_events.count, uint!(1));
+        assert_eq!(room_events.highlights, vec!["cats".to_owned()]);
+        assert_eq!(room_events.next_batch.as_deref(), Some("next1"));
+        assert!(room_events.groups.is_empty());
+
+        let SearchResult { context, rank, result } = &room_events.results[0];
+        assert!(rank.is_some());
+        assert_matches!(
+            result.as_ref().unwrap().deserialize().unwrap(),
+            AnyEvent::Message(AnyMessageEvent::RoomMessage(MessageEvent {
+                content: MessageEventContent::Text(text),
+                event_id,
+                ..
+            })) if text.body == "I like cats" && event_id == event_id!("$hit:example.org")
+        );
+
+        let context = context.as_ref().unwrap();
+        assert_eq!(context.start.as_deref(), Some("start1"));
+        assert_eq!(context.end.as_deref(), Some("end1"));
+        assert_eq!(context.events_before.len(), 1);
+        assert!(context.events_after.is_empty());
+        assert_eq!(context.profile_info.as_ref().unwrap().len(), 1);
+    }
+}
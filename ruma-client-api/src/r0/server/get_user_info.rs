@@ -16,6 +16,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The user to look up.
         #[ruma_api(path)]
@@ -35,6 +36,20 @@ ruma_api! {
     error: crate::Error
 }
 
+impl Request {
+    /// Creates a new `Request` with the given user ID.
+    pub fn new(user_id: UserId) -> Self {
+        Self { user_id }
+    }
+}
+
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self { user_id: None, devices: BTreeMap::new() }
+    }
+}
+
 /// Information about a user's device.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct DeviceInfo {
@@ -55,12 +70,78 @@ pub struct SessionInfo {
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ConnectionInfo {
     /// Most recently seen IP address of the session.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ip: Option<String>,
 
     /// Time when that the session was last active.
-    #[serde(with = "ruma_serde::time::opt_ms_since_unix_epoch")]
+    #[serde(
+        with = "ruma_serde::time::opt_ms_since_unix_epoch",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
     pub last_seen: Option<SystemTime>,
 
     /// User agent string last seen in the session.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub user_agent: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use ruma_api::IncomingResponse as _;
+    use ruma_identifiers::user_id;
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::Response;
+
+    #[test]
+    fn deserialize_response_with_two_devices() {
+        let body = json!({
+            "user_id": "@peter:rabbit.rocks",
+            "devices": {
+                "teapot": {
+                    "sessions": [
+                        {
+                            "connections": [
+                                {
+                                    "ip": "127.0.0.1",
+                                    "last_seen": 1411996332123_u64,
+                                    "user_agent": "curl/7.31.0-DEV"
+                                },
+                                {
+                                    "ip": "10.0.0.2",
+                                    "last_seen": 1411996332123_u64
+                                }
+                            ]
+                        }
+                    ]
+                },
+                "kettle": {
+                    "sessions": [{}]
+                }
+            }
+        });
+        let response =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert_eq!(response.user_id, Some(user_id!("@peter:rabbit.rocks")));
+        assert_eq!(response.devices.len(), 2);
+
+        let connections = &response.devices["teapot"].sessions[0].connections;
+        assert_eq!(connections.len(), 2);
+        assert_eq!(connections[0].ip.as_deref(), Some("127.0.0.1"));
+        assert_eq!(
+            connections[0].last_seen,
+            Some(UNIX_EPOCH + Duration::from_millis(1_411_996_332_123))
+        );
+        assert_eq!(connections[0].user_agent.as_deref(), Some("curl/7.31.0-DEV"));
+        assert_eq!(connections[1].user_agent, None);
+
+        let kettle_sessions = &response.devices["kettle"].sessions;
+        assert_eq!(kettle_sessions.len(), 1);
+        assert!(kettle_sessions[0].connections.is_empty());
+    }
+}
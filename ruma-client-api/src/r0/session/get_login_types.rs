@@ -13,8 +13,10 @@ ruma_api! {
         requires_authentication: false,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {}
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The homeserver's supported login types.
         pub flows: Vec<LoginType>
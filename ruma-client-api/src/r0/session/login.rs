@@ -1,10 +1,12 @@
-//! [POST /_matrix/client/r0/login](https://matrix.org/docs/spec/client_server/r0.6.0#post-matrix-client-r0-login)
+//! [POST /_matrix/client/r0/login](https://matrix.org/docs/spec/client_server/r0.6.1#post-matrix-client-r0-login)
 
 use ruma_api::ruma_api;
 use ruma_common::thirdparty::Medium;
 use ruma_identifiers::{DeviceId, ServerName, UserId};
 use serde::{Deserialize, Serialize};
 
+pub use crate::unversioned::discover_homeserver::{HomeserverInfo, IdentityServerInfo};
+
 ruma_api! {
     metadata: {
         description: "Login to the homeserver.",
@@ -15,10 +17,13 @@ ruma_api! {
         requires_authentication: false,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// Identification information for the user.
+        ///
+        /// Required for password login.
         #[serde(flatten)]
-        pub user: UserInfo,
+        pub identifier: Option<UserIdentifier>,
 
         /// The authentication mechanism.
         #[serde(flatten)]
@@ -34,6 +39,7 @@ ruma_api! {
         pub initial_device_display_name: Option<String>,
     }
 
+    #[non_exhaustive]
     response: {
         /// The fully-qualified Matrix ID that has been registered.
         pub user_id: UserId,
@@ -57,16 +63,34 @@ ruma_api! {
         /// Client configuration provided by the server.
         ///
         /// If present, clients SHOULD use the provided object to reconfigure themselves.
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub well_known: Option<DiscoveryInfo>,
     }
 
     error: crate::Error
 }
 
+impl Request {
+    /// Creates a new `Request` with the given user identifier and login info.
+    pub fn new(identifier: Option<UserIdentifier>, login_info: LoginInfo) -> Self {
+        Self { identifier, login_info, device_id: None, initial_device_display_name: None }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given user ID, access token and device ID.
+    pub fn new(user_id: UserId, access_token: String, device_id: Box<DeviceId>) -> Self {
+        Self { user_id, access_token, home_server: None, device_id, well_known: None }
+    }
+}
+
 /// Identification information for the user.
+///
+/// Serialized as the `identifier` object of the request, tagged by its `type`. The deprecated
+/// top-level `user`, `medium` and `address` fields are accepted when deserializing.
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(from = "user_serde::UserInfo", into = "user_serde::UserInfo")]
-pub enum UserInfo {
+#[serde(try_from = "user_serde::IncomingUserInfo", into = "user_serde::UserInfo")]
+pub enum UserIdentifier {
     /// Either a fully qualified Matrix user ID, or just the localpart (as part of the 'identifier'
     /// field).
     MatrixId(String),
@@ -118,32 +142,28 @@ pub struct DiscoveryInfo {
     pub homeserver: HomeserverInfo,
 
     /// Information about the identity server to connect to.
-    #[serde(rename = "m.identity_server")]
+    #[serde(rename = "m.identity_server", skip_serializing_if = "Option::is_none")]
     pub identity_server: Option<IdentityServerInfo>,
 }
 
-/// Information about the homeserver to connect to.
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct HomeserverInfo {
-    /// The base URL for the homeserver for client-server connections.
-    pub base_url: String,
-}
-
-/// Information about the identity server to connect to.
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct IdentityServerInfo {
-    /// The base URL for the identity server for client-server connections.
-    pub base_url: String,
+impl DiscoveryInfo {
+    /// Creates a new `DiscoveryInfo` with the given homeserver information.
+    pub fn new(homeserver: HomeserverInfo) -> Self {
+        Self { homeserver, identity_server: None }
+    }
 }
 
 mod user_serde;
 
 #[cfg(test)]
 mod tests {
-    use ruma_api::OutgoingRequest;
-    use serde_json::{from_value as from_json_value, json, Value as JsonValue};
+    use ruma_api::{IncomingResponse as _, OutgoingRequest, OutgoingResponse as _};
+    use ruma_identifiers::user_id;
+    use serde_json::{
+        from_slice as from_json_slice, from_value as from_json_value, json, Value as JsonValue,
+    };
 
-    use super::{LoginInfo, Medium, Request, UserInfo};
+    use super::{LoginInfo, Medium, Request, Response, UserIdentifier};
 
     #[test]
     fn deserialize_login_type() {
@@ -169,30 +189,82 @@ mod tests {
     #[test]
     fn deserialize_user() {
         assert_eq!(
-            from_json_value::<UserInfo>(json!({
+            from_json_value::<UserIdentifier>(json!({
                 "identifier": {
                     "type": "m.id.user",
                     "user": "cheeky_monkey"
                 }
             }))
             .unwrap(),
-            UserInfo::MatrixId("cheeky_monkey".into())
+            UserIdentifier::MatrixId("cheeky_monkey".into())
+        );
+    }
+
+    #[test]
+    fn deserialize_third_party_id() {
+        assert_eq!(
+            from_json_value::<UserIdentifier>(json!({
+                "identifier": {
+                    "type": "m.id.thirdparty",
+                    "medium": "email",
+                    "address": "hello@example.com"
+                }
+            }))
+            .unwrap(),
+            UserIdentifier::ThirdPartyId {
+                address: "hello@example.com".into(),
+                medium: Medium::Email
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_phone_number() {
+        assert_eq!(
+            from_json_value::<UserIdentifier>(json!({
+                "identifier": {
+                    "type": "m.id.phone",
+                    "country": "GB",
+                    "phone": "07700900123"
+                }
+            }))
+            .unwrap(),
+            UserIdentifier::PhoneNumber { country: "GB".into(), phone: "07700900123".into() }
+        );
+    }
+
+    #[test]
+    fn deserialize_deprecated_user_fields() {
+        assert_eq!(
+            from_json_value::<UserIdentifier>(json!({ "user": "cheeky_monkey" })).unwrap(),
+            UserIdentifier::MatrixId("cheeky_monkey".into())
+        );
+        assert_eq!(
+            from_json_value::<UserIdentifier>(json!({
+                "medium": "email",
+                "address": "hello@example.com"
+            }))
+            .unwrap(),
+            UserIdentifier::ThirdPartyId {
+                address: "hello@example.com".into(),
+                medium: Medium::Email
+            }
         );
+        assert!(from_json_value::<UserIdentifier>(json!({ "medium": "email" })).is_err());
     }
 
     #[test]
     fn serialize_login_request_body() {
-        let req: http::Request<Vec<u8>> = Request {
-            user: UserInfo::ThirdPartyId {
+        let mut req = Request::new(
+            Some(UserIdentifier::ThirdPartyId {
                 address: "hello@example.com".to_owned(),
                 medium: Medium::Email,
-            },
-            login_info: LoginInfo::Token { token: "0xdeadbeef".to_owned() },
-            device_id: None,
-            initial_device_display_name: Some("test".into()),
-        }
-        .try_into_http_request("https://homeserver.tld", None)
-        .unwrap();
+            }),
+            LoginInfo::Token { token: "0xdeadbeef".to_owned() },
+        );
+        req.initial_device_display_name = Some("test".into());
+        let req: http::Request<Vec<u8>> =
+            req.try_into_http_request("https://homeserver.tld", None).unwrap();
 
         let req_body_value: JsonValue = serde_json::from_slice(req.body()).unwrap();
         assert_eq!(
@@ -209,4 +281,58 @@ mod tests {
             })
         )
     }
+
+    #[test]
+    fn serialize_token_login_without_identifier() {
+        let req: http::Request<Vec<u8>> =
+            Request::new(None, LoginInfo::Token { token: "0xdeadbeef".to_owned() })
+                .try_into_http_request::<Vec<u8>>("https://homeserver.tld", None)
+                .unwrap();
+
+        let req_body_value: JsonValue = serde_json::from_slice(req.body()).unwrap();
+        assert_eq!(req_body_value, json!({ "type": "m.login.token", "token": "0xdeadbeef" }));
+    }
+
+    #[test]
+    fn deserialize_response_with_well_known() {
+        let body = json!({
+            "user_id": "@cheeky_monkey:matrix.org",
+            "access_token": "abc123",
+            "device_id": "GHTYAJCE",
+            "well_known": {
+                "m.homeserver": { "base_url": "https://example.org" }
+            }
+        });
+        let response = Response::try_from_http_response(http::Response::new(
+            serde_json::to_vec(&body).unwrap(),
+        ))
+        .unwrap();
+
+        let well_known = response.well_known.unwrap();
+        assert_eq!(well_known.homeserver.base_url, "https://example.org");
+        assert_eq!(well_known.identity_server, None);
+    }
+
+    #[test]
+    fn response_constructor() {
+        let res: http::Response<Vec<u8>> =
+            Response::new(user_id!("@carl:example.org"), "abc123".into(), "GHTYAJCE".into())
+                .try_into_http_response()
+                .unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(res.body()).unwrap(),
+            json!({
+                "user_id": "@carl:example.org",
+                "access_token": "abc123",
+                "device_id": "GHTYAJCE",
+            })
+        );
+
+        let res = Response::try_from_http_response(res).unwrap();
+        assert_eq!(res.user_id, user_id!("@carl:example.org"));
+        assert_eq!(res.access_token, "abc123");
+        assert_eq!(res.device_id.as_str(), "GHTYAJCE");
+        assert_eq!(res.home_server, None);
+        assert!(res.well_known.is_none());
+    }
 }
@@ -1,19 +1,31 @@
-//! Helper module for the Serialize / Deserialize impl's for the User struct
+//! Helper module for the Serialize / Deserialize impl's for the UserIdentifier enum
 //! in the parent module.
 
+use std::convert::TryFrom;
+
 use ruma_common::thirdparty::Medium;
 use serde::{Deserialize, Serialize};
 
-// The following three structs could just be used in place of the one in the parent module, but
+// The following structs could just be used in place of the one in the parent module, but
 // that one is arguably much easier to deal with.
-#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 pub(crate) struct UserInfo {
-    pub identifier: UserIdentifier,
+    pub identifier: Identifier,
+}
+
+/// Like `UserInfo`, but also accepts the deprecated top-level `user`, `medium` and `address`
+/// fields.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub(crate) struct IncomingUserInfo {
+    pub identifier: Option<Identifier>,
+    pub user: Option<String>,
+    pub medium: Option<Medium>,
+    pub address: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(tag = "type")]
-pub(crate) enum UserIdentifier {
+pub(crate) enum Identifier {
     #[serde(rename = "m.id.user")]
     MatrixId { user: String },
     #[serde(rename = "m.id.thirdparty")]
@@ -22,30 +34,39 @@ pub(crate) enum UserIdentifier {
     PhoneNumber { country: String, phone: String },
 }
 
-impl From<super::UserInfo> for UserInfo {
-    fn from(info: super::UserInfo) -> Self {
-        use super::UserInfo::*;
+impl From<super::UserIdentifier> for UserInfo {
+    fn from(id: super::UserIdentifier) -> Self {
+        use super::UserIdentifier::*;
 
-        match info {
-            MatrixId(user) => UserInfo { identifier: UserIdentifier::MatrixId { user } },
+        match id {
+            MatrixId(user) => UserInfo { identifier: Identifier::MatrixId { user } },
             ThirdPartyId { address, medium } => {
-                UserInfo { identifier: UserIdentifier::ThirdPartyId { address, medium } }
+                UserInfo { identifier: Identifier::ThirdPartyId { address, medium } }
             }
             PhoneNumber { country, phone } => {
-                UserInfo { identifier: UserIdentifier::PhoneNumber { country, phone } }
+                UserInfo { identifier: Identifier::PhoneNumber { country, phone } }
             }
         }
     }
 }
 
-impl From<UserInfo> for super::UserInfo {
-    fn from(info: UserInfo) -> super::UserInfo {
-        use super::UserInfo::*;
+impl TryFrom<IncomingUserInfo> for super::UserIdentifier {
+    type Error = &'static str;
 
-        match info.identifier {
-            UserIdentifier::MatrixId { user } => MatrixId(user),
-            UserIdentifier::ThirdPartyId { address, medium } => ThirdPartyId { address, medium },
-            UserIdentifier::PhoneNumber { country, phone } => PhoneNumber { country, phone },
-        }
+    fn try_from(info: IncomingUserInfo) -> Result<Self, Self::Error> {
+        use super::UserIdentifier::*;
+
+        Ok(match info {
+            IncomingUserInfo { identifier: Some(identifier), .. } => match identifier {
+                Identifier::MatrixId { user } => MatrixId(user),
+                Identifier::ThirdPartyId { address, medium } => ThirdPartyId { address, medium },
+                Identifier::PhoneNumber { country, phone } => PhoneNumber { country, phone },
+            },
+            IncomingUserInfo { user: Some(user), .. } => MatrixId(user),
+            IncomingUserInfo { medium: Some(medium), address: Some(address), .. } => {
+                ThirdPartyId { address, medium }
+            }
+            _ => return Err("missing user identifier"),
+        })
     }
 }
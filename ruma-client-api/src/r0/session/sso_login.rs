@@ -1,25 +1,27 @@
-//! [GET /_matrix/client/r0/login/sso/redirect](https://matrix.org/docs/spec/client_server/r0.6.0#get-matrix-client-r0-login-sso-redirect)
+//! [GET /_matrix/client/r0/login/sso/redirect](https://matrix.org/docs/spec/client_server/r0.6.1#get-matrix-client-r0-login-sso-redirect)
 
 use ruma_api::ruma_api;
 
 ruma_api! {
     metadata: {
-        description: "",
+        description: "Redirect the user's browser to the homeserver's SSO login page.",
         method: GET,
         name: "sso_login",
         path: "/_matrix/client/r0/login/sso/redirect",
         rate_limited: false,
         requires_authentication: false,
-
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// URL to which the homeserver should return the user after completing
         /// authentication with the SSO identity provider.
         #[ruma_api(query)]
+        #[serde(rename = "redirectUrl")]
         pub redirect_url: String,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// Redirect URL to the SSO identity provider.
         #[ruma_api(header = LOCATION)]
@@ -28,3 +30,37 @@ ruma_api! {
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given redirect URL.
+    pub fn new(redirect_url: String) -> Self {
+        Self { redirect_url }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given SSO identity provider URL.
+    pub fn new(location: String) -> Self {
+        Self { location }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::OutgoingRequest as _;
+
+    use super::Request;
+
+    #[test]
+    fn serialize_sso_login_request_uri() {
+        let req = Request::new("https://example.com/sso".into())
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", None)
+            .unwrap();
+
+        assert_eq!(
+            req.uri().to_string(),
+            "https://homeserver.tld/_matrix/client/r0/login/sso/redirect\
+             ?redirectUrl=https%3A%2F%2Fexample.com%2Fsso"
+        );
+    }
+}
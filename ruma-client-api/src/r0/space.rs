@@ -0,0 +1,51 @@
+//! Endpoints for spaces.
+
+pub mod get_hierarchy;
+
+use js_int::UInt;
+use ruma_common::Raw;
+use ruma_events::AnyStrippedStateEvent;
+use ruma_identifiers::{RoomAliasId, RoomId};
+use serde::{Deserialize, Serialize};
+
+/// A chunk of a space hierarchy response, describing one room and its children.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SpaceHierarchyRoomsChunk {
+    /// The canonical alias of the room, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canonical_alias: Option<RoomAliasId>,
+
+    /// The name of the room, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// The number of members joined to the room.
+    pub num_joined_members: UInt,
+
+    /// The ID of the room.
+    pub room_id: RoomId,
+
+    /// The topic of the room, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+
+    /// Whether the room may be viewed by guest users without joining.
+    pub world_readable: bool,
+
+    /// Whether guest users may join the room and participate in it.
+    ///
+    /// If they can, they will be subject to ordinary power level rules like any other user.
+    pub guest_can_join: bool,
+
+    /// The URL for the room's avatar, if one is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+
+    /// The type of the room, like `m.space`, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub room_type: Option<String>,
+
+    /// The stripped `m.space.child` events of the room, if it is a space.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children_state: Vec<Raw<AnyStrippedStateEvent>>,
+}
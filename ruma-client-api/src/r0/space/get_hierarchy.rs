@@ -0,0 +1,145 @@
+//! [GET /_matrix/client/v1/rooms/{roomId}/hierarchy](https://spec.matrix.org/v1.2/client-server-api/#get_matrixclientv1roomsroomidhierarchy)
+
+use js_int::UInt;
+use ruma_api::ruma_api;
+use ruma_identifiers::RoomId;
+
+use super::SpaceHierarchyRoomsChunk;
+
+ruma_api! {
+    metadata: {
+        description: "Get the rooms of a space tree, depth-first.",
+        method: GET,
+        name: "get_hierarchy",
+        path: [(1.2, "/_matrix/client/v1/rooms/:room_id/hierarchy")],
+        rate_limited: true,
+        requires_authentication: true,
+        added: 1.2,
+    }
+
+    #[derive(PartialEq, Eq)]
+    request: {
+        /// The ID of the space to get the hierarchy of.
+        #[ruma_api(path)]
+        pub room_id: RoomId,
+
+        /// A pagination token from a previous response.
+        #[ruma_api(query)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub from: Option<String>,
+
+        /// The maximum number of rooms to include per response.
+        #[ruma_api(query)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub limit: Option<UInt>,
+
+        /// How far to go into the space tree.
+        ///
+        /// If this is not set, the server chooses a limit.
+        #[ruma_api(query)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub max_depth: Option<UInt>,
+
+        /// Whether to only include rooms that are suggested by their parent space.
+        #[ruma_api(query)]
+        #[serde(default, skip_serializing_if = "ruma_serde::is_default")]
+        pub suggested_only: bool,
+    }
+
+    response: {
+        /// A token to get the next page of rooms with, if there are more.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub next_batch: Option<String>,
+
+        /// The rooms of the space tree, starting with the requested space.
+        pub rooms: Vec<SpaceHierarchyRoomsChunk>,
+    }
+
+    error: crate::Error
+}
+
+impl Request {
+    /// Creates a new `Request` with the given space ID.
+    pub fn new(room_id: RoomId) -> Self {
+        Self { room_id, from: None, limit: None, max_depth: None, suggested_only: false }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given rooms.
+    pub fn new(rooms: Vec<SpaceHierarchyRoomsChunk>) -> Self {
+        Self { next_batch: None, rooms }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::uint;
+    use ruma_api::{IncomingResponse as _, MatrixVersion, OutgoingRequest};
+    use ruma_events::AnyStrippedStateEvent;
+    use ruma_identifiers::room_id;
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::{Request, Response};
+
+    #[test]
+    fn serialize_request() {
+        let mut req = Request::new(room_id!("!space:example.org"));
+        req.limit = Some(uint!(10));
+        req.suggested_only = true;
+
+        let request: http::Request<Vec<u8>> = req
+            .try_into_http_request_for_versions(
+                "https://homeserver.tld",
+                Some("auth_tok"),
+                &[MatrixVersion::V1_2],
+            )
+            .unwrap();
+
+        assert_eq!(
+            request.uri().path(),
+            "/_matrix/client/v1/rooms/%21space%3Aexample%2Eorg/hierarchy"
+        );
+        assert_eq!(request.uri().query(), Some("limit=10&suggested_only=true"));
+    }
+
+    #[test]
+    fn deserialize_response() {
+        let body = json!({
+            "next_batch": "next",
+            "rooms": [{
+                "room_id": "!space:example.org",
+                "name": "Space",
+                "num_joined_members": 5,
+                "world_readable": true,
+                "guest_can_join": false,
+                "room_type": "m.space",
+                "children_state": [{
+                    "content": { "via": ["example.org"], "suggested": true },
+                    "sender": "@alice:example.org",
+                    "state_key": "!child:example.org",
+                    "type": "m.space.child"
+                }]
+            }]
+        });
+
+        let response =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert_eq!(response.next_batch.as_deref(), Some("next"));
+        assert_eq!(response.rooms.len(), 1);
+
+        let room = &response.rooms[0];
+        assert_eq!(room.room_id, room_id!("!space:example.org"));
+        assert_eq!(room.room_type.as_deref(), Some("m.space"));
+
+        match room.children_state[0].deserialize().unwrap() {
+            AnyStrippedStateEvent::SpaceChild(event) => {
+                assert_eq!(event.state_key, "!child:example.org");
+                assert!(event.content.suggested);
+            }
+            event => panic!("unexpected event: {:?}", event),
+        }
+    }
+}
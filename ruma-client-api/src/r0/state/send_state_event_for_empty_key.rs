@@ -31,6 +31,7 @@ ruma_api! {
         pub data: Box<RawJsonValue>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// A unique identifier for the event.
         pub event_id: EventId,
@@ -3,14 +3,14 @@
 use std::{collections::BTreeMap, time::Duration};
 
 use js_int::UInt;
-use ruma_api::ruma_api;
+use ruma_api::{ruma_api, Outgoing};
 use ruma_common::{presence::PresenceState, Raw};
 use ruma_events::{
     presence::PresenceEvent, AnyBasicEvent, AnyStrippedStateEvent, AnySyncEphemeralRoomEvent,
-    AnySyncRoomEvent, AnySyncStateEvent, AnyToDeviceEvent,
+    AnySyncRoomEvent, AnySyncStateEvent, AnyToDeviceEvent, InvalidEvent,
 };
 use ruma_identifiers::{DeviceKeyAlgorithm, RoomId, UserId};
-use serde::{Deserialize, Serialize};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::r0::filter::FilterDefinition;
 
@@ -28,7 +28,7 @@ ruma_api! {
         /// A filter represented either as its full JSON definition or the ID of a saved filter.
         #[serde(skip_serializing_if = "Option::is_none")]
         #[ruma_api(query)]
-        pub filter: Option<Filter>,
+        pub filter: Option<Filter<'a>>,
 
         /// A point in time to continue a sync from.
         ///
@@ -58,6 +58,7 @@ ruma_api! {
         pub timeout: Option<Duration>,
     }
 
+    #[non_exhaustive]
     response: {
         /// The batch token to supply in the `since` param of the next `/sync` request.
         pub next_batch: String,
@@ -93,27 +94,78 @@ ruma_api! {
     error: crate::Error
 }
 
+impl<'a> Request<'a> {
+    /// Creates an empty `Request`.
+    pub fn new() -> Self {
+        Self {
+            filter: None,
+            since: None,
+            full_state: false,
+            set_presence: PresenceState::default(),
+            timeout: None,
+        }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given batch token.
+    pub fn new(next_batch: String) -> Self {
+        Self {
+            next_batch,
+            rooms: Rooms::default(),
+            presence: Presence::default(),
+            account_data: AccountData::default(),
+            to_device: ToDevice::default(),
+            device_lists: DeviceLists::default(),
+            device_one_time_keys_count: BTreeMap::new(),
+        }
+    }
+}
+
 /// A filter represented either as its full JSON definition or the ID of a saved filter.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Outgoing)]
 #[allow(clippy::large_enum_variant)]
-#[serde(untagged)]
-pub enum Filter {
+#[incoming_no_deserialize]
+pub enum Filter<'a> {
     // The filter definition needs to be (de)serialized twice because it is a URL-encoded JSON
     // string. Since #[ruma_api(query)] only does the latter and this is a very uncommon
-    // setup, we implement it through custom serde logic for this specific enum variant rather than
-    // adding another ruma_api attribute.
-    //
-    // On the deserialization side, because this is an enum with #[serde(untagged)], serde will
-    // try the variants in order (https://serde.rs/enum-representations.html). That means because
-    // FilterDefinition is the first variant, JSON decoding is attempted first which is almost
-    // functionally equivalent to looking at whether the first symbol is a '{' as the spec says.
-    // (there are probably some corner cases like leading whitespace)
-    #[serde(with = "ruma_serde::json_string")]
+    // setup, we implement it through custom serde logic for this specific enum rather than adding
+    // another ruma_api attribute.
     /// A complete filter definition serialized to JSON.
     FilterDefinition(FilterDefinition),
 
     /// The ID of a filter saved on the server.
-    FilterId(String),
+    FilterId(&'a str),
+}
+
+impl Serialize for Filter<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::FilterDefinition(definition) => {
+                ruma_serde::json_string::serialize(definition, serializer)
+            }
+            Self::FilterId(id) => serializer.serialize_str(id),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for IncomingFilter {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // As the spec says, the parameter is a filter definition if it starts with a `{`, and the
+        // ID of a saved filter otherwise.
+        let filter = String::deserialize(deserializer)?;
+        if filter.starts_with('{') {
+            serde_json::from_str(&filter).map(Self::FilterDefinition).map_err(D::Error::custom)
+        } else {
+            Ok(Self::FilterId(filter))
+        }
+    }
 }
 
 /// Updates to rooms.
@@ -130,12 +182,22 @@ pub struct Rooms {
     /// The rooms that the user has been invited to.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub invite: BTreeMap<RoomId, InvitedRoom>,
+
+    /// The rooms that the user has knocked on.
+    #[cfg(feature = "unstable-pre-spec")]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub knock: BTreeMap<RoomId, KnockedRoom>,
 }
 
 impl Rooms {
     /// Returns true if there is no update in any room.
     pub fn is_empty(&self) -> bool {
-        self.leave.is_empty() && self.join.is_empty() && self.invite.is_empty()
+        #[cfg(not(feature = "unstable-pre-spec"))]
+        let knock_is_empty = true;
+        #[cfg(feature = "unstable-pre-spec")]
+        let knock_is_empty = self.knock.is_empty();
+
+        self.leave.is_empty() && self.join.is_empty() && self.invite.is_empty() && knock_is_empty
     }
 }
 
@@ -247,6 +309,13 @@ impl Timeline {
     pub fn is_empty(&self) -> bool {
         self.events.is_empty()
     }
+
+    /// Deserializes all events, failing on the first invalid one.
+    ///
+    /// The returned error includes the index of the invalid event in `events`.
+    pub fn deserialize_events(&self) -> Result<Vec<AnySyncRoomEvent>, InvalidEvent> {
+        ruma_events::deserialize_events(&self.events)
+    }
 }
 
 /// State events in the room.
@@ -261,6 +330,13 @@ impl State {
     pub fn is_empty(&self) -> bool {
         self.events.is_empty()
     }
+
+    /// Deserializes all events, failing on the first invalid one.
+    ///
+    /// The returned error includes the index of the invalid event in `events`.
+    pub fn deserialize_events(&self) -> Result<Vec<AnySyncStateEvent>, InvalidEvent> {
+        ruma_events::deserialize_events(&self.events)
+    }
 }
 
 /// The private data that this user has attached to this room.
@@ -275,6 +351,13 @@ impl AccountData {
     pub fn is_empty(&self) -> bool {
         self.events.is_empty()
     }
+
+    /// Deserializes all events, failing on the first invalid one.
+    ///
+    /// The returned error includes the index of the invalid event in `events`.
+    pub fn deserialize_events(&self) -> Result<Vec<AnyBasicEvent>, InvalidEvent> {
+        ruma_events::deserialize_events(&self.events)
+    }
 }
 
 /// Ephemeral events not recorded in the timeline or state of the room.
@@ -289,6 +372,13 @@ impl Ephemeral {
     pub fn is_empty(&self) -> bool {
         self.events.is_empty()
     }
+
+    /// Deserializes all events, failing on the first invalid one.
+    ///
+    /// The returned error includes the index of the invalid event in `events`.
+    pub fn deserialize_events(&self) -> Result<Vec<AnySyncEphemeralRoomEvent>, InvalidEvent> {
+        ruma_events::deserialize_events(&self.events)
+    }
 }
 
 /// Information about room for rendering to clients.
@@ -348,6 +438,54 @@ impl InviteState {
     pub fn is_empty(&self) -> bool {
         self.events.is_empty()
     }
+
+    /// Deserializes all events, failing on the first invalid one.
+    ///
+    /// The returned error includes the index of the invalid event in `events`.
+    pub fn deserialize_events(&self) -> Result<Vec<AnyStrippedStateEvent>, InvalidEvent> {
+        ruma_events::deserialize_events(&self.events)
+    }
+}
+
+/// Updates to knocked rooms.
+#[cfg(feature = "unstable-pre-spec")]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct KnockedRoom {
+    /// The knock state.
+    #[serde(default, skip_serializing_if = "KnockState::is_empty")]
+    pub knock_state: KnockState,
+}
+
+#[cfg(feature = "unstable-pre-spec")]
+impl KnockedRoom {
+    /// Returns true if there are no updates to this room.
+    pub fn is_empty(&self) -> bool {
+        self.knock_state.is_empty()
+    }
+}
+
+/// A mapping from a key `events` to a list of `StrippedStateEvent`.
+#[cfg(feature = "unstable-pre-spec")]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct KnockState {
+    /// The list of events.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<Raw<AnyStrippedStateEvent>>,
+}
+
+#[cfg(feature = "unstable-pre-spec")]
+impl KnockState {
+    /// Returns true if there are no state updates.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Deserializes all events, failing on the first invalid one.
+    ///
+    /// The returned error includes the index of the invalid event in `events`.
+    pub fn deserialize_events(&self) -> Result<Vec<AnyStrippedStateEvent>, InvalidEvent> {
+        ruma_events::deserialize_events(&self.events)
+    }
 }
 
 /// Updates to the presence status of other users.
@@ -362,12 +500,20 @@ impl Presence {
     pub fn is_empty(&self) -> bool {
         self.events.is_empty()
     }
+
+    /// Deserializes all events, failing on the first invalid one.
+    ///
+    /// The returned error includes the index of the invalid event in `events`.
+    pub fn deserialize_events(&self) -> Result<Vec<PresenceEvent>, InvalidEvent> {
+        ruma_events::deserialize_events(&self.events)
+    }
 }
 
 /// Messages sent dirrectly between devices.
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct ToDevice {
     /// A list of to-device events.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub events: Vec<Raw<AnyToDeviceEvent>>,
 }
 
@@ -376,6 +522,13 @@ impl ToDevice {
     pub fn is_empty(&self) -> bool {
         self.events.is_empty()
     }
+
+    /// Deserializes all events, failing on the first invalid one.
+    ///
+    /// The returned error includes the index of the invalid event in `events`.
+    pub fn deserialize_events(&self) -> Result<Vec<AnyToDeviceEvent>, InvalidEvent> {
+        ruma_events::deserialize_events(&self.events)
+    }
 }
 
 /// Information on E2E device udpates.
@@ -401,25 +554,35 @@ impl DeviceLists {
 
 #[cfg(test)]
 mod tests {
-    use std::{convert::TryInto, time::Duration};
-
-    use ruma_api::OutgoingRequest;
-    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+    use std::time::Duration;
+
+    use js_int::uint;
+    use ruma_api::{
+        IncomingRequest as _, IncomingResponse as _, OutgoingRequest, OutgoingResponse as _,
+    };
+    use ruma_identifiers::{user_id, DeviceKeyAlgorithm};
+    use serde_json::{
+        from_slice as from_json_slice, from_value as from_json_value, json,
+        to_value as to_json_value, to_vec as to_json_vec, Value as JsonValue,
+    };
 
     use matches::assert_matches;
 
-    use super::{Filter, PresenceState, Request, Timeline};
+    use super::{
+        Filter, IncomingFilter, IncomingRequest, PresenceState, Request, Response, State, Timeline,
+    };
+    use crate::r0::filter::FilterDefinition;
 
     #[test]
     fn serialize_all_params() {
         let req: http::Request<Vec<u8>> = Request {
-            filter: Some(Filter::FilterId("66696p746572".into())),
+            filter: Some(Filter::FilterId("66696p746572")),
             since: Some("s72594_4483_1934".into()),
             full_state: true,
             set_presence: PresenceState::Offline,
             timeout: Some(Duration::from_millis(30000)),
         }
-        .try_into_http_request("https://homeserver.tld", Some("auth_tok"))
+        .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
         .unwrap();
 
         let uri = req.uri();
@@ -449,10 +612,12 @@ mod tests {
             .build()
             .unwrap();
 
-        let req: Request =
-            http::Request::builder().uri(uri).body(Vec::<u8>::new()).unwrap().try_into().unwrap();
+        let req = IncomingRequest::try_from_http_request(
+            http::Request::builder().uri(uri).body(Vec::<u8>::new()).unwrap(),
+        )
+        .unwrap();
 
-        assert_matches!(req.filter, Some(Filter::FilterId(id)) if id == "myfilter");
+        assert_matches!(req.filter, Some(IncomingFilter::FilterId(id)) if id == "myfilter");
         assert_eq!(req.since, Some("myts".into()));
         assert_eq!(req.full_state, false);
         assert_eq!(req.set_presence, PresenceState::Offline);
@@ -468,8 +633,10 @@ mod tests {
             .build()
             .unwrap();
 
-        let req: Request =
-            http::Request::builder().uri(uri).body(Vec::<u8>::new()).unwrap().try_into().unwrap();
+        let req = IncomingRequest::try_from_http_request(
+            http::Request::builder().uri(uri).body(Vec::<u8>::new()).unwrap(),
+        )
+        .unwrap();
 
         assert_matches!(req.filter, None);
         assert_eq!(req.since, None);
@@ -491,16 +658,75 @@ mod tests {
             .build()
             .unwrap();
 
-        let req: Request =
-            http::Request::builder().uri(uri).body(Vec::<u8>::new()).unwrap().try_into().unwrap();
+        let req = IncomingRequest::try_from_http_request(
+            http::Request::builder().uri(uri).body(Vec::<u8>::new()).unwrap(),
+        )
+        .unwrap();
 
-        assert_matches!(req.filter, Some(Filter::FilterId(id)) if id == "EOKFFmdZYF");
+        assert_matches!(req.filter, Some(IncomingFilter::FilterId(id)) if id == "EOKFFmdZYF");
         assert_eq!(req.since, None);
         assert_eq!(req.full_state, false);
         assert_eq!(req.set_presence, PresenceState::Online);
         assert_eq!(req.timeout, Some(Duration::from_millis(0)));
     }
 
+    #[test]
+    fn filter_id_roundtrip_through_http() {
+        let req = Request {
+            filter: Some(Filter::FilterId("66696p746572")),
+            since: None,
+            full_state: false,
+            set_presence: PresenceState::Online,
+            timeout: None,
+        }
+        .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+        .unwrap();
+        assert_eq!(req.uri().query(), Some("filter=66696p746572"));
+
+        let req = IncomingRequest::try_from_http_request(req).unwrap();
+        assert_matches!(req.filter, Some(IncomingFilter::FilterId(id)) if id == "66696p746572");
+    }
+
+    #[test]
+    fn filter_definition_roundtrip_through_http() {
+        let req = Request {
+            filter: Some(Filter::FilterDefinition(FilterDefinition::ignore_all())),
+            since: None,
+            full_state: false,
+            set_presence: PresenceState::Online,
+            timeout: None,
+        }
+        .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+        .unwrap();
+        assert!(req.uri().query().unwrap().starts_with("filter=%7B"));
+
+        let req = IncomingRequest::try_from_http_request(req).unwrap();
+        assert_matches!(
+            req.filter,
+            Some(IncomingFilter::FilterDefinition(FilterDefinition {
+                room: Some(_),
+                presence: Some(_),
+                account_data: Some(_),
+                ..
+            }))
+        );
+    }
+
+    #[test]
+    fn invalid_filter_definition() {
+        let uri = http::Uri::builder()
+            .scheme("https")
+            .authority("matrix.org")
+            .path_and_query("/_matrix/client/r0/sync?filter=%7Bnot_json")
+            .build()
+            .unwrap();
+
+        assert!(IncomingRequest::try_from_http_request(
+            http::Request::builder().uri(uri).body(Vec::<u8>::new()).unwrap()
+        )
+        .is_err());
+    }
+
     #[test]
     fn timeline_serde() {
         let timeline = Timeline { limited: true, prev_batch: None, events: vec![] };
@@ -527,4 +753,134 @@ mod tests {
             from_json_value(timeline_default_serialized).unwrap();
         assert_eq!(timeline_default_deserialized.limited, false);
     }
+
+    #[test]
+    fn deserialize_response_with_e2e_sections() {
+        let body = json!({
+            "next_batch": "s72595_4483_1934",
+            "to_device": {
+                "events": [
+                    {
+                        "type": "m.dummy",
+                        "sender": "@alice:example.org",
+                        "content": {}
+                    }
+                ]
+            },
+            "device_lists": {
+                "changed": ["@alice:example.org"],
+                "left": ["@bob:example.org"]
+            },
+            "device_one_time_keys_count": {
+                "signed_curve25519": 50
+            }
+        });
+        let res =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert_eq!(res.to_device.events.len(), 1);
+        assert_eq!(res.device_lists.changed, vec![user_id!("@alice:example.org")]);
+        assert_eq!(res.device_lists.left, vec![user_id!("@bob:example.org")]);
+        assert_eq!(
+            res.device_one_time_keys_count.get(&DeviceKeyAlgorithm::SignedCurve25519),
+            Some(&uint!(50))
+        );
+    }
+
+    #[test]
+    fn deserialize_response_without_optional_sections() {
+        let body = json!({ "next_batch": "s72595_4483_1934", "to_device": {} });
+        let res =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert!(res.rooms.is_empty());
+        assert!(res.to_device.is_empty());
+        assert!(res.device_lists.is_empty());
+        assert!(res.device_one_time_keys_count.is_empty());
+    }
+
+    #[test]
+    fn request_constructor_sets_no_query_params() {
+        let req: http::Request<Vec<u8>> = Request::new()
+            .try_into_http_request("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+
+        assert!(req.uri().query().unwrap_or("").is_empty());
+    }
+
+    #[test]
+    fn response_constructor_roundtrip_through_http() {
+        let res: http::Response<Vec<u8>> =
+            Response::new("s72595_4483_1934".into()).try_into_http_response().unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(res.body()).unwrap(),
+            json!({ "next_batch": "s72595_4483_1934" })
+        );
+
+        let res = Response::try_from_http_response(res).unwrap();
+        assert_eq!(res.next_batch, "s72595_4483_1934");
+        assert!(res.rooms.is_empty());
+    }
+
+    #[cfg(feature = "unstable-pre-spec")]
+    #[test]
+    fn deserialize_response_with_knocked_room() {
+        let body = json!({
+            "next_batch": "s72595_4483_1934",
+            "rooms": {
+                "knock": {
+                    "!knocked:example.org": {
+                        "knock_state": {
+                            "events": [
+                                {
+                                    "type": "m.room.name",
+                                    "state_key": "",
+                                    "sender": "@alice:example.org",
+                                    "content": { "name": "Knock knock" }
+                                }
+                            ]
+                        }
+                    }
+                }
+            }
+        });
+        let res =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert!(!res.rooms.is_empty());
+        let knocked = &res.rooms.knock[&ruma_identifiers::room_id!("!knocked:example.org")];
+        assert_eq!(knocked.knock_state.events.len(), 1);
+    }
+
+    #[test]
+    fn deserialize_state_events_with_index() {
+        let state = from_json_value::<State>(json!({
+            "events": [
+                {
+                    "type": "m.room.name",
+                    "event_id": "$h29iv0s8:example.com",
+                    "sender": "@carl:example.com",
+                    "origin_server_ts": 1,
+                    "state_key": "",
+                    "content": { "name": "Room" }
+                },
+                {
+                    "type": "m.room.member",
+                    "event_id": "$h29iv0s9:example.com",
+                    "sender": "@carl:example.com",
+                    "origin_server_ts": 1,
+                    "state_key": "@carl:example.com",
+                    "content": { "membership": 1 }
+                }
+            ]
+        }))
+        .unwrap();
+
+        let err = state.deserialize_events().unwrap_err();
+        assert_eq!(err.index(), Some(1));
+        assert_eq!(err.event_type(), Some("m.room.member"));
+    }
 }
@@ -14,6 +14,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq)]
     request: {
         /// The ID of the user creating the tag.
         #[ruma_api(path)]
@@ -32,7 +33,52 @@ ruma_api! {
         pub tag_info: TagInfo,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given user ID, room ID, tag and tag info.
+    pub fn new(user_id: UserId, room_id: RoomId, tag: String, tag_info: TagInfo) -> Self {
+        Self { user_id, room_id, tag, tag_info }
+    }
+}
+
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::OutgoingRequest as _;
+    use ruma_events::tag::TagInfo;
+    use ruma_identifiers::{room_id, user_id};
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+
+    #[test]
+    fn serialize_request() {
+        let req = Request::new(
+            user_id!("@alice:example.org"),
+            room_id!("!roomid:example.org"),
+            "u.work".into(),
+            TagInfo { order: Some(0.5) },
+        );
+
+        let http_req = req
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+        assert_eq!(
+            http_req.uri().path(),
+            "/_matrix/client/r0/user/%40alice%3Aexample%2Eorg/rooms/%21roomid%3Aexample%2Eorg\
+             /tags/u%2Ework"
+        );
+        assert_eq!(from_json_slice::<JsonValue>(http_req.body()).unwrap(), json!({ "order": 0.5 }));
+    }
+}
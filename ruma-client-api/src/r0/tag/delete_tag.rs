@@ -13,6 +13,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The user whose tag will be deleted.
         #[ruma_api(path)]
@@ -27,7 +28,48 @@ ruma_api! {
         pub tag: String,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given user ID, room ID and tag.
+    pub fn new(user_id: UserId, room_id: RoomId, tag: String) -> Self {
+        Self { user_id, room_id, tag }
+    }
+}
+
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::OutgoingRequest as _;
+    use ruma_identifiers::{room_id, user_id};
+
+    use super::Request;
+
+    #[test]
+    fn serialize_request_path() {
+        let req = Request::new(
+            user_id!("@alice:example.org"),
+            room_id!("!roomid:example.org"),
+            "m.favourite".into(),
+        );
+
+        let http_req = req
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+        assert_eq!(
+            http_req.uri().path(),
+            "/_matrix/client/r0/user/%40alice%3Aexample%2Eorg/rooms/%21roomid%3Aexample%2Eorg\
+             /tags/m%2Efavourite"
+        );
+    }
+}
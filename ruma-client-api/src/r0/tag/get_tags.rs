@@ -14,6 +14,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The user whose tags will be retrieved.
         #[ruma_api(path)]
@@ -24,6 +25,7 @@ ruma_api! {
         pub room_id: RoomId,
     }
 
+    #[derive(PartialEq)]
     response: {
         /// The user's tags for the room.
         pub tags: Tags,
@@ -32,13 +34,27 @@ ruma_api! {
     error: crate::Error
 }
 
+impl Request {
+    /// Creates a new `Request` with the given user ID and room ID.
+    pub fn new(user_id: UserId, room_id: RoomId) -> Self {
+        Self { user_id, room_id }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given tags.
+    pub fn new(tags: Tags) -> Self {
+        Self { tags }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use ruma_api::OutgoingResponse as _;
     use serde_json::json;
 
     use super::Response;
     use ruma_events::tag::{TagInfo, Tags};
-    use std::convert::TryFrom;
 
     #[test]
     fn test_serializing_get_tags_response() {
@@ -47,7 +63,7 @@ mod tests {
         tags.insert("u.user_tag".into(), TagInfo { order: Some(0.11) });
         let response = Response { tags };
 
-        let http_response = http::Response::<Vec<u8>>::try_from(response).unwrap();
+        let http_response = response.try_into_http_response::<Vec<u8>>().unwrap();
 
         let json_response: serde_json::Value =
             serde_json::from_slice(http_response.body()).unwrap();
@@ -23,6 +23,12 @@ pub enum DeviceIdOrAllDevices {
     AllDevices,
 }
 
+impl From<Box<DeviceId>> for DeviceIdOrAllDevices {
+    fn from(d: Box<DeviceId>) -> Self {
+        DeviceIdOrAllDevices::DeviceId(d)
+    }
+}
+
 impl Display for DeviceIdOrAllDevices {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
@@ -3,9 +3,9 @@
 use std::collections::BTreeMap;
 
 use ruma_api::ruma_api;
-use ruma_events::EventType;
+use ruma_common::Raw;
+use ruma_events::{AnyToDeviceEventContent, EventType};
 use ruma_identifiers::UserId;
-use serde_json::value::RawValue as RawJsonValue;
 
 use super::DeviceIdOrAllDevices;
 
@@ -31,13 +31,115 @@ ruma_api! {
         /// A map of users to devices to a content for a message event to be
         /// sent to the user's device. Individual message events can be sent
         /// to devices, but all events must be of the same type.
-        /// The content's type for this field will be updated in a future
-        /// release, until then you can create a value using
-        /// `serde_json::value::to_raw_value`.
-        pub messages: BTreeMap<UserId, BTreeMap<DeviceIdOrAllDevices, Box<RawJsonValue>>>
+        pub messages: Messages,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
 }
+
+/// Messages to send in a send-to-device request.
+///
+/// Represented as a map of `{ user-ids => { device-ids => message-content } }`.
+pub type Messages = BTreeMap<UserId, BTreeMap<DeviceIdOrAllDevices, Raw<AnyToDeviceEventContent>>>;
+
+impl<'a> Request<'a> {
+    /// Creates a new `Request` with the given event type, transaction ID and messages.
+    pub fn new(event_type: EventType, txn_id: &'a str, messages: Messages) -> Self {
+        Self { event_type, txn_id, messages }
+    }
+}
+
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use maplit::btreemap;
+    use ruma_api::{IncomingRequest as _, OutgoingRequest as _};
+    use ruma_common::Raw;
+    use ruma_events::{dummy::DummyEventContent, AnyToDeviceEventContent, EventType};
+    use ruma_identifiers::user_id;
+    use ruma_serde::empty::Empty;
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::{DeviceIdOrAllDevices, IncomingRequest, Request};
+
+    fn dummy() -> Raw<AnyToDeviceEventContent> {
+        AnyToDeviceEventContent::Dummy(DummyEventContent(Empty)).into()
+    }
+
+    #[test]
+    fn serialize_wildcard_device() {
+        let messages = btreemap! {
+            user_id!("@alice:example.org") => btreemap! {
+                DeviceIdOrAllDevices::AllDevices => dummy(),
+            },
+        };
+        let req = Request::new(EventType::Dummy, "txn1", messages);
+
+        let http_req = req
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+        assert_eq!(http_req.method(), http::Method::PUT);
+        assert_eq!(http_req.uri().path(), "/_matrix/client/r0/sendToDevice/m%2Edummy/txn1");
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({ "messages": { "@alice:example.org": { "*": {} } } })
+        );
+
+        let incoming = IncomingRequest::try_from_http_request(http_req).unwrap();
+        let devices = &incoming.messages[&user_id!("@alice:example.org")];
+        assert_eq!(devices.keys().collect::<Vec<_>>(), vec![&DeviceIdOrAllDevices::AllDevices]);
+    }
+
+    #[test]
+    fn serialize_multiple_users() {
+        let messages = btreemap! {
+            user_id!("@alice:example.org") => btreemap! {
+                DeviceIdOrAllDevices::DeviceId("ALICEDEVICE".into()) => dummy(),
+                DeviceIdOrAllDevices::DeviceId("OTHERDEVICE".into()) => dummy(),
+            },
+            user_id!("@bob:example.org") => btreemap! {
+                DeviceIdOrAllDevices::AllDevices => dummy(),
+            },
+        };
+        let req = Request::new(EventType::Dummy, "txn2", messages);
+
+        let http_req = req
+            .try_into_http_request::<Vec<u8>>("https://homeserver.tld", Some("auth_tok"))
+            .unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({
+                "messages": {
+                    "@alice:example.org": { "ALICEDEVICE": {}, "OTHERDEVICE": {} },
+                    "@bob:example.org": { "*": {} },
+                }
+            })
+        );
+
+        let incoming = IncomingRequest::try_from_http_request(http_req).unwrap();
+        assert_eq!(incoming.event_type, EventType::Dummy);
+        assert_eq!(incoming.txn_id, "txn2");
+        assert_eq!(
+            incoming
+                .messages
+                .iter()
+                .map(|(user_id, devices)| (user_id.clone(), devices.len()))
+                .collect::<BTreeMap<_, _>>(),
+            btreemap! {
+                user_id!("@alice:example.org") => 2,
+                user_id!("@bob:example.org") => 1,
+            }
+        );
+    }
+}
@@ -4,6 +4,7 @@ use std::time::Duration;
 
 use ruma_api::ruma_api;
 use ruma_identifiers::{RoomId, UserId};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 
 ruma_api! {
     metadata: {
@@ -15,6 +16,7 @@ ruma_api! {
         rate_limited: true,
     }
 
+    #[derive(PartialEq)]
     request: {
         /// The user who has started to type.
         #[ruma_api(path)]
@@ -24,21 +26,114 @@ ruma_api! {
         #[ruma_api(path)]
         pub room_id: RoomId,
 
-        // TODO: Group the following two body fields into an enum
-
-        /// Whether the user is typing or not. If `false`, the `timeout` key can be omitted.
-        pub typing: bool,
-
-        /// The length of time in milliseconds to mark this user as typing.
-        #[serde(
-            with = "ruma_serde::duration::opt_ms",
-            default,
-            skip_serializing_if = "Option::is_none",
-        )]
-        pub timeout: Option<Duration>,
+        /// Whether the user is typing within a length of time or not.
+        #[serde(flatten)]
+        pub state: Typing,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {}
 
     error: crate::Error
 }
+
+impl Request {
+    /// Creates a new `Request` with the given user ID, room ID and typing state.
+    pub fn new(user_id: UserId, room_id: RoomId, state: Typing) -> Self {
+        Self { user_id, room_id, state }
+    }
+}
+
+impl Response {
+    /// Creates an empty `Response`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// A mark for whether the user is typing within a length of time or not.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Typing {
+    /// Not typing.
+    No,
+
+    /// Typing during the specified length of time.
+    Yes(Duration),
+}
+
+#[derive(Deserialize, Serialize)]
+struct TypingInner {
+    typing: bool,
+
+    #[serde(
+        with = "ruma_serde::duration::opt_ms",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    timeout: Option<Duration>,
+}
+
+impl From<Typing> for TypingInner {
+    fn from(typing: Typing) -> Self {
+        match typing {
+            Typing::No => Self { typing: false, timeout: None },
+            Typing::Yes(time) => Self { typing: true, timeout: Some(time) },
+        }
+    }
+}
+
+impl Serialize for Typing {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        TypingInner::from(*self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Typing {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let inner = TypingInner::deserialize(deserializer)?;
+
+        match (inner.typing, inner.timeout) {
+            (false, _) => Ok(Self::No),
+            (true, Some(time)) => Ok(Self::Yes(time)),
+            (true, None) => Err(D::Error::missing_field("timeout")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use ruma_api::OutgoingRequest;
+    use ruma_identifiers::{room_id, user_id};
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::{Request, Typing};
+
+    fn body(state: Typing) -> JsonValue {
+        let req = Request::new(user_id!("@carl:example.org"), room_id!("!room:example.org"), state);
+        let request: http::Request<Vec<u8>> =
+            req.try_into_http_request("https://homeserver.tld", Some("auth_tok")).unwrap();
+
+        from_json_slice(request.body()).unwrap()
+    }
+
+    #[test]
+    fn serialize_typing_on() {
+        assert_eq!(
+            body(Typing::Yes(Duration::from_secs(30))),
+            json!({ "typing": true, "timeout": 30_000 })
+        );
+    }
+
+    #[test]
+    fn serialize_typing_off() {
+        assert_eq!(body(Typing::No), json!({ "typing": false }));
+    }
+}
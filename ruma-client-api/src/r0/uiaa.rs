@@ -106,11 +106,11 @@ impl From<MatrixError> for UiaaResponse {
 }
 
 impl EndpointError for UiaaResponse {
-    fn try_from_response(
-        response: http::Response<Vec<u8>>,
+    fn try_from_response<T: AsRef<[u8]>>(
+        response: http::Response<T>,
     ) -> Result<Self, ResponseDeserializationError> {
         if response.status() == http::StatusCode::UNAUTHORIZED {
-            if let Ok(authentication_info) = from_json_slice::<UiaaInfo>(response.body()) {
+            if let Ok(authentication_info) = from_json_slice::<UiaaInfo>(response.body().as_ref()) {
                 return Ok(UiaaResponse::AuthResponse(authentication_info));
             }
         }
@@ -354,10 +354,8 @@ mod tests {
         }))
         .unwrap();
 
-        let http_response = http::Response::builder()
-            .status(http::StatusCode::UNAUTHORIZED)
-            .body(json.into())
-            .unwrap();
+        let http_response =
+            http::Response::builder().status(http::StatusCode::UNAUTHORIZED).body(json).unwrap();
 
         let parsed_uiaa_info = match UiaaResponse::try_from_response(http_response).unwrap() {
             UiaaResponse::AuthResponse(uiaa_info) => uiaa_info,
@@ -15,6 +15,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The term to search for.
         pub search_term: &'a str,
@@ -37,6 +38,20 @@ ruma_api! {
     error: crate::Error
 }
 
+impl<'a> Request<'a> {
+    /// Creates a new `Request` with the given search term and the default limit.
+    pub fn new(search_term: &'a str) -> Self {
+        Self { search_term, limit: default_limit() }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given results and limited flag.
+    pub fn new(results: Vec<User>, limited: bool) -> Self {
+        Self { results, limited }
+    }
+}
+
 fn default_limit() -> UInt {
     uint!(10)
 }
@@ -59,3 +74,74 @@ pub struct User {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub avatar_url: Option<String>,
 }
+
+impl User {
+    /// Creates a new `User` with the given user ID.
+    pub fn new(user_id: UserId) -> Self {
+        Self { user_id, display_name: None, avatar_url: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::uint;
+    use ruma_api::{IncomingResponse as _, OutgoingRequest as _};
+    use ruma_identifiers::user_id;
+    use serde_json::{
+        from_slice as from_json_slice, json, to_vec as to_json_vec, Value as JsonValue,
+    };
+
+    use super::{Request, Response};
+
+    #[test]
+    fn serialize_request() {
+        let mut req = Request::new("alice");
+        let http_req: http::Request<Vec<u8>> =
+            req.clone().try_into_http_request("https://homeserver.tld", Some("auth_tok")).unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({ "search_term": "alice" })
+        );
+
+        req.limit = uint!(2);
+        let http_req: http::Request<Vec<u8>> =
+            req.try_into_http_request("https://homeserver.tld", Some("auth_tok")).unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({ "search_term": "alice", "limit": 2 })
+        );
+    }
+
+    #[test]
+    fn deserialize_limited_response() {
+        let body = json!({
+            "results": [
+                {
+                    "user_id": "@alice:example.org",
+                    "display_name": "Alice",
+                    "avatar_url": "mxc://example.org/abc"
+                },
+                {
+                    "user_id": "@alicia:example.org"
+                }
+            ],
+            "limited": true
+        });
+        let response =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert!(response.limited);
+        assert_eq!(response.results.len(), 2);
+
+        let alice = &response.results[0];
+        assert_eq!(alice.user_id, user_id!("@alice:example.org"));
+        assert_eq!(alice.display_name.as_deref(), Some("Alice"));
+        assert_eq!(alice.avatar_url.as_deref(), Some("mxc://example.org/abc"));
+
+        let alicia = &response.results[1];
+        assert_eq!(alicia.user_id, user_id!("@alicia:example.org"));
+        assert_eq!(alicia.display_name, None);
+        assert_eq!(alicia.avatar_url, None);
+    }
+}
@@ -14,8 +14,10 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {}
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The username to use.
         pub username: String,
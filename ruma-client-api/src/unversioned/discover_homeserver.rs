@@ -1,4 +1,4 @@
-//! [GET /.well-known/matrix/client](https://matrix.org/docs/spec/client_server/r0.6.0#get-well-known-matrix-client)
+//! [GET /.well-known/matrix/client](https://matrix.org/docs/spec/client_server/r0.6.1#get-well-known-matrix-client)
 
 use ruma_api::ruma_api;
 use serde::{Deserialize, Serialize};
@@ -13,15 +13,17 @@ ruma_api! {
         requires_authentication: false,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {}
 
+    #[derive(PartialEq)]
     response: {
         /// Information about the homeserver to connect to.
         #[serde(rename = "m.homeserver")]
         pub homeserver: HomeserverInfo,
 
         /// Information about the identity server to connect to.
-        #[serde(rename = "m.identity_server")]
+        #[serde(rename = "m.identity_server", skip_serializing_if = "Option::is_none")]
         pub identity_server: Option<IdentityServerInfo>,
     }
 
@@ -71,3 +73,47 @@ impl IdentityServerInfo {
         Self { base_url }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::{IncomingResponse as _, OutgoingRequest as _};
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::{HomeserverInfo, IdentityServerInfo, Request, Response};
+
+    #[test]
+    fn request_path() {
+        let req =
+            Request::new().try_into_http_request::<Vec<u8>>("https://example.org/", None).unwrap();
+        assert_eq!(req.uri(), "https://example.org/.well-known/matrix/client");
+    }
+
+    #[test]
+    fn deserialize_response() {
+        let body = json!({
+            "m.homeserver": { "base_url": "https://matrix.example.org" },
+            "m.identity_server": { "base_url": "https://identity.example.org" },
+            "org.example.custom.property": { "app_url": "https://custom.app.example.org" }
+        });
+        let response =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert_eq!(response.homeserver, HomeserverInfo::new("https://matrix.example.org".into()));
+        assert_eq!(
+            response.identity_server,
+            Some(IdentityServerInfo::new("https://identity.example.org".into()))
+        );
+    }
+
+    #[test]
+    fn deserialize_response_without_identity_server() {
+        let body = json!({ "m.homeserver": { "base_url": "https://matrix.example.org" } });
+        let response =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert_eq!(response.homeserver.base_url, "https://matrix.example.org");
+        assert_eq!(response.identity_server, None);
+    }
+}
@@ -1,8 +1,8 @@
 //! [GET /_matrix/client/versions](https://matrix.org/docs/spec/client_server/r0.6.0#get-matrix-client-versions)
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
-use ruma_api::ruma_api;
+use ruma_api::{ruma_api, MatrixVersion};
 
 ruma_api! {
     metadata: {
@@ -14,8 +14,11 @@ ruma_api! {
         requires_authentication: false,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {}
 
+    #[derive(PartialEq, Eq)]
+    #[non_exhaustive]
     response: {
         /// A list of Matrix client API protocol versions supported by the homeserver.
         pub versions: Vec<String>,
@@ -40,4 +43,77 @@ impl Response {
     pub fn new(versions: Vec<String>) -> Self {
         Self { versions, unstable_features: BTreeMap::new() }
     }
+
+    /// Whether the homeserver supports the given version of the client-server API, e.g. `r0.6.0`.
+    pub fn supports(&self, version: &str) -> bool {
+        self.versions.iter().any(|v| v == version)
+    }
+
+    /// The versions of the Matrix specification supported by the homeserver, oldest first.
+    ///
+    /// Versions released after this crate are included as `MatrixVersion::Newer`. Strings that
+    /// aren't versions of the specification are skipped rather than treated as an error; they are
+    /// still available in `versions`.
+    pub fn known_versions(&self) -> BTreeSet<MatrixVersion> {
+        self.versions.iter().filter_map(|v| MatrixVersion::from_versions_str(v)).collect()
+    }
+
+    /// Whether the homeserver advertises the given unstable feature as enabled.
+    ///
+    /// Features that are missing from `unstable_features` are considered disabled.
+    pub fn supports_feature(&self, feature: &str) -> bool {
+        self.unstable_features.get(feature).copied().unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::{IncomingResponse as _, MatrixVersion};
+    use serde_json::{json, to_vec as to_json_vec};
+
+    use super::Response;
+
+    #[test]
+    fn deserialize_versions_and_unstable_features() {
+        let body = json!({
+            "versions": ["r0.0.1", "r0.5.0", "r0.6.0"],
+            "unstable_features": {
+                "org.matrix.label_based_filtering": true,
+                "org.matrix.e2e_cross_signing": false
+            }
+        });
+        let response =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+
+        assert!(response.supports("r0.6.0"));
+        assert!(!response.supports("r0.6.1"));
+        assert!(response.supports_feature("org.matrix.label_based_filtering"));
+        assert!(!response.supports_feature("org.matrix.e2e_cross_signing"));
+        assert!(!response.supports_feature("org.matrix.unknown"));
+    }
+
+    #[test]
+    fn known_versions_with_future_versions() {
+        let response = Response::new(vec![
+            "r0.5.0".into(),
+            "v1.2".into(),
+            "r0.6.1".into(),
+            "v1.1".into(),
+            "v1.99".into(),
+            "not a version".into(),
+        ]);
+
+        let newest = MatrixVersion::Newer { major: 1, minor: 99 };
+        let known: Vec<_> = response.known_versions().into_iter().collect();
+        assert_eq!(
+            known,
+            vec![MatrixVersion::V1_0, MatrixVersion::V1_1, MatrixVersion::V1_2, newest]
+        );
+        assert_eq!(response.known_versions().iter().max(), Some(&newest));
+
+        let known: Vec<_> =
+            Response::new(vec!["v2.0".into()]).known_versions().into_iter().collect();
+        assert_eq!(known, vec![MatrixVersion::Newer { major: 2, minor: 0 }]);
+    }
 }
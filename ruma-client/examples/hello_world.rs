@@ -2,15 +2,11 @@ use std::{convert::TryFrom, env, process::exit};
 
 use http::Uri;
 use ruma::{
-    api::client::r0::{alias::get_alias, membership::join_room_by_id, message::send_message_event},
-    events::{
-        room::message::{MessageEventContent, TextMessageEventContent},
-        EventType,
-    },
+    api::client::r0::{alias::get_alias, membership::join_room_by_id},
+    events::room::message::{MessageEventContent, TextMessageEventContent},
     RoomAliasId,
 };
 use ruma_client::{self, Client};
-use serde_json::value::to_raw_value as to_raw_json_value;
 
 async fn hello_world(homeserver_url: Uri, room_alias: &RoomAliasId) -> anyhow::Result<()> {
     let client = Client::new(homeserver_url, None);
@@ -23,16 +19,10 @@ async fn hello_world(homeserver_url: Uri, room_alias: &RoomAliasId) -> anyhow::R
     client.request(join_room_by_id::Request::new(&room_id)).await?;
 
     client
-        .request(send_message_event::Request {
-            room_id: &room_id,
-            event_type: EventType::RoomMessage,
-            txn_id: "1",
-            data: to_raw_json_value(&MessageEventContent::Text(TextMessageEventContent {
-                body: "Hello World!".to_owned(),
-                formatted: None,
-                relates_to: None,
-            }))?,
-        })
+        .send_message(
+            &room_id,
+            &MessageEventContent::Text(TextMessageEventContent::plain("Hello World!")),
+        )
         .await?;
 
     Ok(())
@@ -9,14 +9,14 @@ use ruma::{
     },
     presence::PresenceState,
 };
-use ruma_client::{self, HttpClient};
+use ruma_client::{self, PlainHttpClient};
 
 async fn log_messages(
     homeserver_url: Uri,
     username: String,
     password: String,
 ) -> anyhow::Result<()> {
-    let client = HttpClient::new(homeserver_url, None);
+    let client = PlainHttpClient::new(homeserver_url, None);
 
     client.log_in(username, password, None, None).await?;
 
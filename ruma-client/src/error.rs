@@ -57,7 +57,7 @@ impl<E> From<http::uri::InvalidUriParts> for Error<E> {
 #[doc(hidden)]
 impl<E> From<hyper::Error> for Error<E> {
     fn from(err: hyper::Error) -> Self {
-        Error::Response(ResponseError(err))
+        Error::Response(ResponseError::new(err))
     }
 }
 
@@ -69,8 +69,51 @@ impl<E> From<FromHttpResponseError<E>> for Error<E> {
 
 impl<E: Debug + Display> std::error::Error for Error<E> {}
 
+/// An error that can occur during server discovery with `Client::with_discovery`.
+///
+/// The variants correspond to the failure outcomes of the
+/// [discovery process](https://matrix.org/docs/spec/client_server/r0.6.1#well-known-uri) in the
+/// spec.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DiscoveryError {
+    /// The discovery information couldn't be fetched or is malformed (`FAIL_PROMPT`).
+    ///
+    /// The user should be informed of the problem and asked for a homeserver URL instead.
+    FailPrompt(Error<ruma_client_api::Error>),
+    /// The discovered homeserver base URL is not a valid `https` URL (`FAIL_ERROR`).
+    ///
+    /// Contains the base URL as sent by the server. The login should not continue.
+    FailError(String),
+}
+
+impl Display for DiscoveryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FailPrompt(err) => write!(f, "Couldn't discover the homeserver: {}", err),
+            Self::FailError(base_url) => {
+                write!(f, "The discovered homeserver base URL is invalid: {}", base_url)
+            }
+        }
+    }
+}
+
+impl From<Error<ruma_client_api::Error>> for DiscoveryError {
+    fn from(err: Error<ruma_client_api::Error>) -> Self {
+        DiscoveryError::FailPrompt(err)
+    }
+}
+
+impl std::error::Error for DiscoveryError {}
+
 #[derive(Debug)]
 pub struct UrlError(http::Error);
 
 #[derive(Debug)]
-pub struct ResponseError(hyper::Error);
+pub struct ResponseError(Box<dyn std::error::Error + Send + Sync>);
+
+impl ResponseError {
+    pub(crate) fn new(err: impl std::error::Error + Send + Sync + 'static) -> Self {
+        Self(Box::new(err))
+    }
+}
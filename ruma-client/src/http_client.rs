@@ -0,0 +1,84 @@
+//! The HTTP transport used by `Client`.
+
+use std::{cmp, convert::TryFrom};
+
+use async_trait::async_trait;
+use bytes::{BufMut, Bytes, BytesMut};
+use http::{Request as HttpRequest, Response as HttpResponse};
+use hyper::{body::HttpBody as _, client::connect::Connect, Client as HyperClient};
+
+/// The largest buffer preallocated for a response body based on its size hint, so a bogus
+/// `Content-Length` can't make the client allocate arbitrary amounts of memory upfront.
+const MAX_PREALLOCATED_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// An HTTP backend that `Client` can use to send requests.
+///
+/// This decouples the API types from the transport, so users can plug in any HTTP library (or a
+/// test double). `Client::new` and `Client::https` use a `hyper` client.
+///
+/// Requests are serialized directly into `RequestBody` and responses are parsed directly from
+/// `ResponseBody`, so backends can use the buffer types of their HTTP library and don't need to
+/// copy bodies into and out of a `Vec<u8>`. The `raw_body` fields of media endpoints are still
+/// `Vec<u8>` though, so their contents are copied once.
+#[async_trait]
+pub trait HttpClient: Sync {
+    /// The type of the request bodies this backend sends, e.g. `Vec<u8>` or `bytes::BytesMut`.
+    type RequestBody: AsRef<[u8]> + Default + BufMut + Send;
+
+    /// The type of the response bodies this backend receives, e.g. `Vec<u8>` or `bytes::Bytes`.
+    type ResponseBody: AsRef<[u8]> + Send;
+
+    /// The error type returned by `send`.
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Sends the given request and returns the full response, including its body.
+    async fn send(
+        &self,
+        request: HttpRequest<Self::RequestBody>,
+    ) -> Result<HttpResponse<Self::ResponseBody>, Self::Error>;
+}
+
+#[async_trait]
+impl<C> HttpClient for HyperClient<C>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
+    type RequestBody = BytesMut;
+    type ResponseBody = Bytes;
+    type Error = hyper::Error;
+
+    async fn send(
+        &self,
+        request: HttpRequest<BytesMut>,
+    ) -> Result<HttpResponse<Bytes>, Self::Error> {
+        // Freezing the buffer and converting it into a `hyper::Body` doesn't copy it, which matters
+        // for media uploads.
+        let hyper_response =
+            self.request(request.map(|body| hyper::Body::from(body.freeze()))).await?;
+        let (head, mut body) = hyper_response.into_parts();
+
+        // A body that arrives in a single chunk is passed on as-is, without copying it.
+        let first_chunk = match body.data().await {
+            Some(chunk) => chunk?,
+            None => return Ok(HttpResponse::from_parts(head, Bytes::new())),
+        };
+        let second_chunk = match body.data().await {
+            Some(chunk) => chunk?,
+            None => return Ok(HttpResponse::from_parts(head, first_chunk)),
+        };
+
+        // Otherwise, each chunk is copied straight into the final buffer.
+        let size_hint = usize::try_from(body.size_hint().lower()).unwrap_or(usize::MAX);
+        let mut full_body = BytesMut::with_capacity(
+            cmp::min(size_hint, MAX_PREALLOCATED_BODY_SIZE)
+                .saturating_add(first_chunk.len() + second_chunk.len()),
+        );
+        full_body.extend_from_slice(&first_chunk);
+        full_body.extend_from_slice(&second_chunk);
+        while let Some(chunk) = body.data().await {
+            full_body.extend_from_slice(&chunk?);
+        }
+
+        Ok(HttpResponse::from_parts(head, full_body.freeze()))
+    }
+}
@@ -101,32 +101,60 @@
 #![deny(missing_copy_implementations, missing_debug_implementations, missing_docs)]
 
 use std::{
-    convert::TryFrom,
-    sync::{Arc, Mutex},
-    time::Duration,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use assign::assign;
 use futures_core::stream::{Stream, TryStream};
 use futures_util::stream;
-use http::{uri::Uri, Response as HttpResponse};
-use hyper::{client::HttpConnector, Client as HyperClient};
+use http::{uri::Uri, StatusCode};
+use hyper::{
+    client::{connect::Connect, HttpConnector},
+    Client as HyperClient,
+};
 #[cfg(feature = "hyper-tls")]
 use hyper_tls::HttpsConnector;
-use ruma_api::OutgoingRequest;
-use ruma_client_api::r0::sync::sync_events::{
-    Filter as SyncFilter, Request as SyncRequest, Response as SyncResponse,
+use ruma_api::{error::IntoHttpError, IncomingResponse as _, OutgoingRequest};
+use ruma_client_api::{
+    r0::{
+        message::send_message_event,
+        session::{login, logout, sso_login},
+        sync::sync_events::{
+            Filter as SyncFilter, Request as SyncRequest, Response as SyncResponse,
+        },
+    },
+    unversioned::{discover_homeserver, get_supported_versions},
 };
-use ruma_identifiers::DeviceId;
+use ruma_events::{EventType, MessageEventContent};
+use ruma_identifiers::{DeviceId, RoomId, UserId};
 use ruma_serde::urlencoded;
+use serde_json::value::RawValue as RawJsonValue;
 use std::collections::BTreeMap;
 
+use self::{error::ResponseError, observer::Observer, send_queue::QueueState};
+
 mod error;
+mod http_client;
+mod observer;
+mod room_memberships;
+mod room_state;
+mod send_queue;
 mod session;
+mod timeline;
 
 pub use self::{
-    error::Error,
+    error::{DiscoveryError, Error},
+    http_client::HttpClient,
+    observer::RequestInfo,
+    room_memberships::{room_display_name, RoomMemberships},
+    room_state::RoomState,
+    send_queue::{MemoryQueueStore, QueueStore, QueuedEvent, SendQueue, SendResult},
     session::{Identification, Session},
+    timeline::{MissingRange, Timeline},
 };
 
 /// A client for the Matrix client-server API.
@@ -139,28 +167,34 @@ struct ClientData<C> {
     /// The URL of the homeserver to connect to.
     homeserver_url: Uri,
     /// The underlying HTTP client.
-    hyper: HyperClient<C>,
+    http_client: C,
     /// User session data.
     session: Mutex<Option<Session>>,
+    /// Callback invoked for every request, if any.
+    observer: Mutex<Option<Observer>>,
+    /// The response of the homeserver's `/versions` endpoint, once fetched.
+    server_versions: Mutex<Option<get_supported_versions::Response>>,
+    /// Prefix for generated transaction IDs, unique to this client instance.
+    txn_id_prefix: u128,
+    /// Counter for generated transaction IDs.
+    txn_id_counter: AtomicU64,
+    /// The queue returned by `Client::queue`.
+    queue: Arc<QueueState<MemoryQueueStore>>,
 }
 
 /// Non-secured variant of the client (using plain HTTP requests)
-pub type HttpClient = Client<HttpConnector>;
+pub type PlainHttpClient = Client<HyperClient<HttpConnector>>;
 
-impl HttpClient {
+impl PlainHttpClient {
     /// Creates a new client for making HTTP requests to the given homeserver.
     pub fn new(homeserver_url: Uri, session: Option<Session>) -> Self {
-        Self(Arc::new(ClientData {
-            homeserver_url,
-            hyper: HyperClient::builder().build_http(),
-            session: Mutex::new(session),
-        }))
+        Self::with_http_client(HyperClient::builder().build_http(), homeserver_url, session)
     }
 }
 
 /// Secured variant of the client (using HTTPS requests)
 #[cfg(feature = "tls")]
-pub type HttpsClient = Client<HttpsConnector<HttpConnector>>;
+pub type HttpsClient = Client<HyperClient<HttpsConnector<HttpConnector>>>;
 
 #[cfg(feature = "tls")]
 impl HttpsClient {
@@ -168,17 +202,13 @@ impl HttpsClient {
     pub fn https(homeserver_url: Uri, session: Option<Session>) -> Self {
         let connector = HttpsConnector::new();
 
-        Self(Arc::new(ClientData {
-            homeserver_url,
-            hyper: HyperClient::builder().build(connector),
-            session: Mutex::new(session),
-        }))
+        Self::with_http_client(HyperClient::builder().build(connector), homeserver_url, session)
     }
 }
 
-impl<C> Client<C>
+impl<C> Client<HyperClient<C>>
 where
-    C: hyper::client::connect::Connect + Clone + Send + Sync + 'static,
+    C: Connect + Clone + Send + Sync + 'static,
 {
     /// Creates a new client using the given `hyper::Client`.
     ///
@@ -188,13 +218,80 @@ where
         homeserver_url: Uri,
         session: Option<Session>,
     ) -> Self {
+        Self::with_http_client(hyper_client, homeserver_url, session)
+    }
+}
+
+impl<C: HttpClient> Client<C> {
+    /// Creates a new client that sends its requests through the given HTTP backend.
+    ///
+    /// This allows using an HTTP library other than `hyper`, or a test double.
+    pub fn with_http_client(http_client: C, homeserver_url: Uri, session: Option<Session>) -> Self {
         Self(Arc::new(ClientData {
             homeserver_url,
-            hyper: hyper_client,
+            http_client,
             session: Mutex::new(session),
+            observer: Mutex::new(None),
+            server_versions: Mutex::new(None),
+            // Clients restored from a saved session reuse the access token, so the counter alone
+            // would repeat transaction IDs of an earlier run.
+            txn_id_prefix: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_millis()),
+            txn_id_counter: AtomicU64::new(0),
+            queue: Arc::default(),
         }))
     }
 
+    /// Creates a new client for the homeserver of the given user, found through
+    /// [server discovery](https://matrix.org/docs/spec/client_server/r0.6.1#server-discovery).
+    ///
+    /// The discovery information is fetched from `https://<server name>/.well-known/matrix/client`
+    /// through the given HTTP backend. If the server doesn't serve it, the server name of the user
+    /// ID is used as the homeserver URL. Identity server information is ignored.
+    pub async fn with_discovery(
+        http_client: C,
+        user_id: &UserId,
+        session: Option<Session>,
+    ) -> Result<Self, DiscoveryError> {
+        let server_url = format!("https://{}", user_id.server_name());
+        let base_url =
+            fetch_discovered_base_url(&http_client, &server_url).await?.unwrap_or(server_url);
+        let homeserver_url =
+            parse_base_url(&base_url).ok_or(DiscoveryError::FailError(base_url))?;
+
+        Ok(Self::with_http_client(http_client, homeserver_url, session))
+    }
+
+    /// Registers a callback that is invoked once for every request made by this client, after the
+    /// response was received (or sending the request failed).
+    ///
+    /// Request and response bodies are not included, use `with_body_observer` for that. This
+    /// replaces any previously registered observer, including for clones of this client.
+    pub fn with_observer(self, observer: impl Fn(&RequestInfo) + Send + Sync + 'static) -> Self {
+        self.set_observer(observer, false);
+        self
+    }
+
+    /// Like `with_observer`, but also includes the request and response bodies in the
+    /// `RequestInfo` passed to the callback.
+    pub fn with_body_observer(
+        self,
+        observer: impl Fn(&RequestInfo) + Send + Sync + 'static,
+    ) -> Self {
+        self.set_observer(observer, true);
+        self
+    }
+
+    fn set_observer(
+        &self,
+        observer: impl Fn(&RequestInfo) + Send + Sync + 'static,
+        include_bodies: bool,
+    ) {
+        *self.0.observer.lock().expect("observer mutex was poisoned") =
+            Some(Observer { callback: Arc::new(observer), include_bodies });
+    }
+
     /// Get a copy of the current `Session`, if any.
     ///
     /// Useful for serializing and persisting the session to be restored later.
@@ -202,6 +299,24 @@ where
         self.0.session.lock().expect("session mutex was poisoned").clone()
     }
 
+    /// Get the versions of the client-server API and the unstable features supported by the
+    /// homeserver.
+    ///
+    /// The versions are only fetched the first time this is called. Later calls, including on
+    /// clones of this client, return the cached response.
+    pub async fn server_versions(
+        &self,
+    ) -> Result<get_supported_versions::Response, Error<ruma_client_api::Error>> {
+        if let Some(versions) = &*self.0.server_versions.lock().unwrap() {
+            return Ok(versions.clone());
+        }
+
+        let versions = self.request(get_supported_versions::Request::new()).await?;
+        *self.0.server_versions.lock().unwrap() = Some(versions.clone());
+
+        Ok(versions)
+    }
+
     /// Log in with a username and password.
     ///
     /// In contrast to `api::r0::session::login::call()`, this method stores the
@@ -214,16 +329,51 @@ where
         device_id: Option<Box<DeviceId>>,
         initial_device_display_name: Option<String>,
     ) -> Result<Session, Error<ruma_client_api::Error>> {
-        use ruma_client_api::r0::session::login;
+        self.log_in_with_request(assign!(
+            login::Request::new(
+                Some(login::UserIdentifier::MatrixId(user)),
+                login::LoginInfo::Password { password },
+            ),
+            { device_id, initial_device_display_name }
+        ))
+        .await
+    }
 
-        let response = self
-            .request(login::Request {
-                user: login::UserInfo::MatrixId(user),
-                login_info: login::LoginInfo::Password { password },
-                device_id,
-                initial_device_display_name,
-            })
-            .await?;
+    /// Log in with a login token, e.g. one obtained through single sign-on.
+    ///
+    /// Like `log_in`, this method stores the session data returned by the endpoint in this client.
+    pub async fn log_in_with_token(
+        &self,
+        token: String,
+        device_id: Option<Box<DeviceId>>,
+        initial_device_display_name: Option<String>,
+    ) -> Result<Session, Error<ruma_client_api::Error>> {
+        self.log_in_with_request(assign!(
+            login::Request::new(None, login::LoginInfo::Token { token }),
+            { device_id, initial_device_display_name }
+        ))
+        .await
+    }
+
+    /// Builds the URL of the homeserver's single sign-on login page.
+    ///
+    /// After a successful login, the homeserver redirects the user's browser to `redirect_url`,
+    /// with a `loginToken` query parameter that can be passed to `log_in_with_token`.
+    pub fn sso_login_url(
+        &self,
+        redirect_url: String,
+    ) -> Result<Uri, Error<ruma_client_api::Error>> {
+        let http_request = sso_login::Request::new(redirect_url)
+            .try_into_http_request::<Vec<u8>>(&self.0.homeserver_url.to_string(), None)?;
+
+        Ok(http_request.uri().clone())
+    }
+
+    async fn log_in_with_request(
+        &self,
+        request: login::Request,
+    ) -> Result<Session, Error<ruma_client_api::Error>> {
+        let response = self.request(request).await?;
 
         let session = Session {
             access_token: response.access_token,
@@ -237,6 +387,18 @@ where
         Ok(session)
     }
 
+    /// Log out, invalidating the access token of the current session.
+    ///
+    /// Once the homeserver has invalidated the token, the session is removed from this client, so
+    /// subsequent requests that need authentication fail with `Error::AuthenticationRequired`
+    /// instead of being sent with a dead token.
+    pub async fn log_out(&self) -> Result<(), Error<ruma_client_api::Error>> {
+        self.request(logout::Request::new()).await?;
+        *self.0.session.lock().unwrap() = None;
+
+        Ok(())
+    }
+
     /// Register as a guest. In contrast to `api::r0::account::register::call()`,
     /// this method stores the session data returned by the endpoint in this
     /// client, instead of returning it.
@@ -319,18 +481,23 @@ where
     /// If the since parameter is None, the first Item might take a significant time to arrive and
     /// be deserialized, because it contains all events that have occurred in the whole lifetime of
     /// the logged-in users account and are visible to them.
-    pub fn sync(
+    pub fn sync<'a>(
         &self,
-        filter: Option<SyncFilter>,
+        filter: Option<SyncFilter<'a>>,
         since: Option<String>,
         set_presence: ruma_common::presence::PresenceState,
         timeout: Option<Duration>,
     ) -> impl Stream<Item = Result<SyncResponse, Error<ruma_client_api::Error>>>
-           + TryStream<Ok = SyncResponse, Error = Error<ruma_client_api::Error>> {
+           + TryStream<Ok = SyncResponse, Error = Error<ruma_client_api::Error>>
+           + 'a
+    where
+        C: 'a,
+    {
         let client = self.clone();
         stream::try_unfold(since, move |since| {
             let client = client.clone();
             let filter = filter.clone();
+            let set_presence = set_presence.clone();
 
             async move {
                 let response = client
@@ -349,6 +516,53 @@ where
         })
     }
 
+    /// Sends a message event to the given room, using a newly generated transaction ID.
+    ///
+    /// The event type is taken from the content. Use the `send_message_event` endpoint with
+    /// `request` directly if you need control over the transaction ID, e.g. to retry sending an
+    /// event.
+    pub async fn send_message<T: MessageEventContent>(
+        &self,
+        room_id: &RoomId,
+        content: &T,
+    ) -> Result<send_message_event::Response, Error<ruma_client_api::Error>> {
+        let txn_id = self.next_txn_id();
+        let request = send_message_event::Request::new(room_id, &txn_id, content)
+            .map_err(IntoHttpError::from)?;
+
+        self.request(request).await
+    }
+
+    /// Sends a message event with arbitrary JSON content to the given room, using a newly
+    /// generated transaction ID.
+    ///
+    /// Prefer `send_message` for event types ruma knows about.
+    pub async fn send_raw_message_event(
+        &self,
+        room_id: &RoomId,
+        event_type: EventType,
+        data: Box<RawJsonValue>,
+    ) -> Result<send_message_event::Response, Error<ruma_client_api::Error>> {
+        let txn_id = self.next_txn_id();
+
+        self.request(send_message_event::Request::new_raw(room_id, event_type, &txn_id, data)).await
+    }
+
+    /// Returns a handle to the `SendQueue` of this client, which keeps pending events in memory.
+    ///
+    /// Every call returns a handle to the same queue, so events queued through one handle are sent
+    /// by flushing any other. Use `SendQueue::with_store` to persist pending events across
+    /// restarts.
+    pub fn queue(&self) -> SendQueue<C> {
+        SendQueue::with_state(self.clone(), self.0.queue.clone())
+    }
+
+    /// Generates a transaction ID that is unique for this client.
+    fn next_txn_id(&self) -> String {
+        let count = self.0.txn_id_counter.fetch_add(1, Ordering::SeqCst);
+        format!("{}.{}", self.0.txn_id_prefix, count)
+    }
+
     /// Makes a request to a Matrix API endpoint.
     pub async fn request<Request: OutgoingRequest>(
         &self,
@@ -366,7 +580,7 @@ where
         let client = self.0.clone();
         let mut http_request = {
             let session;
-            let access_token = if Request::METADATA.requires_authentication {
+            let access_token = if Request::METADATA.authentication.requires_authentication() {
                 session = client.session.lock().unwrap();
                 if let Some(s) = &*session {
                     Some(s.access_token.as_str())
@@ -377,7 +591,10 @@ where
                 None
             };
 
-            request.try_into_http_request(&client.homeserver_url.to_string(), access_token)?
+            request.try_into_http_request::<C::RequestBody>(
+                &client.homeserver_url.to_string(),
+                access_token,
+            )?
         };
 
         let extra_params = urlencoded::to_string(extra_params).unwrap();
@@ -390,15 +607,58 @@ where
             path_and_query: Some(new_path_and_query.parse()?),
         }))?;
 
-        let hyper_response = client.hyper.request(http_request.map(hyper::Body::from)).await?;
-        let (head, body) = hyper_response.into_parts();
+        // The observer is cloned out of the mutex so the callback doesn't run while it is locked,
+        // which would deadlock if the callback registers a new observer.
+        let observer = client.observer.lock().unwrap().clone();
+        let mut request_info = observer
+            .as_ref()
+            .map(|observer| RequestInfo::new(&http_request, observer.include_bodies));
+
+        let http_response = client.http_client.send(http_request).await;
+
+        if let (Some(observer), Some(info)) = (observer, &mut request_info) {
+            if let Ok(response) = &http_response {
+                info.set_response(response, observer.include_bodies);
+            }
+
+            (observer.callback)(info);
+        }
+
+        let http_response = http_response.map_err(|e| Error::Response(ResponseError::new(e)))?;
+
+        Ok(Request::IncomingResponse::try_from_http_response(http_response)?)
+    }
+}
+
+/// Fetches the homeserver base URL from the `.well-known/matrix/client` of the given server.
+///
+/// Returns `None` if the server doesn't serve discovery information.
+async fn fetch_discovered_base_url<C: HttpClient>(
+    http_client: &C,
+    server_url: &str,
+) -> Result<Option<String>, Error<ruma_client_api::Error>> {
+    let http_request = discover_homeserver::Request::new()
+        .try_into_http_request::<C::RequestBody>(server_url, None)?;
+    let http_response =
+        http_client.send(http_request).await.map_err(|e| Error::Response(ResponseError::new(e)))?;
+
+    if http_response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    Ok(Some(
+        discover_homeserver::Response::try_from_http_response(http_response)?.homeserver.base_url,
+    ))
+}
 
-        // FIXME: We read the response into a contiguous buffer here (not actually required for
-        // deserialization) and then copy the whole thing to convert from Bytes to Vec<u8>.
-        let full_body = hyper::body::to_bytes(body).await?;
-        let full_response = HttpResponse::from_parts(head, full_body.as_ref().to_owned());
+/// Parses a discovered homeserver base URL, which has to be an absolute `https` URL without a
+/// query. Trailing slashes are removed.
+fn parse_base_url(base_url: &str) -> Option<Uri> {
+    let uri = base_url.trim_end_matches('/').parse::<Uri>().ok()?;
 
-        Ok(Request::IncomingResponse::try_from(full_response)?)
+    match (uri.scheme_str(), uri.authority(), uri.query()) {
+        (Some("https"), Some(_), None) => Some(uri),
+        _ => None,
     }
 }
 
@@ -0,0 +1,85 @@
+//! Hooks for observing the requests sent by `Client`.
+
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+use http::{
+    header::{HeaderValue, AUTHORIZATION},
+    HeaderMap, Method, Request as HttpRequest, Response as HttpResponse, StatusCode,
+};
+
+/// Information about a request made by `Client`, passed to the observer registered with
+/// `Client::with_observer` or `Client::with_body_observer`.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct RequestInfo {
+    /// The HTTP method of the request.
+    pub method: Method,
+
+    /// The path of the request, without the query string.
+    pub path: String,
+
+    /// The headers of the request.
+    ///
+    /// The value of the `Authorization` header is replaced with `<redacted>`, so the access token
+    /// never ends up in logs.
+    pub headers: HeaderMap,
+
+    /// The body of the request.
+    ///
+    /// Only set if the observer was registered with `Client::with_body_observer`.
+    pub body: Option<Vec<u8>>,
+
+    /// The status code of the response, or `None` if no response was received.
+    pub status: Option<StatusCode>,
+
+    /// The body of the response.
+    ///
+    /// Only set if the observer was registered with `Client::with_body_observer` and a response
+    /// was received.
+    pub response_body: Option<Vec<u8>>,
+}
+
+impl RequestInfo {
+    pub(crate) fn new(request: &HttpRequest<impl AsRef<[u8]>>, include_body: bool) -> Self {
+        let mut headers = request.headers().clone();
+        if let Some(authorization) = headers.get_mut(AUTHORIZATION) {
+            *authorization = HeaderValue::from_static("<redacted>");
+        }
+
+        Self {
+            method: request.method().clone(),
+            path: request.uri().path().to_owned(),
+            headers,
+            body: if include_body { Some(request.body().as_ref().to_owned()) } else { None },
+            status: None,
+            response_body: None,
+        }
+    }
+
+    pub(crate) fn set_response(
+        &mut self,
+        response: &HttpResponse<impl AsRef<[u8]>>,
+        include_body: bool,
+    ) {
+        self.status = Some(response.status());
+        if include_body {
+            self.response_body = Some(response.body().as_ref().to_owned());
+        }
+    }
+}
+
+/// A callback invoked once for every request made by `Client`.
+#[derive(Clone)]
+pub(crate) struct Observer {
+    pub callback: Arc<dyn Fn(&RequestInfo) + Send + Sync>,
+    pub include_bodies: bool,
+}
+
+impl Debug for Observer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Observer").field("include_bodies", &self.include_bodies).finish()
+    }
+}
@@ -0,0 +1,189 @@
+//! Tracking the members of a room, including lazy-loaded ones, and computing display names for
+//! them and for the room.
+
+use std::{collections::BTreeMap, convert::TryFrom};
+
+use ruma_client_api::r0::sync::sync_events::RoomSummary;
+use ruma_common::Raw;
+use ruma_events::{
+    room::member::{MemberEventContent, MembershipState},
+    SyncStateEvent,
+};
+use ruma_identifiers::{RoomAliasId, UserId};
+
+/// The number of members used to compose a room name if the server didn't send any heroes.
+const MAX_HEROES: usize = 5;
+
+/// The membership of every known member of a room.
+///
+/// With lazy-loading, member events arrive piecemeal, in the `state` section of sync responses
+/// and `/messages` responses and in timelines. Feed all of them to `apply` or `apply_raw`, in the
+/// order they were received, to keep the latest membership of each user.
+#[derive(Clone, Debug)]
+pub struct RoomMemberships {
+    own_user_id: UserId,
+    members: BTreeMap<UserId, MemberEventContent>,
+}
+
+impl RoomMemberships {
+    /// Creates an empty `RoomMemberships` for the room as seen by the user with the given ID.
+    pub fn new(own_user_id: UserId) -> Self {
+        Self { own_user_id, members: BTreeMap::new() }
+    }
+
+    /// The ID of the user whose view of the room this is.
+    pub fn own_user_id(&self) -> &UserId {
+        &self.own_user_id
+    }
+
+    /// The latest member event content of the given user, if it is known.
+    pub fn member(&self, user_id: &UserId) -> Option<&MemberEventContent> {
+        self.members.get(user_id)
+    }
+
+    /// Applies a member event.
+    ///
+    /// Events whose `state_key` is not a user ID are ignored.
+    pub fn apply(&mut self, event: &SyncStateEvent<MemberEventContent>) {
+        if let Ok(user_id) = event.state_key_as_user_id() {
+            self.members.insert(user_id, event.content.clone());
+        }
+    }
+
+    /// Applies the given event if it is a valid member event, and ignores it otherwise.
+    ///
+    /// This accepts events with or without a `room_id`, so the events of sync and `/messages`
+    /// responses can be used directly.
+    pub fn apply_raw<T>(&mut self, event: &Raw<T>) {
+        if let Ok(event) =
+            serde_json::from_str::<SyncStateEvent<MemberEventContent>>(event.json().get())
+        {
+            self.apply(&event);
+        }
+    }
+
+    /// The display name to show for the given user.
+    ///
+    /// This is the user's display name, followed by their user ID if other joined or invited
+    /// members use the same display name. Users without a known display name are shown by their
+    /// user ID.
+    pub fn display_name_for(&self, user_id: &UserId) -> String {
+        let displayname = match self.displayname(user_id) {
+            Some(displayname) => displayname,
+            None => return user_id.to_string(),
+        };
+
+        let is_ambiguous = self.members.iter().any(|(other_id, member)| {
+            other_id != user_id
+                && is_joined_or_invited(member)
+                && non_empty(member.displayname.as_deref()) == Some(displayname)
+        });
+
+        if is_ambiguous {
+            format!("{} ({})", displayname, user_id)
+        } else {
+            displayname.to_owned()
+        }
+    }
+
+    fn displayname(&self, user_id: &UserId) -> Option<&str> {
+        non_empty(self.members.get(user_id)?.displayname.as_deref())
+    }
+
+    /// Up to `MAX_HEROES` joined or invited members other than the own user, ordered by user ID.
+    fn default_heroes(&self) -> Vec<String> {
+        self.members
+            .iter()
+            .filter(|(user_id, member)| {
+                **user_id != self.own_user_id && is_joined_or_invited(member)
+            })
+            .take(MAX_HEROES)
+            .map(|(user_id, _)| user_id.to_string())
+            .collect()
+    }
+
+    fn joined_or_invited_count(&self) -> u64 {
+        self.members.values().filter(|member| is_joined_or_invited(member)).count() as u64
+    }
+}
+
+/// Calculates the display name of a room, following [the specification][spec].
+///
+/// In order, this uses the room's `name`, its `canonical_alias`, or a name composed from the
+/// heroes of the room summary, like "Alice, Bob and 3 others". If the own user is alone in the
+/// room, the name is "Empty room", or "Empty room (was Alice)" if there are heroes.
+///
+/// Since the server only sends the summary fields that changed, `summary` should contain the
+/// latest value of every field. Missing fields are computed from `memberships`.
+///
+/// [spec]: https://matrix.org/docs/spec/client_server/r0.6.1#calculating-the-display-name-for-a-room
+pub fn room_display_name(
+    name: Option<&str>,
+    canonical_alias: Option<&RoomAliasId>,
+    summary: &RoomSummary,
+    memberships: &RoomMemberships,
+) -> String {
+    if let Some(name) = non_empty(name) {
+        return name.to_owned();
+    }
+
+    if let Some(alias) = canonical_alias {
+        return alias.to_string();
+    }
+
+    let heroes = if summary.heroes.is_empty() {
+        memberships.default_heroes()
+    } else {
+        summary.heroes.clone()
+    };
+    let hero_names: Vec<_> = heroes
+        .iter()
+        .map(|hero| match UserId::try_from(hero.as_str()) {
+            Ok(user_id) => memberships.display_name_for(&user_id),
+            Err(_) => hero.clone(),
+        })
+        .collect();
+
+    let member_count = match (summary.joined_member_count, summary.invited_member_count) {
+        (None, None) => memberships.joined_or_invited_count(),
+        (joined, invited) => joined.map_or(0, u64::from) + invited.map_or(0, u64::from),
+    };
+    // Heroes don't include the own user.
+    let other_count = member_count.saturating_sub(1);
+
+    if other_count == 0 {
+        return if hero_names.is_empty() {
+            "Empty room".to_owned()
+        } else {
+            format!("Empty room (was {})", join_names(&hero_names, 0))
+        };
+    }
+
+    let remaining = other_count.saturating_sub(hero_names.len() as u64);
+    join_names(&hero_names, remaining)
+}
+
+/// Joins names like "Alice, Bob and Charlie", or "Alice, Bob and 3 others" if there are
+/// `remaining` names that aren't listed.
+fn join_names(names: &[String], remaining: u64) -> String {
+    let mut parts = names.to_vec();
+    match remaining {
+        0 => {}
+        1 => parts.push("1 other".to_owned()),
+        _ => parts.push(format!("{} others", remaining)),
+    }
+
+    match parts.split_last() {
+        None => String::new(),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+fn is_joined_or_invited(member: &MemberEventContent) -> bool {
+    member.membership == MembershipState::Join || member.membership == MembershipState::Invite
+}
+
+fn non_empty(s: Option<&str>) -> Option<&str> {
+    s.filter(|s| !s.is_empty())
+}
@@ -0,0 +1,120 @@
+//! Accumulating the state and timeline of a room from successive sync responses.
+
+use std::collections::BTreeMap;
+
+use ruma_client_api::r0::sync::sync_events::{Response as SyncResponse, State, Timeline};
+use ruma_common::Raw;
+use ruma_events::{AnyStateEvent, AnySyncRoomEvent, EventType};
+use ruma_identifiers::RoomId;
+use serde::Deserialize;
+use serde_json::{
+    value::{to_raw_value as to_raw_json_value, RawValue as RawJsonValue},
+    Map as JsonObject, Value as JsonValue,
+};
+
+/// The current state and timeline of a room, built up from successive sync responses.
+///
+/// Feed every sync response to `apply_sync`, in the order they were received. For each response,
+/// the room's `state` section (the state right before the timeline) is applied first, then the
+/// timeline events are appended, with timeline state events updating the current state as well.
+///
+/// If a sync response has a limited (gappy) timeline, the events between the previously known
+/// timeline and the new one are missing, so the timeline is reset instead of appended to. The
+/// state stays correct, since the server sends the state changes of the gap in the `state`
+/// section. `prev_batch` can then be used to paginate backwards into the gap.
+///
+/// Events whose `type` or `state_key` can't be read are left out of the current state.
+#[derive(Clone, Debug)]
+pub struct RoomState {
+    room_id: RoomId,
+    state: BTreeMap<(EventType, String), Raw<AnyStateEvent>>,
+    timeline: Vec<Raw<AnySyncRoomEvent>>,
+    prev_batch: Option<String>,
+}
+
+impl RoomState {
+    /// Creates an empty `RoomState` for the room with the given ID.
+    pub fn new(room_id: RoomId) -> Self {
+        Self { room_id, state: BTreeMap::new(), timeline: Vec::new(), prev_batch: None }
+    }
+
+    /// The ID of the room.
+    pub fn room_id(&self) -> &RoomId {
+        &self.room_id
+    }
+
+    /// The current state of the room, keyed by event type and state key.
+    pub fn state(&self) -> &BTreeMap<(EventType, String), Raw<AnyStateEvent>> {
+        &self.state
+    }
+
+    /// The current state event with the given type and state key, if any.
+    pub fn state_event(
+        &self,
+        event_type: EventType,
+        state_key: &str,
+    ) -> Option<&Raw<AnyStateEvent>> {
+        self.state.get(&(event_type, state_key.to_owned()))
+    }
+
+    /// The timeline events received since the last gap, oldest first.
+    pub fn timeline(&self) -> &[Raw<AnySyncRoomEvent>] {
+        &self.timeline
+    }
+
+    /// The token to paginate backwards from the start of `timeline`, if the server sent one.
+    pub fn prev_batch(&self) -> Option<&str> {
+        self.prev_batch.as_deref()
+    }
+
+    /// Applies the updates to this room in the given sync response.
+    ///
+    /// Does nothing if the response doesn't contain the room.
+    pub fn apply_sync(&mut self, response: &SyncResponse) {
+        if let Some(room) = response.rooms.join.get(&self.room_id) {
+            self.apply(&room.state, &room.timeline);
+        } else if let Some(room) = response.rooms.leave.get(&self.room_id) {
+            self.apply(&room.state, &room.timeline);
+        }
+    }
+
+    fn apply(&mut self, state: &State, timeline: &Timeline) {
+        for event in &state.events {
+            self.update_state(event.json());
+        }
+
+        if timeline.limited || self.timeline.is_empty() {
+            self.timeline.clear();
+            self.prev_batch = timeline.prev_batch.clone();
+        }
+
+        for event in &timeline.events {
+            self.update_state(event.json());
+            self.timeline.push(event.clone());
+        }
+    }
+
+    fn update_state(&mut self, event: &RawJsonValue) {
+        #[derive(Deserialize)]
+        struct StateEventKey {
+            #[serde(rename = "type")]
+            event_type: EventType,
+            state_key: Option<String>,
+        }
+
+        let key = match serde_json::from_str::<StateEventKey>(event.get()) {
+            Ok(StateEventKey { event_type, state_key: Some(state_key) }) => (event_type, state_key),
+            _ => return,
+        };
+
+        let mut object = match serde_json::from_str::<JsonObject<String, JsonValue>>(event.get()) {
+            Ok(object) => object,
+            Err(_) => return,
+        };
+        object.insert("room_id".to_owned(), self.room_id.as_str().into());
+
+        if let Ok(json) = to_raw_json_value(&object) {
+            self.state.insert(key, Raw::from_json(json));
+        }
+    }
+}
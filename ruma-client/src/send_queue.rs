@@ -0,0 +1,318 @@
+//! A queue for sending message events in order, with retries.
+
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Debug, Formatter},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures_core::stream::Stream;
+use futures_timer::Delay;
+use futures_util::stream::{self, FuturesUnordered, StreamExt as _};
+use ruma_client_api::r0::message::send_message_event;
+use ruma_events::{EventType, MessageEventContent};
+use ruma_identifiers::{EventId, RoomId};
+use serde::{Deserialize, Serialize};
+use serde_json::value::{to_raw_value as to_raw_json_value, RawValue as RawJsonValue};
+
+use crate::{Client, Error, HttpClient};
+
+/// The longest delay between two attempts to send an event.
+const MAX_BACKOFF: Duration = Duration::from_secs(10 * 60);
+
+/// A message event waiting to be sent by a `SendQueue`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct QueuedEvent {
+    /// The transaction ID used for every attempt to send this event.
+    pub txn_id: String,
+
+    /// The room to send the event to.
+    pub room_id: RoomId,
+
+    /// The type of the event.
+    pub event_type: EventType,
+
+    /// The serialized content of the event.
+    pub content: Box<RawJsonValue>,
+}
+
+/// Storage for the events of a `SendQueue` that haven't been sent yet.
+///
+/// Implement this to persist pending events, e.g. in a database, so they are still sent after a
+/// restart. Restored events keep their transaction IDs, so the homeserver doesn't store an event
+/// twice if an earlier attempt went through without the client noticing.
+pub trait QueueStore: Send + Sync {
+    /// Adds an event to the end of the queue.
+    fn push(&self, event: QueuedEvent);
+
+    /// Returns all pending events, in the order they were pushed.
+    fn pending(&self) -> Vec<QueuedEvent>;
+
+    /// Removes the event with the given transaction ID.
+    fn remove(&self, txn_id: &str);
+}
+
+/// A `QueueStore` that keeps pending events in memory.
+#[derive(Debug, Default)]
+pub struct MemoryQueueStore {
+    events: Mutex<Vec<QueuedEvent>>,
+}
+
+impl QueueStore for MemoryQueueStore {
+    fn push(&self, event: QueuedEvent) {
+        self.events.lock().expect("queue store mutex was poisoned").push(event);
+    }
+
+    fn pending(&self) -> Vec<QueuedEvent> {
+        self.events.lock().expect("queue store mutex was poisoned").clone()
+    }
+
+    fn remove(&self, txn_id: &str) {
+        self.events.lock().expect("queue store mutex was poisoned").retain(|e| e.txn_id != txn_id);
+    }
+}
+
+/// The outcome of sending a queued event.
+#[derive(Debug)]
+pub enum SendResult {
+    /// The event was sent and removed from the queue.
+    Sent {
+        /// The transaction ID of the event.
+        txn_id: String,
+
+        /// The room the event was sent to.
+        room_id: RoomId,
+
+        /// The ID the homeserver assigned to the event.
+        event_id: EventId,
+    },
+
+    /// Sending the event failed on every attempt.
+    ///
+    /// The event stays in the queue, and later events for the same room are held back until the
+    /// next `flush`, so they aren't sent out of order.
+    Failed {
+        /// The transaction ID of the event.
+        txn_id: String,
+
+        /// The room the event was supposed to be sent to.
+        room_id: RoomId,
+
+        /// The error of the last attempt.
+        error: Error<ruma_client_api::Error>,
+    },
+}
+
+impl SendResult {
+    /// The room of the event.
+    fn room_id(&self) -> &RoomId {
+        match self {
+            Self::Sent { room_id, .. } | Self::Failed { room_id, .. } => room_id,
+        }
+    }
+}
+
+/// The state shared by all handles to the same queue.
+#[derive(Debug, Default)]
+pub(crate) struct QueueState<S> {
+    store: Arc<S>,
+    /// The rooms that one of the handles is currently sending an event to, so concurrent flushes
+    /// neither send the same event twice nor reorder the events of a room.
+    sending_rooms: Mutex<BTreeSet<RoomId>>,
+}
+
+/// A queue that sends message events in order, retrying failed attempts.
+///
+/// Events are added with `send` and sent by driving the stream returned by `flush`, e.g. in a
+/// task spawned on the async runtime of your choice. Events for the same room are sent one after
+/// another, in the order they were queued, while different rooms don't wait for each other.
+///
+/// Clones of a queue are handles to the same queue.
+pub struct SendQueue<C, S = MemoryQueueStore> {
+    client: Client<C>,
+    state: Arc<QueueState<S>>,
+    max_attempts: u32,
+    initial_backoff: Duration,
+}
+
+impl<C: HttpClient, S: QueueStore> SendQueue<C, S> {
+    /// Creates a send queue that keeps pending events in the given store.
+    ///
+    /// Events that are already in the store are sent by the next `flush`.
+    pub fn with_store(client: Client<C>, store: Arc<S>) -> Self {
+        Self::with_state(client, Arc::new(QueueState { store, sending_rooms: Mutex::default() }))
+    }
+
+    /// Creates a handle to the queue with the given state.
+    pub(crate) fn with_state(client: Client<C>, state: Arc<QueueState<S>>) -> Self {
+        Self { client, state, max_attempts: 5, initial_backoff: Duration::from_secs(1) }
+    }
+
+    /// Sets how often sending an event is attempted before giving up, and the delay before the
+    /// first retry. The delay doubles with every further retry.
+    ///
+    /// Defaults to 5 attempts, starting with a delay of one second.
+    pub fn with_retries(self, max_attempts: u32, initial_backoff: Duration) -> Self {
+        Self { max_attempts: max_attempts.max(1), initial_backoff, ..self }
+    }
+
+    /// Adds a message event to the end of the queue and returns its transaction ID.
+    ///
+    /// The event type is taken from the content.
+    pub fn send<T: MessageEventContent>(
+        &self,
+        room_id: &RoomId,
+        content: &T,
+    ) -> serde_json::Result<String> {
+        Ok(self.send_raw(room_id, content.event_type().into(), to_raw_json_value(content)?))
+    }
+
+    /// Adds a message event with arbitrary JSON content to the end of the queue and returns its
+    /// transaction ID.
+    pub fn send_raw(
+        &self,
+        room_id: &RoomId,
+        event_type: EventType,
+        content: Box<RawJsonValue>,
+    ) -> String {
+        let txn_id = self.client.next_txn_id();
+        self.state.store.push(QueuedEvent {
+            txn_id: txn_id.clone(),
+            room_id: room_id.clone(),
+            event_type,
+            content,
+        });
+
+        txn_id
+    }
+
+    /// Sends the queued events, yielding the outcome for each of them.
+    ///
+    /// Events for different rooms are sent concurrently, so retries for one room don't hold back
+    /// the others. The stream ends once the queue is empty, apart from events held back because
+    /// of an earlier failure and events of rooms another `flush` is currently sending to. Events
+    /// queued while the stream is running are sent by it as well.
+    pub fn flush(&self) -> impl Stream<Item = SendResult> + '_ {
+        let claims =
+            RoomClaims { sending_rooms: &self.state.sending_rooms, rooms: BTreeSet::new() };
+
+        stream::unfold(
+            (claims, BTreeSet::new(), FuturesUnordered::new()),
+            move |(mut claims, mut failed_rooms, mut in_flight)| async move {
+                // Start sending the next event of every room that isn't busy or held back.
+                for event in self.state.store.pending() {
+                    if !failed_rooms.contains(&event.room_id) && claims.claim(&event.room_id) {
+                        in_flight.push(self.send_with_retries(event));
+                    }
+                }
+
+                let result = in_flight.next().await?;
+                if let SendResult::Failed { room_id, .. } = &result {
+                    failed_rooms.insert(room_id.clone());
+                }
+                claims.release(result.room_id());
+
+                Some((result, (claims, failed_rooms, in_flight)))
+            },
+        )
+    }
+
+    async fn send_with_retries(&self, event: QueuedEvent) -> SendResult {
+        let mut attempt = 1;
+
+        loop {
+            let request = send_message_event::Request::new_raw(
+                &event.room_id,
+                event.event_type.clone(),
+                &event.txn_id,
+                event.content.clone(),
+            );
+
+            match self.client.request(request).await {
+                Ok(response) => {
+                    self.state.store.remove(&event.txn_id);
+
+                    return SendResult::Sent {
+                        txn_id: event.txn_id,
+                        room_id: event.room_id,
+                        event_id: response.event_id,
+                    };
+                }
+                Err(error) if attempt >= self.max_attempts => {
+                    return SendResult::Failed {
+                        txn_id: event.txn_id,
+                        room_id: event.room_id,
+                        error,
+                    };
+                }
+                Err(_) => {
+                    let backoff = self
+                        .initial_backoff
+                        .checked_mul(2u32.saturating_pow(attempt - 1))
+                        .map_or(MAX_BACKOFF, |backoff| backoff.min(MAX_BACKOFF));
+                    Delay::new(backoff).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+impl<C, S> Clone for SendQueue<C, S> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            state: self.state.clone(),
+            max_attempts: self.max_attempts,
+            initial_backoff: self.initial_backoff,
+        }
+    }
+}
+
+impl<C: Debug, S> Debug for SendQueue<C, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendQueue")
+            .field("client", &self.client)
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .finish()
+    }
+}
+
+/// The rooms a `flush` is currently sending an event to.
+///
+/// The claims are released when the stream is dropped, so an abandoned `flush` doesn't block its
+/// rooms for good.
+struct RoomClaims<'a> {
+    sending_rooms: &'a Mutex<BTreeSet<RoomId>>,
+    rooms: BTreeSet<RoomId>,
+}
+
+impl RoomClaims<'_> {
+    /// Claims the given room, unless this or another `flush` is already sending an event to it.
+    fn claim(&mut self, room_id: &RoomId) -> bool {
+        let mut sending_rooms = self.sending_rooms.lock().expect("queue mutex was poisoned");
+        if !sending_rooms.insert(room_id.clone()) {
+            return false;
+        }
+
+        self.rooms.insert(room_id.clone());
+        true
+    }
+
+    fn release(&mut self, room_id: &RoomId) {
+        self.rooms.remove(room_id);
+        self.sending_rooms.lock().expect("queue mutex was poisoned").remove(room_id);
+    }
+}
+
+impl Drop for RoomClaims<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut sending_rooms) = self.sending_rooms.lock() {
+            for room_id in &self.rooms {
+                sending_rooms.remove(room_id);
+            }
+        }
+    }
+}
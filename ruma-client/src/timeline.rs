@@ -0,0 +1,188 @@
+//! Tracking the timeline of a room across sync responses, including the gaps left by limited
+//! timelines.
+
+use std::collections::BTreeSet;
+
+use ruma_client_api::r0::{
+    message::get_message_events::{self, Direction},
+    sync::sync_events::{Response as SyncResponse, Timeline as SyncTimeline},
+};
+use ruma_common::Raw;
+use ruma_events::AnySyncRoomEvent;
+use ruma_identifiers::{EventId, RoomId};
+use serde::Deserialize;
+use serde_json::value::RawValue as RawJsonValue;
+
+use crate::{Client, Error, HttpClient};
+
+/// The timeline of a room, built up from successive sync responses.
+///
+/// Feed every sync response to `apply_sync`, in the order they were received. If a sync response
+/// has a limited (gappy) timeline, the events between the previously known timeline and the new
+/// one are missing. Unlike `RoomState`, which starts over in that case, `Timeline` keeps the
+/// events it already has and records the gap in `missing_ranges`, so it can be filled later with
+/// `backfill`.
+///
+/// Events are deduplicated by event ID. Events whose `event_id` can't be read are always kept.
+#[derive(Clone, Debug)]
+pub struct Timeline {
+    room_id: RoomId,
+    events: Vec<Raw<AnySyncRoomEvent>>,
+    event_ids: BTreeSet<EventId>,
+    missing_ranges: Vec<MissingRange>,
+    prev_batch: Option<String>,
+}
+
+/// A range of events missing from a `Timeline`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MissingRange {
+    /// The position in `Timeline::events` right before which events are missing.
+    pub index: usize,
+
+    /// The token to paginate backwards into the gap from.
+    pub from: String,
+}
+
+impl Timeline {
+    /// Creates an empty `Timeline` for the room with the given ID.
+    pub fn new(room_id: RoomId) -> Self {
+        Self {
+            room_id,
+            events: Vec::new(),
+            event_ids: BTreeSet::new(),
+            missing_ranges: Vec::new(),
+            prev_batch: None,
+        }
+    }
+
+    /// The ID of the room.
+    pub fn room_id(&self) -> &RoomId {
+        &self.room_id
+    }
+
+    /// The known timeline events, oldest first.
+    pub fn events(&self) -> &[Raw<AnySyncRoomEvent>] {
+        &self.events
+    }
+
+    /// The gaps in the timeline, ordered by their position in `events`.
+    pub fn missing_ranges(&self) -> &[MissingRange] {
+        &self.missing_ranges
+    }
+
+    /// The token to paginate backwards from the start of `events` into older history, if the
+    /// server sent one.
+    pub fn prev_batch(&self) -> Option<&str> {
+        self.prev_batch.as_deref()
+    }
+
+    /// Applies the timeline updates to this room in the given sync response.
+    ///
+    /// Does nothing if the response doesn't contain the room.
+    pub fn apply_sync(&mut self, response: &SyncResponse) {
+        if let Some(room) = response.rooms.join.get(&self.room_id) {
+            self.apply_timeline(&room.timeline);
+        } else if let Some(room) = response.rooms.leave.get(&self.room_id) {
+            self.apply_timeline(&room.timeline);
+        }
+    }
+
+    /// Applies the timeline section of a single room from a sync response.
+    ///
+    /// A limited timeline without a `prev_batch` token leaves a gap that can't be backfilled, so
+    /// it isn't recorded.
+    pub fn apply_timeline(&mut self, timeline: &SyncTimeline) {
+        if self.events.is_empty() {
+            self.prev_batch = timeline.prev_batch.clone();
+        } else if let (true, Some(from)) = (timeline.limited, &timeline.prev_batch) {
+            self.missing_ranges.push(MissingRange { index: self.events.len(), from: from.clone() });
+        }
+
+        for event in &timeline.events {
+            if let Some(id) = event_id(event.json()) {
+                if !self.event_ids.insert(id) {
+                    continue;
+                }
+            }
+
+            self.events.push(event.clone());
+        }
+    }
+
+    /// Fills the most recent gap in the timeline by paginating backwards through the
+    /// `/rooms/{roomId}/messages` endpoint, requesting at most `max_pages` pages.
+    ///
+    /// Pagination stops when an event from before the gap is reached, or when the server has no
+    /// older events. Returns `true` if the gap was closed, and `false` if there was no gap or the
+    /// page limit was hit. In the latter case the gap is kept, with a token to continue from.
+    pub async fn backfill<C: HttpClient>(
+        &mut self,
+        client: &Client<C>,
+        max_pages: usize,
+    ) -> Result<bool, Error<ruma_client_api::Error>> {
+        let (index, mut from) = match self.missing_ranges.last() {
+            Some(range) => (range.index, range.from.clone()),
+            None => return Ok(false),
+        };
+
+        for _ in 0..max_pages {
+            let response = client
+                .request(get_message_events::Request::new(
+                    self.room_id.clone(),
+                    from,
+                    Direction::Backward,
+                ))
+                .await?;
+
+            let no_more_events = response.chunk.is_empty();
+            let mut reached_known_event = false;
+
+            // The chunk is ordered newest first, so every event is inserted before the previous one.
+            for event in response.chunk {
+                let event = Raw::<AnySyncRoomEvent>::from_json(event.into_json());
+
+                if let Some(id) = event_id(event.json()) {
+                    if !self.event_ids.insert(id.clone()) {
+                        // A known event from before the gap means the gap is closed, other known
+                        // events are duplicates.
+                        if self.events[..index]
+                            .iter()
+                            .any(|known| event_id(known.json()).as_ref() == Some(&id))
+                        {
+                            reached_known_event = true;
+                            break;
+                        }
+
+                        continue;
+                    }
+                }
+
+                self.events.insert(index, event);
+            }
+
+            from = match response.end {
+                Some(end) if !no_more_events && !reached_known_event => end,
+                _ => {
+                    self.missing_ranges.pop();
+                    return Ok(true);
+                }
+            };
+
+            if let Some(range) = self.missing_ranges.last_mut() {
+                range.from = from.clone();
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+/// Reads the event ID of the given event JSON, if it has a valid one.
+fn event_id(event: &RawJsonValue) -> Option<EventId> {
+    #[derive(Deserialize)]
+    struct EventIdHelper {
+        event_id: EventId,
+    }
+
+    serde_json::from_str::<EventIdHelper>(event.get()).ok().map(|helper| helper.event_id)
+}
@@ -0,0 +1,107 @@
+//! Test doubles shared by the integration tests of `ruma-client`.
+
+// Every test crate includes this module, but none of them uses all of it.
+#![allow(dead_code)]
+
+use std::{
+    fmt::{self, Debug, Display, Formatter},
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use http::{Request as HttpRequest, Response as HttpResponse, StatusCode, Uri};
+use ruma_client::{Client, HttpClient, Session};
+use serde_json::Value as JsonValue;
+
+/// The error of a request that didn't reach the homeserver.
+#[derive(Debug)]
+pub struct Offline;
+
+impl Display for Offline {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("offline")
+    }
+}
+
+impl std::error::Error for Offline {}
+
+/// A request received by a `MockBackend`.
+#[derive(Clone, Debug)]
+pub struct RecordedRequest {
+    pub uri: Uri,
+    pub body: Vec<u8>,
+}
+
+type Handler =
+    dyn Fn(&HttpRequest<Vec<u8>>) -> Result<HttpResponse<Vec<u8>>, Offline> + Send + Sync;
+
+/// An HTTP backend that records every request and answers it with a handler.
+pub struct MockBackend {
+    handler: Box<Handler>,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockBackend {
+    /// Creates a backend that answers every request with the given handler.
+    pub fn new(
+        handler: impl Fn(&HttpRequest<Vec<u8>>) -> Result<HttpResponse<Vec<u8>>, Offline>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        Self { handler: Box::new(handler), requests: Arc::default() }
+    }
+
+    /// Creates a backend that answers every request with the given JSON body.
+    pub fn json(body: JsonValue) -> Self {
+        Self::new(move |_| Ok(json_response(StatusCode::OK, &body)))
+    }
+
+    /// Returns a handle to the requests received by this backend, which can still be used after
+    /// the backend was moved into a `Client`.
+    pub fn requests(&self) -> Arc<Mutex<Vec<RecordedRequest>>> {
+        self.requests.clone()
+    }
+}
+
+impl Debug for MockBackend {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockBackend").field("requests", &self.requests).finish()
+    }
+}
+
+#[async_trait]
+impl HttpClient for MockBackend {
+    type RequestBody = Vec<u8>;
+    type ResponseBody = Vec<u8>;
+    type Error = Offline;
+
+    async fn send(
+        &self,
+        request: HttpRequest<Vec<u8>>,
+    ) -> Result<HttpResponse<Vec<u8>>, Self::Error> {
+        self.requests
+            .lock()
+            .unwrap()
+            .push(RecordedRequest { uri: request.uri().clone(), body: request.body().clone() });
+
+        (self.handler)(&request)
+    }
+}
+
+/// Creates a response with the given status code and JSON body.
+pub fn json_response(status: StatusCode, body: &JsonValue) -> HttpResponse<Vec<u8>> {
+    let mut response = HttpResponse::new(serde_json::to_vec(body).unwrap());
+    *response.status_mut() = status;
+    response
+}
+
+/// A session with the access token `secret_token`.
+pub fn session() -> Session {
+    Session { access_token: "secret_token".into(), identification: None }
+}
+
+/// Creates a client for `https://example.com` that sends its requests through the given backend.
+pub fn client(backend: MockBackend, session: Option<Session>) -> Client<MockBackend> {
+    Client::with_http_client(backend, "https://example.com".parse().unwrap(), session)
+}
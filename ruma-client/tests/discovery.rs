@@ -0,0 +1,90 @@
+use http::StatusCode;
+use matches::assert_matches;
+use ruma::{api::client::r0::alias::get_alias, room_alias_id, room_id, user_id};
+use ruma_client::{Client, DiscoveryError};
+use serde_json::{json, Value as JsonValue};
+
+mod common;
+
+use common::{json_response, session, MockBackend};
+
+/// A backend that serves the given well-known response for `example.com`, and answers
+/// `get_alias` requests sent to `homeserver_host`.
+fn well_known_backend(
+    status: StatusCode,
+    well_known: JsonValue,
+    homeserver_host: &'static str,
+) -> MockBackend {
+    MockBackend::new(move |request| {
+        let uri = request.uri();
+        if uri.path() == "/.well-known/matrix/client" {
+            assert_eq!(uri.host(), Some("example.com"));
+            Ok(json_response(status, &well_known))
+        } else {
+            assert_eq!(uri.host(), Some(homeserver_host));
+            Ok(json_response(
+                StatusCode::OK,
+                &json!({ "room_id": "!n8f893n9:example.com", "servers": [] }),
+            ))
+        }
+    })
+}
+
+/// A backend that serves the given well-known response and answers `get_alias` requests sent to
+/// `matrix.example.com`.
+fn backend(well_known: JsonValue) -> MockBackend {
+    well_known_backend(StatusCode::OK, well_known, "matrix.example.com")
+}
+
+async fn get_alias_through(client: &Client<MockBackend>) {
+    let response = client
+        .request(get_alias::Request::new(&room_alias_id!("#example_room:example.com")))
+        .await
+        .unwrap();
+    assert_eq!(response.room_id, room_id!("!n8f893n9:example.com"));
+}
+
+#[tokio::test]
+async fn discovered_base_url_is_used() {
+    let backend = backend(json!({
+        "m.homeserver": { "base_url": "https://matrix.example.com/" },
+        "m.identity_server": { "base_url": "https://identity.example.com" }
+    }));
+
+    let client = Client::with_discovery(backend, &user_id!("@alice:example.com"), Some(session()))
+        .await
+        .unwrap();
+    get_alias_through(&client).await;
+}
+
+#[tokio::test]
+async fn missing_well_known_falls_back_to_server_name() {
+    let backend = well_known_backend(
+        StatusCode::NOT_FOUND,
+        json!({ "errcode": "M_NOT_FOUND", "error": "Not found" }),
+        "example.com",
+    );
+
+    let client = Client::with_discovery(backend, &user_id!("@alice:example.com"), Some(session()))
+        .await
+        .unwrap();
+    get_alias_through(&client).await;
+}
+
+#[tokio::test]
+async fn malformed_well_known_is_fail_prompt() {
+    let backend = backend(json!({ "m.homeserver": {} }));
+
+    let result = Client::with_discovery(backend, &user_id!("@alice:example.com"), None).await;
+    assert_matches!(result, Err(DiscoveryError::FailPrompt(_)));
+}
+
+#[tokio::test]
+async fn invalid_base_url_is_fail_error() {
+    for base_url in &["http://matrix.example.com", "not a url", "/relative/path"] {
+        let backend = backend(json!({ "m.homeserver": { "base_url": base_url } }));
+
+        let result = Client::with_discovery(backend, &user_id!("@alice:example.com"), None).await;
+        assert_matches!(result, Err(DiscoveryError::FailError(url)) if url == *base_url);
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use http::{header::CONTENT_TYPE, Request as HttpRequest, Response as HttpResponse, StatusCode};
+use ruma::{
+    api::client::r0::{
+        alias::get_alias,
+        media::{create_content, get_content},
+    },
+    room_alias_id, room_id, server_name,
+};
+use ruma_client::{Client, HttpClient};
+use serde_json::json;
+
+mod common;
+
+use common::{client, json_response, session, MockBackend, Offline};
+
+/// A media repository that, like the `hyper` backend, sends `BytesMut` and receives `Bytes`
+/// bodies.
+#[derive(Debug, Default)]
+struct BytesBackend {
+    file: Mutex<Bytes>,
+}
+
+#[async_trait]
+impl HttpClient for BytesBackend {
+    type RequestBody = BytesMut;
+    type ResponseBody = Bytes;
+    type Error = Offline;
+
+    async fn send(&self, request: HttpRequest<BytesMut>) -> Result<HttpResponse<Bytes>, Offline> {
+        let mut file = self.file.lock().unwrap();
+
+        let response = if request.uri().path().starts_with("/_matrix/media/r0/upload") {
+            *file = request.into_body().freeze();
+            HttpResponse::new(r#"{ "content_uri": "mxc://example.com/media" }"#.into())
+        } else {
+            let mut response = HttpResponse::new(file.clone());
+            response.headers_mut().insert(CONTENT_TYPE, "image/png".parse().unwrap());
+            response
+        };
+
+        Ok(response)
+    }
+}
+
+#[tokio::test]
+async fn in_memory_backend_get_alias() {
+    let backend = MockBackend::new(|request| {
+        assert_eq!(
+            request.uri().path(),
+            "/_matrix/client/r0/directory/room/%23example%5Froom%3Aexample%2Ecom"
+        );
+
+        let body = json!({
+            "room_id": "!n8f893n9:example.com",
+            "servers": ["example.com"],
+        });
+        Ok(json_response(StatusCode::OK, &body))
+    });
+    let client = client(backend, Some(session()));
+
+    let response = client
+        .request(get_alias::Request::new(&room_alias_id!("#example_room:example.com")))
+        .await
+        .unwrap();
+
+    assert_eq!(response.room_id, room_id!("!n8f893n9:example.com"));
+    assert_eq!(response.servers, vec!["example.com".to_owned()]);
+}
+
+#[tokio::test]
+async fn media_with_bytes_bodies() {
+    let client = Client::with_http_client(
+        BytesBackend::default(),
+        "https://example.com".parse().unwrap(),
+        Some(session()),
+    );
+    let file = vec![0xff; 1024];
+
+    let response = client.request(create_content::Request::new(file.clone())).await.unwrap();
+    assert_eq!(response.content_uri.as_str(), "mxc://example.com/media");
+
+    let response = client
+        .request(get_content::Request::new("media".into(), server_name!("example.com")))
+        .await
+        .unwrap();
+    assert_eq!(response.file, file);
+    assert_eq!(response.content_type, "image/png");
+}
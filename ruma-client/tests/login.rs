@@ -0,0 +1,63 @@
+use http::StatusCode;
+use ruma::user_id;
+use serde_json::{json, Value as JsonValue};
+
+mod common;
+
+use common::{client, json_response, MockBackend};
+
+/// A backend that expects a login request with the given body and logs the user in.
+fn login_backend(expected_body: JsonValue) -> MockBackend {
+    MockBackend::new(move |request| {
+        assert_eq!(request.uri().path(), "/_matrix/client/r0/login");
+        assert_eq!(serde_json::from_slice::<JsonValue>(request.body()).unwrap(), expected_body);
+
+        let body = json!({
+            "user_id": "@alice:example.com",
+            "access_token": "abc123",
+            "device_id": "GHTYAJCE"
+        });
+        Ok(json_response(StatusCode::OK, &body))
+    })
+}
+
+#[tokio::test]
+async fn log_in_with_password() {
+    let backend = login_backend(json!({
+        "identifier": { "type": "m.id.user", "user": "alice" },
+        "type": "m.login.password",
+        "password": "secret"
+    }));
+    let client = client(backend, None);
+
+    let session = client.log_in("alice".into(), "secret".into(), None, None).await.unwrap();
+    assert_eq!(session.access_token, "abc123");
+    assert_eq!(client.session(), Some(session));
+}
+
+#[tokio::test]
+async fn log_in_with_token() {
+    let backend = login_backend(json!({
+        "type": "m.login.token",
+        "token": "login_token",
+        "initial_device_display_name": "Laptop"
+    }));
+    let client = client(backend, None);
+
+    let session =
+        client.log_in_with_token("login_token".into(), None, Some("Laptop".into())).await.unwrap();
+    assert_eq!(session.identification.unwrap().user_id, user_id!("@alice:example.com"));
+    assert!(client.session().is_some());
+}
+
+#[test]
+fn sso_login_url() {
+    let backend = login_backend(JsonValue::Null);
+    let client = client(backend, None);
+
+    assert_eq!(
+        client.sso_login_url("https://app.example.org/sso".into()).unwrap().to_string(),
+        "https://example.com/_matrix/client/r0/login/sso/redirect\
+         ?redirectUrl=https%3A%2F%2Fapp.example.org%2Fsso"
+    );
+}
@@ -0,0 +1,28 @@
+use http::{header::AUTHORIZATION, StatusCode};
+use matches::assert_matches;
+use ruma_client::Error;
+use serde_json::json;
+
+mod common;
+
+use common::{client, json_response, session, MockBackend};
+
+/// A backend that expects a logout request for the `secret_token` access token.
+fn logout_backend() -> MockBackend {
+    MockBackend::new(|request| {
+        assert_eq!(request.uri().path(), "/_matrix/client/r0/logout");
+        assert_eq!(request.headers()[AUTHORIZATION], "Bearer secret_token");
+
+        Ok(json_response(StatusCode::OK, &json!({})))
+    })
+}
+
+#[tokio::test]
+async fn log_out_clears_session() {
+    let client = client(logout_backend(), Some(session()));
+
+    client.log_out().await.unwrap();
+    assert_eq!(client.session(), None);
+
+    assert_matches!(client.log_out().await, Err(Error::AuthenticationRequired));
+}
@@ -0,0 +1,89 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+use http::{header::AUTHORIZATION, Method, StatusCode};
+use ruma::{
+    api::client::r0::{account::whoami, alias::get_alias},
+    room_alias_id,
+};
+use ruma_client::RequestInfo;
+use serde_json::json;
+
+mod common;
+
+use common::{client, session, MockBackend};
+
+/// A backend that answers every request with a body that is a valid `get_alias` and `whoami`
+/// response.
+fn backend() -> MockBackend {
+    MockBackend::json(json!({
+        "room_id": "!n8f893n9:example.com",
+        "servers": ["example.com"],
+        "user_id": "@alice:example.com",
+    }))
+}
+
+#[tokio::test]
+async fn observer_invoked_once_per_request() {
+    let seen: Arc<Mutex<Vec<RequestInfo>>> = Arc::default();
+    let client = client(backend(), Some(session())).with_observer({
+        let seen = seen.clone();
+        move |info| seen.lock().unwrap().push(info.clone())
+    });
+
+    client.request(whoami::Request::new()).await.unwrap();
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 1);
+
+    let info = &seen[0];
+    assert_eq!(info.method, Method::GET);
+    assert_eq!(info.path, "/_matrix/client/r0/account/whoami");
+    assert_eq!(info.status, Some(StatusCode::OK));
+    assert_eq!(info.headers[AUTHORIZATION], "<redacted>");
+    assert!(info.body.is_none());
+    assert!(info.response_body.is_none());
+}
+
+#[tokio::test]
+async fn body_observer_includes_bodies() {
+    let seen: Arc<Mutex<Vec<RequestInfo>>> = Arc::default();
+    let client = client(backend(), Some(session())).with_body_observer({
+        let seen = seen.clone();
+        move |info| seen.lock().unwrap().push(info.clone())
+    });
+
+    client
+        .request(get_alias::Request::new(&room_alias_id!("#example_room:example.com")))
+        .await
+        .unwrap();
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 1);
+    assert!(seen[0].body.is_some());
+    assert!(seen[0].response_body.as_deref().unwrap().starts_with(b"{"));
+}
+
+#[tokio::test]
+async fn observer_can_replace_itself() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let client = client(backend(), Some(session()));
+    client.clone().with_observer({
+        let client = client.clone();
+        let calls = calls.clone();
+        move |_| {
+            let calls = calls.clone();
+            client.clone().with_observer(move |_| {
+                calls.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+    });
+
+    client.request(whoami::Request::new()).await.unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+    client.request(whoami::Request::new()).await.unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,149 @@
+use std::convert::TryFrom;
+
+use ruma::{
+    api::client::r0::sync::sync_events::RoomSummary, events::AnySyncStateEvent, room_alias_id,
+    user_id, Raw, UserId,
+};
+use ruma_client::{room_display_name, RoomMemberships};
+use serde_json::{from_value as from_json_value, json, Value as JsonValue};
+
+fn member_event(user_id: &str, membership: &str, displayname: Option<&str>) -> JsonValue {
+    json!({
+        "content": { "displayname": displayname, "membership": membership },
+        "event_id": "$h29iv0s8:example.org",
+        "origin_server_ts": 1,
+        "sender": user_id,
+        "state_key": user_id,
+        "type": "m.room.member"
+    })
+}
+
+/// Memberships of a room as seen by `@me:example.org`.
+fn memberships(members: &[(&str, &str, Option<&str>)]) -> RoomMemberships {
+    let mut memberships = RoomMemberships::new(user_id!("@me:example.org"));
+    memberships.apply_raw(
+        &from_json_value::<Raw<AnySyncStateEvent>>(member_event("@me:example.org", "join", None))
+            .unwrap(),
+    );
+
+    for (user_id, membership, displayname) in members {
+        let event = member_event(user_id, membership, *displayname);
+        memberships.apply_raw(&from_json_value::<Raw<AnySyncStateEvent>>(event).unwrap());
+    }
+
+    memberships
+}
+
+fn summary(heroes: &[&str], joined: u32, invited: u32) -> RoomSummary {
+    from_json_value(json!({
+        "m.heroes": heroes,
+        "m.joined_member_count": joined,
+        "m.invited_member_count": invited
+    }))
+    .unwrap()
+}
+
+#[test]
+fn display_name_disambiguation() {
+    let memberships = memberships(&[
+        ("@alice:example.org", "join", Some("Alice")),
+        ("@bob:example.org", "join", Some("Bob")),
+        ("@bob:example.com", "invite", Some("Bob")),
+        ("@carl:example.org", "join", None),
+        // Members that left don't make display names ambiguous.
+        ("@alice:example.com", "leave", Some("Alice")),
+    ]);
+
+    let display_name =
+        |user_id: &str| memberships.display_name_for(&UserId::try_from(user_id).unwrap());
+
+    assert_eq!(display_name("@alice:example.org"), "Alice");
+    assert_eq!(display_name("@bob:example.org"), "Bob (@bob:example.org)");
+    assert_eq!(display_name("@bob:example.com"), "Bob (@bob:example.com)");
+    assert_eq!(display_name("@carl:example.org"), "@carl:example.org");
+    assert_eq!(display_name("@unknown:example.org"), "@unknown:example.org");
+}
+
+#[test]
+fn later_member_events_replace_earlier_ones() {
+    let mut memberships = memberships(&[("@alice:example.org", "join", Some("Alice"))]);
+    memberships.apply_raw(
+        &from_json_value::<Raw<AnySyncStateEvent>>(member_event(
+            "@alice:example.org",
+            "join",
+            Some("Alice Margatroid"),
+        ))
+        .unwrap(),
+    );
+
+    assert_eq!(memberships.display_name_for(&user_id!("@alice:example.org")), "Alice Margatroid");
+}
+
+#[test]
+fn room_display_names() {
+    let alias = room_alias_id!("#room:example.org");
+    let members = memberships(&[
+        ("@alice:example.org", "join", Some("Alice")),
+        ("@bob:example.org", "join", Some("Bob")),
+        ("@carl:example.org", "invite", Some("Carl")),
+        ("@bob:example.com", "join", Some("Bob")),
+    ]);
+    let alone = memberships(&[("@alice:example.org", "leave", Some("Alice"))]);
+
+    let cases: &[(Option<&str>, Option<&_>, RoomSummary, &RoomMemberships, &str)] = &[
+        // The name takes precedence over everything else.
+        (Some("The room"), Some(&alias), summary(&[], 2, 0), &members, "The room"),
+        // An empty name is ignored.
+        (Some(""), Some(&alias), summary(&[], 2, 0), &members, "#room:example.org"),
+        // All other members are heroes.
+        (None, None, summary(&["@alice:example.org"], 2, 0), &members, "Alice"),
+        (
+            None,
+            None,
+            summary(&["@alice:example.org", "@carl:example.org"], 2, 1),
+            &members,
+            "Alice and Carl",
+        ),
+        // Heroes with the same display name are disambiguated.
+        (
+            None,
+            None,
+            summary(&["@alice:example.org", "@bob:example.org", "@bob:example.com"], 4, 0),
+            &members,
+            "Alice, Bob (@bob:example.org) and Bob (@bob:example.com)",
+        ),
+        // There are more members than heroes.
+        (None, None, summary(&["@alice:example.org"], 3, 0), &members, "Alice and 1 other"),
+        (
+            None,
+            None,
+            summary(&["@alice:example.org", "@carl:example.org"], 10, 3),
+            &members,
+            "Alice, Carl and 10 others",
+        ),
+        // Heroes whose member event isn't loaded yet are shown by user ID.
+        (None, None, summary(&["@dan:example.org"], 2, 0), &members, "@dan:example.org"),
+        // The own user is alone.
+        (None, None, summary(&[], 1, 0), &alone, "Empty room"),
+        (None, None, summary(&["@alice:example.org"], 1, 0), &alone, "Empty room (was Alice)"),
+        // Without a summary, heroes and counts are computed from the known members.
+        (
+            None,
+            None,
+            RoomSummary::default(),
+            &members,
+            "Alice, Bob (@bob:example.com), Bob (@bob:example.org) and Carl",
+        ),
+    ];
+
+    for (name, canonical_alias, summary, memberships, expected) in cases {
+        assert_eq!(
+            room_display_name(*name, *canonical_alias, summary, memberships),
+            *expected,
+            "name: {:?}, canonical alias: {:?}, summary: {:?}",
+            name,
+            canonical_alias,
+            summary
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use ruma::{
+    api::client::r0::sync::sync_events::Response as SyncResponse,
+    events::{AnyStateEvent, EventType},
+    room_id,
+};
+use ruma_api::IncomingResponse as _;
+use ruma_client::RoomState;
+use serde_json::{json, Value as JsonValue};
+
+fn name_event(event_id: &str, name: &str) -> JsonValue {
+    json!({
+        "content": { "name": name },
+        "event_id": event_id,
+        "origin_server_ts": 1,
+        "sender": "@alice:example.org",
+        "state_key": "",
+        "type": "m.room.name"
+    })
+}
+
+fn message_event(event_id: &str) -> JsonValue {
+    json!({
+        "content": { "body": "Hello", "msgtype": "m.text" },
+        "event_id": event_id,
+        "origin_server_ts": 1,
+        "sender": "@alice:example.org",
+        "type": "m.room.message"
+    })
+}
+
+fn sync_response(state: Vec<JsonValue>, timeline: Vec<JsonValue>, limited: bool) -> SyncResponse {
+    let body = json!({
+        "next_batch": "s1",
+        "rooms": {
+            "join": {
+                "!room:example.org": {
+                    "state": { "events": state },
+                    "timeline": { "events": timeline, "limited": limited, "prev_batch": "p1" }
+                }
+            }
+        }
+    });
+
+    SyncResponse::try_from_http_response(http::Response::new(serde_json::to_vec(&body).unwrap()))
+        .unwrap()
+}
+
+fn room_name(room: &RoomState) -> String {
+    match room.state_event(EventType::RoomName, "").unwrap().deserialize().unwrap() {
+        AnyStateEvent::RoomName(event) => event.content.name().unwrap().to_owned(),
+        event => panic!("unexpected event: {:?}", event),
+    }
+}
+
+#[test]
+fn fold_two_syncs_with_name_change() {
+    let mut room = RoomState::new(room_id!("!room:example.org"));
+
+    room.apply_sync(&sync_response(
+        vec![name_event("$name1:example.org", "First")],
+        vec![message_event("$msg1:example.org")],
+        false,
+    ));
+    assert_eq!(room_name(&room), "First");
+    assert_eq!(room.timeline().len(), 1);
+
+    room.apply_sync(&sync_response(
+        vec![],
+        vec![name_event("$name2:example.org", "Second"), message_event("$msg2:example.org")],
+        false,
+    ));
+    assert_eq!(room_name(&room), "Second");
+    assert_eq!(room.timeline().len(), 3);
+    assert_eq!(room.state().len(), 1);
+}
+
+#[test]
+fn limited_sync_resets_timeline() {
+    let mut room = RoomState::new(room_id!("!room:example.org"));
+
+    room.apply_sync(&sync_response(vec![], vec![message_event("$msg1:example.org")], false));
+    room.apply_sync(&sync_response(
+        vec![name_event("$name1:example.org", "During the gap")],
+        vec![message_event("$msg2:example.org")],
+        true,
+    ));
+
+    assert_eq!(room_name(&room), "During the gap");
+    assert_eq!(room.timeline().len(), 1);
+    assert_eq!(room.prev_batch(), Some("p1"));
+}
+
+#[test]
+fn other_rooms_are_ignored() {
+    let mut room = RoomState::new(room_id!("!other:example.org"));
+    room.apply_sync(&sync_response(vec![name_event("$name1:example.org", "First")], vec![], false));
+
+    assert!(room.state().is_empty());
+    assert!(room.timeline().is_empty());
+}
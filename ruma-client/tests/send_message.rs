@@ -0,0 +1,58 @@
+use ruma::{
+    events::{
+        room::message::{MessageEventContent, TextMessageEventContent},
+        EventType,
+    },
+    room_id,
+};
+use serde_json::{json, value::to_raw_value as to_raw_json_value};
+
+mod common;
+
+use common::{client, session, MockBackend};
+
+/// A backend that answers every request with a `send_message_event` response.
+fn backend() -> MockBackend {
+    MockBackend::json(json!({ "event_id": "$event:example.com" }))
+}
+
+#[tokio::test]
+async fn consecutive_sends_use_distinct_txn_ids() {
+    let backend = backend();
+    let requests = backend.requests();
+    let client = client(backend, Some(session()));
+
+    let room_id = room_id!("!n8f893n9:example.com");
+    let content = MessageEventContent::Text(TextMessageEventContent::plain("Hello World!"));
+    client.send_message(&room_id, &content).await.unwrap();
+    client.send_message(&room_id, &content).await.unwrap();
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+
+    let prefix = "/_matrix/client/r0/rooms/%21n8f893n9%3Aexample%2Ecom/send/m%2Eroom%2Emessage/";
+    let txn_ids: Vec<_> =
+        requests.iter().map(|request| request.uri.path().strip_prefix(prefix).unwrap()).collect();
+    assert_ne!(txn_ids[0], txn_ids[1]);
+}
+
+#[tokio::test]
+async fn send_raw_message_event() {
+    let backend = backend();
+    let requests = backend.requests();
+    let client = client(backend, Some(session()));
+
+    let data = to_raw_json_value(&json!({ "answer": 42 })).unwrap();
+    client
+        .send_raw_message_event(
+            &room_id!("!n8f893n9:example.com"),
+            EventType::Custom("org.example.answer".into()),
+            data,
+        )
+        .await
+        .unwrap();
+
+    assert!(requests.lock().unwrap()[0].uri.path().starts_with(
+        "/_matrix/client/r0/rooms/%21n8f893n9%3Aexample%2Ecom/send/org%2Eexample%2Eanswer/"
+    ));
+}
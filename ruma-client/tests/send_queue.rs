@@ -0,0 +1,231 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use futures_util::stream::StreamExt as _;
+use http::StatusCode;
+use matches::assert_matches;
+use ruma::{
+    events::room::message::{MessageEventContent, TextMessageEventContent},
+    identifiers::RoomId,
+    room_id,
+};
+use ruma_client::{MemoryQueueStore, QueueStore, SendQueue, SendResult};
+use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+mod common;
+
+use common::{client, json_response, session, MockBackend, Offline, RecordedRequest};
+
+/// A request to send a message, as seen by the homeserver.
+#[derive(Clone, Debug, PartialEq)]
+struct Attempt {
+    txn_id: String,
+    body: String,
+}
+
+/// Returns the message body of a request to send a message.
+fn message_body(body: &[u8]) -> String {
+    from_json_slice::<JsonValue>(body).unwrap()["body"].as_str().unwrap().to_owned()
+}
+
+/// A backend that fails the given number of attempts to send a message with the given body.
+fn flaky_backend(failures: Vec<(&'static str, usize)>) -> MockBackend {
+    let failures: Mutex<BTreeMap<_, _>> = Mutex::new(failures.into_iter().collect());
+    let count = AtomicUsize::new(0);
+    MockBackend::new(move |request| {
+        let index = count.fetch_add(1, Ordering::SeqCst);
+        let body = message_body(request.body());
+
+        if let Some(remaining) = failures.lock().unwrap().get_mut(body.as_str()) {
+            if *remaining > 0 {
+                *remaining -= 1;
+                return Err(Offline);
+            }
+        }
+
+        Ok(json_response(StatusCode::OK, &json!({ "event_id": format!("${}:example.com", index) })))
+    })
+}
+
+/// All attempts to send a message, including failed ones.
+fn attempts(requests: &Mutex<Vec<RecordedRequest>>) -> Vec<Attempt> {
+    requests
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|request| Attempt {
+            txn_id: request.uri.path().rsplit('/').next().unwrap().replace("%2E", "."),
+            body: message_body(&request.body),
+        })
+        .collect()
+}
+
+fn text(body: &str) -> MessageEventContent {
+    MessageEventContent::Text(TextMessageEventContent::plain(body))
+}
+
+/// The attempts to send one of the given message bodies.
+fn attempts_for(attempts: &[Attempt], bodies: &[&str]) -> Vec<Attempt> {
+    attempts.iter().filter(|attempt| bodies.contains(&attempt.body.as_str())).cloned().collect()
+}
+
+/// The transaction IDs of the events sent to the given room, in the order they were sent.
+fn sent_to(results: &[SendResult], room: &RoomId) -> Vec<String> {
+    results
+        .iter()
+        .filter_map(|result| match result {
+            SendResult::Sent { txn_id, room_id, .. } if room_id == room => Some(txn_id.clone()),
+            SendResult::Sent { .. } => None,
+            SendResult::Failed { error, .. } => panic!("unexpected failure: {}", error),
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn retries_preserve_order() {
+    let backend = flaky_backend(vec![("1", 2), ("2", 1)]);
+    let requests = backend.requests();
+    let queue = client(backend, Some(session())).queue().with_retries(3, Duration::from_secs(0));
+
+    let room_a = room_id!("!a:example.com");
+    let room_b = room_id!("!b:example.com");
+    let txn_1 = queue.send(&room_a, &text("1")).unwrap();
+    let txn_2 = queue.send(&room_b, &text("2")).unwrap();
+    let txn_3 = queue.send(&room_a, &text("3")).unwrap();
+
+    let results: Vec<_> = queue.flush().collect().await;
+    assert_eq!(results.len(), 3);
+    assert_eq!(sent_to(&results, &room_a), vec![txn_1.clone(), txn_3.clone()]);
+    assert_eq!(sent_to(&results, &room_b), vec![txn_2.clone()]);
+
+    // Retries reuse the transaction ID of the first attempt.
+    let attempts = attempts(&requests);
+    assert_eq!(
+        attempts_for(&attempts, &["1", "3"]),
+        vec![
+            Attempt { txn_id: txn_1.clone(), body: "1".into() },
+            Attempt { txn_id: txn_1.clone(), body: "1".into() },
+            Attempt { txn_id: txn_1, body: "1".into() },
+            Attempt { txn_id: txn_3, body: "3".into() },
+        ]
+    );
+    assert_eq!(
+        attempts_for(&attempts, &["2"]),
+        vec![
+            Attempt { txn_id: txn_2.clone(), body: "2".into() },
+            Attempt { txn_id: txn_2, body: "2".into() },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn failed_event_holds_back_its_room() {
+    let backend = flaky_backend(vec![("a1", usize::MAX)]);
+    let requests = backend.requests();
+    let store = Arc::new(MemoryQueueStore::default());
+    let queue = SendQueue::with_store(client(backend, Some(session())), store.clone())
+        .with_retries(2, Duration::from_secs(0));
+
+    let room_a = room_id!("!a:example.com");
+    let room_b = room_id!("!b:example.com");
+    queue.send(&room_a, &text("a1")).unwrap();
+    queue.send(&room_a, &text("a2")).unwrap();
+    queue.send(&room_b, &text("b1")).unwrap();
+
+    let results: Vec<_> = queue.flush().collect().await;
+    assert_eq!(results.len(), 2);
+    assert!(results
+        .iter()
+        .any(|result| matches!(result, SendResult::Failed { room_id, .. } if *room_id == room_a)));
+    assert!(results
+        .iter()
+        .any(|result| matches!(result, SendResult::Sent { room_id, .. } if *room_id == room_b)));
+
+    // `a2` is never attempted, so it can't overtake `a1`.
+    let attempts = attempts(&requests);
+    assert_eq!(attempts_for(&attempts, &["a1", "a2"]).len(), 2);
+    assert!(attempts_for(&attempts, &["a2"]).is_empty());
+
+    let pending: Vec<_> =
+        store.pending().into_iter().map(|event| event.content.get().to_owned()).collect();
+    assert_eq!(pending.len(), 2);
+    assert!(pending[0].contains("a1"));
+    assert!(pending[1].contains("a2"));
+}
+
+#[tokio::test]
+async fn retries_do_not_hold_back_other_rooms() {
+    let backend = flaky_backend(vec![("a1", usize::MAX)]);
+    let queue =
+        client(backend, Some(session())).queue().with_retries(2, Duration::from_secs(60 * 60));
+
+    let room_a = room_id!("!a:example.com");
+    let room_b = room_id!("!b:example.com");
+    queue.send(&room_a, &text("a1")).unwrap();
+    let txn_b = queue.send(&room_b, &text("b1")).unwrap();
+
+    // `b1` is sent while `a1` waits an hour for its retry.
+    let mut results = Box::pin(queue.flush());
+    assert_matches!(
+        results.next().await,
+        Some(SendResult::Sent { txn_id, .. }) if txn_id == txn_b
+    );
+}
+
+#[tokio::test]
+async fn client_queue_is_shared() {
+    let backend = flaky_backend(Vec::new());
+    let requests = backend.requests();
+    let client = client(backend, Some(session()));
+
+    let room_id = room_id!("!a:example.com");
+    let txn_id = client.queue().send(&room_id, &text("1")).unwrap();
+
+    let results: Vec<_> = client.queue().flush().collect().await;
+    assert_eq!(sent_to(&results, &room_id), vec![txn_id.clone()]);
+    assert_eq!(attempts(&requests), vec![Attempt { txn_id, body: "1".into() }]);
+}
+
+#[tokio::test]
+async fn restart_resends_pending_events_once() {
+    let store = Arc::new(MemoryQueueStore::default());
+    let room_id = room_id!("!a:example.com");
+
+    // The first run is offline the whole time.
+    let offline = flaky_backend(vec![("1", usize::MAX), ("2", usize::MAX)]);
+    let queue = SendQueue::with_store(client(offline, Some(session())), store.clone())
+        .with_retries(1, Duration::from_secs(0));
+    let txn_1 = queue.send(&room_id, &text("1")).unwrap();
+    let txn_2 = queue.send(&room_id, &text("2")).unwrap();
+
+    let results: Vec<_> = queue.flush().collect().await;
+    assert_eq!(results.len(), 1);
+    assert_matches!(&results[0], SendResult::Failed { txn_id, .. } if *txn_id == txn_1);
+    drop(queue);
+
+    // After a restart, a new client picks up the stored events.
+    let online = flaky_backend(Vec::new());
+    let requests = online.requests();
+    let queue = SendQueue::with_store(client(online, Some(session())), store.clone());
+
+    let results: Vec<_> = queue.flush().collect().await;
+    assert_eq!(results.len(), 2);
+    assert!(store.pending().is_empty());
+
+    // Flushing an empty queue doesn't send anything.
+    assert!(queue.flush().collect::<Vec<_>>().await.is_empty());
+
+    assert_eq!(
+        attempts(&requests),
+        vec![
+            Attempt { txn_id: txn_1, body: "1".into() },
+            Attempt { txn_id: txn_2, body: "2".into() },
+        ]
+    );
+}
@@ -0,0 +1,38 @@
+use http::StatusCode;
+use ruma_api::MatrixVersion;
+use serde_json::json;
+
+mod common;
+
+use common::{client, json_response, MockBackend};
+
+/// A backend that answers `/versions` requests.
+fn versions_backend() -> MockBackend {
+    MockBackend::new(|request| {
+        assert_eq!(request.uri().path(), "/_matrix/client/versions");
+
+        let body = json!({
+            "versions": ["r0.5.0", "r0.6.1", "v1.1", "v9.9"],
+            "unstable_features": { "org.matrix.msc2432": true }
+        });
+        Ok(json_response(StatusCode::OK, &body))
+    })
+}
+
+#[tokio::test]
+async fn server_versions_are_cached() {
+    let backend = versions_backend();
+    let requests = backend.requests();
+    let client = client(backend, None);
+
+    let versions = client.server_versions().await.unwrap();
+    let known: Vec<_> = versions.known_versions().into_iter().collect();
+    assert_eq!(
+        known,
+        vec![MatrixVersion::V1_0, MatrixVersion::V1_1, MatrixVersion::Newer { major: 9, minor: 9 }]
+    );
+    assert!(versions.supports_feature("org.matrix.msc2432"));
+
+    client.clone().server_versions().await.unwrap();
+    assert_eq!(requests.lock().unwrap().len(), 1);
+}
@@ -0,0 +1,161 @@
+use http::StatusCode;
+use ruma::{api::client::r0::sync::sync_events::Response as SyncResponse, room_id};
+use ruma_api::IncomingResponse as _;
+use ruma_client::{MissingRange, Timeline};
+use serde_json::{json, Value as JsonValue};
+
+mod common;
+
+use common::{client, json_response, session, MockBackend};
+
+fn message_event(event_id: &str) -> JsonValue {
+    json!({
+        "content": { "body": "Hello", "msgtype": "m.text" },
+        "event_id": event_id,
+        "origin_server_ts": 1,
+        "sender": "@alice:example.org",
+        "type": "m.room.message"
+    })
+}
+
+fn sync_response(events: &[&str], limited: bool, prev_batch: &str) -> SyncResponse {
+    let events: Vec<_> = events.iter().map(|id| message_event(id)).collect();
+    let body = json!({
+        "next_batch": "s1",
+        "rooms": {
+            "join": {
+                "!room:example.org": {
+                    "timeline": { "events": events, "limited": limited, "prev_batch": prev_batch }
+                }
+            }
+        }
+    });
+
+    SyncResponse::try_from_http_response(http::Response::new(serde_json::to_vec(&body).unwrap()))
+        .unwrap()
+}
+
+fn event_ids(timeline: &Timeline) -> Vec<String> {
+    timeline
+        .events()
+        .iter()
+        .map(|event| {
+            serde_json::from_str::<JsonValue>(event.json().get()).unwrap()["event_id"]
+                .as_str()
+                .unwrap()
+                .to_owned()
+        })
+        .collect()
+}
+
+/// A backend that answers `/messages` requests with canned pages, keyed by `from` token.
+fn messages_backend(
+    pages: Vec<(&'static str, Vec<&'static str>, Option<&'static str>)>,
+) -> MockBackend {
+    MockBackend::new(move |request| {
+        assert_eq!(
+            request.uri().path(),
+            "/_matrix/client/r0/rooms/%21room%3Aexample%2Eorg/messages"
+        );
+        let query = request.uri().query().unwrap();
+        assert!(query.contains("dir=b"));
+
+        let (from, chunk, end) = pages
+            .iter()
+            .find(|(from, _, _)| query.contains(&format!("from={}", from)))
+            .expect("unexpected pagination token");
+        let chunk: Vec<_> = chunk
+            .iter()
+            .map(|id| {
+                let mut event = message_event(id);
+                event["room_id"] = "!room:example.org".into();
+                event
+            })
+            .collect();
+
+        Ok(json_response(StatusCode::OK, &json!({ "start": from, "end": end, "chunk": chunk })))
+    })
+}
+
+fn timeline_with_gap() -> Timeline {
+    let mut timeline = Timeline::new(room_id!("!room:example.org"));
+
+    timeline.apply_sync(&sync_response(&["$1:example.org", "$2:example.org"], false, "p0"));
+    timeline.apply_sync(&sync_response(&["$6:example.org", "$7:example.org"], true, "p1"));
+
+    timeline
+}
+
+#[test]
+fn limited_sync_records_gap() {
+    let mut timeline = timeline_with_gap();
+
+    assert_eq!(timeline.prev_batch(), Some("p0"));
+    assert_eq!(timeline.missing_ranges(), &[MissingRange { index: 2, from: "p1".into() }]);
+
+    // A duplicated event in a later sync is dropped.
+    timeline.apply_sync(&sync_response(&["$7:example.org", "$8:example.org"], false, "p2"));
+    assert_eq!(
+        event_ids(&timeline),
+        vec![
+            "$1:example.org",
+            "$2:example.org",
+            "$6:example.org",
+            "$7:example.org",
+            "$8:example.org"
+        ]
+    );
+    assert_eq!(timeline.missing_ranges().len(), 1);
+}
+
+#[tokio::test]
+async fn backfill_closes_gap_on_overlap() {
+    let mut timeline = timeline_with_gap();
+    let backend = messages_backend(vec![
+        // The first page repeats an event from after the gap.
+        ("p1", vec!["$6:example.org", "$5:example.org", "$4:example.org"], Some("t1")),
+        // The second page overlaps with the events before the gap.
+        ("t1", vec!["$3:example.org", "$2:example.org", "$1:example.org"], Some("t2")),
+    ]);
+    let client = client(backend, Some(session()));
+
+    assert!(timeline.backfill(&client, 5).await.unwrap());
+    assert_eq!(
+        event_ids(&timeline),
+        vec![
+            "$1:example.org",
+            "$2:example.org",
+            "$3:example.org",
+            "$4:example.org",
+            "$5:example.org",
+            "$6:example.org",
+            "$7:example.org",
+        ]
+    );
+    assert!(timeline.missing_ranges().is_empty());
+
+    // Without gaps, there is nothing to backfill.
+    assert!(!timeline.backfill(&client, 5).await.unwrap());
+}
+
+#[tokio::test]
+async fn backfill_stops_at_page_limit() {
+    let mut timeline = timeline_with_gap();
+    let backend =
+        messages_backend(vec![("p1", vec!["$5:example.org", "$4:example.org"], Some("t1"))]);
+    let client = client(backend, Some(session()));
+
+    assert!(!timeline.backfill(&client, 1).await.unwrap());
+    assert_eq!(
+        event_ids(&timeline),
+        vec![
+            "$1:example.org",
+            "$2:example.org",
+            "$4:example.org",
+            "$5:example.org",
+            "$6:example.org",
+            "$7:example.org",
+        ]
+    );
+    assert_eq!(timeline.missing_ranges(), &[MissingRange { index: 2, from: "t1".into() }]);
+}
@@ -1,8 +1,10 @@
 //! Common types for [encryption] related tasks.
 //!
 //! [encryption]: https://matrix.org/docs/spec/client_server/r0.6.1#id76
+//!
+//! Requires the `std` feature.
 
-use std::collections::BTreeMap;
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
 
 use ruma_identifiers::{DeviceId, DeviceKeyId, EventEncryptionAlgorithm, UserId};
 use serde::{Deserialize, Serialize};
@@ -37,3 +39,68 @@ pub struct UnsignedDeviceInfo {
     /// The display name which the user set on the device.
     pub device_display_name: Option<String>,
 }
+
+/// A key for the SignedCurve25519 algorithm
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedKey {
+    /// Base64-encoded 32-byte Curve25519 public key.
+    pub key: String,
+
+    /// Signatures for the key object.
+    pub signatures: BTreeMap<UserId, BTreeMap<DeviceKeyId, String>>,
+}
+
+/// A one-time public key for "pre-key" messages.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum OneTimeKey {
+    /// A key containing signatures, for the SignedCurve25519 algorithm.
+    SignedKey(SignedKey),
+
+    /// A string-valued key, for the Ed25519 and Curve25519 algorithms.
+    Key(String),
+}
+
+/// The one-time keys for a given device, keyed by `<algorithm>:<key id>`.
+pub type OneTimeKeys = BTreeMap<Box<DeviceId>, BTreeMap<DeviceKeyId, OneTimeKey>>;
+
+/// A cross signing key.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CrossSigningKey {
+    /// The ID of the user the key belongs to.
+    pub user_id: UserId,
+
+    /// What the key is used for.
+    pub usage: Vec<KeyUsage>,
+
+    /// The public key. The object must have exactly one property.
+    pub keys: BTreeMap<String, String>,
+
+    /// Signatures of the key. Only optional for master key.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub signatures: BTreeMap<UserId, BTreeMap<String, String>>,
+}
+
+impl CrossSigningKey {
+    /// Creates a new `CrossSigningKey` with the given user ID, usage, keys and signatures.
+    pub fn new(
+        user_id: UserId,
+        usage: Vec<KeyUsage>,
+        keys: BTreeMap<String, String>,
+        signatures: BTreeMap<UserId, BTreeMap<String, String>>,
+    ) -> Self {
+        Self { user_id, usage, keys, signatures }
+    }
+}
+
+/// The usage of a cross signing key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyUsage {
+    /// Master key.
+    Master,
+    /// Self-signing key.
+    SelfSigning,
+    /// User-signing key.
+    UserSigning,
+}
@@ -1,11 +1,18 @@
 //! Common types for other ruma crates.
 
 #![warn(missing_docs, missing_debug_implementations)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+#[cfg(feature = "std")]
 pub mod encryption;
 pub mod presence;
 pub mod push;
+#[cfg(feature = "serde_json")]
 mod raw;
+pub mod receipt;
 pub mod thirdparty;
 
+#[cfg(feature = "serde_json")]
 pub use self::raw::Raw;
@@ -2,23 +2,22 @@
 //!
 //! [presence]: https://matrix.org/docs/spec/client_server/r0.6.1#id62
 
-use serde::{Deserialize, Serialize};
-use strum::{Display, EnumString};
+use ruma_serde::string_enum;
 
-/// A description of a user's connectivity and availability for chat.
-#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, Deserialize, Serialize)]
-#[non_exhaustive]
-#[serde(rename_all = "snake_case")]
-#[strum(serialize_all = "snake_case")]
-pub enum PresenceState {
-    /// Disconnected from the service.
-    Offline,
+string_enum! {
+    /// A description of a user's connectivity and availability for chat.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum PresenceState {
+        /// Disconnected from the service.
+        Offline => "offline",
 
-    /// Connected to the service.
-    Online,
+        /// Connected to the service.
+        Online => "online",
 
-    /// Connected to the service but not available for chat.
-    Unavailable,
+        /// Connected to the service but not available for chat.
+        Unavailable => "unavailable",
+    }
 }
 
 impl Default for PresenceState {
@@ -2,6 +2,8 @@
 //!
 //! [push]: https://matrix.org/docs/spec/client_server/r0.6.1#id89
 
+use alloc::{string::String, vec::Vec};
+
 use serde::{Deserialize, Serialize};
 
 mod action;
@@ -9,7 +11,7 @@ mod any_push_rule;
 mod condition;
 
 pub use self::{
-    action::{Action, Tweak},
+    action::{Action, CustomTweakValue, Tweak},
     any_push_rule::{AnyPushRule, MissingConditionsError, MissingPatternError},
     condition::{ComparisonOperator, PushCondition, RoomMemberCountIs},
 };
@@ -1,7 +1,13 @@
-use std::fmt::{self, Formatter};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::{
+    convert::TryFrom,
+    fmt::{self, Formatter},
+};
 
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use serde_json::value::RawValue as RawJsonValue;
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
 
 /// This represents the different actions that should be taken when a rule is matched, and
 /// controls how notifications are delivered to the client.
@@ -44,23 +50,152 @@ pub enum Tweak {
     /// `false`.
     Highlight(#[serde(default = "ruma_serde::default_true")] bool),
 
-    /// A custom tweak
+    /// A custom tweak.
     Custom {
         /// The name of the custom tweak (`set_tweak` field)
         name: String,
 
         /// The value of the custom tweak
-        value: Box<RawJsonValue>,
+        value: CustomTweakValue,
     },
 }
 
-impl<'de> Deserialize<'de> for Action {
+/// The value of a custom tweak.
+///
+/// This can hold any value that can appear in a JSON document, without depending on a particular
+/// serialization format.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CustomTweakValue {
+    /// `null`.
+    Null,
+
+    /// A boolean.
+    Bool(bool),
+
+    /// An integer that fits into an `i64`.
+    Integer(i64),
+
+    /// A positive integer that is too large for an `i64`.
+    UnsignedInteger(u64),
+
+    /// A floating point number.
+    Float(f64),
+
+    /// A string.
+    String(String),
+
+    /// An array of values.
+    Array(Vec<CustomTweakValue>),
+
+    /// An object.
+    Object(BTreeMap<String, CustomTweakValue>),
+}
+
+impl Serialize for CustomTweakValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Null => serializer.serialize_unit(),
+            Self::Bool(value) => serializer.serialize_bool(*value),
+            Self::Integer(value) => serializer.serialize_i64(*value),
+            Self::UnsignedInteger(value) => serializer.serialize_u64(*value),
+            Self::Float(value) => serializer.serialize_f64(*value),
+            Self::String(value) => serializer.serialize_str(value),
+            Self::Array(values) => serializer.collect_seq(values),
+            Self::Object(values) => serializer.collect_map(values),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for CustomTweakValue {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        use serde::de::{MapAccess, Visitor};
+        struct CustomTweakValueVisitor;
+        impl<'de> Visitor<'de> for CustomTweakValueVisitor {
+            type Value = CustomTweakValue;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+                write!(formatter, "any value")
+            }
+
+            fn visit_unit<E>(self) -> Result<Self::Value, E> {
+                Ok(CustomTweakValue::Null)
+            }
+
+            fn visit_none<E>(self) -> Result<Self::Value, E> {
+                Ok(CustomTweakValue::Null)
+            }
 
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                CustomTweakValue::deserialize(deserializer)
+            }
+
+            fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+                Ok(CustomTweakValue::Bool(v))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+                Ok(CustomTweakValue::Integer(v))
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+                Ok(i64::try_from(v)
+                    .map_or(CustomTweakValue::UnsignedInteger(v), CustomTweakValue::Integer))
+            }
+
+            fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+                Ok(CustomTweakValue::Float(v))
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(CustomTweakValue::String(v.into()))
+            }
+
+            fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+                Ok(CustomTweakValue::String(v))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut values = Vec::new();
+                while let Some(value) = seq.next_element()? {
+                    values.push(value);
+                }
+
+                Ok(CustomTweakValue::Array(values))
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut values = BTreeMap::new();
+                while let Some((key, value)) = map.next_entry()? {
+                    values.insert(key, value);
+                }
+
+                Ok(CustomTweakValue::Object(values))
+            }
+        }
+
+        deserializer.deserialize_any(CustomTweakValueVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for Action {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
         struct ActionVisitor;
         impl<'de> Visitor<'de> for ActionVisitor {
             type Value = Action;
@@ -111,8 +246,11 @@ impl Serialize for Action {
 }
 
 mod tweak_serde {
+    use alloc::string::String;
+
     use serde::{Deserialize, Serialize};
-    use serde_json::value::RawValue as RawJsonValue;
+
+    use super::CustomTweakValue;
 
     /// Values for the `set_tweak` action.
     #[derive(Clone, Deserialize, Serialize)]
@@ -123,7 +261,7 @@ mod tweak_serde {
         Custom {
             #[serde(rename = "set_tweak")]
             name: String,
-            value: Box<RawJsonValue>,
+            value: CustomTweakValue,
         },
     }
 
@@ -167,7 +305,7 @@ mod tweak_serde {
 
 #[cfg(test)]
 mod tests {
-    use super::{Action, Tweak};
+    use super::{Action, CustomTweakValue, Tweak};
 
     use matches::assert_matches;
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
@@ -234,4 +372,47 @@ mod tests {
             Action::SetTweak(Tweak::Highlight(true))
         );
     }
+
+    #[test]
+    fn serialize_tweak_custom() {
+        let value = CustomTweakValue::Object(
+            vec![("level".into(), CustomTweakValue::Integer(3))].into_iter().collect(),
+        );
+
+        assert_eq!(
+            to_json_value(&Action::SetTweak(Tweak::Custom {
+                name: "org.example.tweak".into(),
+                value
+            }))
+            .unwrap(),
+            json!({ "set_tweak": "org.example.tweak", "value": { "level": 3 } })
+        );
+    }
+
+    #[test]
+    fn deserialize_tweak_custom() {
+        let json_data = json!({
+            "set_tweak": "org.example.tweak",
+            "value": [null, true, -1, 18446744073709551615u64, 0.5, "loud", { "level": 3 }]
+        });
+
+        assert_matches!(
+            from_json_value::<Action>(json_data).unwrap(),
+            Action::SetTweak(Tweak::Custom { name, value: CustomTweakValue::Array(values) })
+                if name == "org.example.tweak"
+                    && values == vec![
+                        CustomTweakValue::Null,
+                        CustomTweakValue::Bool(true),
+                        CustomTweakValue::Integer(-1),
+                        CustomTweakValue::UnsignedInteger(u64::MAX),
+                        CustomTweakValue::Float(0.5),
+                        CustomTweakValue::String("loud".into()),
+                        CustomTweakValue::Object(
+                            vec![("level".into(), CustomTweakValue::Integer(3))]
+                                .into_iter()
+                                .collect()
+                        ),
+                    ]
+        );
+    }
 }
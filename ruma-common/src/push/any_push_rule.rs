@@ -1,6 +1,6 @@
-use std::{
+use alloc::{string::String, vec::Vec};
+use core::{
     convert::TryFrom,
-    error::Error,
     fmt::{self, Display, Formatter},
 };
 
@@ -103,7 +103,8 @@ impl Display for MissingPatternError {
     }
 }
 
-impl Error for MissingPatternError {}
+#[cfg(feature = "std")]
+impl std::error::Error for MissingPatternError {}
 
 impl TryFrom<AnyPushRule> for PatternedPushRule {
     type Error = MissingPatternError;
@@ -131,7 +132,8 @@ impl Display for MissingConditionsError {
     }
 }
 
-impl Error for MissingConditionsError {}
+#[cfg(feature = "std")]
+impl std::error::Error for MissingConditionsError {}
 
 impl TryFrom<AnyPushRule> for ConditionalPushRule {
     type Error = MissingConditionsError;
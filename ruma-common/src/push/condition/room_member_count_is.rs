@@ -1,4 +1,5 @@
-use std::{
+use alloc::string::{String, ToString};
+use core::{
     fmt::{self, Display, Formatter},
     ops::{Bound, RangeBounds, RangeFrom, RangeTo, RangeToInclusive},
     str::FromStr,
@@ -1,4 +1,6 @@
-use std::{
+use alloc::{boxed::Box, format};
+use core::{
+    any::type_name,
     clone::Clone,
     fmt::{self, Debug, Formatter},
     marker::PhantomData,
@@ -71,7 +73,6 @@ impl<T> Clone for Raw<T> {
 
 impl<T> Debug for Raw<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        use std::any::type_name;
         f.debug_struct(&format!("Raw::<{}>", type_name::<T>())).field("json", &self.json).finish()
     }
 }
@@ -0,0 +1,35 @@
+//! Common types for the [receipts module][receipts]
+//!
+//! [receipts]: https://matrix.org/docs/spec/client_server/r0.6.1#id57
+
+use ruma_serde::string_enum;
+
+string_enum! {
+    /// The type of receipt.
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    #[non_exhaustive]
+    pub enum ReceiptType {
+        /// m.read
+        Read => "m.read",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::ReceiptType;
+
+    #[test]
+    fn serde_read() {
+        assert_eq!(to_json_value(ReceiptType::Read).unwrap(), json!("m.read"));
+        assert_eq!(from_json_value::<ReceiptType>(json!("m.read")).unwrap(), ReceiptType::Read);
+    }
+
+    #[test]
+    fn display_and_parse_read() {
+        assert_eq!(ReceiptType::Read.to_string(), "m.read");
+        assert_eq!("m.read".parse::<ReceiptType>().unwrap(), ReceiptType::Read);
+        assert_eq!(ReceiptType::from("m.unknown").as_str(), "m.unknown");
+    }
+}
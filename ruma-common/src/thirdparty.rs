@@ -2,10 +2,13 @@
 //!
 //! [thirdparty]: https://matrix.org/docs/spec/client_server/r0.6.1#id153
 
-use std::collections::BTreeMap;
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::time::SystemTime;
 
+#[cfg(feature = "std")]
 use ruma_identifiers::{RoomAliasId, UserId};
-
+use ruma_serde::string_enum;
 use serde::{Deserialize, Serialize};
 
 /// Metadata about a third party protocol.
@@ -78,6 +81,9 @@ pub struct ProtocolInstance {
     pub fields: BTreeMap<String, String>,
 
     /// A unique identifier across all instances.
+    ///
+    /// Also deserialized from `instance_id`, as some application services call it.
+    #[serde(alias = "instance_id")]
     pub network_id: String,
 }
 
@@ -139,6 +145,9 @@ impl From<FieldTypeInit> for FieldType {
 }
 
 /// A third party network location.
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[non_exhaustive]
 pub struct Location {
@@ -152,6 +161,7 @@ pub struct Location {
     pub fields: BTreeMap<String, String>,
 }
 
+#[cfg(feature = "std")]
 impl Location {
     /// Creates a new `Location` with the given alias, protocol and fields.
     pub fn new(alias: RoomAliasId, protocol: String, fields: BTreeMap<String, String>) -> Self {
@@ -160,6 +170,9 @@ impl Location {
 }
 
 /// A third party network user.
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct User {
     /// A matrix user ID representing a third party user.
@@ -172,6 +185,7 @@ pub struct User {
     pub fields: BTreeMap<String, String>,
 }
 
+#[cfg(feature = "std")]
 impl User {
     /// Creates a new `User` with the given userid, protocol and fields.
     pub fn new(userid: UserId, protocol: String, fields: BTreeMap<String, String>) -> Self {
@@ -179,14 +193,86 @@ impl User {
     }
 }
 
-/// The medium of a third party identifier.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
-#[non_exhaustive]
-#[serde(rename_all = "lowercase")]
-pub enum Medium {
-    /// Email address identifier
-    Email,
+string_enum! {
+    /// The medium of a third party identifier.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum Medium {
+        /// Email address identifier
+        Email => "email",
+
+        /// Phone number identifier
+        MSISDN => "msisdn",
+    }
+}
+
+/// An identifier external to Matrix.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct ThirdPartyIdentifier {
+    /// The third party identifier address.
+    pub address: String,
+
+    /// The medium of third party identifier.
+    pub medium: Medium,
+
+    /// The time when the identifier was validated by the identity server.
+    #[serde(with = "ruma_serde::time::ms_since_unix_epoch")]
+    pub validated_at: SystemTime,
 
-    /// Phone number identifier
-    MSISDN,
+    /// The time when the homeserver associated the third party identifier with the user.
+    #[serde(with = "ruma_serde::time::ms_since_unix_epoch")]
+    pub added_at: SystemTime,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::{Medium, ProtocolInstance, ThirdPartyIdentifier};
+
+    #[test]
+    fn third_party_identifier_serde() {
+        let third_party_id = ThirdPartyIdentifier {
+            address: "monkey@banana.island".into(),
+            medium: Medium::Email,
+            validated_at: UNIX_EPOCH + Duration::from_millis(1_535_176_800_000),
+            added_at: UNIX_EPOCH + Duration::from_millis(1_535_336_848_756),
+        };
+
+        let third_party_id_serialized = json!({
+            "medium": "email",
+            "address": "monkey@banana.island",
+            "validated_at": 1_535_176_800_000u64,
+            "added_at": 1_535_336_848_756u64
+        });
+
+        assert_eq!(to_json_value(third_party_id.clone()).unwrap(), third_party_id_serialized);
+        assert_eq!(third_party_id, from_json_value(third_party_id_serialized).unwrap());
+    }
+
+    #[test]
+    fn protocol_instance_network_id_spellings() {
+        for key in &["network_id", "instance_id"] {
+            let instance = from_json_value::<ProtocolInstance>(json!({
+                "desc": "Freenode",
+                "fields": { "network": "freenode" },
+                key.to_string(): "freenode",
+            }))
+            .unwrap();
+            assert_eq!(instance.network_id, "freenode");
+
+            assert_eq!(
+                to_json_value(instance).unwrap(),
+                json!({
+                    "desc": "Freenode",
+                    "fields": { "network": "freenode" },
+                    "network_id": "freenode",
+                })
+            );
+        }
+    }
 }
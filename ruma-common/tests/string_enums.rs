@@ -0,0 +1,9 @@
+use ruma_common::{presence::PresenceState, receipt::ReceiptType, thirdparty::Medium};
+use ruma_serde::test::assert_string_enum_roundtrip;
+
+#[test]
+fn string_enums_roundtrip() {
+    assert_string_enum_roundtrip::<Medium>();
+    assert_string_enum_roundtrip::<PresenceState>();
+    assert_string_enum_roundtrip::<ReceiptType>();
+}
@@ -2,7 +2,10 @@
 
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{Data, DataStruct, DeriveInput, Field, Fields, FieldsNamed, Ident};
+use syn::{
+    Data, DataStruct, DeriveInput, Field, Fields, FieldsNamed, GenericArgument, Ident,
+    PathArguments, Type,
+};
 
 use crate::{
     event_parse::{to_kind_variation, EventKind, EventKindVariation},
@@ -47,16 +50,28 @@ pub fn expand_event(input: DeriveInput) -> syn::Result<TokenStream> {
 
     let conversion_impl = expand_from_into(&input, &kind, &var, &fields, &import_path);
 
-    let eq_impl = expand_eq_ord_event(&input, &fields);
+    let deserialize_strict_impl = expand_deserialize_strict(&input, &fields, &import_path);
+
+    let constructors_impl = expand_constructors(&input, &fields);
+
+    let content_json_impl = expand_content_json(&input, &import_path);
+
+    let unsigned_accessors_impl = expand_unsigned_accessors(&input, &var, &fields);
 
     Ok(quote! {
         #conversion_impl
 
+        #constructors_impl
+
+        #content_json_impl
+
+        #unsigned_accessors_impl
+
         #serialize_impl
 
         #deserialize_impl
 
-        #eq_impl
+        #deserialize_strict_impl
     })
 }
 
@@ -86,8 +101,10 @@ fn expand_serialize_event(
                 }
             } else if name == "origin_server_ts" {
                 quote! {
-                    let time_since_epoch =
-                        self.origin_server_ts.duration_since(::std::time::UNIX_EPOCH).unwrap();
+                    let time_since_epoch = self
+                        .origin_server_ts
+                        .duration_since(::std::time::UNIX_EPOCH)
+                        .map_err(|_| S::Error::custom("origin_server_ts is before the UNIX epoch"))?;
 
                     let timestamp = <#import_path::exports::js_int::UInt as ::std::convert::TryFrom<_>>::try_from(
                         time_since_epoch.as_millis(),
@@ -147,6 +164,11 @@ fn expand_deserialize_event(
     let (impl_generics, ty_gen, where_clause) = input.generics.split_for_impl();
     let is_generic = !input.generics.params.is_empty();
 
+    // Some homeservers send `prev_content` inside of `unsigned`, so `unsigned` has to be kept as
+    // raw JSON until `prev_content` has been looked up there.
+    let has_prev_content = fields.iter().any(|f| f.ident.as_ref().unwrap() == "prev_content");
+    let has_unsigned = fields.iter().any(|f| f.ident.as_ref().unwrap() == "unsigned");
+
     let enum_variants = fields
         .iter()
         .map(|field| {
@@ -168,6 +190,8 @@ fn expand_deserialize_event(
                 }
             } else if name == "origin_server_ts" {
                 quote! { #import_path::exports::js_int::UInt }
+            } else if name == "unsigned" && has_prev_content {
+                quote! { Box<#import_path::exports::serde_json::value::RawValue> }
             } else {
                 quote! { #ty }
             }
@@ -213,8 +237,30 @@ fn expand_deserialize_event(
                 }
             }
         } else if name == "prev_content" {
+            // The top-level `prev_content` takes precedence over the one in `unsigned`.
+            let unsigned_prev_content = if has_unsigned {
+                quote! {
+                    match &unsigned {
+                        Some(json) => {
+                            #import_path::exports::serde_json::from_str::<
+                                #import_path::UnsignedPrevContentDeHelper,
+                            >(json.get())
+                            .map_err(A::Error::custom)?
+                            .prev_content
+                        }
+                        None => None,
+                    }
+                }
+            } else {
+                quote! { None }
+            };
+
             if is_generic {
                 quote! {
+                    let prev_content = match prev_content {
+                        Some(json) => Some(json),
+                        None => #unsigned_prev_content,
+                    };
                     let prev_content = if let Some(json) = prev_content {
                         Some(C::from_parts(&event_type, json).map_err(A::Error::custom)?)
                     } else {
@@ -223,10 +269,15 @@ fn expand_deserialize_event(
                 }
             } else {
                 quote! {
-                    let prev_content = if let Some(content) = prev_content {
-                        Some(content)
-                    } else {
-                        None
+                    let prev_content = match prev_content {
+                        Some(content) => Some(content),
+                        None => match #unsigned_prev_content {
+                            Some(json) => Some(
+                                #import_path::exports::serde_json::from_str(json.get())
+                                    .map_err(A::Error::custom)?,
+                            ),
+                            None => None,
+                        },
                     };
                 }
             }
@@ -239,6 +290,27 @@ fn expand_deserialize_event(
                     })
                     .ok_or_else(|| #import_path::exports::serde::de::Error::missing_field("origin_server_ts"))?;
             }
+        } else if name == "redacts" && !is_generic {
+            // Newer room versions place `redacts` inside of `content`, so fall back to it if there
+            // is no top-level `redacts`.
+            quote! {
+                let redacts = match redacts {
+                    Some(id) => id,
+                    None => content.redacts.clone().ok_or_else(|| {
+                        #import_path::exports::serde::de::Error::missing_field("redacts")
+                    })?,
+                };
+            }
+        } else if name == "unsigned" && has_prev_content {
+            quote! {
+                let unsigned = match unsigned {
+                    Some(json) => {
+                        #import_path::exports::serde_json::from_str(json.get())
+                            .map_err(A::Error::custom)?
+                    }
+                    None => Default::default(),
+                };
+            }
         } else if name == "unsigned" {
             quote! { let unsigned = unsigned.unwrap_or_default(); }
         } else {
@@ -347,6 +419,201 @@ fn expand_deserialize_event(
     })
 }
 
+fn expand_deserialize_strict(
+    input: &DeriveInput,
+    fields: &[Field],
+    import_path: &TokenStream,
+) -> TokenStream {
+    let ident = &input.ident;
+    let (impl_gen, ty_gen, where_clause) = input.generics.split_for_impl();
+
+    let known_fields = fields.iter().flat_map(|f| &f.ident).map(|f| f.to_string());
+
+    let check_state_key = if fields.iter().flat_map(|f| &f.ident).any(|f| f == "state_key") {
+        quote! {
+            #import_path::check_state_key(
+                #import_path::EventContent::event_type(&event.content),
+                &event.state_key,
+            )?;
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    quote! {
+        impl #impl_gen #ident #ty_gen #where_clause {
+            /// Deserializes the event from `json` like its `Deserialize` implementation, but fails
+            /// if the event has top-level fields that are not part of the event, or, for state
+            /// events, an invalid `state_key`.
+            pub fn deserialize_strict(
+                json: &#import_path::exports::serde_json::value::RawValue,
+            ) -> #import_path::exports::serde_json::Result<Self> {
+                const KNOWN_FIELDS: &[&str] = &["type", #( #known_fields ),*];
+
+                #import_path::check_known_fields(json, KNOWN_FIELDS)?;
+                let event: Self = #import_path::exports::serde_json::from_str(json.get())?;
+                #check_state_key
+
+                Ok(event)
+            }
+        }
+    }
+}
+
+fn expand_content_json(input: &DeriveInput, import_path: &TokenStream) -> TokenStream {
+    let ident = &input.ident;
+    let (impl_gen, ty_gen, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_gen #ident #ty_gen #where_clause {
+            /// Serializes the `content` of the event to a JSON value, regardless of its type.
+            ///
+            /// This is useful when the content has to be handled as JSON, e.g. for logging,
+            /// hashing or signing.
+            pub fn content_json(
+                &self,
+            ) -> #import_path::exports::serde_json::Result<#import_path::exports::serde_json::Value>
+            {
+                #import_path::exports::serde_json::to_value(&self.content)
+            }
+        }
+    }
+}
+
+/// Generates methods that read from the `Unsigned` data of events that aren't redacted, which
+/// are only generated if the event also has an `origin_server_ts`.
+fn expand_unsigned_accessors(
+    input: &DeriveInput,
+    var: &EventKindVariation,
+    fields: &[Field],
+) -> TokenStream {
+    let has_field = |name: &str| fields.iter().any(|f| f.ident.as_ref().unwrap() == name);
+    if var.is_redacted() || !has_field("unsigned") || !has_field("origin_server_ts") {
+        return TokenStream::new();
+    }
+
+    let ident = &input.ident;
+    let (impl_gen, ty_gen, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_gen #ident #ty_gen #where_clause {
+            /// The client-supplied transaction ID from `unsigned`, if this client sent the event.
+            pub fn transaction_id(&self) -> Option<&str> {
+                self.unsigned.transaction_id()
+            }
+
+            /// A best-guess local timestamp for ordering this event, computed from `unsigned.age`
+            /// and the time `now` at which the event was received.
+            ///
+            /// `origin_server_ts` is set by the sender's homeserver and can't be trusted across
+            /// federation, so it is only used if there is no `age`.
+            pub fn timestamp_adjusted(
+                &self,
+                now: ::std::time::SystemTime,
+            ) -> ::std::time::SystemTime {
+                self.unsigned.sent_at(now).unwrap_or(self.origin_server_ts)
+            }
+        }
+    }
+}
+
+/// Generates a `new` constructor taking the required fields of the event, and `with_*` methods
+/// for the fields that `new` fills in.
+fn expand_constructors(input: &DeriveInput, fields: &[Field]) -> TokenStream {
+    let ident = &input.ident;
+    let (impl_gen, ty_gen, where_clause) = input.generics.split_for_impl();
+
+    let is_defaulted = |name: &str| matches!(name, "prev_content" | "unsigned");
+
+    let (required, defaulted): (Vec<_>, Vec<_>) =
+        fields.iter().partition(|f| !is_defaulted(&f.ident.as_ref().unwrap().to_string()));
+
+    let required_idents: Vec<_> = required.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+    let required_types = required.iter().map(|f| &f.ty);
+
+    let defaulted_values = defaulted.iter().map(|f| {
+        let name = f.ident.as_ref().unwrap();
+        match name.to_string().as_str() {
+            "prev_content" => quote! { #name: None },
+            _ => quote! { #name: ::std::default::Default::default() },
+        }
+    });
+
+    let setters = defaulted.iter().map(|f| {
+        let name = f.ident.as_ref().unwrap();
+        let setter = Ident::new(&format!("with_{}", name), Span::call_site());
+        let doc = format!("Sets `{}` and returns the event.", name);
+
+        match option_inner_type(&f.ty) {
+            Some(inner) => quote! {
+                #[doc = #doc]
+                pub fn #setter(mut self, #name: #inner) -> Self {
+                    self.#name = Some(#name);
+                    self
+                }
+            },
+            None => {
+                let ty = &f.ty;
+                quote! {
+                    #[doc = #doc]
+                    pub fn #setter(mut self, #name: #ty) -> Self {
+                        self.#name = #name;
+                        self
+                    }
+                }
+            }
+        }
+    });
+
+    let new_doc = if defaulted.is_empty() {
+        "Creates a new event with the given fields.".to_owned()
+    } else {
+        let defaulted_names = defaulted
+            .iter()
+            .map(|f| match f.ident.as_ref().unwrap().to_string().as_str() {
+                "prev_content" => "`prev_content` to `None`".to_owned(),
+                name => format!("`{}` to its default", name),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(
+            "Creates a new event with the given fields, setting {}.\n\n\
+             Use the `with_*` methods to set these fields to other values.",
+            defaulted_names
+        )
+    };
+
+    quote! {
+        impl #impl_gen #ident #ty_gen #where_clause {
+            #[doc = #new_doc]
+            pub fn new(#( #required_idents: #required_types ),*) -> Self {
+                Self { #( #required_idents, )* #( #defaulted_values, )* }
+            }
+
+            #( #setters )*
+        }
+    }
+}
+
+/// Returns `T` if `ty` is `Option<T>`.
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if segment.ident == "Option" => {
+            match args.args.first()? {
+                GenericArgument::Type(inner) => Some(inner),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
 fn expand_from_into(
     input: &DeriveInput,
     kind: &EventKind,
@@ -403,40 +670,6 @@ fn expand_from_into(
     }
 }
 
-fn expand_eq_ord_event(input: &DeriveInput, fields: &[Field]) -> Option<TokenStream> {
-    if fields.iter().flat_map(|f| f.ident.as_ref()).any(|f| f == "event_id") {
-        let ident = &input.ident;
-        let (impl_gen, ty_gen, where_clause) = input.generics.split_for_impl();
-
-        Some(quote! {
-            impl #impl_gen ::std::cmp::PartialEq for #ident #ty_gen #where_clause {
-                /// This checks if two `EventId`s are equal.
-                fn eq(&self, other: &Self) -> ::std::primitive::bool {
-                    self.event_id == other.event_id
-                }
-            }
-
-            impl #impl_gen ::std::cmp::Eq for #ident #ty_gen #where_clause {}
-
-            impl #impl_gen ::std::cmp::PartialOrd for #ident #ty_gen #where_clause {
-                /// This compares `EventId`s and orders them lexicographically.
-                fn partial_cmp(&self, other: &Self) -> ::std::option::Option<::std::cmp::Ordering> {
-                    self.event_id.partial_cmp(&other.event_id)
-                }
-            }
-
-            impl #impl_gen ::std::cmp::Ord for #ident #ty_gen #where_clause {
-                /// This compares `EventId`s and orders them lexicographically.
-                fn cmp(&self, other: &Self) -> ::std::cmp::Ordering {
-                    self.event_id.cmp(&other.event_id)
-                }
-            }
-        })
-    } else {
-        None
-    }
-}
-
 /// CamelCase's a field ident like "foo_bar" to "FooBar".
 fn to_camel_case(name: &Ident) -> Ident {
     let span = name.span();
@@ -186,6 +186,8 @@ pub fn expand_event_content(
             #[derive(
                 Clone,
                 Debug,
+                PartialEq,
+                Eq,
                 #import_path::exports::serde::Deserialize,
                 #import_path::exports::serde::Serialize
             )]
@@ -219,13 +221,86 @@ pub fn expand_event_content(
 
     let event_content = generate_event_content_impl(ident, event_type, import_path);
 
+    let strict_event_content = expand_strict_event_content(input, import_path);
+
     Ok(quote! {
         #event_content
 
         #redacted
+
+        #strict_event_content
     })
 }
 
+/// Create a `StrictEventContent` implementation for a struct with named fields.
+///
+/// It deserializes the content into a copy of the struct with the same serde attributes plus
+/// `#[serde(deny_unknown_fields)]`, so the strict and permissive deserialization can't diverge.
+/// Nothing is generated for enums, generic structs and structs with flattened fields, since
+/// `deny_unknown_fields` doesn't support flattening.
+fn expand_strict_event_content(input: &DeriveInput, import_path: &TokenStream) -> TokenStream {
+    let fields = match &input.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(syn::FieldsNamed { named, .. }),
+            ..
+        }) if input.generics.params.is_empty() => named,
+        _ => return TokenStream::new(),
+    };
+
+    let is_serde_attr = |attr: &syn::Attribute| {
+        attr.path.is_ident("serde")
+            || attr.path.is_ident("cfg_attr") && attr.tokens.to_string().contains("serde")
+    };
+    let is_flattened = |field: &syn::Field| {
+        field.attrs.iter().any(|attr| {
+            is_serde_attr(attr)
+                && attr
+                    .tokens
+                    .to_string()
+                    .split(|c: char| !c.is_alphanumeric() && c != '_')
+                    .any(|t| t == "flatten")
+        })
+    };
+
+    if fields.iter().any(is_flattened) {
+        return TokenStream::new();
+    }
+
+    let ident = &input.ident;
+    let container_attrs = input.attrs.iter().filter(|attr| is_serde_attr(attr));
+    let shadow_fields = fields.iter().map(|field| {
+        let name = &field.ident;
+        let ty = &field.ty;
+        let attrs = field.attrs.iter().filter(|attr| is_serde_attr(attr));
+
+        quote! {
+            #( #attrs )*
+            #name: #ty
+        }
+    });
+
+    quote! {
+        const _: () = {
+            #[derive(#import_path::exports::serde::Deserialize)]
+            #( #container_attrs )*
+            #[serde(deny_unknown_fields)]
+            #[allow(dead_code)]
+            struct StrictHelper {
+                #( #shadow_fields, )*
+            }
+
+            impl #import_path::StrictEventContent for #ident {
+                fn deserialize_strict(
+                    json: &#import_path::exports::serde_json::value::RawValue,
+                ) -> #import_path::exports::serde_json::Result<Self> {
+                    #import_path::exports::serde_json::from_str::<StrictHelper>(json.get())?;
+                    #import_path::exports::serde_json::from_str(json.get())
+                }
+            }
+        };
+    }
+}
+
 /// Create a `BasicEventContent` implementation for a struct
 pub fn expand_basic_event_content(
     input: &DeriveInput,
@@ -124,22 +124,24 @@ fn expand_any_with_deser(
     };
 
     let event_deserialize_impl = quote! {
-        impl<'de> #import_path::exports::serde::de::Deserialize<'de> for #ident {
-            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        impl #ident {
+            /// Deserializes the event from `json`, given its already known `type`.
+            ///
+            /// This lets enums that contain this one deserialize it without reading the event's
+            /// type again.
+            #[doc(hidden)]
+            pub fn from_raw_json_with_type<E>(
+                ev_type: &str,
+                json: &#import_path::exports::serde_json::value::RawValue,
+            ) -> Result<Self, E>
             where
-                D: #import_path::exports::serde::de::Deserializer<'de>,
+                E: #import_path::exports::serde::de::Error,
             {
-                use #import_path::exports::serde::de::Error as _;
-
-                let json = Box::<#import_path::exports::serde_json::value::RawValue>::deserialize(deserializer)?;
-                let #import_path::EventDeHelper { ev_type, .. } =
-                    #import_path::from_raw_json_value(&json)?;
-
-                match ev_type.as_str() {
+                match ev_type {
                     #(
                         #events => {
                             let event = #import_path::exports::serde_json::from_str::<#content>(json.get())
-                                .map_err(D::Error::custom)?;
+                                .map_err(E::custom)?;
                             Ok(Self::#variants(event))
                         },
                     )*
@@ -147,6 +149,19 @@ fn expand_any_with_deser(
                 }
             }
         }
+
+        impl<'de> #import_path::exports::serde::de::Deserialize<'de> for #ident {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: #import_path::exports::serde::de::Deserializer<'de>,
+            {
+                let json = Box::<#import_path::exports::serde_json::value::RawValue>::deserialize(deserializer)?;
+                let #import_path::EventDeHelper { ev_type, .. } =
+                    #import_path::from_raw_json_value(&json)?;
+
+                Self::from_raw_json_with_type(&ev_type, &json)
+            }
+        }
     };
 
     let event_enum_to_from_sync = expand_conversion_impl(kind, var, &variants, import_path);
@@ -157,6 +172,8 @@ fn expand_any_with_deser(
 
     let redact_impl = expand_redact(&ident, kind, var, &variants, import_path);
 
+    let downcast_impl = expand_downcast_methods(&ident, events, variants, &content);
+
     Some(quote! {
         #any_enum
 
@@ -166,12 +183,60 @@ fn expand_any_with_deser(
 
         #redact_impl
 
+        #downcast_impl
+
         #event_deserialize_impl
 
         #redacted_enum
     })
 }
 
+/// Generates `as_*` and `into_*` methods that return the inner event if it is of a given type.
+fn expand_downcast_methods(
+    ident: &Ident,
+    events: &[LitStr],
+    variants: &[Ident],
+    content: &[TokenStream],
+) -> TokenStream {
+    let methods = events.iter().zip(variants).zip(content).map(|((event, variant), event_ty)| {
+        let span = event.span();
+        let event_str = event.value();
+        // `to_camel_case` made sure that all event types start with `m.`.
+        let method_suffix = event_str[2..].replace('.', "_");
+
+        let as_method = Ident::new(&format!("as_{}", method_suffix), span);
+        let into_method = Ident::new(&format!("into_{}", method_suffix), span);
+
+        let as_docs =
+            format!("Returns a reference to the inner event if this is a `{}` event.", event_str);
+        let into_docs = format!("Returns the inner event if this is a `{}` event.", event_str);
+
+        quote! {
+            #[doc = #as_docs]
+            pub fn #as_method(&self) -> Option<&#event_ty> {
+                match self {
+                    Self::#variant(event) => Some(event),
+                    _ => None,
+                }
+            }
+
+            #[doc = #into_docs]
+            pub fn #into_method(self) -> Option<#event_ty> {
+                match self {
+                    Self::#variant(event) => Some(event),
+                    _ => None,
+                }
+            }
+        }
+    });
+
+    quote! {
+        impl #ident {
+            #( #methods )*
+        }
+    }
+}
+
 fn expand_conversion_impl(
     kind: &EventKind,
     var: &EventKindVariation,
@@ -212,6 +277,23 @@ fn expand_conversion_impl(
                 TokenStream::new()
             };
 
+            let custom = if var == &EventKindVariation::Full {
+                quote! {
+                    #ident::Custom(event) => Self::Custom(event.map(
+                        #import_path::#sync_struct::from,
+                        |json| {
+                            json.remove("room_id");
+                        },
+                    )),
+                }
+            } else {
+                quote! {
+                    #ident::Custom(event) => {
+                        Self::Custom(#import_path::#sync_struct::from(event))
+                    },
+                }
+            };
+
             Some(quote! {
                 impl From<#ident> for #sync {
                     fn from(event: #ident) -> Self {
@@ -222,9 +304,7 @@ fn expand_conversion_impl(
                                 },
                             )*
                             #redaction
-                            #ident::Custom(event) => {
-                                Self::Custom(#import_path::#sync_struct::from(event))
-                            },
+                            #custom
                         }
                     }
                 }
@@ -248,6 +328,27 @@ fn expand_conversion_impl(
                 TokenStream::new()
             };
 
+            let custom = if var == &EventKindVariation::Sync {
+                quote! {
+                    Self::Custom(event) => {
+                        let room_id_json =
+                            #import_path::exports::serde_json::Value::from(room_id.as_str());
+                        #full::Custom(event.map(
+                            |event| event.into_full_event(room_id),
+                            |json| {
+                                json.insert("room_id".to_owned(), room_id_json);
+                            },
+                        ))
+                    },
+                }
+            } else {
+                quote! {
+                    Self::Custom(event) => {
+                        #full::Custom(event.into_full_event(room_id))
+                    },
+                }
+            };
+
             Some(quote! {
                 impl #ident {
                     /// Convert this sync event into a full event, one with a room_id field.
@@ -259,9 +360,7 @@ fn expand_conversion_impl(
                                 },
                             )*
                             #redaction
-                            Self::Custom(event) => {
-                                #full::Custom(event.into_full_event(room_id))
-                            },
+                            #custom
                         }
                     }
                 }
@@ -447,6 +546,7 @@ fn expand_redact(
                             }
                         )*
                         Self::Custom(event) => {
+                            let event = event.into_event();
                             let content = event.content.redact(version);
                             #redaction_enum::Custom(#redaction_type {
                                 content,
@@ -559,7 +659,7 @@ fn generate_custom_variant(
                     let event = #import_path::exports::serde_json::from_str::<
                         #import_path::#event_struct<#import_path::custom::RedactedCustomEventContent>,
                     >(json.get())
-                    .map_err(D::Error::custom)?;
+                    .map_err(E::custom)?;
 
                     Ok(Self::Custom(event))
                 },
@@ -569,15 +669,24 @@ fn generate_custom_variant(
         (
             quote! {
                 /// An event not defined by the Matrix specification
-                Custom(#import_path::#event_struct<#import_path::custom::CustomEventContent>),
+                ///
+                /// The original JSON of the event is kept, so fields that are not part of the
+                /// event's typed representation survive serialization.
+                Custom(
+                    #import_path::custom::CustomEvent<
+                        #import_path::#event_struct<#import_path::custom::CustomEventContent>,
+                    >,
+                ),
             },
             quote! {
                 event => {
                     let event =
                         #import_path::exports::serde_json::from_str::<
-                            #import_path::#event_struct<#import_path::custom::CustomEventContent>
+                            #import_path::custom::CustomEvent<
+                                #import_path::#event_struct<#import_path::custom::CustomEventContent>,
+                            >,
                         >(json.get())
-                        .map_err(D::Error::custom)?;
+                        .map_err(E::custom)?;
 
                     Ok(Self::Custom(event))
                 },
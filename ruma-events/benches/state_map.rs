@@ -0,0 +1,80 @@
+// `cargo bench` works, but if you use `cargo bench -- --save-baseline <name>`
+// or pass any other args to it, it fails with the error
+// `cargo bench unknown option --save-baseline`.
+// To pass args to criterion, use this form
+// `cargo bench --features criterion --bench <name of the bench> -- --save-baseline <name>`.
+
+#![allow(unused_imports, dead_code)]
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "criterion")]
+use criterion::{criterion_group, criterion_main, Criterion};
+use ruma_events::EventType;
+
+/// 10,000 state keys of a room: mostly members, some custom state and the usual singletons.
+fn state_keys() -> Vec<(EventType, String)> {
+    let singletons = [
+        EventType::RoomCreate,
+        EventType::RoomJoinRules,
+        EventType::RoomName,
+        EventType::RoomPowerLevels,
+        EventType::RoomTopic,
+    ];
+
+    (0..10_000)
+        .map(|i| match i % 10 {
+            0 => (EventType::Custom(format!("io.ruma.state.{}", i % 100)), i.to_string()),
+            1 => (singletons[i % singletons.len()].clone(), String::new()),
+            _ => (EventType::RoomMember, format!("@user{}:example.org", i)),
+        })
+        .collect()
+}
+
+/// Builds a state map keyed by `EventType`, and looks up every entry.
+#[cfg(feature = "criterion")]
+fn event_type_keys(c: &mut Criterion) {
+    let keys = state_keys();
+
+    c.bench_function("state map keyed by `(EventType, String)`", |b| {
+        b.iter(|| {
+            let map: BTreeMap<_, _> = keys.iter().cloned().zip(0..).collect();
+            for key in &keys {
+                assert!(map.contains_key(key));
+            }
+        })
+    });
+}
+
+/// Builds a state map keyed by the event type string, like before `EventType` was ordered by its
+/// string, and looks up every entry.
+#[cfg(feature = "criterion")]
+fn string_keys(c: &mut Criterion) {
+    let keys = state_keys();
+
+    c.bench_function("state map keyed by `(String, String)`", |b| {
+        b.iter(|| {
+            let map: BTreeMap<_, _> = keys
+                .iter()
+                .map(|(event_type, state_key)| (event_type.to_string(), state_key.clone()))
+                .zip(0..)
+                .collect();
+            for (event_type, state_key) in &keys {
+                assert!(map.contains_key(&(event_type.to_string(), state_key.clone())));
+            }
+        })
+    });
+}
+
+#[cfg(feature = "criterion")]
+criterion_group!(benches, event_type_keys, string_keys);
+
+#[cfg(feature = "criterion")]
+criterion_main!(benches);
+
+#[cfg(not(feature = "criterion"))]
+fn main() {
+    // To run the benchmarks the "criterion" feature must be enabled use:
+    // `cargo bench --features criterion --bench state_map`
+    panic!("Enable the criterion feature to run benchmarks");
+}
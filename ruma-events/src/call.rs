@@ -2,16 +2,23 @@
 //!
 //! This module also contains types shared by events in its child namespaces.
 
-use serde::{Deserialize, Serialize};
-use strum::{Display, EnumString};
+use ruma_serde::string_enum;
+use serde::{
+    de::{Deserializer, Error as _},
+    ser::Serializer,
+    Deserialize, Serialize,
+};
 
 pub mod answer;
 pub mod candidates;
 pub mod hangup;
 pub mod invite;
+pub mod negotiate;
+pub mod reject;
+pub mod select_answer;
 
 /// A VoIP session description.
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct SessionDescription {
     /// The type of session description.
     #[serde(rename = "type")]
@@ -21,15 +28,95 @@ pub struct SessionDescription {
     pub sdp: String,
 }
 
-/// The type of VoIP session description.
-#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, Deserialize, Serialize)]
+string_enum! {
+    /// The type of VoIP session description.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum SessionDescriptionType {
+        /// An answer.
+        Answer => "answer",
+
+        /// An offer.
+        Offer => "offer",
+    }
+}
+
+/// The version of the VoIP specification a call event adheres to.
+///
+/// Version 0 is sent as the integer `0`, while later versions are sent as strings, like `"1"`.
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[non_exhaustive]
-#[serde(rename_all = "snake_case")]
-#[strum(serialize_all = "snake_case")]
-pub enum SessionDescriptionType {
-    /// An answer.
-    Answer,
-
-    /// An offer.
-    Offer,
+pub enum VoipVersion {
+    /// Version 0, the original VoIP specification.
+    V0,
+
+    /// Version 1, which adds `party_id`, `m.call.select_answer`, `m.call.reject` and
+    /// `m.call.negotiate`.
+    V1,
+
+    #[doc(hidden)]
+    _Custom(String),
+}
+
+impl Serialize for VoipVersion {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::V0 => serializer.serialize_u64(0),
+            Self::V1 => serializer.serialize_str("1"),
+            Self::_Custom(version) => serializer.serialize_str(version),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for VoipVersion {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum IntOrString {
+            Int(u64),
+            String(String),
+        }
+
+        match IntOrString::deserialize(deserializer)? {
+            IntOrString::Int(0) => Ok(Self::V0),
+            IntOrString::Int(version) => {
+                Err(D::Error::custom(format!("invalid integer VoIP version: {}", version)))
+            }
+            IntOrString::String(version) => Ok(match version.as_str() {
+                "1" => Self::V1,
+                _ => Self::_Custom(version),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use matches::assert_matches;
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::VoipVersion;
+
+    #[test]
+    fn serialize_version() {
+        assert_eq!(to_json_value(VoipVersion::V0).unwrap(), json!(0));
+        assert_eq!(to_json_value(VoipVersion::V1).unwrap(), json!("1"));
+    }
+
+    #[test]
+    fn deserialize_version() {
+        assert_eq!(from_json_value::<VoipVersion>(json!(0)).unwrap(), VoipVersion::V0);
+        assert_eq!(from_json_value::<VoipVersion>(json!("1")).unwrap(), VoipVersion::V1);
+        assert_matches!(
+            from_json_value::<VoipVersion>(json!("2")).unwrap(),
+            VoipVersion::_Custom(version) if version == "2"
+        );
+        assert!(from_json_value::<VoipVersion>(json!(1)).is_err());
+    }
 }
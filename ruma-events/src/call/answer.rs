@@ -1,17 +1,16 @@
 //! Types for the *m.call.answer* event.
 
-use js_int::UInt;
 use ruma_events_macros::MessageEventContent;
 use serde::{Deserialize, Serialize};
 
-use super::SessionDescription;
+use super::{SessionDescription, VoipVersion};
 use crate::MessageEvent;
 
 /// This event is sent by the callee when they wish to answer the call.
 pub type AnswerEvent = MessageEvent<AnswerEventContent>;
 
 /// The payload for `AnswerEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, MessageEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, MessageEventContent)]
 #[ruma_event(type = "m.call.answer")]
 pub struct AnswerEventContent {
     /// The VoIP session description object. The session description type must be *answer*.
@@ -20,6 +19,11 @@ pub struct AnswerEventContent {
     /// The ID of the call this event relates to.
     pub call_id: String,
 
+    /// The unique ID of the party that sent this event, if it adheres to version 1 or later of the
+    /// VoIP specification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub party_id: Option<String>,
+
     /// The version of the VoIP specification this messages adheres to.
-    pub version: UInt,
+    pub version: VoipVersion,
 }
@@ -4,6 +4,7 @@ use js_int::UInt;
 use ruma_events_macros::MessageEventContent;
 use serde::{Deserialize, Serialize};
 
+use super::VoipVersion;
 use crate::MessageEvent;
 
 /// This event is sent by callers after sending an invite and by the callee after answering. Its
@@ -11,7 +12,7 @@ use crate::MessageEvent;
 pub type CandidatesEvent = MessageEvent<CandidatesEventContent>;
 
 /// The payload for `CandidatesEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, MessageEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, MessageEventContent)]
 #[ruma_event(type = "m.call.candidates")]
 pub struct CandidatesEventContent {
     /// The ID of the call this event relates to.
@@ -20,12 +21,17 @@ pub struct CandidatesEventContent {
     /// A list of candidates.
     pub candidates: Vec<Candidate>,
 
+    /// The unique ID of the party that sent this event, if it adheres to version 1 or later of the
+    /// VoIP specification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub party_id: Option<String>,
+
     /// The version of the VoIP specification this messages adheres to.
-    pub version: UInt,
+    pub version: VoipVersion,
 }
 
 /// An ICE (Interactive Connectivity Establishment) candidate.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Candidate {
     /// The SDP "a" line of the candidate.
@@ -1,10 +1,10 @@
 //! Types for the *m.call.hangup* event.
 
-use js_int::UInt;
 use ruma_events_macros::MessageEventContent;
+use ruma_serde::string_enum;
 use serde::{Deserialize, Serialize};
-use strum::{Display, EnumString};
 
+use super::VoipVersion;
 use crate::MessageEvent;
 
 /// Sent by either party to signal their termination of the call. This can be sent either once the
@@ -12,33 +12,38 @@ use crate::MessageEvent;
 pub type HangupEvent = MessageEvent<HangupEventContent>;
 
 /// The payload for `HangupEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, MessageEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, MessageEventContent)]
 #[ruma_event(type = "m.call.hangup")]
 pub struct HangupEventContent {
     /// The ID of the call this event relates to.
     pub call_id: String,
 
+    /// The unique ID of the party that sent this event, if it adheres to version 1 or later of the
+    /// VoIP specification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub party_id: Option<String>,
+
     /// The version of the VoIP specification this messages adheres to.
-    pub version: UInt,
+    pub version: VoipVersion,
 
     /// Optional error reason for the hangup.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reason: Option<Reason>,
 }
 
-/// A reason for a hangup.
-///
-/// This should not be provided when the user naturally ends or rejects the call. When there was an
-/// error in the call negotiation, this should be `ice_failed` for when ICE negotiation fails or
-/// `invite_timeout` for when the other party did not answer in time.
-#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, Deserialize, Serialize)]
-#[non_exhaustive]
-#[serde(rename_all = "snake_case")]
-#[strum(serialize_all = "snake_case")]
-pub enum Reason {
-    /// ICE negotiation failure.
-    IceFailed,
-
-    /// Party did not answer in time.
-    InviteTimeout,
+string_enum! {
+    /// A reason for a hangup.
+    ///
+    /// This should not be provided when the user naturally ends or rejects the call. When there was
+    /// an error in the call negotiation, this should be `ice_failed` for when ICE negotiation fails
+    /// or `invite_timeout` for when the other party did not answer in time.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum Reason {
+        /// ICE negotiation failure.
+        IceFailed => "ice_failed",
+
+        /// Party did not answer in time.
+        InviteTimeout => "invite_timeout",
+    }
 }
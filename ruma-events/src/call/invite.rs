@@ -1,17 +1,19 @@
 //! Types for the *m.call.invite* event.
 
+use std::time::Duration;
+
 use js_int::UInt;
 use ruma_events_macros::MessageEventContent;
 use serde::{Deserialize, Serialize};
 
-use super::SessionDescription;
-use crate::MessageEvent;
+use super::{SessionDescription, VoipVersion};
+use crate::{duration_to_millis, MessageEvent};
 
 /// This event is sent by the caller when they wish to establish a call.
 pub type InviteEvent = MessageEvent<InviteEventContent>;
 
 /// The payload for `InviteEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, MessageEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, MessageEventContent)]
 #[ruma_event(type = "m.call.invite")]
 pub struct InviteEventContent {
     /// A unique identifer for the call.
@@ -25,6 +27,23 @@ pub struct InviteEventContent {
     /// The session description object. The session description type must be *offer*.
     pub offer: SessionDescription,
 
+    /// The unique ID of the party that sent this event, if it adheres to version 1 or later of the
+    /// VoIP specification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub party_id: Option<String>,
+
     /// The version of the VoIP specification this messages adheres to.
-    pub version: UInt,
+    pub version: VoipVersion,
+}
+
+impl InviteEventContent {
+    /// The time that the invite is valid for, as a `Duration`.
+    pub fn lifetime_duration(&self) -> Duration {
+        Duration::from_millis(self.lifetime.into())
+    }
+
+    /// Sets `lifetime` from a `Duration`.
+    pub fn set_lifetime_duration(&mut self, lifetime: Duration) {
+        self.lifetime = duration_to_millis(lifetime);
+    }
 }
@@ -0,0 +1,46 @@
+//! Types for the *m.call.negotiate* event.
+
+use std::time::Duration;
+
+use js_int::UInt;
+use ruma_events_macros::MessageEventContent;
+use serde::{Deserialize, Serialize};
+
+use super::{SessionDescription, VoipVersion};
+use crate::{duration_to_millis, MessageEvent};
+
+/// This event is sent by either party during a call to renegotiate the session, e.g. to add a
+/// video stream.
+pub type NegotiateEvent = MessageEvent<NegotiateEventContent>;
+
+/// The payload for `NegotiateEvent`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, MessageEventContent)]
+#[ruma_event(type = "m.call.negotiate")]
+pub struct NegotiateEventContent {
+    /// The ID of the call this event relates to.
+    pub call_id: String,
+
+    /// The unique ID of the party that sent this event.
+    pub party_id: String,
+
+    /// The time in milliseconds that the negotiation is valid for.
+    pub lifetime: UInt,
+
+    /// The session description object of the negotiation, either an *offer* or an *answer*.
+    pub description: SessionDescription,
+
+    /// The version of the VoIP specification this messages adheres to.
+    pub version: VoipVersion,
+}
+
+impl NegotiateEventContent {
+    /// The time that the negotiation is valid for, as a `Duration`.
+    pub fn lifetime_duration(&self) -> Duration {
+        Duration::from_millis(self.lifetime.into())
+    }
+
+    /// Sets `lifetime` from a `Duration`.
+    pub fn set_lifetime_duration(&mut self, lifetime: Duration) {
+        self.lifetime = duration_to_millis(lifetime);
+    }
+}
@@ -0,0 +1,32 @@
+//! Types for the *m.call.reject* event.
+
+use ruma_events_macros::MessageEventContent;
+use serde::{Deserialize, Serialize};
+
+use super::VoipVersion;
+use crate::MessageEvent;
+
+/// This event is sent by the callee to reject an invite. Unlike a hangup, it also tells the
+/// callee's other devices to stop ringing.
+pub type RejectEvent = MessageEvent<RejectEventContent>;
+
+/// The payload for `RejectEvent`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, MessageEventContent)]
+#[ruma_event(type = "m.call.reject")]
+pub struct RejectEventContent {
+    /// The ID of the call this event relates to.
+    pub call_id: String,
+
+    /// The unique ID of the party that sent this event.
+    pub party_id: String,
+
+    /// The version of the VoIP specification this messages adheres to.
+    pub version: VoipVersion,
+}
+
+impl RejectEventContent {
+    /// Creates a new `RejectEventContent` for version 1 of the VoIP specification.
+    pub fn new(call_id: String, party_id: String) -> Self {
+        Self { call_id, party_id, version: VoipVersion::V1 }
+    }
+}
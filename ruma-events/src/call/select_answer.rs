@@ -0,0 +1,35 @@
+//! Types for the *m.call.select_answer* event.
+
+use ruma_events_macros::MessageEventContent;
+use serde::{Deserialize, Serialize};
+
+use super::VoipVersion;
+use crate::MessageEvent;
+
+/// This event is sent by the caller when they have chosen an answer, to tell the other callees
+/// that their answers were not selected.
+pub type SelectAnswerEvent = MessageEvent<SelectAnswerEventContent>;
+
+/// The payload for `SelectAnswerEvent`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, MessageEventContent)]
+#[ruma_event(type = "m.call.select_answer")]
+pub struct SelectAnswerEventContent {
+    /// The ID of the call this event relates to.
+    pub call_id: String,
+
+    /// The unique ID of the party that sent this event.
+    pub party_id: String,
+
+    /// The `party_id` of the answer that was selected.
+    pub selected_party_id: String,
+
+    /// The version of the VoIP specification this messages adheres to.
+    pub version: VoipVersion,
+}
+
+impl SelectAnswerEventContent {
+    /// Creates a new `SelectAnswerEventContent` for version 1 of the VoIP specification.
+    pub fn new(call_id: String, party_id: String, selected_party_id: String) -> Self {
+        Self { call_id, party_id, selected_party_id, version: VoipVersion::V1 }
+    }
+}
@@ -0,0 +1,57 @@
+//! Deserialization helpers for the `compat` feature.
+//!
+//! They relax the parsing of specific fields to accept spec violations that real-world
+//! homeservers are known to produce.
+
+use std::collections::BTreeMap;
+
+use js_int::Int;
+use serde::{
+    de::{Deserializer, Error},
+    Deserialize,
+};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum IntOrString {
+    Int(Int),
+    String(String),
+}
+
+impl IntOrString {
+    fn into_int<E: Error>(self) -> Result<Int, E> {
+        match self {
+            Self::Int(int) => Ok(int),
+            Self::String(string) => string.parse().map_err(E::custom),
+        }
+    }
+}
+
+/// Deserializes an integer that may also be encoded as a string, like `"50"`.
+pub fn int_or_string<'de, D>(deserializer: D) -> Result<Int, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    IntOrString::deserialize(deserializer)?.into_int()
+}
+
+/// Deserializes a map whose integer values may also be encoded as strings, like `"50"`.
+pub fn btreemap_int_or_string<'de, D, K>(deserializer: D) -> Result<BTreeMap<K, Int>, D::Error>
+where
+    D: Deserializer<'de>,
+    K: Deserialize<'de> + Ord,
+{
+    BTreeMap::<K, IntOrString>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(key, value)| Ok((key, value.into_int()?)))
+        .collect()
+}
+
+/// Deserializes a `null` value as the type's default value.
+pub fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
@@ -1,8 +1,16 @@
 //! Types for custom events outside of the Matrix specification.
 
+use std::ops::Deref;
+
 use ruma_identifiers::RoomVersionId;
-use serde::Serialize;
-use serde_json::{value::RawValue as RawJsonValue, Value as JsonValue};
+use serde::{
+    de::{DeserializeOwned, Deserializer, Error as _},
+    Deserialize, Serialize, Serializer,
+};
+use serde_json::{
+    value::{to_raw_value as to_raw_json_value, RawValue as RawJsonValue},
+    Map as JsonObject, Value as JsonValue,
+};
 
 use crate::{
     BasicEventContent, EphemeralRoomEventContent, EventContent, HasDeserializeFields,
@@ -10,6 +18,98 @@ use crate::{
     RedactedStateEventContent, RoomEventContent, StateEventContent,
 };
 
+/// An event not defined by the Matrix specification, as contained in the `Custom` variant of the
+/// `Any*Event` enums.
+///
+/// When deserialized, it keeps the original JSON of the event, which is used again for
+/// serialization. This way, fields outside of the standard event shape survive a round-trip. The
+/// typed event is available through `Deref`, but can't be modified in place since that would make
+/// it disagree with the original JSON; use `into_event` and `new` for that.
+#[derive(Clone, Debug)]
+pub struct CustomEvent<E> {
+    event: E,
+    json: Option<Box<RawJsonValue>>,
+}
+
+impl<E> CustomEvent<E> {
+    /// Creates a new `CustomEvent` from the given typed event.
+    ///
+    /// It is serialized from the typed event, since there is no original JSON.
+    pub fn new(event: E) -> Self {
+        Self { event, json: None }
+    }
+
+    /// The typed event.
+    pub fn event(&self) -> &E {
+        &self.event
+    }
+
+    /// Converts `self` into the typed event, discarding the original JSON.
+    pub fn into_event(self) -> E {
+        self.event
+    }
+
+    /// The JSON this event was deserialized from, if any.
+    pub fn json(&self) -> Option<&RawJsonValue> {
+        self.json.as_deref()
+    }
+
+    /// Converts the typed event with `f`, and applies the same change to the original JSON with
+    /// `edit_json`, if there is one.
+    #[doc(hidden)]
+    pub fn map<E2>(
+        self,
+        f: impl FnOnce(E) -> E2,
+        edit_json: impl FnOnce(&mut JsonObject<String, JsonValue>),
+    ) -> CustomEvent<E2> {
+        let json = self.json.and_then(|json| {
+            let mut object = serde_json::from_str(json.get()).ok()?;
+            edit_json(&mut object);
+            to_raw_json_value(&object).ok()
+        });
+
+        CustomEvent { event: f(self.event), json }
+    }
+}
+
+impl<E> From<E> for CustomEvent<E> {
+    fn from(event: E) -> Self {
+        Self::new(event)
+    }
+}
+
+impl<E> Deref for CustomEvent<E> {
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        &self.event
+    }
+}
+
+impl<E: Serialize> Serialize for CustomEvent<E> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match &self.json {
+            Some(json) => json.serialize(serializer),
+            None => self.event.serialize(serializer),
+        }
+    }
+}
+
+impl<'de, E: DeserializeOwned> Deserialize<'de> for CustomEvent<E> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let json = Box::<RawJsonValue>::deserialize(deserializer)?;
+        let event = serde_json::from_str(json.get()).map_err(D::Error::custom)?;
+
+        Ok(Self { event, json: Some(json) })
+    }
+}
+
 /// A custom event's type and `content` JSON object.
 #[derive(Clone, Debug, Serialize)]
 pub struct CustomEventContent {
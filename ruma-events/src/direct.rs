@@ -16,7 +16,7 @@ pub type DirectEvent = crate::BasicEvent<DirectEventContent>;
 ///
 /// A mapping of `UserId`s to a list of `RoomId`s which are considered *direct* for that
 /// particular user.
-#[derive(Clone, Debug, Deserialize, Serialize, BasicEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, BasicEventContent)]
 #[ruma_event(type = "m.direct")]
 pub struct DirectEventContent(pub BTreeMap<UserId, Vec<RoomId>>);
 
@@ -20,7 +20,7 @@ use crate::BasicEvent;
 pub type DummyEvent = BasicEvent<DummyEventContent>;
 
 /// The payload for `DummyEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, BasicEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, BasicEventContent)]
 #[ruma_event(type = "m.dummy")]
 pub struct DummyEventContent(pub Empty);
 
@@ -1,8 +1,15 @@
+use std::convert::TryFrom;
+
 use ruma_events_macros::event_enum;
 use serde::{de, Serialize};
 use serde_json::value::RawValue as RawJsonValue;
 
-use crate::{from_raw_json_value, EventDeHelper};
+use crate::{
+    from_raw_json_value,
+    room::redaction::{RedactionEvent, SyncRedactionEvent},
+    BasicEvent, BasicEventContent, EphemeralRoomEventContent, EventDeHelper,
+    SyncEphemeralRoomEvent,
+};
 
 event_enum! {
     /// Any basic event.
@@ -14,6 +21,7 @@ event_enum! {
         "m.presence",
         "m.push_rules",
         "m.room_key",
+        "m.secret_storage.default_key",
         "m.tag",
     ]
 }
@@ -36,6 +44,9 @@ event_enum! {
         "m.call.invite",
         "m.call.hangup",
         "m.call.candidates",
+        "m.call.negotiate",
+        "m.call.reject",
+        "m.call.select_answer",
         "m.room.encrypted",
         "m.room.message",
         "m.room.message.feedback",
@@ -64,6 +75,8 @@ event_enum! {
         "m.room.third_party_invite",
         "m.room.tombstone",
         "m.room.topic",
+        "m.space.child",
+        "m.space.parent",
     ]
 }
 
@@ -139,29 +152,31 @@ impl<'de> de::Deserialize<'de> for AnyEvent {
         D: de::Deserializer<'de>,
     {
         let json = Box::<RawJsonValue>::deserialize(deserializer)?;
-        let EventDeHelper { state_key, event_id, room_id, unsigned, .. } =
+        let EventDeHelper { ev_type, state_key, event_id, room_id, unsigned, .. } =
             from_raw_json_value(&json)?;
 
         // Determine whether the event is a state, message, ephemeral, or basic event
         // based on the fields present.
         if state_key.is_some() {
             Ok(match unsigned {
-                Some(unsigned) if unsigned.redacted_because.is_some() => {
-                    AnyEvent::RedactedState(from_raw_json_value(&json)?)
-                }
-                _ => AnyEvent::State(from_raw_json_value(&json)?),
+                Some(unsigned) if unsigned.redacted_because.is_some() => AnyEvent::RedactedState(
+                    AnyRedactedStateEvent::from_raw_json_with_type(&ev_type, &json)?,
+                ),
+                _ => AnyEvent::State(AnyStateEvent::from_raw_json_with_type(&ev_type, &json)?),
             })
         } else if event_id.is_some() {
             Ok(match unsigned {
-                Some(unsigned) if unsigned.redacted_because.is_some() => {
-                    AnyEvent::RedactedMessage(from_raw_json_value(&json)?)
-                }
-                _ => AnyEvent::Message(from_raw_json_value(&json)?),
+                Some(unsigned) if unsigned.redacted_because.is_some() => AnyEvent::RedactedMessage(
+                    AnyRedactedMessageEvent::from_raw_json_with_type(&ev_type, &json)?,
+                ),
+                _ => AnyEvent::Message(AnyMessageEvent::from_raw_json_with_type(&ev_type, &json)?),
             })
         } else if room_id.is_some() {
-            Ok(AnyEvent::Ephemeral(from_raw_json_value(&json)?))
+            Ok(AnyEvent::Ephemeral(AnyEphemeralRoomEvent::from_raw_json_with_type(
+                &ev_type, &json,
+            )?))
         } else {
-            Ok(AnyEvent::Basic(from_raw_json_value(&json)?))
+            Ok(AnyEvent::Basic(AnyBasicEvent::from_raw_json_with_type(&ev_type, &json)?))
         }
     }
 }
@@ -172,21 +187,27 @@ impl<'de> de::Deserialize<'de> for AnyRoomEvent {
         D: de::Deserializer<'de>,
     {
         let json = Box::<RawJsonValue>::deserialize(deserializer)?;
-        let EventDeHelper { state_key, unsigned, .. } = from_raw_json_value(&json)?;
+        let EventDeHelper { ev_type, state_key, unsigned, .. } = from_raw_json_value(&json)?;
 
         if state_key.is_some() {
             Ok(match unsigned {
                 Some(unsigned) if unsigned.redacted_because.is_some() => {
-                    AnyRoomEvent::RedactedState(from_raw_json_value(&json)?)
+                    AnyRoomEvent::RedactedState(AnyRedactedStateEvent::from_raw_json_with_type(
+                        &ev_type, &json,
+                    )?)
                 }
-                _ => AnyRoomEvent::State(from_raw_json_value(&json)?),
+                _ => AnyRoomEvent::State(AnyStateEvent::from_raw_json_with_type(&ev_type, &json)?),
             })
         } else {
             Ok(match unsigned {
                 Some(unsigned) if unsigned.redacted_because.is_some() => {
-                    AnyRoomEvent::RedactedMessage(from_raw_json_value(&json)?)
+                    AnyRoomEvent::RedactedMessage(AnyRedactedMessageEvent::from_raw_json_with_type(
+                        &ev_type, &json,
+                    )?)
                 }
-                _ => AnyRoomEvent::Message(from_raw_json_value(&json)?),
+                _ => AnyRoomEvent::Message(AnyMessageEvent::from_raw_json_with_type(
+                    &ev_type, &json,
+                )?),
             })
         }
     }
@@ -198,22 +219,142 @@ impl<'de> de::Deserialize<'de> for AnySyncRoomEvent {
         D: de::Deserializer<'de>,
     {
         let json = Box::<RawJsonValue>::deserialize(deserializer)?;
-        let EventDeHelper { state_key, unsigned, .. } = from_raw_json_value(&json)?;
+        let EventDeHelper { ev_type, state_key, unsigned, .. } = from_raw_json_value(&json)?;
 
         if state_key.is_some() {
             Ok(match unsigned {
                 Some(unsigned) if unsigned.redacted_because.is_some() => {
-                    AnySyncRoomEvent::RedactedState(from_raw_json_value(&json)?)
+                    AnySyncRoomEvent::RedactedState(
+                        AnyRedactedSyncStateEvent::from_raw_json_with_type(&ev_type, &json)?,
+                    )
                 }
-                _ => AnySyncRoomEvent::State(from_raw_json_value(&json)?),
+                _ => AnySyncRoomEvent::State(AnySyncStateEvent::from_raw_json_with_type(
+                    &ev_type, &json,
+                )?),
             })
         } else {
             Ok(match unsigned {
                 Some(unsigned) if unsigned.redacted_because.is_some() => {
-                    AnySyncRoomEvent::RedactedMessage(from_raw_json_value(&json)?)
+                    AnySyncRoomEvent::RedactedMessage(
+                        AnyRedactedSyncMessageEvent::from_raw_json_with_type(&ev_type, &json)?,
+                    )
                 }
-                _ => AnySyncRoomEvent::Message(from_raw_json_value(&json)?),
+                _ => AnySyncRoomEvent::Message(AnySyncMessageEvent::from_raw_json_with_type(
+                    &ev_type, &json,
+                )?),
             })
         }
     }
 }
+
+/// Implements `From<$variant_ty>` for `$enum`, and `TryFrom<$enum>` for `$variant_ty`, which
+/// returns the original event if it is of a different variant.
+macro_rules! variant_conversions {
+    ($enum:ident { $( $variant:ident($variant_ty:ident) ),* $(,)? }) => {
+        $(
+            impl From<$variant_ty> for $enum {
+                fn from(event: $variant_ty) -> Self {
+                    Self::$variant(event)
+                }
+            }
+
+            impl TryFrom<$enum> for $variant_ty {
+                type Error = $enum;
+
+                fn try_from(event: $enum) -> Result<Self, Self::Error> {
+                    match event {
+                        $enum::$variant(event) => Ok(event),
+                        other => Err(other),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+variant_conversions!(AnyEvent {
+    Basic(AnyBasicEvent),
+    Ephemeral(AnyEphemeralRoomEvent),
+    Message(AnyMessageEvent),
+    State(AnyStateEvent),
+    RedactedMessage(AnyRedactedMessageEvent),
+    RedactedState(AnyRedactedStateEvent),
+});
+
+variant_conversions!(AnyRoomEvent {
+    Message(AnyMessageEvent),
+    State(AnyStateEvent),
+    RedactedMessage(AnyRedactedMessageEvent),
+    RedactedState(AnyRedactedStateEvent),
+});
+
+variant_conversions!(AnySyncRoomEvent {
+    Message(AnySyncMessageEvent),
+    State(AnySyncStateEvent),
+    RedactedMessage(AnyRedactedSyncMessageEvent),
+    RedactedState(AnyRedactedSyncStateEvent),
+});
+
+impl AnyRoomEvent {
+    /// Returns the *m.room.redaction* event that redacted this event, if it has been redacted.
+    ///
+    /// Clients can use its `sender` and `content.reason` to show who redacted the event and why.
+    pub fn redacted_because(&self) -> Option<&RedactionEvent> {
+        match self {
+            Self::Message(_) | Self::State(_) => None,
+            Self::RedactedMessage(event) => event.unsigned().redacted_because.as_deref(),
+            Self::RedactedState(event) => event.unsigned().redacted_because.as_deref(),
+        }
+    }
+}
+
+impl AnySyncRoomEvent {
+    /// Returns the *m.room.redaction* event that redacted this event, if it has been redacted.
+    ///
+    /// Clients can use its `sender` and `content.reason` to show who redacted the event and why.
+    pub fn redacted_because(&self) -> Option<&SyncRedactionEvent> {
+        match self {
+            Self::Message(_) | Self::State(_) => None,
+            Self::RedactedMessage(event) => event.unsigned().redacted_because.as_deref(),
+            Self::RedactedState(event) => event.unsigned().redacted_because.as_deref(),
+        }
+    }
+}
+
+impl From<AnyRoomEvent> for AnyEvent {
+    fn from(event: AnyRoomEvent) -> Self {
+        match event {
+            AnyRoomEvent::Message(event) => Self::Message(event),
+            AnyRoomEvent::State(event) => Self::State(event),
+            AnyRoomEvent::RedactedMessage(event) => Self::RedactedMessage(event),
+            AnyRoomEvent::RedactedState(event) => Self::RedactedState(event),
+        }
+    }
+}
+
+/// Fails with the original event for basic and ephemeral events.
+impl TryFrom<AnyEvent> for AnyRoomEvent {
+    type Error = AnyEvent;
+
+    fn try_from(event: AnyEvent) -> Result<Self, Self::Error> {
+        match event {
+            AnyEvent::Message(event) => Ok(Self::Message(event)),
+            AnyEvent::State(event) => Ok(Self::State(event)),
+            AnyEvent::RedactedMessage(event) => Ok(Self::RedactedMessage(event)),
+            AnyEvent::RedactedState(event) => Ok(Self::RedactedState(event)),
+            other => Err(other),
+        }
+    }
+}
+
+impl<C: BasicEventContent> From<C> for BasicEvent<C> {
+    fn from(content: C) -> Self {
+        Self { content }
+    }
+}
+
+impl<C: EphemeralRoomEventContent> From<C> for SyncEphemeralRoomEvent<C> {
+    fn from(content: C) -> Self {
+        Self { content }
+    }
+}
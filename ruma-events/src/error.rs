@@ -3,6 +3,76 @@ use std::{
     fmt::{self, Display, Formatter},
 };
 
+use serde::Deserialize;
+use serde_json::value::RawValue as RawJsonValue;
+
+/// An error returned when an event failed to deserialize.
+///
+/// In contrast to a plain `serde_json::Error`, it carries the JSON of the offending event, so it
+/// can be identified in a large response.
+#[derive(Clone, Debug)]
+pub struct InvalidEvent {
+    json: Box<RawJsonValue>,
+    event_type: Option<String>,
+    index: Option<usize>,
+    message: String,
+}
+
+impl InvalidEvent {
+    pub(crate) fn new(json: Box<RawJsonValue>, error: serde_json::Error) -> Self {
+        #[derive(Deserialize)]
+        struct EventTypeDeHelper {
+            #[serde(rename = "type")]
+            event_type: String,
+        }
+
+        let event_type = serde_json::from_str::<EventTypeDeHelper>(json.get())
+            .ok()
+            .map(|helper| helper.event_type);
+
+        Self { json, event_type, index: None, message: error.to_string() }
+    }
+
+    pub(crate) fn with_index(self, index: usize) -> Self {
+        Self { index: Some(index), ..self }
+    }
+
+    /// The full JSON of the event that failed to deserialize.
+    pub fn json(&self) -> &RawJsonValue {
+        &self.json
+    }
+
+    /// The `type` field of the event, if it could be found.
+    pub fn event_type(&self) -> Option<&str> {
+        self.event_type.as_deref()
+    }
+
+    /// The position of the event in the list it was deserialized from, if any.
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// The message of the underlying deserialization error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for InvalidEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid event")?;
+        if let Some(event_type) = &self.event_type {
+            write!(f, " of type `{}`", event_type)?;
+        }
+        if let Some(index) = self.index {
+            write!(f, " at index {}", index)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl Error for InvalidEvent {}
+
 /// An error returned when attempting to create an event with data that would make it invalid.
 ///
 /// This type is similar to [`InvalidEvent`](struct.InvalidEvent.html), but used during the
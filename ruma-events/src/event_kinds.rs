@@ -10,14 +10,14 @@ use crate::{
 };
 
 /// A basic event – one that consists only of it's type and the `content` object.
-#[derive(Clone, Debug, Event)]
+#[derive(Clone, Debug, PartialEq, Eq, Event)]
 pub struct BasicEvent<C: BasicEventContent> {
     /// Data specific to the event type.
     pub content: C,
 }
 
 /// An ephemeral room event.
-#[derive(Clone, Debug, Event)]
+#[derive(Clone, Debug, PartialEq, Eq, Event)]
 pub struct EphemeralRoomEvent<C: EphemeralRoomEventContent> {
     /// Data specific to the event type.
     pub content: C,
@@ -27,7 +27,7 @@ pub struct EphemeralRoomEvent<C: EphemeralRoomEventContent> {
 }
 
 /// An ephemeral room event without a `room_id`.
-#[derive(Clone, Debug, Event)]
+#[derive(Clone, Debug, PartialEq, Eq, Event)]
 pub struct SyncEphemeralRoomEvent<C: EphemeralRoomEventContent> {
     /// Data specific to the event type.
     pub content: C,
@@ -35,10 +35,9 @@ pub struct SyncEphemeralRoomEvent<C: EphemeralRoomEventContent> {
 
 /// A message event.
 ///
-/// `MessageEvent` implements the comparison traits using only
-/// the `event_id` field, a sorted list would be sorted lexicographically based on
-/// the event's `EventId`.
-#[derive(Clone, Debug, Event)]
+/// Two `MessageEvent`s are equal if all of their fields are equal.
+/// Events have no ordering.
+#[derive(Clone, Debug, PartialEq, Eq, Event)]
 pub struct MessageEvent<C: MessageEventContent> {
     /// Data specific to the event type.
     pub content: C,
@@ -61,10 +60,9 @@ pub struct MessageEvent<C: MessageEventContent> {
 
 /// A message event without a `room_id`.
 ///
-/// `SyncMessageEvent` implements the comparison traits using only
-/// the `event_id` field, a sorted list would be sorted lexicographically based on
-/// the event's `EventId`.
-#[derive(Clone, Debug, Event)]
+/// Two `SyncMessageEvent`s are equal if all of their fields are equal.
+/// Events have no ordering.
+#[derive(Clone, Debug, PartialEq, Eq, Event)]
 pub struct SyncMessageEvent<C: MessageEventContent> {
     /// Data specific to the event type.
     pub content: C,
@@ -84,10 +82,9 @@ pub struct SyncMessageEvent<C: MessageEventContent> {
 
 /// A redacted message event.
 ///
-/// `RedactedMessageEvent` implements the comparison traits using only
-/// the `event_id` field, a sorted list would be sorted lexicographically based on
-/// the event's `EventId`.
-#[derive(Clone, Debug, Event)]
+/// Two `RedactedMessageEvent`s are equal if all of their fields are equal.
+/// Events have no ordering.
+#[derive(Clone, Debug, PartialEq, Eq, Event)]
 pub struct RedactedMessageEvent<C: RedactedMessageEventContent> {
     /// Data specific to the event type.
     pub content: C,
@@ -110,10 +107,9 @@ pub struct RedactedMessageEvent<C: RedactedMessageEventContent> {
 
 /// A redacted message event without a `room_id`.
 ///
-/// `RedactedSyncMessageEvent` implements the comparison traits using only
-/// the `event_id` field, a sorted list would be sorted lexicographically based on
-/// the event's `EventId`.
-#[derive(Clone, Debug, Event)]
+/// Two `RedactedSyncMessageEvent`s are equal if all of their fields are equal.
+/// Events have no ordering.
+#[derive(Clone, Debug, PartialEq, Eq, Event)]
 pub struct RedactedSyncMessageEvent<C: RedactedMessageEventContent> {
     /// Data specific to the event type.
     // #[serde(default, skip_serializing_if = "is_zst")]
@@ -134,10 +130,24 @@ pub struct RedactedSyncMessageEvent<C: RedactedMessageEventContent> {
 
 /// A state event.
 ///
-/// `StateEvent` implements the comparison traits using only
-/// the `event_id` field, a sorted list would be sorted lexicographically based on
-/// the event's `EventId`.
-#[derive(Clone, Debug, Event)]
+/// Two `StateEvent`s are equal if all of their fields are equal.
+/// Events have no ordering.
+///
+/// # Example
+///
+/// ```
+/// # use std::time::SystemTime;
+/// use ruma_events::room::topic::{TopicEvent, TopicEventContent};
+/// use ruma_identifiers::{event_id, room_id, user_id};
+///
+/// let content = TopicEventContent { topic: "Hello".into() };
+/// let (event_id, sender) = (event_id!("$h29iv0s8:example.com"), user_id!("@carl:example.com"));
+/// let (ts, room_id) = (SystemTime::now(), room_id!("!r:example.com"));
+/// let event = TopicEvent::new(content, event_id, sender, ts, room_id, "".into());
+/// let json = serde_json::to_value(&event).unwrap();
+/// assert_eq!(json["content"], serde_json::json!({ "topic": "Hello" }));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Event)]
 pub struct StateEvent<C: StateEventContent> {
     /// Data specific to the event type.
     pub content: C,
@@ -167,12 +177,21 @@ pub struct StateEvent<C: StateEventContent> {
     pub unsigned: Unsigned,
 }
 
+impl<C: StateEventContent> StateEvent<C> {
+    /// Whether this event changes the state, i.e. whether `content` differs from `prev_content`.
+    ///
+    /// Returns `true` if there is no `prev_content`. The contents are compared structurally as
+    /// JSON, so differences in field order don't count as a change.
+    pub fn is_state_change(&self) -> bool {
+        is_state_change(&self.content, self.prev_content.as_ref())
+    }
+}
+
 /// A state event without a `room_id`.
 ///
-/// `SyncStateEvent` implements the comparison traits using only
-/// the `event_id` field, a sorted list would be sorted lexicographically based on
-/// the event's `EventId`.
-#[derive(Clone, Debug, Event)]
+/// Two `SyncStateEvent`s are equal if all of their fields are equal.
+/// Events have no ordering.
+#[derive(Clone, Debug, PartialEq, Eq, Event)]
 pub struct SyncStateEvent<C: StateEventContent> {
     /// Data specific to the event type.
     pub content: C,
@@ -199,9 +218,19 @@ pub struct SyncStateEvent<C: StateEventContent> {
     pub unsigned: Unsigned,
 }
 
+impl<C: StateEventContent> SyncStateEvent<C> {
+    /// Whether this event changes the state, i.e. whether `content` differs from `prev_content`.
+    ///
+    /// Returns `true` if there is no `prev_content`. The contents are compared structurally as
+    /// JSON, so differences in field order don't count as a change.
+    pub fn is_state_change(&self) -> bool {
+        is_state_change(&self.content, self.prev_content.as_ref())
+    }
+}
+
 /// A stripped-down state event, used for previews of rooms the user has been
 /// invited to.
-#[derive(Clone, Debug, Event)]
+#[derive(Clone, Debug, PartialEq, Eq, Event)]
 pub struct StrippedStateEvent<C: StateEventContent> {
     /// Data specific to the event type.
     pub content: C,
@@ -218,10 +247,9 @@ pub struct StrippedStateEvent<C: StateEventContent> {
 
 /// A redacted state event.
 ///
-/// `RedactedStateEvent` implements the comparison traits using only
-/// the `event_id` field, a sorted list would be sorted lexicographically based on
-/// the event's `EventId`.
-#[derive(Clone, Debug, Event)]
+/// Two `RedactedStateEvent`s are equal if all of their fields are equal.
+/// Events have no ordering.
+#[derive(Clone, Debug, PartialEq, Eq, Event)]
 pub struct RedactedStateEvent<C: RedactedStateEventContent> {
     /// Data specific to the event type.
     pub content: C,
@@ -250,10 +278,9 @@ pub struct RedactedStateEvent<C: RedactedStateEventContent> {
 
 /// A redacted state event without a `room_id`.
 ///
-/// `RedactedSyncStateEvent` implements the comparison traits using only
-/// the `event_id` field, a sorted list would be sorted lexicographically based on
-/// the event's `EventId`.
-#[derive(Clone, Debug, Event)]
+/// Two `RedactedSyncStateEvent`s are equal if all of their fields are equal.
+/// Events have no ordering.
+#[derive(Clone, Debug, PartialEq, Eq, Event)]
 pub struct RedactedSyncStateEvent<C: RedactedStateEventContent> {
     /// Data specific to the event type.
     // #[serde(default, skip_serializing_if = "is_zst")]
@@ -279,7 +306,7 @@ pub struct RedactedSyncStateEvent<C: RedactedStateEventContent> {
 }
 
 /// A stripped-down redacted state event.
-#[derive(Clone, Debug, Event)]
+#[derive(Clone, Debug, PartialEq, Eq, Event)]
 pub struct RedactedStrippedStateEvent<C: RedactedStateEventContent> {
     /// Data specific to the event type.
     pub content: C,
@@ -295,7 +322,7 @@ pub struct RedactedStrippedStateEvent<C: RedactedStateEventContent> {
 }
 
 /// An event sent using send-to-device messaging.
-#[derive(Clone, Debug, Event)]
+#[derive(Clone, Debug, PartialEq, Eq, Event)]
 pub struct ToDeviceEvent<C: EventContent> {
     /// Data specific to the event type.
     pub content: C,
@@ -303,3 +330,15 @@ pub struct ToDeviceEvent<C: EventContent> {
     /// The fully-qualified ID of the user who sent this event.
     pub sender: UserId,
 }
+
+fn is_state_change<C: StateEventContent>(content: &C, prev_content: Option<&C>) -> bool {
+    let prev_content = match prev_content {
+        Some(prev_content) => prev_content,
+        None => return true,
+    };
+
+    match (serde_json::to_value(content), serde_json::to_value(prev_content)) {
+        (Ok(content), Ok(prev_content)) => content != prev_content,
+        _ => true,
+    }
+}
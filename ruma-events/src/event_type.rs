@@ -1,9 +1,17 @@
-use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::{
+    cmp::Ordering,
+    fmt::{Display, Formatter, Result as FmtResult},
+    hash::{Hash, Hasher},
+};
 
 use serde::{Deserialize, Serialize};
 
 /// The type of an event.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+///
+/// Event types are compared, ordered and hashed by their string representation (see `as_str`), so
+/// `Custom` values holding the type of a known variant are equal to that variant, and maps keyed
+/// by `EventType` are ordered like maps keyed by the event type strings.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[non_exhaustive]
 #[serde(from = "String", into = "String")]
 pub enum EventType {
@@ -19,6 +27,15 @@ pub enum EventType {
     /// m.call.invite
     CallInvite,
 
+    /// m.call.negotiate
+    CallNegotiate,
+
+    /// m.call.reject
+    CallReject,
+
+    /// m.call.select_answer
+    CallSelectAnswer,
+
     /// m.direct
     Direct,
 
@@ -127,6 +144,15 @@ pub enum EventType {
     /// m.room_key_request
     RoomKeyRequest,
 
+    /// m.secret_storage.default_key
+    SecretStorageDefaultKey,
+
+    /// m.space.child
+    SpaceChild,
+
+    /// m.space.parent
+    SpaceParent,
+
     /// m.sticker
     Sticker,
 
@@ -140,13 +166,19 @@ pub enum EventType {
     Custom(String),
 }
 
-impl Display for EventType {
-    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        let event_type_str = match *self {
+impl EventType {
+    /// Creates a string slice from this `EventType`.
+    ///
+    /// This is the event type string sent over the wire, like `m.room.message`.
+    pub fn as_str(&self) -> &str {
+        match self {
             EventType::CallAnswer => "m.call.answer",
             EventType::CallCandidates => "m.call.candidates",
             EventType::CallHangup => "m.call.hangup",
             EventType::CallInvite => "m.call.invite",
+            EventType::CallNegotiate => "m.call.negotiate",
+            EventType::CallReject => "m.call.reject",
+            EventType::CallSelectAnswer => "m.call.select_answer",
             EventType::Direct => "m.direct",
             EventType::Dummy => "m.dummy",
             EventType::ForwardedRoomKey => "m.forwarded_room_key",
@@ -183,13 +215,70 @@ impl Display for EventType {
             EventType::RoomTopic => "m.room.topic",
             EventType::RoomKey => "m.room_key",
             EventType::RoomKeyRequest => "m.room_key_request",
+            EventType::SecretStorageDefaultKey => "m.secret_storage.default_key",
+            EventType::SpaceChild => "m.space.child",
+            EventType::SpaceParent => "m.space.parent",
             EventType::Sticker => "m.sticker",
             EventType::Tag => "m.tag",
             EventType::Typing => "m.typing",
-            EventType::Custom(ref event_type) => event_type,
-        };
+            EventType::Custom(event_type) => event_type,
+        }
+    }
+
+    /// Whether events of this type are state events that must have an empty `state_key`, because
+    /// there can only be one of them in a room.
+    ///
+    /// Returns `false` for non-state events, for state events that use their `state_key` (like
+    /// `m.room.member`, which uses it for the affected user's ID) and for custom events.
+    pub fn expects_empty_state_key(&self) -> bool {
+        matches!(
+            self,
+            EventType::RoomAvatar
+                | EventType::RoomCanonicalAlias
+                | EventType::RoomCreate
+                | EventType::RoomEncryption
+                | EventType::RoomGuestAccess
+                | EventType::RoomHistoryVisibility
+                | EventType::RoomJoinRules
+                | EventType::RoomName
+                | EventType::RoomPinnedEvents
+                | EventType::RoomPowerLevels
+                | EventType::RoomServerAcl
+                | EventType::RoomTombstone
+                | EventType::RoomTopic
+        )
+    }
+}
+
+impl Display for EventType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialEq for EventType {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for EventType {}
+
+impl PartialOrd for EventType {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EventType {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
 
-        write!(f, "{}", event_type_str)
+impl Hash for EventType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
     }
 }
 
@@ -203,6 +292,9 @@ where
             "m.call.candidates" => EventType::CallCandidates,
             "m.call.hangup" => EventType::CallHangup,
             "m.call.invite" => EventType::CallInvite,
+            "m.call.negotiate" => EventType::CallNegotiate,
+            "m.call.reject" => EventType::CallReject,
+            "m.call.select_answer" => EventType::CallSelectAnswer,
             "m.direct" => EventType::Direct,
             "m.dummy" => EventType::Dummy,
             "m.forwarded_room_key" => EventType::ForwardedRoomKey,
@@ -239,6 +331,9 @@ where
             "m.room.topic" => EventType::RoomTopic,
             "m.room_key" => EventType::RoomKey,
             "m.room_key_request" => EventType::RoomKeyRequest,
+            "m.secret_storage.default_key" => EventType::SecretStorageDefaultKey,
+            "m.space.child" => EventType::SpaceChild,
+            "m.space.parent" => EventType::SpaceParent,
             "m.sticker" => EventType::Sticker,
             "m.tag" => EventType::Tag,
             "m.typing" => EventType::Typing,
@@ -249,14 +344,23 @@ where
 
 impl From<EventType> for String {
     fn from(event_type: EventType) -> String {
-        event_type.to_string()
+        match event_type {
+            EventType::Custom(event_type) => event_type,
+            event_type => event_type.as_str().to_owned(),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        collections::{hash_map::DefaultHasher, BTreeMap},
+        hash::{Hash, Hasher},
+    };
+
+    use maplit::btreemap;
     use ruma_serde::test::serde_json_eq;
-    use serde_json::json;
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
     use super::EventType;
 
@@ -267,6 +371,9 @@ mod tests {
         serde_json_eq(EventType::CallCandidates, json!("m.call.candidates"));
         serde_json_eq(EventType::CallHangup, json!("m.call.hangup"));
         serde_json_eq(EventType::CallInvite, json!("m.call.invite"));
+        serde_json_eq(EventType::CallNegotiate, json!("m.call.negotiate"));
+        serde_json_eq(EventType::CallReject, json!("m.call.reject"));
+        serde_json_eq(EventType::CallSelectAnswer, json!("m.call.select_answer"));
         serde_json_eq(EventType::Direct, json!("m.direct"));
         serde_json_eq(EventType::Dummy, json!("m.dummy"));
         serde_json_eq(EventType::ForwardedRoomKey, json!("m.forwarded_room_key"));
@@ -303,9 +410,132 @@ mod tests {
         serde_json_eq(EventType::RoomTopic, json!("m.room.topic"));
         serde_json_eq(EventType::RoomKey, json!("m.room_key"));
         serde_json_eq(EventType::RoomKeyRequest, json!("m.room_key_request"));
+        serde_json_eq(EventType::SecretStorageDefaultKey, json!("m.secret_storage.default_key"));
+        serde_json_eq(EventType::SpaceChild, json!("m.space.child"));
+        serde_json_eq(EventType::SpaceParent, json!("m.space.parent"));
         serde_json_eq(EventType::Sticker, json!("m.sticker"));
         serde_json_eq(EventType::Tag, json!("m.tag"));
         serde_json_eq(EventType::Typing, json!("m.typing"));
         serde_json_eq(EventType::Custom("io.ruma.test".into()), json!("io.ruma.test"));
     }
+
+    #[test]
+    fn map_key() {
+        let map = btreemap! {
+            EventType::RoomName => 50,
+            EventType::Custom("io.ruma.test".into()) => 25,
+        };
+        let json = json!({ "m.room.name": 50, "io.ruma.test": 25 });
+
+        assert_eq!(to_json_value(&map).unwrap(), json);
+        assert_eq!(from_json_value::<BTreeMap<EventType, i64>>(json).unwrap(), map);
+    }
+
+    #[test]
+    fn expects_empty_state_key() {
+        assert!(EventType::RoomName.expects_empty_state_key());
+        assert!(EventType::RoomPowerLevels.expects_empty_state_key());
+        assert!(!EventType::RoomMember.expects_empty_state_key());
+        assert!(!EventType::RoomThirdPartyInvite.expects_empty_state_key());
+        assert!(!EventType::RoomMessage.expects_empty_state_key());
+        assert!(!EventType::Custom("io.ruma.test".into()).expects_empty_state_key());
+    }
+
+    #[test]
+    fn as_str_agrees_with_display() {
+        let event_types = [
+            EventType::CallAnswer,
+            EventType::CallCandidates,
+            EventType::CallHangup,
+            EventType::CallInvite,
+            EventType::CallNegotiate,
+            EventType::CallReject,
+            EventType::CallSelectAnswer,
+            EventType::Direct,
+            EventType::Dummy,
+            EventType::ForwardedRoomKey,
+            EventType::FullyRead,
+            EventType::KeyVerificationAccept,
+            EventType::KeyVerificationCancel,
+            EventType::KeyVerificationKey,
+            EventType::KeyVerificationMac,
+            EventType::KeyVerificationRequest,
+            EventType::KeyVerificationStart,
+            EventType::IgnoredUserList,
+            EventType::Presence,
+            EventType::PushRules,
+            EventType::Receipt,
+            EventType::RoomAliases,
+            EventType::RoomAvatar,
+            EventType::RoomCanonicalAlias,
+            EventType::RoomCreate,
+            EventType::RoomEncrypted,
+            EventType::RoomEncryption,
+            EventType::RoomGuestAccess,
+            EventType::RoomHistoryVisibility,
+            EventType::RoomJoinRules,
+            EventType::RoomMember,
+            EventType::RoomMessage,
+            EventType::RoomMessageFeedback,
+            EventType::RoomName,
+            EventType::RoomPinnedEvents,
+            EventType::RoomPowerLevels,
+            EventType::RoomRedaction,
+            EventType::RoomServerAcl,
+            EventType::RoomThirdPartyInvite,
+            EventType::RoomTombstone,
+            EventType::RoomTopic,
+            EventType::RoomKey,
+            EventType::RoomKeyRequest,
+            EventType::SecretStorageDefaultKey,
+            EventType::SpaceChild,
+            EventType::SpaceParent,
+            EventType::Sticker,
+            EventType::Tag,
+            EventType::Typing,
+            EventType::Custom("io.ruma.test".into()),
+        ];
+
+        for event_type in &event_types {
+            assert_eq!(event_type.as_str(), event_type.to_string());
+            assert_eq!(EventType::from(event_type.as_str()), *event_type);
+        }
+    }
+
+    #[test]
+    fn ordered_and_compared_by_string() {
+        let mut event_types = [
+            EventType::Typing,
+            EventType::Custom("io.ruma.test".into()),
+            EventType::RoomName,
+            EventType::CallAnswer,
+            EventType::Custom("m.room.member.custom".into()),
+            EventType::RoomMember,
+        ];
+        event_types.sort();
+
+        let strings: Vec<_> = event_types.iter().map(EventType::as_str).collect();
+        assert_eq!(
+            strings,
+            [
+                "io.ruma.test",
+                "m.call.answer",
+                "m.room.member",
+                "m.room.member.custom",
+                "m.room.name",
+                "m.typing"
+            ]
+        );
+
+        let custom_name = EventType::Custom("m.room.name".into());
+        assert_eq!(custom_name, EventType::RoomName);
+        assert_eq!(hash(&custom_name), hash(&EventType::RoomName));
+        assert_ne!(EventType::Custom("m.room.nam".into()), EventType::RoomName);
+    }
+
+    fn hash(event_type: &EventType) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        event_type.hash(&mut hasher);
+        hasher.finish()
+    }
 }
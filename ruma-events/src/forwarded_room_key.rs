@@ -12,7 +12,7 @@ use crate::BasicEvent;
 pub type ForwardedRoomKeyEvent = BasicEvent<ForwardedRoomKeyEventContent>;
 
 /// The payload for `ForwardedRoomKeyEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, BasicEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, BasicEventContent)]
 #[ruma_event(type = "m.forwarded_room_key")]
 pub struct ForwardedRoomKeyEventContent {
     /// The encryption algorithm the key in this event is to be used with.
@@ -13,9 +13,29 @@ use crate::EphemeralRoomEvent;
 pub type FullyReadEvent = EphemeralRoomEvent<FullyReadEventContent>;
 
 /// The payload for `FullyReadEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, EphemeralRoomEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize, EphemeralRoomEventContent)]
 #[ruma_event(type = "m.fully_read")]
 pub struct FullyReadEventContent {
     /// The event the user's read marker is located at in the room.
     pub event_id: EventId,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use ruma_identifiers::event_id;
+
+    use super::FullyReadEventContent;
+
+    #[test]
+    fn deduplicate_in_hash_set() {
+        let mut set = HashSet::new();
+        set.insert(FullyReadEventContent { event_id: event_id!("$first:example.org") });
+        set.insert(FullyReadEventContent { event_id: event_id!("$second:example.org") });
+        set.insert(FullyReadEventContent { event_id: event_id!("$first:example.org") });
+
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&FullyReadEventContent { event_id: event_id!("$second:example.org") }));
+    }
+}
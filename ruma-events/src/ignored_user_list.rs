@@ -10,7 +10,7 @@ use crate::BasicEvent;
 pub type IgnoredUserListEvent = BasicEvent<IgnoredUserListEventContent>;
 
 /// The payload for `IgnoredUserListEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, BasicEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize, BasicEventContent)]
 #[ruma_event(type = "m.ignored_user_list")]
 pub struct IgnoredUserListEventContent {
     /// A list of users to ignore.
@@ -2,8 +2,7 @@
 //!
 //! This module also contains types shared by events in its child namespaces.
 
-use serde::{Deserialize, Serialize};
-use strum::{Display, EnumString};
+use ruma_serde::string_enum;
 
 pub mod accept;
 pub mod cancel;
@@ -12,61 +11,61 @@ pub mod mac;
 pub mod request;
 pub mod start;
 
-/// A hash algorithm.
-#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, Serialize, Deserialize)]
-#[non_exhaustive]
-#[serde(rename_all = "snake_case")]
-#[strum(serialize_all = "snake_case")]
-pub enum HashAlgorithm {
-    /// The SHA256 hash algorithm.
-    Sha256,
+string_enum! {
+    /// A hash algorithm.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum HashAlgorithm {
+        /// The SHA256 hash algorithm.
+        Sha256 => "sha256",
+    }
 }
 
-/// A key agreement protocol.
-#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, Serialize, Deserialize)]
-#[non_exhaustive]
-#[serde(rename_all = "kebab-case")]
-#[strum(serialize_all = "kebab-case")]
-pub enum KeyAgreementProtocol {
-    /// The [Curve25519](https://cr.yp.to/ecdh.html) key agreement protocol.
-    Curve25519,
-    /// The Curve25519 key agreement protocol with check for public keys.
-    Curve25519HkdfSha256,
+string_enum! {
+    /// A key agreement protocol.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum KeyAgreementProtocol {
+        /// The [Curve25519](https://cr.yp.to/ecdh.html) key agreement protocol.
+        Curve25519 => "curve25519",
+        /// The Curve25519 key agreement protocol with check for public keys.
+        Curve25519HkdfSha256 => "curve25519-hkdf-sha256",
+    }
 }
 
-/// A message authentication code algorithm.
-#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, Serialize, Deserialize)]
-#[non_exhaustive]
-#[serde(rename_all = "kebab-case")]
-#[strum(serialize_all = "kebab-case")]
-pub enum MessageAuthenticationCode {
-    /// The HKDF-HMAC-SHA256 MAC.
-    HkdfHmacSha256,
-    /// The HMAC-SHA256 MAC.
-    HmacSha256,
+string_enum! {
+    /// A message authentication code algorithm.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum MessageAuthenticationCode {
+        /// The HKDF-HMAC-SHA256 MAC.
+        HkdfHmacSha256 => "hkdf-hmac-sha256",
+        /// The HMAC-SHA256 MAC.
+        HmacSha256 => "hmac-sha256",
+    }
 }
 
-/// A Short Authentication String method.
-#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, Serialize, Deserialize)]
-#[non_exhaustive]
-#[serde(rename_all = "snake_case")]
-#[strum(serialize_all = "snake_case")]
-pub enum ShortAuthenticationString {
-    /// The decimal method.
-    Decimal,
-
-    /// The emoji method.
-    Emoji,
+string_enum! {
+    /// A Short Authentication String method.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum ShortAuthenticationString {
+        /// The decimal method.
+        Decimal => "decimal",
+
+        /// The emoji method.
+        Emoji => "emoji",
+    }
 }
 
-/// A Short Authentication String (SAS) verification method.
-#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, Deserialize, Serialize)]
-#[non_exhaustive]
-pub enum VerificationMethod {
-    /// The *m.sas.v1* verification method.
-    #[serde(rename = "m.sas.v1")]
-    #[strum(serialize = "m.sas.v1")]
-    MSasV1,
+string_enum! {
+    /// A Short Authentication String (SAS) verification method.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum VerificationMethod {
+        /// The *m.sas.v1* verification method.
+        MSasV1 => "m.sas.v1",
+    }
 }
 
 #[cfg(test)]
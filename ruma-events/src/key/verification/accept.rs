@@ -17,7 +17,7 @@ use crate::BasicEvent;
 pub type AcceptEvent = BasicEvent<AcceptEventContent>;
 
 /// The payload for `AcceptEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, BasicEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, BasicEventContent)]
 #[ruma_event(type = "m.key.verification.accept")]
 pub struct AcceptEventContent {
     /// An opaque identifier for the verification process.
@@ -33,7 +33,7 @@ pub struct AcceptEventContent {
 
 /// An enum representing the different method specific
 /// *m.key.verification.accept* content.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[non_exhaustive]
 #[serde(untagged)]
 pub enum AcceptMethod {
@@ -45,7 +45,7 @@ pub enum AcceptMethod {
 }
 
 /// Method specific content of a unknown key verification method.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct CustomContent {
     /// The name of the method.
     pub method: String,
@@ -56,7 +56,7 @@ pub struct CustomContent {
 }
 
 /// The payload of an *m.key.verification.accept* event using the *m.sas.v1* method.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[non_exhaustive]
 #[serde(rename = "m.sas.v1", tag = "method")]
 pub struct MSasV1Content {
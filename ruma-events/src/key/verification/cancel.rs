@@ -13,7 +13,7 @@ use crate::BasicEvent;
 pub type CancelEvent = BasicEvent<CancelEventContent>;
 
 /// The payload for `CancelEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, BasicEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, BasicEventContent)]
 #[ruma_event(type = "m.key.verification.cancel")]
 pub struct CancelEventContent {
     /// The opaque identifier for the verification process/request.
@@ -31,7 +31,7 @@ pub struct CancelEventContent {
 /// An error code for why the process/request was cancelled by the user.
 ///
 /// Custom error codes should use the Java package naming convention.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[non_exhaustive]
 #[serde(from = "String", into = "String")]
 pub enum CancelCode {
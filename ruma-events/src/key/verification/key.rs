@@ -11,7 +11,7 @@ use crate::BasicEvent;
 pub type KeyEvent = BasicEvent<KeyEventContent>;
 
 /// The payload for `KeyEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, BasicEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, BasicEventContent)]
 #[ruma_event(type = "m.key.verification.key")]
 pub struct KeyEventContent {
     /// An opaque identifier for the verification process.
@@ -13,7 +13,7 @@ use crate::BasicEvent;
 pub type MacEvent = BasicEvent<MacEventContent>;
 
 /// The payload for `MacEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, BasicEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, BasicEventContent)]
 #[ruma_event(type = "m.key.verification.mac")]
 pub struct MacEventContent {
     /// An opaque identifier for the verification process.
@@ -15,7 +15,7 @@ use crate::BasicEvent;
 pub type RequestEvent = BasicEvent<RequestEventContent>;
 
 /// The payload for `RequestEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, BasicEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, BasicEventContent)]
 #[ruma_event(type = "m.key.verification.request")]
 pub struct RequestEventContent {
     /// The device ID which is initiating the request.
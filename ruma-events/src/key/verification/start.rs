@@ -18,7 +18,7 @@ use crate::{BasicEvent, InvalidInput};
 pub type StartEvent = BasicEvent<StartEventContent>;
 
 /// The payload of an *m.key.verification.start* event.
-#[derive(Clone, Debug, Deserialize, Serialize, BasicEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, BasicEventContent)]
 #[ruma_event(type = "m.key.verification.start")]
 pub struct StartEventContent {
     /// The device ID which is initiating the process.
@@ -38,7 +38,7 @@ pub struct StartEventContent {
 
 /// An enum representing the different method specific
 /// *m.key.verification.start* content.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[non_exhaustive]
 #[serde(untagged)]
 pub enum StartMethod {
@@ -50,7 +50,7 @@ pub enum StartMethod {
 }
 
 /// Method specific content of a unknown key verification method.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct CustomContent {
     /// The name of the method.
     pub method: String,
@@ -61,7 +61,7 @@ pub struct CustomContent {
 }
 
 /// The payload of an *m.key.verification.start* event using the *m.sas.v1* method.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[non_exhaustive]
 #[serde(rename = "m.sas.v1", tag = "method")]
 pub struct MSasV1Content {
@@ -70,6 +70,15 @@
 //! respective event traits whose `content` field is simply a `serde_json::Value` value, which
 //! represents arbitrary JSON.
 //!
+//! # Forward compatibility
+//!
+//! The Matrix specification keeps adding new values for fields like `msgtype` or `membership`.
+//! Enums representing such values have a hidden `_Custom` variant that unknown values deserialize
+//! into, so events using a newer version of the spec still deserialize and re-serialize losslessly.
+//! Code matching on these enums needs a wildcard arm, and should use accessors like `as_str` or
+//! `room::message::MessageEventContent::msgtype` rather than matching on `_Custom` directly, since
+//! values may move from `_Custom` to a dedicated variant in any release.
+//!
 //! # Serialization and deserialization
 //!
 //! All concrete event types in ruma-events can be serialized via the `Serialize` trait from
@@ -86,6 +95,58 @@
 //! type alias), allowing content to be converted to and from JSON indepedently of the surrounding
 //! event structure, if needed.
 //!
+//! # Strict deserialization
+//!
+//! By default, event structs ignore fields they don't know about, which is what clients want when
+//! talking to servers implementing newer versions of the specification. Servers validating client
+//! input can use the `deserialize_strict` constructor of the event structs (e.g.
+//! `StateEvent::deserialize_strict`) instead, which rejects events with unknown fields. For state
+//! events, it also rejects a non-empty `state_key` on events that can only exist once per room
+//! (see `EventType::expects_empty_state_key`) and a `state_key` that isn't a user ID on
+//! *m.room.member* events.
+//!
+//! A field is unknown if it is a top-level field of the event that is not part of the event's
+//! shape, e.g. `state_key` on a message event. The event's `content` is not checked, since some
+//! content types intentionally keep arbitrary fields (like `custom::CustomEventContent`); unknown
+//! fields in `content` are handled by the content type's own `Deserialize` implementation.
+//!
+//! Event content can be checked on its own with `from_str_strict` (e.g.
+//! `from_str_strict::<MessageEventContent>(json)`), which fails on unknown fields inside the
+//! content, catching typos like `formated_body`. It is available for content types that implement
+//! `StrictEventContent`: the structs created with the event content derives get an implementation
+//! that deserializes into a mirror of the struct with `#[serde(deny_unknown_fields)]`, so the
+//! strict and the default deserialization accept the same fields. Structs with flattened fields
+//! and most enums don't implement it.
+//!
+//! # Compatibility with non-compliant homeservers
+//!
+//! Some homeservers emit events that violate the specification, which mostly matters for clients
+//! syncing old rooms. The `compat` cargo feature relaxes deserialization for these known cases:
+//!
+//! * integers encoded as strings (like `"50"`) in `m.room.power_levels` are accepted
+//! * a `null` or missing `url` in `m.room.avatar` deserializes as an empty string
+//!
+//! Without the feature, these events fail to deserialize. Independent of the feature, optional
+//! fields treat `null` the same as an absent field, and URLs are kept as opaque strings without
+//! being validated.
+//!
+//! # Hashing
+//!
+//! Small content types made up of identifiers and strings implement `Hash`, so they can be
+//! deduplicated in a `HashSet`:
+//!
+//! * `fully_read::FullyReadEventContent`
+//! * `ignored_user_list::IgnoredUserListEventContent`
+//! * `receipt::{ReceiptEventContent, Receipts, Receipt}`
+//! * `room::canonical_alias::CanonicalAliasEventContent`
+//! * `room::name::NameEventContent`
+//! * `room::pinned_events::PinnedEventsEventContent`
+//! * `room::tombstone::TombstoneEventContent`
+//! * `room::topic::TopicEventContent`
+//! * `typing::TypingEventContent`
+//!
+//! Content types containing floating point numbers, like `tag::TagInfo`, can't implement it.
+//!
 //! # Collections
 //!
 //! With the trait-based approach to events, it's easy to write generic collection types like
@@ -117,11 +178,16 @@
 #![allow(clippy::unnested_or_patterns)]
 #![allow(clippy::unknown_clippy_lints)]
 
-use std::fmt::Debug;
+use std::{
+    collections::BTreeMap,
+    convert::TryFrom,
+    fmt::Debug,
+    time::{Duration, SystemTime},
+};
 
-use js_int::Int;
+use js_int::{Int, UInt};
 use ruma_common::Raw;
-use ruma_identifiers::{EventEncryptionAlgorithm, RoomId};
+use ruma_identifiers::{EventEncryptionAlgorithm, RoomId, UserId};
 use serde::{
     de::{self, IgnoredAny},
     Deserialize, Serialize,
@@ -130,6 +196,8 @@ use serde_json::value::RawValue as RawJsonValue;
 
 use self::room::redaction::{RedactionEvent, SyncRedactionEvent};
 
+#[cfg(feature = "compat")]
+mod compat;
 mod enums;
 mod error;
 mod event_kinds;
@@ -173,6 +241,8 @@ pub mod receipt;
 pub mod room;
 pub mod room_key;
 pub mod room_key_request;
+pub mod secret_storage;
+pub mod space;
 pub mod sticker;
 pub mod tag;
 pub mod typing;
@@ -189,7 +259,7 @@ pub use self::{
         AnySyncMessageEvent, AnySyncRoomEvent, AnySyncStateEvent, AnyToDeviceEvent,
         AnyToDeviceEventContent,
     },
-    error::{FromStrError, InvalidInput},
+    error::{FromStrError, InvalidEvent, InvalidInput},
     event_kinds::{
         BasicEvent, EphemeralRoomEvent, MessageEvent, RedactedMessageEvent, RedactedStateEvent,
         RedactedStrippedStateEvent, RedactedSyncMessageEvent, RedactedSyncStateEvent, StateEvent,
@@ -201,7 +271,7 @@ pub use self::{
 
 /// Extra information about an event that is not incorporated into the event's
 /// hash.
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Deserialize, Serialize)]
 pub struct Unsigned {
     /// The time in milliseconds that has elapsed since the event was sent. This
     /// field is generated by the local homeserver, and may be incorrect if the
@@ -226,11 +296,33 @@ impl Unsigned {
     pub fn is_empty(&self) -> bool {
         self.age.is_none() && self.transaction_id.is_none()
     }
+
+    /// The time that has elapsed since the event was sent, as a `Duration`.
+    ///
+    /// Returns `None` if `age` is absent or negative.
+    pub fn age_duration(&self) -> Option<Duration> {
+        self.age.and_then(|age| u64::try_from(i64::from(age)).ok()).map(Duration::from_millis)
+    }
+
+    /// The client-supplied transaction ID, for matching the event to its local echo.
+    pub fn transaction_id(&self) -> Option<&str> {
+        self.transaction_id.as_deref()
+    }
+
+    /// Estimates when the event was sent in local time, by subtracting `age` from the time `now`
+    /// at which the event was received.
+    ///
+    /// Negative ages, as sent by some homeservers, are treated as zero. Returns `None` if `age` is
+    /// absent.
+    pub fn sent_at(&self, now: SystemTime) -> Option<SystemTime> {
+        let age = u64::try_from(i64::from(self.age?)).unwrap_or(0);
+        now.checked_sub(Duration::from_millis(age))
+    }
 }
 
 /// Extra information about a redacted event that is not incorporated into the event's
 /// hash.
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Deserialize, Serialize)]
 pub struct RedactedUnsigned {
     /// The event that redacted this event, if any.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -251,7 +343,7 @@ impl RedactedUnsigned {
 
 /// Extra information about a redacted sync event that is not incorporated into the sync event's
 /// hash.
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Deserialize, Serialize)]
 pub struct RedactedSyncUnsigned {
     /// The event that redacted this event, if any.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -333,6 +425,24 @@ pub trait EventContent: Sized + Serialize {
     fn from_parts(event_type: &str, content: Box<RawJsonValue>) -> Result<Self, serde_json::Error>;
 }
 
+/// Event content that can be deserialized strictly, failing on unknown fields.
+///
+/// See `from_str_strict`.
+pub trait StrictEventContent: EventContent + de::DeserializeOwned {
+    /// Deserializes the content from `json` like its `Deserialize` implementation, but fails if it
+    /// has fields that are not part of the content.
+    fn deserialize_strict(json: &RawJsonValue) -> serde_json::Result<Self>;
+}
+
+/// Deserializes event content from a JSON string, failing if it has fields that are not part of
+/// the content.
+///
+/// The default `Deserialize` implementations ignore unknown fields.
+pub fn from_str_strict<T: StrictEventContent>(json: &str) -> serde_json::Result<T> {
+    let json: Box<RawJsonValue> = serde_json::from_str(json)?;
+    T::deserialize_strict(&json)
+}
+
 /// Extension trait for Raw<EventContent>
 pub trait RawExt<T: EventContent> {
     /// Try to deserialize the JSON as event content
@@ -349,6 +459,31 @@ where
     }
 }
 
+/// Extension trait for deserializing `Raw` events with errors that include the event's JSON.
+pub trait RawEventExt<T> {
+    /// Try to deserialize the JSON into the expected event type.
+    fn deserialize_event(&self) -> Result<T, InvalidEvent>;
+}
+
+impl<T: de::DeserializeOwned> RawEventExt<T> for Raw<T> {
+    fn deserialize_event(&self) -> Result<T, InvalidEvent> {
+        self.deserialize().map_err(|e| InvalidEvent::new(self.json().to_owned(), e))
+    }
+}
+
+/// Deserializes a list of events, failing on the first invalid event.
+///
+/// The returned error includes the index of the invalid event in `events`.
+pub fn deserialize_events<T: de::DeserializeOwned>(
+    events: &[Raw<T>],
+) -> Result<Vec<T>, InvalidEvent> {
+    events
+        .iter()
+        .enumerate()
+        .map(|(index, event)| event.deserialize_event().map_err(|e| e.with_index(index)))
+        .collect()
+}
+
 /// Marker trait for the content of an ephemeral room event.
 pub trait EphemeralRoomEventContent: EventContent {}
 
@@ -418,6 +553,14 @@ pub struct UnsignedDeHelper {
     pub redacted_because: Option<IgnoredAny>,
 }
 
+/// Helper struct to extract a `prev_content` that was sent inside of `unsigned`.
+#[doc(hidden)]
+#[derive(Debug, Deserialize)]
+pub struct UnsignedPrevContentDeHelper {
+    /// The previous content of a state event, as sent by some homeservers.
+    pub prev_content: Option<Box<RawJsonValue>>,
+}
+
 /// Helper struct to determine the event kind from a serde_json::value::RawValue.
 #[doc(hidden)]
 #[derive(Debug, Deserialize)]
@@ -442,6 +585,46 @@ pub struct EventDeHelper {
     pub unsigned: Option<UnsignedDeHelper>,
 }
 
+/// Helper function for strict event deserialization, fails if `val` has a top-level field that is
+/// not in `known_fields`.
+#[doc(hidden)]
+pub fn check_known_fields(
+    val: &RawJsonValue,
+    known_fields: &'static [&'static str],
+) -> serde_json::Result<()> {
+    let fields: BTreeMap<String, IgnoredAny> = serde_json::from_str(val.get())?;
+
+    match fields.keys().find(|field| !known_fields.contains(&field.as_str())) {
+        Some(field) => Err(de::Error::unknown_field(field, known_fields)),
+        None => Ok(()),
+    }
+}
+
+/// Helper function for strict state event deserialization, fails if `state_key` is not valid for
+/// events of type `event_type`.
+///
+/// Singleton state events need an empty state key, *m.room.member* events need a user ID.
+#[doc(hidden)]
+pub fn check_state_key(event_type: &str, state_key: &str) -> serde_json::Result<()> {
+    let event_type = EventType::from(event_type);
+
+    if event_type.expects_empty_state_key() && !state_key.is_empty() {
+        return Err(de::Error::custom(format!(
+            "expected an empty `state_key` for `{}` events, found `{}`",
+            event_type, state_key
+        )));
+    }
+
+    if event_type == EventType::RoomMember && UserId::try_from(state_key).is_err() {
+        return Err(de::Error::custom(format!(
+            "expected a user ID as the `state_key` of `m.room.member` events, found `{}`",
+            state_key
+        )));
+    }
+
+    Ok(())
+}
+
 /// Helper function for serde_json::value::RawValue deserialization.
 #[doc(hidden)]
 pub fn from_raw_json_value<T, E>(val: &RawJsonValue) -> Result<T, E>
@@ -451,3 +634,9 @@ where
 {
     serde_json::from_str(val.get()).map_err(E::custom)
 }
+
+/// Converts a `Duration` to milliseconds, saturating at the largest value that can be represented
+/// in JSON.
+pub(crate) fn duration_to_millis(duration: Duration) -> UInt {
+    UInt::try_from(duration.as_millis()).unwrap_or(UInt::MAX)
+}
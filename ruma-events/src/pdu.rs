@@ -17,7 +17,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 
 /// Enum for PDU schemas
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum Pdu {
     /// PDU for room versions 1 and 2.
@@ -27,7 +27,7 @@ pub enum Pdu {
 }
 
 /// A 'persistent data unit' (event) for room versions 1 and 2.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct RoomV1Pdu {
     /// Event ID for the PDU.
     pub event_id: EventId,
@@ -88,7 +88,7 @@ pub struct RoomV1Pdu {
 }
 
 /// A 'persistent data unit' (event) for room versions 3 and beyond.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct RoomV3Pdu {
     /// The room this event belongs to.
     pub room_id: RoomId,
@@ -144,7 +144,7 @@ pub struct RoomV3Pdu {
 }
 
 /// PDU type without event and room IDs.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum PduStub {
     /// Stub for PDUs of room version 1 and 2.
@@ -167,7 +167,7 @@ impl PduStub {
 }
 
 /// Stub for PDUs of room version 1 and 2.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct RoomV1PduStub {
     /// The user id of the user who sent this event.
     pub sender: UserId,
@@ -243,7 +243,7 @@ impl RoomV1PduStub {
 }
 
 /// Stub for PDUs of room versions 3 and above.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct RoomV3PduStub {
     /// The user id of the user who sent this event.
     pub sender: UserId,
@@ -318,7 +318,7 @@ impl RoomV3PduStub {
 }
 
 /// Content hashes of a PDU.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct EventHash {
     /// The SHA-256 hash.
     pub sha256: String,
@@ -2,14 +2,18 @@
 //!
 //! The only content valid for this event is `PresenceEventContent.
 
+use std::time::Duration;
+
 use js_int::UInt;
 use ruma_common::presence::PresenceState;
 use ruma_events_macros::{Event, EventContent};
 use ruma_identifiers::UserId;
 use serde::{Deserialize, Serialize};
 
+use crate::duration_to_millis;
+
 /// Presence event.
-#[derive(Clone, Debug, Event)]
+#[derive(Clone, Debug, PartialEq, Eq, Event)]
 pub struct PresenceEvent {
     /// Data specific to the event type.
     pub content: PresenceEventContent,
@@ -22,7 +26,7 @@ pub struct PresenceEvent {
 ///
 /// This is the only event content a `PresenceEvent` can contain as it's
 /// `content` field.
-#[derive(Clone, Debug, Deserialize, Serialize, EventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, EventContent)]
 #[ruma_event(type = "m.presence")]
 pub struct PresenceEventContent {
     /// The current avatar URL for this user.
@@ -49,9 +53,23 @@ pub struct PresenceEventContent {
     pub status_msg: Option<String>,
 }
 
+impl PresenceEventContent {
+    /// The time since this user last performed some action, as a `Duration`.
+    pub fn last_active_duration(&self) -> Option<Duration> {
+        self.last_active_ago.map(|ms| Duration::from_millis(ms.into()))
+    }
+
+    /// Sets `last_active_ago` from a `Duration`.
+    pub fn set_last_active_duration(&mut self, duration: Option<Duration>) {
+        self.last_active_ago = duration.map(duration_to_millis);
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use js_int::uint;
+    use std::time::Duration;
+
+    use js_int::{uint, UInt};
     use matches::assert_matches;
     use ruma_common::presence::PresenceState;
     use ruma_identifiers::user_id;
@@ -120,4 +138,49 @@ mod tests {
                 && last_active_ago == uint!(2_478_593)
         );
     }
+
+    #[test]
+    fn last_active_ago_out_of_range() {
+        let event_with_last_active_ago = |last_active_ago: u64| {
+            json!({
+                "content": {
+                    "last_active_ago": last_active_ago,
+                    "presence": "online"
+                },
+                "sender": "@example:localhost",
+                "type": "m.presence"
+            })
+        };
+
+        assert_matches!(
+            from_json_value::<PresenceEvent>(event_with_last_active_ago(9_007_199_254_740_991))
+                .unwrap()
+                .content
+                .last_active_ago,
+            Some(last_active_ago) if last_active_ago == UInt::MAX
+        );
+        assert!(from_json_value::<PresenceEvent>(event_with_last_active_ago(
+            9_007_199_254_740_993
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn last_active_duration() {
+        let mut content = from_json_value::<PresenceEventContent>(json!({
+            "last_active_ago": 1500,
+            "presence": "online"
+        }))
+        .unwrap();
+        assert_eq!(content.last_active_duration(), Some(Duration::new(1, 500_000_000)));
+
+        content.set_last_active_duration(Some(Duration::from_secs(3)));
+        assert_eq!(content.last_active_ago, Some(uint!(3000)));
+
+        content.set_last_active_duration(Some(Duration::from_secs(u64::MAX)));
+        assert_eq!(content.last_active_ago, Some(UInt::MAX));
+
+        content.set_last_active_duration(None);
+        assert_eq!(content.last_active_duration(), None);
+    }
 }
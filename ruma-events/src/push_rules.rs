@@ -10,6 +10,8 @@ use crate::BasicEvent;
 pub type PushRulesEvent = BasicEvent<PushRulesEventContent>;
 
 /// The payload for `PushRulesEvent`.
+///
+/// Doesn't implement `PartialEq` since custom push rule tweaks contain raw JSON.
 #[derive(Clone, Debug, Deserialize, Serialize, BasicEventContent)]
 #[ruma_event(type = "m.push_rules")]
 pub struct PushRulesEventContent {
@@ -19,7 +19,7 @@ pub type ReceiptEvent = EphemeralRoomEvent<ReceiptEventContent>;
 ///
 /// A mapping of event ID to a collection of receipts for this event ID. The event ID is the ID of
 /// the event being acknowledged and *not* an ID for the receipt itself.
-#[derive(Clone, Debug, Deserialize, Serialize, EphemeralRoomEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize, EphemeralRoomEventContent)]
 #[ruma_event(type = "m.receipt")]
 pub struct ReceiptEventContent(pub BTreeMap<EventId, Receipts>);
 
@@ -38,7 +38,7 @@ impl DerefMut for ReceiptEventContent {
 }
 
 /// A collection of receipts.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct Receipts {
     /// A collection of users who have sent *m.read* receipts for this event.
     #[serde(default, rename = "m.read")]
@@ -51,7 +51,7 @@ pub struct Receipts {
 pub type UserReceipts = BTreeMap<UserId, Receipt>;
 
 /// An acknowledgement of an event.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct Receipt {
     /// The time when the receipt was sent.
     #[serde(
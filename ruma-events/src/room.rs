@@ -27,8 +27,11 @@ pub mod third_party_invite;
 pub mod tombstone;
 pub mod topic;
 
+#[cfg(test)]
+mod proptests;
+
 /// Metadata about an image.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct ImageInfo {
     /// The height of the image in pixels.
     #[serde(rename = "h", skip_serializing_if = "Option::is_none")]
@@ -60,7 +63,7 @@ pub struct ImageInfo {
 }
 
 /// Metadata about a thumbnail.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct ThumbnailInfo {
     /// The height of the thumbnail in pixels.
     #[serde(rename = "h", skip_serializing_if = "Option::is_none")]
@@ -80,7 +83,7 @@ pub struct ThumbnailInfo {
 }
 
 /// A file sent to a room with end-to-end encryption enabled.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct EncryptedFile {
     /// The URL to the file.
     pub url: String,
@@ -100,7 +103,7 @@ pub struct EncryptedFile {
 }
 
 /// A [JSON Web Key](https://tools.ietf.org/html/rfc7517#appendix-A.3) object.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct JsonWebKey {
     /// Key type. Must be `oct`.
     pub kty: String,
@@ -13,7 +13,7 @@ use crate::{
 pub type AliasesEvent = StateEvent<AliasesEventContent>;
 
 /// The payload for `AliasesEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, StateEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, StateEventContent)]
 #[non_exhaustive]
 #[ruma_event(type = "m.room.aliases", custom_redacted)]
 pub struct AliasesEventContent {
@@ -45,7 +45,7 @@ impl AliasesEventContent {
 }
 
 /// An aliases event that has been redacted.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct RedactedAliasesEventContent {
     /// A list of room aliases.
     ///
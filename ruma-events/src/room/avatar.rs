@@ -12,7 +12,7 @@ use crate::StateEvent;
 pub type AvatarEvent = StateEvent<AvatarEventContent>;
 
 /// The payload for `AvatarEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, StateEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, StateEventContent)]
 #[non_exhaustive]
 #[ruma_event(type = "m.room.avatar")]
 pub struct AvatarEventContent {
@@ -22,6 +22,12 @@ pub struct AvatarEventContent {
 
     /// Information about the avatar thumbnail image.
     /// URL of the avatar image.
+    ///
+    /// With the `compat` feature, a missing or `null` URL deserializes as an empty string.
+    #[cfg_attr(
+        feature = "compat",
+        serde(default, deserialize_with = "crate::compat::null_as_default")
+    )]
     pub url: String,
 }
 
@@ -31,3 +37,34 @@ impl AvatarEventContent {
         Self { info: None, url }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{from_value as from_json_value, json};
+
+    use super::AvatarEventContent;
+
+    #[test]
+    fn invalid_url_preserved() {
+        let content =
+            from_json_value::<AvatarEventContent>(json!({ "url": "not an mxc:// uri" })).unwrap();
+        assert_eq!(content.url, "not an mxc:// uri");
+    }
+
+    #[cfg(feature = "compat")]
+    #[test]
+    fn compat_null_or_missing_url() {
+        let content = from_json_value::<AvatarEventContent>(json!({ "url": null })).unwrap();
+        assert_eq!(content.url, "");
+
+        let content = from_json_value::<AvatarEventContent>(json!({})).unwrap();
+        assert_eq!(content.url, "");
+    }
+
+    #[cfg(not(feature = "compat"))]
+    #[test]
+    fn null_or_missing_url_rejected() {
+        assert!(from_json_value::<AvatarEventContent>(json!({ "url": null })).is_err());
+        assert!(from_json_value::<AvatarEventContent>(json!({})).is_err());
+    }
+}
@@ -10,7 +10,7 @@ use crate::StateEvent;
 pub type CanonicalAliasEvent = StateEvent<CanonicalAliasEventContent>;
 
 /// The payload for `CanonicalAliasEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, StateEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize, StateEventContent)]
 #[non_exhaustive]
 #[ruma_event(type = "m.room.canonical_alias")]
 pub struct CanonicalAliasEventContent {
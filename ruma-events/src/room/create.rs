@@ -11,7 +11,7 @@ use crate::StateEvent;
 pub type CreateEvent = StateEvent<CreateEventContent>;
 
 /// The payload for `CreateEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, StateEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, StateEventContent)]
 #[non_exhaustive]
 #[ruma_event(type = "m.room.create")]
 pub struct CreateEventContent {
@@ -44,7 +44,7 @@ impl CreateEventContent {
 }
 
 /// A reference to an old room replaced during a room version upgrade.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[non_exhaustive]
 pub struct PreviousRoom {
     /// The ID of the old room.
@@ -70,10 +70,10 @@ fn default_room_version_id() -> RoomVersionId {
 mod tests {
     use matches::assert_matches;
     use ruma_common::Raw;
-    use ruma_identifiers::{user_id, RoomVersionId};
+    use ruma_identifiers::{event_id, room_id, user_id, RoomVersionId};
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
-    use super::CreateEventContent;
+    use super::{CreateEventContent, PreviousRoom};
 
     #[test]
     fn serialization() {
@@ -114,4 +114,52 @@ mod tests {
             } if creator == "@carl:example.com"
         );
     }
+
+    #[test]
+    fn deserialization_v1_defaults() {
+        let json = json!({ "creator": "@a:b" });
+
+        assert_matches!(
+            from_json_value::<Raw<CreateEventContent>>(json)
+                .unwrap()
+                .deserialize()
+                .unwrap(),
+            CreateEventContent {
+                creator,
+                federate: true,
+                room_version: RoomVersionId::Version1,
+                predecessor: None,
+            } if creator == "@a:b"
+        );
+    }
+
+    #[test]
+    fn predecessor_roundtrip() {
+        let json = json!({
+            "creator": "@carl:example.com",
+            "room_version": "6",
+            "predecessor": {
+                "room_id": "!old:example.com",
+                "event_id": "$tombstone:example.com"
+            }
+        });
+
+        let content = from_json_value::<Raw<CreateEventContent>>(json.clone())
+            .unwrap()
+            .deserialize()
+            .unwrap();
+
+        assert_matches!(
+            &content,
+            CreateEventContent {
+                creator,
+                federate: true,
+                room_version: RoomVersionId::Version6,
+                predecessor: Some(PreviousRoom { room_id, event_id }),
+            } if *creator == user_id!("@carl:example.com")
+                && *room_id == room_id!("!old:example.com")
+                && *event_id == event_id!("$tombstone:example.com")
+        );
+        assert_eq!(to_json_value(&content).unwrap(), json);
+    }
 }
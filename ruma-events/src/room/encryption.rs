@@ -1,16 +1,18 @@
 //! Types for the *m.room.encryption* event.
 
+use std::time::Duration;
+
 use js_int::UInt;
 use ruma_events_macros::StateEventContent;
 use serde::{Deserialize, Serialize};
 
-use crate::{EventEncryptionAlgorithm, StateEvent};
+use crate::{duration_to_millis, EventEncryptionAlgorithm, StateEvent};
 
 /// Defines how messages sent in this room should be encrypted.
 pub type EncryptionEvent = StateEvent<EncryptionEventContent>;
 
 /// The payload for `EncryptionEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, StateEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, StateEventContent)]
 #[non_exhaustive]
 #[ruma_event(type = "m.room.encryption")]
 pub struct EncryptionEventContent {
@@ -37,4 +39,14 @@ impl EncryptionEventContent {
     pub fn new(algorithm: EventEncryptionAlgorithm) -> Self {
         Self { algorithm, rotation_period_ms: None, rotation_period_msgs: None }
     }
+
+    /// How long the session should be used before changing it, as a `Duration`.
+    pub fn rotation_period(&self) -> Option<Duration> {
+        self.rotation_period_ms.map(|ms| Duration::from_millis(ms.into()))
+    }
+
+    /// Sets `rotation_period_ms` from a `Duration`.
+    pub fn set_rotation_period(&mut self, period: Option<Duration>) {
+        self.rotation_period_ms = period.map(duration_to_millis);
+    }
 }
@@ -1,10 +1,10 @@
 //! Types for the *m.room.guest_access* event.
 
 use ruma_events_macros::StateEventContent;
+use ruma_serde::string_enum;
 use serde::{Deserialize, Serialize};
-use strum::{Display, EnumString};
 
-use crate::StateEvent;
+use crate::{StateEvent, SyncStateEvent};
 
 /// Controls whether guest users are allowed to join rooms.
 ///
@@ -13,7 +13,7 @@ use crate::StateEvent;
 pub type GuestAccessEvent = StateEvent<GuestAccessEventContent>;
 
 /// The payload for `GuestAccessEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, StateEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, StateEventContent)]
 #[non_exhaustive]
 #[ruma_event(type = "m.room.guest_access")]
 pub struct GuestAccessEventContent {
@@ -28,15 +28,86 @@ impl GuestAccessEventContent {
     }
 }
 
-/// A policy for guest user access to a room.
-#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, Deserialize, Serialize)]
-#[non_exhaustive]
-#[serde(rename_all = "snake_case")]
-#[strum(serialize_all = "snake_case")]
-pub enum GuestAccess {
-    /// Guests are allowed to join the room.
-    CanJoin,
-
-    /// Guests are not allowed to join the room.
-    Forbidden,
+impl GuestAccessEvent {
+    /// Returns the new guest access policy if this event changed it.
+    ///
+    /// An event without `prev_content` is always considered a change.
+    pub fn guest_access_change(&self) -> Option<GuestAccess> {
+        guest_access_change(&self.content, self.prev_content.as_ref())
+    }
+}
+
+impl SyncStateEvent<GuestAccessEventContent> {
+    /// Returns the new guest access policy if this event changed it.
+    ///
+    /// An event without `prev_content` is always considered a change.
+    pub fn guest_access_change(&self) -> Option<GuestAccess> {
+        guest_access_change(&self.content, self.prev_content.as_ref())
+    }
+}
+
+/// Internal function so all `GuestAccessEventContent` state event kinds can share the same
+/// implementation.
+fn guest_access_change(
+    content: &GuestAccessEventContent,
+    prev_content: Option<&GuestAccessEventContent>,
+) -> Option<GuestAccess> {
+    match prev_content {
+        Some(prev) if prev.guest_access == content.guest_access => None,
+        _ => Some(content.guest_access.clone()),
+    }
+}
+
+string_enum! {
+    /// A policy for guest user access to a room.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum GuestAccess {
+        /// Guests are allowed to join the room.
+        CanJoin => "can_join",
+
+        /// Guests are not allowed to join the room.
+        Forbidden => "forbidden",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_common::Raw;
+    use serde_json::{from_value as from_json_value, json, Value as JsonValue};
+
+    use super::{GuestAccess, GuestAccessEvent};
+
+    fn guest_access_event(prev_content: Option<JsonValue>) -> GuestAccessEvent {
+        let mut json = json!({
+            "type": "m.room.guest_access",
+            "content": {
+                "guest_access": "can_join"
+            },
+            "event_id": "$h29iv0s8:example.com",
+            "origin_server_ts": 1,
+            "room_id": "!n8f893n9:example.com",
+            "sender": "@carl:example.com",
+            "state_key": ""
+        });
+
+        if let Some(prev_content) = prev_content {
+            json["prev_content"] = prev_content;
+        }
+
+        from_json_value::<Raw<GuestAccessEvent>>(json).unwrap().deserialize().unwrap()
+    }
+
+    #[test]
+    fn guest_access_change() {
+        assert_eq!(guest_access_event(None).guest_access_change(), Some(GuestAccess::CanJoin));
+        assert_eq!(
+            guest_access_event(Some(json!({ "guest_access": "forbidden" }))).guest_access_change(),
+            Some(GuestAccess::CanJoin)
+        );
+        assert_eq!(
+            guest_access_event(Some(json!({ "guest_access": "can_join" }))).guest_access_change(),
+            None
+        );
+    }
 }
@@ -1,8 +1,8 @@
 //! Types for the *m.room.history_visibility* event.
 
 use ruma_events_macros::StateEventContent;
+use ruma_serde::string_enum;
 use serde::{Deserialize, Serialize};
-use strum::{Display, EnumString};
 
 use crate::StateEvent;
 
@@ -11,7 +11,7 @@ use crate::StateEvent;
 pub type HistoryVisibilityEvent = StateEvent<HistoryVisibilityEventContent>;
 
 /// The payload for `HistoryVisibilityEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, StateEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, StateEventContent)]
 #[non_exhaustive]
 #[ruma_event(type = "m.room.history_visibility")]
 pub struct HistoryVisibilityEventContent {
@@ -27,27 +27,28 @@ impl HistoryVisibilityEventContent {
     }
 }
 
-/// Who can see a room's history.
-#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, Deserialize, Serialize)]
-#[non_exhaustive]
-#[serde(rename_all = "snake_case")]
-#[strum(serialize_all = "snake_case")]
-pub enum HistoryVisibility {
-    /// Previous events are accessible to newly joined members from the point they were invited
-    /// onwards. Events stop being accessible when the member's state changes to something other
-    /// than *invite* or *join*.
-    Invited,
-
-    /// Previous events are accessible to newly joined members from the point they joined the room
-    /// onwards. Events stop being accessible when the member's state changes to something other
-    /// than *join*.
-    Joined,
-
-    /// Previous events are always accessible to newly joined members. All events in the room are
-    /// accessible, even those sent when the member was not a part of the room.
-    Shared,
-
-    /// All events while this is the `HistoryVisibility` value may be shared by any
-    /// participating homeserver with anyone, regardless of whether they have ever joined the room.
-    WorldReadable,
+string_enum! {
+    /// Who can see a room's history.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum HistoryVisibility {
+        /// Previous events are accessible to newly joined members from the point they were
+        /// invited onwards. Events stop being accessible when the member's state changes to
+        /// something other than *invite* or *join*.
+        Invited => "invited",
+
+        /// Previous events are accessible to newly joined members from the point they joined the
+        /// room onwards. Events stop being accessible when the member's state changes to something
+        /// other than *join*.
+        Joined => "joined",
+
+        /// Previous events are always accessible to newly joined members. All events in the room
+        /// are accessible, even those sent when the member was not a part of the room.
+        Shared => "shared",
+
+        /// All events while this is the `HistoryVisibility` value may be shared by any
+        /// participating homeserver with anyone, regardless of whether they have ever joined the
+        /// room.
+        WorldReadable => "world_readable",
+    }
 }
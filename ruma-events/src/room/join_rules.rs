@@ -1,16 +1,16 @@
 //! Types for the *m.room.join_rules* event.
 
 use ruma_events_macros::StateEventContent;
+use ruma_serde::string_enum;
 use serde::{Deserialize, Serialize};
-use strum::{Display, EnumString};
 
-use crate::StateEvent;
+use crate::{StateEvent, SyncStateEvent};
 
 /// Describes how users are allowed to join the room.
 pub type JoinRulesEvent = StateEvent<JoinRulesEventContent>;
 
 /// The payload for `JoinRulesEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, StateEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, StateEventContent)]
 #[non_exhaustive]
 #[ruma_event(type = "m.room.join_rules")]
 pub struct JoinRulesEventContent {
@@ -26,22 +26,97 @@ impl JoinRulesEventContent {
     }
 }
 
-/// The rule used for users wishing to join this room.
-#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, Deserialize, Serialize)]
-#[non_exhaustive]
-#[serde(rename_all = "lowercase")]
-#[strum(serialize_all = "lowercase")]
-pub enum JoinRule {
-    /// A user who wishes to join the room must first receive an invite to the room from someone
-    /// already inside of the room.
-    Invite,
+impl JoinRulesEvent {
+    /// Returns the new join rule if this event changed it.
+    ///
+    /// An event without `prev_content` is always considered a change.
+    pub fn join_rule_change(&self) -> Option<JoinRule> {
+        join_rule_change(&self.content, self.prev_content.as_ref())
+    }
+}
+
+impl SyncStateEvent<JoinRulesEventContent> {
+    /// Returns the new join rule if this event changed it.
+    ///
+    /// An event without `prev_content` is always considered a change.
+    pub fn join_rule_change(&self) -> Option<JoinRule> {
+        join_rule_change(&self.content, self.prev_content.as_ref())
+    }
+}
+
+/// Internal function so all `JoinRulesEventContent` state event kinds can share the same
+/// implementation.
+fn join_rule_change(
+    content: &JoinRulesEventContent,
+    prev_content: Option<&JoinRulesEventContent>,
+) -> Option<JoinRule> {
+    match prev_content {
+        Some(prev) if prev.join_rule == content.join_rule => None,
+        _ => Some(content.join_rule.clone()),
+    }
+}
+
+string_enum! {
+    /// The rule used for users wishing to join this room.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum JoinRule {
+        /// A user who wishes to join the room must first receive an invite to the room from someone
+        /// already inside of the room.
+        Invite => "invite",
 
-    /// Reserved but not yet implemented by the Matrix specification.
-    Knock,
+        /// Users can request an invite to the room.
+        ///
+        /// This also accepts the unstable `xyz.amorgan.knock` join rule when deserializing.
+        Knock => "knock" | "xyz.amorgan.knock",
 
-    /// Reserved but not yet implemented by the Matrix specification.
-    Private,
+        /// Reserved but not yet implemented by the Matrix specification.
+        Private => "private",
 
-    /// Anyone can join the room without any prior action.
-    Public,
+        /// Anyone can join the room without any prior action.
+        Public => "public",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use matches::assert_matches;
+    use ruma_common::Raw;
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::{JoinRule, JoinRulesEventContent};
+    use crate::StateEvent;
+
+    #[test]
+    fn knock_join_rule() {
+        let json = json!({
+            "type": "m.room.join_rules",
+            "content": {
+                "join_rule": "knock"
+            },
+            "event_id": "$h29iv0s8:example.com",
+            "origin_server_ts": 1,
+            "room_id": "!n8f893n9:example.com",
+            "sender": "@carl:example.com",
+            "state_key": ""
+        });
+
+        let event = from_json_value::<Raw<StateEvent<JoinRulesEventContent>>>(json.clone())
+            .unwrap()
+            .deserialize()
+            .unwrap();
+
+        assert_eq!(event.content.join_rule, JoinRule::Knock);
+        assert_eq!(event.join_rule_change(), Some(JoinRule::Knock));
+        assert_eq!(to_json_value(&event).unwrap(), json);
+    }
+
+    #[test]
+    fn unstable_knock_join_rule() {
+        assert_matches!(
+            from_json_value::<JoinRulesEventContent>(json!({ "join_rule": "xyz.amorgan.knock" }))
+                .unwrap(),
+            JoinRulesEventContent { join_rule: JoinRule::Knock }
+        );
+    }
 }
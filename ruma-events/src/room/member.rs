@@ -1,13 +1,17 @@
 //! Types for the *m.room.member* event.
 
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, convert::TryFrom};
 
 use ruma_events_macros::StateEventContent;
-use ruma_identifiers::{ServerKeyId, ServerName, UserId};
+use ruma_identifiers::{Error as IdentifierError, RoomVersionId, ServerKeyId, ServerName, UserId};
+use ruma_serde::string_enum;
 use serde::{Deserialize, Serialize};
-use strum::{Display, EnumString};
+use serde_json::value::RawValue as RawJsonValue;
 
-use crate::{StateEvent, StrippedStateEvent, SyncStateEvent};
+use crate::{
+    EventContent, HasDeserializeFields, RedactedEventContent, RedactedStateEventContent,
+    StateEvent, StrippedStateEvent, SyncStateEvent,
+};
 
 /// The current membership state of a user in the room.
 ///
@@ -37,8 +41,8 @@ use crate::{StateEvent, StrippedStateEvent, SyncStateEvent};
 pub type MemberEvent = StateEvent<MemberEventContent>;
 
 /// The payload for `MemberEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, StateEventContent)]
-#[ruma_event(type = "m.room.member")]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, StateEventContent)]
+#[ruma_event(type = "m.room.member", custom_redacted)]
 pub struct MemberEventContent {
     /// The avatar URL for this user, if any. This is added by the homeserver.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -57,36 +61,114 @@ pub struct MemberEventContent {
     #[ruma_event(skip_redaction)]
     pub membership: MembershipState,
 
+    /// An optional reason for the membership change, e.g. why a user wants to knock on a room or
+    /// why they have been kicked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
     /// If this member event is the successor to a third party invitation, this field will
     /// contain information about that invitation.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub third_party_invite: Option<ThirdPartyInvite>,
+
+    /// The user that authorised this join through a restricted join rule, if any.
+    ///
+    /// This is used by the server of the joining user to prove that it is allowed to join the
+    /// room without an invite.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub join_authorised_via_users_server: Option<UserId>,
 }
 
-/// The membership state of a user.
-#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, Deserialize, Serialize)]
-#[non_exhaustive]
-#[serde(rename_all = "lowercase")]
-#[strum(serialize_all = "lowercase")]
-pub enum MembershipState {
-    /// The user is banned.
-    Ban,
+impl MemberEventContent {
+    /// Redacts a `MemberEventContent` according to the redaction rules of the given room version.
+    ///
+    /// `membership` is always kept. Room versions 9 and later also keep
+    /// `join_authorised_via_users_server`.
+    pub fn redact(self, version: RoomVersionId) -> RedactedMemberEventContent {
+        let join_authorised_via_users_server = if keeps_join_authorisation(&version) {
+            self.join_authorised_via_users_server
+        } else {
+            None
+        };
+
+        RedactedMemberEventContent { membership: self.membership, join_authorised_via_users_server }
+    }
+}
+
+/// Whether the redaction rules of the given room version keep `join_authorised_via_users_server`.
+fn keeps_join_authorisation(room_version: &RoomVersionId) -> bool {
+    room_version.as_str().parse::<u32>().map_or(false, |version| version >= 9)
+}
+
+/// A member event that has been redacted.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RedactedMemberEventContent {
+    /// The membership state of this user.
+    pub membership: MembershipState,
+
+    /// The user that authorised this join through a restricted join rule, if any.
+    ///
+    /// Only kept after redaction in room version 9 and later.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub join_authorised_via_users_server: Option<UserId>,
+}
+
+impl EventContent for RedactedMemberEventContent {
+    fn event_type(&self) -> &str {
+        "m.room.member"
+    }
+
+    fn from_parts(event_type: &str, content: Box<RawJsonValue>) -> Result<Self, serde_json::Error> {
+        if event_type != "m.room.member" {
+            return Err(::serde::de::Error::custom(format!(
+                "expected event type `m.room.member`, found `{}`",
+                event_type
+            )));
+        }
+
+        serde_json::from_str(content.get())
+    }
+}
 
-    /// The user has been invited.
-    Invite,
+// `membership` is always kept, so there is no empty version of this content.
+impl RedactedEventContent for RedactedMemberEventContent {
+    fn has_serialize_fields(&self) -> bool {
+        true
+    }
+
+    fn has_deserialize_fields() -> HasDeserializeFields {
+        HasDeserializeFields::True
+    }
+}
+
+impl RedactedStateEventContent for RedactedMemberEventContent {}
+
+string_enum! {
+    /// The membership state of a user.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum MembershipState {
+        /// The user is banned.
+        Ban => "ban",
+
+        /// The user has been invited.
+        Invite => "invite",
 
-    /// The user has joined.
-    Join,
+        /// The user has joined.
+        Join => "join",
 
-    /// The user has requested to join.
-    Knock,
+        /// The user has requested to join.
+        ///
+        /// This also accepts the unstable `xyz.amorgan.knock` membership when deserializing.
+        Knock => "knock" | "xyz.amorgan.knock",
 
-    /// The user has left.
-    Leave,
+        /// The user has left.
+        Leave => "leave",
+    }
 }
 
 /// Information about a third party invitation.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct ThirdPartyInvite {
     /// A name which can be displayed to represent the user instead of their third party
     /// identifier.
@@ -99,7 +181,7 @@ pub struct ThirdPartyInvite {
 
 /// A block of content which has been signed, which servers can use to verify a third party
 /// invitation.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct SignedContent {
     /// The invited Matrix user ID.
     ///
@@ -115,7 +197,7 @@ pub struct SignedContent {
 }
 
 /// Translation of the membership change in `m.room.member` event.
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[non_exhaustive]
 pub enum MembershipChange {
     /// No change.
@@ -151,6 +233,18 @@ pub enum MembershipChange {
     /// User had their invite revoked.
     InvitationRevoked,
 
+    /// User knocked on the room.
+    Knocked,
+
+    /// User retracted their knock.
+    KnockRetracted,
+
+    /// User's knock was denied.
+    KnockDenied,
+
+    /// User's knock was accepted, i.e. they were invited.
+    KnockAccepted,
+
     /// `displayname` or `avatar_url` changed.
     ProfileChanged {
         /// Whether the `displayname` changed.
@@ -182,12 +276,17 @@ fn membership_change(
             displayname: None,
             is_direct: None,
             membership: St::Leave,
+            reason: None,
             third_party_invite: None,
+            join_authorised_via_users_server: None,
         }
     };
 
-    match (prev_content.membership, &content.membership) {
-        (St::Invite, St::Invite) | (St::Leave, St::Leave) | (St::Ban, St::Ban) => Ch::None,
+    match (&prev_content.membership, &content.membership) {
+        (St::Invite, St::Invite)
+        | (St::Leave, St::Leave)
+        | (St::Ban, St::Ban)
+        | (St::Knock, St::Knock) => Ch::None,
         (St::Invite, St::Join) | (St::Leave, St::Join) => Ch::Joined,
         (St::Invite, St::Leave) => {
             if sender == state_key {
@@ -212,7 +311,19 @@ fn membership_change(
         (St::Join, St::Ban) => Ch::KickedAndBanned,
         (St::Leave, St::Invite) => Ch::Invited,
         (St::Ban, St::Leave) => Ch::Unbanned,
-        (St::Knock, _) | (_, St::Knock) => Ch::NotImplemented,
+        (St::Leave, St::Knock) => Ch::Knocked,
+        (St::Knock, St::Leave) => {
+            if sender == state_key {
+                Ch::KnockRetracted
+            } else {
+                Ch::KnockDenied
+            }
+        }
+        (St::Knock, St::Invite) => Ch::KnockAccepted,
+        (St::Knock, St::Ban) => Ch::Banned,
+        (St::Knock, St::Join) => Ch::Error,
+        (_, St::Knock) => Ch::Error,
+        _ => Ch::NotImplemented,
     }
 }
 
@@ -223,6 +334,13 @@ impl MemberEvent {
     pub fn membership_change(&self) -> MembershipChange {
         membership_change(&self.content, self.prev_content.as_ref(), &self.sender, &self.state_key)
     }
+
+    /// The ID of the user whose membership this event is about, parsed from `state_key`.
+    ///
+    /// Fails if the `state_key` is not a valid user ID.
+    pub fn state_key_as_user_id(&self) -> Result<UserId, IdentifierError> {
+        UserId::try_from(self.state_key.as_str())
+    }
 }
 
 impl SyncStateEvent<MemberEventContent> {
@@ -232,6 +350,13 @@ impl SyncStateEvent<MemberEventContent> {
     pub fn membership_change(&self) -> MembershipChange {
         membership_change(&self.content, self.prev_content.as_ref(), &self.sender, &self.state_key)
     }
+
+    /// The ID of the user whose membership this event is about, parsed from `state_key`.
+    ///
+    /// Fails if the `state_key` is not a valid user ID.
+    pub fn state_key_as_user_id(&self) -> Result<UserId, IdentifierError> {
+        UserId::try_from(self.state_key.as_str())
+    }
 }
 
 impl StrippedStateEvent<MemberEventContent> {
@@ -241,19 +366,32 @@ impl StrippedStateEvent<MemberEventContent> {
     pub fn membership_change(&self) -> MembershipChange {
         membership_change(&self.content, None, &self.sender, &self.state_key)
     }
+
+    /// The ID of the user whose membership this event is about, parsed from `state_key`.
+    ///
+    /// Fails if the `state_key` is not a valid user ID.
+    pub fn state_key_as_user_id(&self) -> Result<UserId, IdentifierError> {
+        UserId::try_from(self.state_key.as_str())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::time::{Duration, UNIX_EPOCH};
+    use std::{
+        convert::TryFrom,
+        time::{Duration, UNIX_EPOCH},
+    };
 
     use maplit::btreemap;
     use matches::assert_matches;
     use ruma_common::Raw;
-    use ruma_identifiers::{server_key_id, server_name};
-    use serde_json::{from_value as from_json_value, json};
+    use ruma_identifiers::{server_key_id, server_name, user_id, RoomVersionId};
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
-    use super::{MemberEventContent, MembershipState, SignedContent, ThirdPartyInvite};
+    use super::{
+        MemberEventContent, MembershipChange, MembershipState, RedactedMemberEventContent,
+        SignedContent, ThirdPartyInvite,
+    };
     use crate::StateEvent;
 
     #[test]
@@ -281,7 +419,9 @@ mod tests {
                     displayname: None,
                     is_direct: None,
                     membership: MembershipState::Join,
+                    reason: None,
                     third_party_invite: None,
+                    join_authorised_via_users_server: None,
                 },
                 event_id,
                 origin_server_ts,
@@ -327,7 +467,9 @@ mod tests {
                     displayname: None,
                     is_direct: None,
                     membership: MembershipState::Join,
+                    reason: None,
                     third_party_invite: None,
+                    join_authorised_via_users_server: None,
                 },
                 event_id,
                 origin_server_ts,
@@ -340,7 +482,9 @@ mod tests {
                     displayname: None,
                     is_direct: None,
                     membership: MembershipState::Join,
+                    reason: None,
                     third_party_invite: None,
+                    join_authorised_via_users_server: None,
                 }),
             } if event_id == "$h29iv0s8:example.com"
                 && origin_server_ts == UNIX_EPOCH + Duration::from_millis(1)
@@ -391,10 +535,12 @@ mod tests {
                     displayname: Some(displayname),
                     is_direct: Some(true),
                     membership: MembershipState::Invite,
+                    reason: None,
                     third_party_invite: Some(ThirdPartyInvite {
                         display_name: third_party_displayname,
                         signed: SignedContent { mxid, signatures, token },
                     }),
+                    join_authorised_via_users_server: None,
                 },
                 event_id,
                 origin_server_ts,
@@ -465,7 +611,9 @@ mod tests {
                     displayname: None,
                     is_direct: None,
                     membership: MembershipState::Join,
+                    reason: None,
                     third_party_invite: None,
+                    join_authorised_via_users_server: None,
                 },
                 event_id,
                 origin_server_ts,
@@ -478,10 +626,12 @@ mod tests {
                     displayname: Some(displayname),
                     is_direct: Some(true),
                     membership: MembershipState::Invite,
+                    reason: None,
                     third_party_invite: Some(ThirdPartyInvite {
                         display_name: third_party_displayname,
                         signed: SignedContent { mxid, signatures, token },
                     }),
+                    join_authorised_via_users_server: None,
                 }),
             } if event_id == "$143273582443PhrSn:example.org"
                 && origin_server_ts == UNIX_EPOCH + Duration::from_millis(233)
@@ -501,4 +651,108 @@ mod tests {
                 && token == "abc123"
         );
     }
+
+    #[test]
+    fn knock_with_reason() {
+        let json = json!({
+            "type": "m.room.member",
+            "content": {
+                "membership": "knock",
+                "reason": "Looking for support"
+            },
+            "event_id": "$h29iv0s8:example.com",
+            "origin_server_ts": 1,
+            "room_id": "!n8f893n9:example.com",
+            "sender": "@carl:example.com",
+            "state_key": "@carl:example.com"
+        });
+
+        let event = from_json_value::<Raw<StateEvent<MemberEventContent>>>(json.clone())
+            .unwrap()
+            .deserialize()
+            .unwrap();
+
+        assert_matches!(
+            &event.content,
+            MemberEventContent {
+                membership: MembershipState::Knock,
+                reason: Some(reason),
+                ..
+            } if reason == "Looking for support"
+        );
+        assert_matches!(event.membership_change(), MembershipChange::Knocked);
+        assert_eq!(to_json_value(&event).unwrap(), json);
+    }
+
+    #[test]
+    fn unstable_knock_membership() {
+        assert_eq!(
+            from_json_value::<MembershipState>(json!("xyz.amorgan.knock")).unwrap(),
+            MembershipState::Knock
+        );
+        assert_eq!("xyz.amorgan.knock".parse::<MembershipState>().unwrap(), MembershipState::Knock);
+        assert_eq!(MembershipState::Knock.to_string(), "knock");
+        assert_eq!(to_json_value(MembershipState::Knock).unwrap(), json!("knock"));
+    }
+
+    #[test]
+    fn join_authorised_via_users_server() {
+        let json = json!({
+            "join_authorised_via_users_server": "@alice:example.org",
+            "membership": "join"
+        });
+
+        let content = from_json_value::<MemberEventContent>(json.clone()).unwrap();
+        assert_eq!(content.join_authorised_via_users_server, Some(user_id!("@alice:example.org")));
+        assert_eq!(to_json_value(&content).unwrap(), json);
+    }
+
+    #[test]
+    fn redact_join_authorised_via_users_server() {
+        let content = from_json_value::<MemberEventContent>(json!({
+            "displayname": "Carl",
+            "join_authorised_via_users_server": "@alice:example.org",
+            "membership": "join"
+        }))
+        .unwrap();
+
+        assert_eq!(
+            content.clone().redact(RoomVersionId::Version6),
+            RedactedMemberEventContent {
+                membership: MembershipState::Join,
+                join_authorised_via_users_server: None,
+            }
+        );
+
+        let redacted = content.redact(RoomVersionId::try_from("9").unwrap());
+        assert_eq!(
+            to_json_value(&redacted).unwrap(),
+            json!({
+                "join_authorised_via_users_server": "@alice:example.org",
+                "membership": "join"
+            })
+        );
+    }
+
+    #[test]
+    fn state_key_as_user_id() {
+        let mut json = json!({
+            "type": "m.room.member",
+            "content": {
+                "membership": "join"
+            },
+            "event_id": "$h29iv0s8:example.com",
+            "origin_server_ts": 1,
+            "room_id": "!n8f893n9:example.com",
+            "sender": "@carl:example.com",
+            "state_key": "@carl:example.com"
+        });
+
+        let event = from_json_value::<StateEvent<MemberEventContent>>(json.clone()).unwrap();
+        assert_eq!(event.state_key_as_user_id().unwrap(), user_id!("@carl:example.com"));
+
+        json["state_key"] = "example.com".into();
+        let event = from_json_value::<StateEvent<MemberEventContent>>(json).unwrap();
+        assert!(event.state_key_as_user_id().is_err());
+    }
 }
@@ -1,17 +1,21 @@
 //! Types for the *m.room.message* event.
 
-use std::fmt;
+use std::collections::BTreeMap;
 
 use js_int::UInt;
 use ruma_events_macros::MessageEventContent;
 use ruma_identifiers::EventId;
-use serde::{Deserialize, Serialize};
+use ruma_serde::string_enum;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{value::RawValue as RawJsonValue, Value as JsonValue};
 
 use super::{EncryptedFile, ImageInfo, ThumbnailInfo};
 
 pub mod feedback;
 
-use crate::MessageEvent as OuterMessageEvent;
+use crate::{
+    check_known_fields, from_raw_json_value, MessageEvent as OuterMessageEvent, StrictEventContent,
+};
 
 /// This event is used when sending messages in a room.
 ///
@@ -19,50 +23,337 @@ use crate::MessageEvent as OuterMessageEvent;
 pub type MessageEvent = OuterMessageEvent<MessageEventContent>;
 
 /// The payload for `MessageEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, MessageEventContent)]
+///
+/// Message types that are not known to ruma deserialize into a hidden variant instead of failing,
+/// so `match`es on this type need a wildcard arm. Use `msgtype` and `body` to inspect such messages.
+#[derive(Clone, Debug, PartialEq, Eq, MessageEventContent)]
 #[non_exhaustive]
 #[ruma_event(type = "m.room.message")]
-#[serde(tag = "msgtype")]
 pub enum MessageEventContent {
     /// An audio message.
-    #[serde(rename = "m.audio")]
     Audio(AudioMessageEventContent),
 
     /// An emote message.
-    #[serde(rename = "m.emote")]
     Emote(EmoteMessageEventContent),
 
     /// A file message.
-    #[serde(rename = "m.file")]
     File(FileMessageEventContent),
 
     /// An image message.
-    #[serde(rename = "m.image")]
     Image(ImageMessageEventContent),
 
     /// A location message.
-    #[serde(rename = "m.location")]
     Location(LocationMessageEventContent),
 
     /// A notice message.
-    #[serde(rename = "m.notice")]
     Notice(NoticeMessageEventContent),
 
     /// A server notice message.
-    #[serde(rename = "m.server_notice")]
     ServerNotice(ServerNoticeMessageEventContent),
 
     /// A text message.
-    #[serde(rename = "m.text")]
     Text(TextMessageEventContent),
 
     /// A video message.
-    #[serde(rename = "m.video")]
     Video(VideoMessageEventContent),
+
+    #[doc(hidden)]
+    _Custom(CustomEventContent),
+}
+
+impl MessageEventContent {
+    /// The `msgtype` string of this message.
+    pub fn msgtype(&self) -> &str {
+        match self {
+            Self::Audio(_) => "m.audio",
+            Self::Emote(_) => "m.emote",
+            Self::File(_) => "m.file",
+            Self::Image(_) => "m.image",
+            Self::Location(_) => "m.location",
+            Self::Notice(_) => "m.notice",
+            Self::ServerNotice(_) => "m.server_notice",
+            Self::Text(_) => "m.text",
+            Self::Video(_) => "m.video",
+            Self::_Custom(content) => &content.msgtype,
+        }
+    }
+
+    /// The textual representation of this message.
+    pub fn body(&self) -> &str {
+        match self {
+            Self::Audio(content) => &content.body,
+            Self::Emote(content) => &content.body,
+            Self::File(content) => &content.body,
+            Self::Image(content) => &content.body,
+            Self::Location(content) => &content.body,
+            Self::Notice(content) => &content.body,
+            Self::ServerNotice(content) => &content.body,
+            Self::Text(content) => &content.body,
+            Self::Video(content) => &content.body,
+            Self::_Custom(content) => &content.body,
+        }
+    }
+
+    /// Information about related messages, for the message types that can have it.
+    pub fn relates_to(&self) -> Option<&RelatesTo> {
+        match self {
+            Self::Emote(content) => content.relates_to.as_ref(),
+            Self::Notice(content) => content.relates_to.as_ref(),
+            Self::Text(content) => content.relates_to.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// The new content of the message edited by this message, if it is an edit.
+    pub fn new_content(&self) -> Option<&MessageEventContent> {
+        match self {
+            Self::Emote(content) => content.new_content.as_deref(),
+            Self::Notice(content) => content.new_content.as_deref(),
+            Self::Text(content) => content.new_content.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Applies an edit to this message, returning the content to display instead of it.
+    ///
+    /// `new_content` is the `m.new_content` of the edit. The reply relation of this message is
+    /// kept, since an edit can't change which message is replied to.
+    pub fn apply_replacement(&self, new_content: MessageEventContent) -> MessageEventContent {
+        let relates_to = self
+            .relates_to()
+            .and_then(|relates_to| relates_to.in_reply_to.clone())
+            .map(|in_reply_to| RelatesTo { in_reply_to: Some(in_reply_to), replacement: None });
+
+        let mut content = new_content;
+        match &mut content {
+            Self::Emote(content) => {
+                content.relates_to = relates_to;
+                content.new_content = None;
+            }
+            Self::Notice(content) => {
+                content.relates_to = relates_to;
+                content.new_content = None;
+            }
+            Self::Text(content) => {
+                content.relates_to = relates_to;
+                content.new_content = None;
+            }
+            _ => {}
+        }
+
+        content
+    }
+
+    /// Creates a plain text reply to `original_message`.
+    ///
+    /// The body is prefixed with a quote of the original message, as described by the
+    /// [rich reply fallback](https://matrix.org/docs/spec/client_server/r0.6.1#fallbacks-for-rich-replies)
+    /// rules, and the reply relation to the original event is set.
+    pub fn text_reply_plain(reply: impl Into<String>, original_message: &MessageEvent) -> Self {
+        let quote = plain_quote_fallback(original_message);
+
+        Self::Text(TextMessageEventContent {
+            body: format!("{}\n\n{}", quote, reply.into()),
+            formatted: None,
+            relates_to: Some(RelatesTo {
+                in_reply_to: Some(InReplyTo { event_id: original_message.event_id.clone() }),
+                replacement: None,
+            }),
+            new_content: None,
+        })
+    }
+}
+
+/// Returns the content a client should display for `original`, given the edits of it that are
+/// known.
+///
+/// The newest edit wins, by `origin_server_ts` and then by event ID. Edits are ignored if they were
+/// sent by another user than `original`, replace a different event (e.g. an earlier edit rather
+/// than `original` itself) or have no `m.new_content`. Without any applicable edit, the content of
+/// `original` is returned.
+pub fn resolve_edits<'a>(
+    original: &MessageEvent,
+    edits: impl IntoIterator<Item = &'a MessageEvent>,
+) -> MessageEventContent {
+    edits
+        .into_iter()
+        .filter(|edit| edit.sender == original.sender)
+        .filter_map(|edit| {
+            let replacement = edit.content.relates_to()?.replacement.as_ref()?;
+            if replacement.event_id != original.event_id {
+                return None;
+            }
+
+            Some((edit, edit.content.new_content()?))
+        })
+        .max_by(|(a, _), (b, _)| {
+            a.origin_server_ts
+                .cmp(&b.origin_server_ts)
+                .then_with(|| a.event_id.as_str().cmp(b.event_id.as_str()))
+        })
+        .map_or_else(
+            || original.content.clone(),
+            |(_, new_content)| original.content.apply_replacement(new_content.clone()),
+        )
+}
+
+/// Builds the quoted fallback of `original_message` to prepend to a plain text reply.
+fn plain_quote_fallback(original_message: &MessageEvent) -> String {
+    let sender = &original_message.sender;
+    let body = match &original_message.content {
+        MessageEventContent::Audio(_) => "sent an audio file.",
+        MessageEventContent::File(_) => "sent a file.",
+        MessageEventContent::Image(_) => "sent an image.",
+        MessageEventContent::Location(_) => "sent a location.",
+        MessageEventContent::Video(_) => "sent a video.",
+        content => strip_reply_fallback(content.body()),
+    };
+
+    let mut lines = body.lines();
+    let first_line = lines.next().unwrap_or_default();
+    let mut quote = match &original_message.content {
+        MessageEventContent::Emote(_) => format!("> * <{}> {}", sender, first_line),
+        _ => format!("> <{}> {}", sender, first_line),
+    };
+
+    for line in lines {
+        quote.push_str("\n> ");
+        quote.push_str(line);
+    }
+
+    quote
+}
+
+/// Removes the quoted fallback of a previous reply from the start of `body`, so replies to
+/// replies don't nest quotes.
+fn strip_reply_fallback(body: &str) -> &str {
+    let mut rest = body;
+    while rest.starts_with("> ") || rest == ">" || rest.starts_with(">\n") {
+        rest = rest.find('\n').map_or("", |i| &rest[i + 1..]);
+    }
+
+    if rest.len() == body.len() {
+        body
+    } else {
+        rest.trim_start_matches('\n')
+    }
+}
+
+impl Serialize for MessageEventContent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        #[serde(tag = "msgtype")]
+        enum KnownMessageType<'a> {
+            #[serde(rename = "m.audio")]
+            Audio(&'a AudioMessageEventContent),
+            #[serde(rename = "m.emote")]
+            Emote(&'a EmoteMessageEventContent),
+            #[serde(rename = "m.file")]
+            File(&'a FileMessageEventContent),
+            #[serde(rename = "m.image")]
+            Image(&'a ImageMessageEventContent),
+            #[serde(rename = "m.location")]
+            Location(&'a LocationMessageEventContent),
+            #[serde(rename = "m.notice")]
+            Notice(&'a NoticeMessageEventContent),
+            #[serde(rename = "m.server_notice")]
+            ServerNotice(&'a ServerNoticeMessageEventContent),
+            #[serde(rename = "m.text")]
+            Text(&'a TextMessageEventContent),
+            #[serde(rename = "m.video")]
+            Video(&'a VideoMessageEventContent),
+        }
+
+        let known = match self {
+            Self::Audio(content) => KnownMessageType::Audio(content),
+            Self::Emote(content) => KnownMessageType::Emote(content),
+            Self::File(content) => KnownMessageType::File(content),
+            Self::Image(content) => KnownMessageType::Image(content),
+            Self::Location(content) => KnownMessageType::Location(content),
+            Self::Notice(content) => KnownMessageType::Notice(content),
+            Self::ServerNotice(content) => KnownMessageType::ServerNotice(content),
+            Self::Text(content) => KnownMessageType::Text(content),
+            Self::Video(content) => KnownMessageType::Video(content),
+            Self::_Custom(content) => return content.serialize(serializer),
+        };
+
+        known.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MessageEventContent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct MessageTypeDeHelper {
+            msgtype: String,
+        }
+
+        let json = Box::<RawJsonValue>::deserialize(deserializer)?;
+        let MessageTypeDeHelper { msgtype } = from_raw_json_value(&json)?;
+
+        Ok(match msgtype.as_str() {
+            "m.audio" => Self::Audio(from_raw_json_value(&json)?),
+            "m.emote" => Self::Emote(from_raw_json_value(&json)?),
+            "m.file" => Self::File(from_raw_json_value(&json)?),
+            "m.image" => Self::Image(from_raw_json_value(&json)?),
+            "m.location" => Self::Location(from_raw_json_value(&json)?),
+            "m.notice" => Self::Notice(from_raw_json_value(&json)?),
+            "m.server_notice" => Self::ServerNotice(from_raw_json_value(&json)?),
+            "m.text" => Self::Text(from_raw_json_value(&json)?),
+            "m.video" => Self::Video(from_raw_json_value(&json)?),
+            _ => Self::_Custom(from_raw_json_value(&json)?),
+        })
+    }
+}
+
+/// Checks the fields of the message type's struct, since the flattened `FormattedBody` rules out
+/// `#[serde(deny_unknown_fields)]`. Messages with a custom `msgtype` are not checked.
+impl StrictEventContent for MessageEventContent {
+    fn deserialize_strict(json: &RawJsonValue) -> serde_json::Result<Self> {
+        let content: Self = from_raw_json_value(json)?;
+        let known_fields: &'static [&'static str] = match content {
+            Self::Audio(_) | Self::Image(_) | Self::Video(_) => {
+                &["msgtype", "body", "info", "url", "file"]
+            }
+            Self::File(_) => &["msgtype", "body", "filename", "info", "url", "file"],
+            Self::Location(_) => &["msgtype", "body", "geo_uri", "info"],
+            Self::Emote(_) | Self::Notice(_) | Self::Text(_) => {
+                &["msgtype", "body", "format", "formatted_body", "m.relates_to", "m.new_content"]
+            }
+            Self::ServerNotice(_) => {
+                &["msgtype", "body", "server_notice_type", "admin_contact", "limit_type"]
+            }
+            Self::_Custom(_) => return Ok(content),
+        };
+
+        check_known_fields(json, known_fields)?;
+        Ok(content)
+    }
+}
+
+/// The payload for a message with a `msgtype` that is not known to ruma.
+#[doc(hidden)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CustomEventContent {
+    /// The message type.
+    msgtype: String,
+
+    /// The textual representation of this message.
+    body: String,
+
+    /// The remaining fields of the content.
+    #[serde(flatten)]
+    data: BTreeMap<String, JsonValue>,
 }
 
 /// The payload for an audio message.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct AudioMessageEventContent {
     /// The textual representation of this message.
     pub body: String,
@@ -82,7 +373,7 @@ pub struct AudioMessageEventContent {
 }
 
 /// Metadata about an audio clip.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct AudioInfo {
     /// The duration of the audio in milliseconds.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -98,7 +389,7 @@ pub struct AudioInfo {
 }
 
 /// The payload for an emote message.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct EmoteMessageEventContent {
     /// The emote action to perform.
     pub body: String,
@@ -106,10 +397,40 @@ pub struct EmoteMessageEventContent {
     /// Formatted form of the message `body`.
     #[serde(flatten)]
     pub formatted: Option<FormattedBody>,
+
+    /// Information about related messages for
+    /// [rich replies](https://matrix.org/docs/spec/client_server/r0.6.1#rich-replies) and edits.
+    #[serde(rename = "m.relates_to", skip_serializing_if = "Option::is_none")]
+    pub relates_to: Option<RelatesTo>,
+
+    /// The new content of the message this message edits, if it is an edit.
+    #[serde(rename = "m.new_content", skip_serializing_if = "Option::is_none")]
+    pub new_content: Option<Box<MessageEventContent>>,
+}
+
+impl EmoteMessageEventContent {
+    /// A convenience constructor to create a plain-text emote.
+    pub fn plain(body: impl Into<String>) -> Self {
+        Self { body: body.into(), formatted: None, relates_to: None, new_content: None }
+    }
+
+    /// A convenience constructor to create an html emote.
+    pub fn html(body: impl Into<String>, html_body: impl Into<String>) -> Self {
+        Self { formatted: Some(FormattedBody::html(html_body)), ..Self::plain(body) }
+    }
+
+    /// A convenience constructor to create a markdown emote.
+    ///
+    /// Returns an html emote if some markdown formatting was detected, otherwise returns a
+    /// plain-text emote.
+    #[cfg(feature = "markdown")]
+    pub fn markdown(body: impl AsRef<str> + Into<String>) -> Self {
+        Self { formatted: FormattedBody::markdown(&body), ..Self::plain(body) }
+    }
 }
 
 /// The payload for a file message.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct FileMessageEventContent {
     /// A human-readable description of the file. This is recommended to be the filename of the
     /// original upload.
@@ -134,7 +455,7 @@ pub struct FileMessageEventContent {
 }
 
 /// Metadata about a file.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct FileInfo {
     /// The mimetype of the file, e.g. "application/msword."
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -158,7 +479,7 @@ pub struct FileInfo {
 }
 
 /// The payload for an image message.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct ImageMessageEventContent {
     /// A textual representation of the image. This could be the alt text of the image, the filename
     /// of the image, or some kind of content description for accessibility e.g. "image attachment."
@@ -179,7 +500,7 @@ pub struct ImageMessageEventContent {
 }
 
 /// The payload for a location message.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct LocationMessageEventContent {
     /// A description of the location e.g. "Big Ben, London, UK,"or some kind of content description
     /// for accessibility, e.g. "location attachment."
@@ -194,7 +515,7 @@ pub struct LocationMessageEventContent {
 }
 
 /// Thumbnail info associated with a location.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct LocationInfo {
     /// Metadata about the image referred to in `thumbnail_url` or `thumbnail_file`.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -212,7 +533,7 @@ pub struct LocationInfo {
 }
 
 /// The payload for a notice message.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct NoticeMessageEventContent {
     /// The notice text to send.
     pub body: String,
@@ -222,20 +543,38 @@ pub struct NoticeMessageEventContent {
     pub formatted: Option<FormattedBody>,
 
     /// Information about related messages for
-    /// [rich replies](https://matrix.org/docs/spec/client_server/r0.6.1#rich-replies).
+    /// [rich replies](https://matrix.org/docs/spec/client_server/r0.6.1#rich-replies) and edits.
     #[serde(rename = "m.relates_to", skip_serializing_if = "Option::is_none")]
     pub relates_to: Option<RelatesTo>,
+
+    /// The new content of the message this message edits, if it is an edit.
+    #[serde(rename = "m.new_content", skip_serializing_if = "Option::is_none")]
+    pub new_content: Option<Box<MessageEventContent>>,
 }
 
 impl NoticeMessageEventContent {
     /// A convenience constructor to create a plain text notices.
     pub fn plain(body: impl Into<String>) -> Self {
-        Self { body: body.into(), formatted: None, relates_to: None }
+        Self { body: body.into(), formatted: None, relates_to: None, new_content: None }
+    }
+
+    /// A convenience constructor to create an html notice.
+    pub fn html(body: impl Into<String>, html_body: impl Into<String>) -> Self {
+        Self { formatted: Some(FormattedBody::html(html_body)), ..Self::plain(body) }
+    }
+
+    /// A convenience constructor to create a markdown notice.
+    ///
+    /// Returns an html notice if some markdown formatting was detected, otherwise returns a
+    /// plain-text notice.
+    #[cfg(feature = "markdown")]
+    pub fn markdown(body: impl AsRef<str> + Into<String>) -> Self {
+        Self { formatted: FormattedBody::markdown(&body), ..Self::plain(body) }
     }
 }
 
 /// The payload for a server notice message.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct ServerNoticeMessageEventContent {
     /// A human-readable description of the notice.
     pub body: String,
@@ -256,51 +595,43 @@ pub struct ServerNoticeMessageEventContent {
     pub limit_type: Option<LimitType>,
 }
 
-/// Types of server notices.
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
-#[non_exhaustive]
-pub enum ServerNoticeType {
-    /// The server has exceeded some limit which requires the server administrator to intervene.
-    #[serde(rename = "m.server_notice.usage_limit_reached")]
-    UsageLimitReached,
-}
-
-/// Types of usage limits.
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
-#[non_exhaustive]
-#[serde(rename_all = "snake_case")]
-pub enum LimitType {
-    /// The server's number of active users in the last 30 days has exceeded the maximum.
-    ///
-    /// New connections are being refused by the server. What defines "active" is left as an
-    /// implementation detail, however servers are encouraged to treat syncing users as "active".
-    MonthlyActiveUser,
+string_enum! {
+    /// Types of server notices.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum ServerNoticeType {
+        /// The server has exceeded some limit which requires the server administrator to intervene.
+        UsageLimitReached => "m.server_notice.usage_limit_reached",
+    }
 }
 
-/// The format for the formatted representation of a message body.
-#[derive(Clone, Debug, Deserialize, Serialize)]
-#[non_exhaustive]
-pub enum MessageFormat {
-    /// HTML.
-    #[serde(rename = "org.matrix.custom.html")]
-    Html,
-
-    /// A custom message format.
-    Custom(String),
+string_enum! {
+    /// Types of usage limits.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum LimitType {
+        /// The server's number of active users in the last 30 days has exceeded the maximum.
+        ///
+        /// New connections are being refused by the server. What defines "active" is left as an
+        /// implementation detail, however servers are encouraged to treat syncing users as
+        /// "active".
+        MonthlyActiveUser => "monthly_active_user",
+    }
 }
 
-impl fmt::Display for MessageFormat {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Html => f.write_str("org.matrix.custom.html"),
-            Self::Custom(fmt) => f.write_str(fmt),
-        }
+string_enum! {
+    /// The format for the formatted representation of a message body.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum MessageFormat {
+        /// HTML.
+        Html => "org.matrix.custom.html",
     }
 }
 
 /// Common message event content fields for message types that have separate plain-text and
 /// formatted representations.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct FormattedBody {
     /// The format used in the `formatted_body`.
     pub format: MessageFormat,
@@ -315,10 +646,31 @@ impl FormattedBody {
     pub fn html(body: impl Into<String>) -> Self {
         Self { format: MessageFormat::Html, body: body.into() }
     }
+
+    /// Creates a new HTML-formatted message body by parsing the markdown in `body`.
+    ///
+    /// Returns `None` if no markdown formatting was found.
+    #[cfg(feature = "markdown")]
+    pub fn markdown(body: impl AsRef<str>) -> Option<Self> {
+        let body = body.as_ref();
+        let mut html_body = String::new();
+        pulldown_cmark::html::push_html(&mut html_body, pulldown_cmark::Parser::new(body));
+
+        // Plain text is still escaped by the HTML renderer, so compare against the escaped body.
+        let mut escaped_body = String::new();
+        pulldown_cmark::escape::escape_html(&mut escaped_body, body)
+            .expect("writing to a String can't fail");
+
+        if html_body == format!("<p>{}</p>\n", escaped_body) {
+            None
+        } else {
+            Some(Self::html(html_body))
+        }
+    }
 }
 
 /// The payload for a text message.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct TextMessageEventContent {
     /// The body of the message.
     pub body: String,
@@ -328,15 +680,33 @@ pub struct TextMessageEventContent {
     pub formatted: Option<FormattedBody>,
 
     /// Information about related messages for
-    /// [rich replies](https://matrix.org/docs/spec/client_server/r0.6.1#rich-replies).
+    /// [rich replies](https://matrix.org/docs/spec/client_server/r0.6.1#rich-replies) and edits.
     #[serde(rename = "m.relates_to", skip_serializing_if = "Option::is_none")]
     pub relates_to: Option<RelatesTo>,
+
+    /// The new content of the message this message edits, if it is an edit.
+    #[serde(rename = "m.new_content", skip_serializing_if = "Option::is_none")]
+    pub new_content: Option<Box<MessageEventContent>>,
 }
 
 impl TextMessageEventContent {
     /// A convenience constructor to create a plain text message.
     pub fn plain(body: impl Into<String>) -> Self {
-        Self { body: body.into(), formatted: None, relates_to: None }
+        Self { body: body.into(), formatted: None, relates_to: None, new_content: None }
+    }
+
+    /// A convenience constructor to create an html message.
+    pub fn html(body: impl Into<String>, html_body: impl Into<String>) -> Self {
+        Self { formatted: Some(FormattedBody::html(html_body)), ..Self::plain(body) }
+    }
+
+    /// A convenience constructor to create a markdown message.
+    ///
+    /// Returns an html message if some markdown formatting was detected, otherwise returns a
+    /// plain text message.
+    #[cfg(feature = "markdown")]
+    pub fn markdown(body: impl AsRef<str> + Into<String>) -> Self {
+        Self { formatted: FormattedBody::markdown(&body), ..Self::plain(body) }
     }
 
     /// A convenience constructor to create a plain text message.
@@ -347,7 +717,7 @@ impl TextMessageEventContent {
 }
 
 /// The payload for a video message.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct VideoMessageEventContent {
     /// A description of the video, e.g. "Gangnam Style," or some kind of content description for
     /// accessibility, e.g. "video attachment."
@@ -368,7 +738,7 @@ pub struct VideoMessageEventContent {
 }
 
 /// Metadata about a video.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct VideoInfo {
     /// The duration of the video in milliseconds.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -407,21 +777,67 @@ pub struct VideoInfo {
 }
 
 /// Information about related messages for
-/// [rich replies](https://matrix.org/docs/spec/client_server/r0.6.1#rich-replies).
-#[derive(Clone, Debug, Deserialize, Serialize)]
+/// [rich replies](https://matrix.org/docs/spec/client_server/r0.6.1#rich-replies) and edits.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "RelatesToJsonRepr", into = "RelatesToJsonRepr")]
 pub struct RelatesTo {
     /// Information about another message being replied to.
-    #[serde(rename = "m.in_reply_to")]
     pub in_reply_to: Option<InReplyTo>,
+
+    /// Information about the message replaced by this message, if it is an edit.
+    pub replacement: Option<Replacement>,
+}
+
+/// The JSON representation of `RelatesTo`, where a replacement is a relation with the `rel_type`
+/// *m.replace*.
+#[derive(Deserialize, Serialize)]
+struct RelatesToJsonRepr {
+    #[serde(rename = "m.in_reply_to", skip_serializing_if = "Option::is_none")]
+    in_reply_to: Option<InReplyTo>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rel_type: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    event_id: Option<EventId>,
+}
+
+impl From<RelatesToJsonRepr> for RelatesTo {
+    fn from(repr: RelatesToJsonRepr) -> Self {
+        let replacement = match (repr.rel_type.as_deref(), repr.event_id) {
+            (Some("m.replace"), Some(event_id)) => Some(Replacement { event_id }),
+            _ => None,
+        };
+
+        Self { in_reply_to: repr.in_reply_to, replacement }
+    }
+}
+
+impl From<RelatesTo> for RelatesToJsonRepr {
+    fn from(relates_to: RelatesTo) -> Self {
+        let (rel_type, event_id) = match relates_to.replacement {
+            Some(Replacement { event_id }) => (Some("m.replace".to_owned()), Some(event_id)),
+            None => (None, None),
+        };
+
+        Self { in_reply_to: relates_to.in_reply_to, rel_type, event_id }
+    }
 }
 
 /// Information about the event a "rich reply" is replying to.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct InReplyTo {
     /// The event being replied to.
     pub event_id: EventId,
 }
 
+/// Information about the event an edit replaces.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Replacement {
+    /// The event being edited.
+    pub event_id: EventId,
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::{Duration, UNIX_EPOCH};
@@ -431,8 +847,12 @@ mod tests {
     use ruma_identifiers::{event_id, room_id, user_id};
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
-    use super::{AudioMessageEventContent, FormattedBody, MessageEventContent, MessageFormat};
+    use super::{
+        AudioMessageEventContent, EmoteMessageEventContent, FormattedBody, MessageEventContent,
+        MessageFormat, NoticeMessageEventContent,
+    };
     use crate::{
+        from_str_strict,
         room::message::{InReplyTo, RelatesTo, TextMessageEventContent},
         MessageEvent, Unsigned,
     };
@@ -498,6 +918,7 @@ mod tests {
                 body: "Hello, <em>World</em>!".into(),
             }),
             relates_to: None,
+            new_content: None,
         });
 
         assert_eq!(
@@ -535,7 +956,9 @@ mod tests {
                 in_reply_to: Some(InReplyTo {
                     event_id: event_id!("$15827405538098VGFWH:example.com"),
                 }),
+                replacement: None,
             }),
+            new_content: None,
         });
 
         let json_data = json!({
@@ -551,6 +974,88 @@ mod tests {
         assert_eq!(to_json_value(&message_event_content).unwrap(), json_data);
     }
 
+    #[test]
+    fn html_content_serialization() {
+        let message_event_content = MessageEventContent::Notice(NoticeMessageEventContent::html(
+            "Hello, World!",
+            "Hello, <em>World</em>!",
+        ));
+
+        assert_eq!(
+            to_json_value(&message_event_content).unwrap(),
+            json!({
+                "body": "Hello, World!",
+                "msgtype": "m.notice",
+                "format": "org.matrix.custom.html",
+                "formatted_body": "Hello, <em>World</em>!",
+            })
+        );
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn markdown_content_serialization() {
+        let formatted = TextMessageEventContent::markdown("Hello, *World*!");
+        assert_eq!(formatted.formatted.unwrap().body, "<p>Hello, <em>World</em>!</p>\n");
+
+        let plain = EmoteMessageEventContent::markdown("Hello, World!");
+        assert!(plain.formatted.is_none());
+
+        let escaped = TextMessageEventContent::markdown("Tom & Jerry <3");
+        assert!(escaped.formatted.is_none());
+    }
+
+    #[test]
+    fn text_reply_plain() {
+        // The example from the rich replies section of the specification.
+        let original_message = MessageEvent {
+            content: MessageEventContent::Text(TextMessageEventContent::plain(
+                "This is the original body",
+            )),
+            event_id: event_id!("$original_event:example.org"),
+            origin_server_ts: UNIX_EPOCH + Duration::from_millis(1),
+            room_id: room_id!("!room:example.org"),
+            sender: user_id!("@alice:example.org"),
+            unsigned: Unsigned::default(),
+        };
+
+        let reply = MessageEventContent::text_reply_plain(
+            "This is where the reply goes",
+            &original_message,
+        );
+
+        assert_eq!(
+            to_json_value(&reply).unwrap(),
+            json!({
+                "body": "> <@alice:example.org> This is the original body\n\nThis is where the reply goes",
+                "msgtype": "m.text",
+                "m.relates_to": {
+                    "m.in_reply_to": {
+                        "event_id": "$original_event:example.org"
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn text_reply_plain_to_reply() {
+        let original_message = MessageEvent {
+            content: MessageEventContent::Emote(EmoteMessageEventContent::plain(
+                "> <@bob:example.org> First\n> message\n\nwaves\nback",
+            )),
+            event_id: event_id!("$original_event:example.org"),
+            origin_server_ts: UNIX_EPOCH + Duration::from_millis(1),
+            room_id: room_id!("!room:example.org"),
+            sender: user_id!("@alice:example.org"),
+            unsigned: Unsigned::default(),
+        };
+
+        let reply = MessageEventContent::text_reply_plain("Hi!", &original_message);
+
+        assert_eq!(reply.body(), "> * <@alice:example.org> waves\n> back\n\nHi!");
+    }
+
     #[test]
     fn content_deserialization() {
         let json_data = json!({
@@ -573,6 +1078,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn content_deserialization_size_out_of_range() {
+        let json_data = json!({
+            "body": "test",
+            "msgtype": "m.image",
+            "url": "mxc://example.com/image",
+            "info": {
+                "size": 9_007_199_254_740_993_u64
+            }
+        });
+
+        assert!(from_json_value::<Raw<MessageEventContent>>(json_data)
+            .unwrap()
+            .deserialize()
+            .is_err());
+    }
+
     #[test]
     fn content_deserialization_failure() {
         let json_data = json!({
@@ -584,4 +1106,53 @@ mod tests {
             .deserialize()
             .is_err());
     }
+
+    #[test]
+    fn custom_msgtype_deserialization() {
+        let json_data = json!({
+            "body": "a poll",
+            "msgtype": "org.example.poll",
+            "answers": ["yes", "no"]
+        });
+
+        let content = from_json_value::<Raw<MessageEventContent>>(json_data.clone())
+            .unwrap()
+            .deserialize()
+            .unwrap();
+        assert_eq!(content.msgtype(), "org.example.poll");
+        assert_eq!(content.body(), "a poll");
+        assert_eq!(to_json_value(&content).unwrap(), json_data);
+    }
+
+    #[test]
+    fn custom_msgtype_without_body_deserialization_failure() {
+        let json_data = json!({ "msgtype": "org.example.poll" });
+
+        assert!(from_json_value::<Raw<MessageEventContent>>(json_data)
+            .unwrap()
+            .deserialize()
+            .is_err());
+    }
+
+    #[test]
+    fn strict_deserialization() {
+        let text = r#"{ "msgtype": "m.text", "body": "*hi*", "format": "org.matrix.custom.html", "formatted_body": "<em>hi</em>" }"#;
+        assert_matches!(
+            from_str_strict::<MessageEventContent>(text),
+            Ok(MessageEventContent::Text(TextMessageEventContent { formatted: Some(_), .. }))
+        );
+
+        let typo = r#"{ "msgtype": "m.text", "body": "*hi*", "formated_body": "<em>hi</em>" }"#;
+        assert!(from_str_strict::<MessageEventContent>(typo)
+            .unwrap_err()
+            .to_string()
+            .contains("unknown field `formated_body`"));
+        assert!(serde_json::from_str::<MessageEventContent>(typo).is_ok());
+
+        let emote = r#"{ "msgtype": "m.emote", "body": "waves", "geo_uri": "geo:0,0" }"#;
+        assert!(from_str_strict::<MessageEventContent>(emote).is_err());
+
+        let custom = r#"{ "msgtype": "org.example.poll", "body": "a poll", "answers": [] }"#;
+        assert!(from_str_strict::<MessageEventContent>(custom).is_ok());
+    }
 }
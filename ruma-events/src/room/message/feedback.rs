@@ -2,8 +2,8 @@
 
 use ruma_events_macros::MessageEventContent;
 use ruma_identifiers::EventId;
+use ruma_serde::string_enum;
 use serde::{Deserialize, Serialize};
-use strum::{Display, EnumString};
 
 use crate::MessageEvent;
 
@@ -14,7 +14,7 @@ use crate::MessageEvent;
 pub type FeedbackEvent = MessageEvent<FeedbackEventContent>;
 
 /// The payload for `FeedbackEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, MessageEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, MessageEventContent)]
 #[non_exhaustive]
 #[ruma_event(type = "m.room.message.feedback")]
 pub struct FeedbackEventContent {
@@ -33,15 +33,15 @@ impl FeedbackEventContent {
     }
 }
 
-/// A type of feedback.
-#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, Deserialize, Serialize)]
-#[non_exhaustive]
-#[serde(rename_all = "snake_case")]
-#[strum(serialize_all = "snake_case")]
-pub enum FeedbackType {
-    /// Sent when a message is received.
-    Delivered,
-
-    /// Sent when a message has been observed by the end user.
-    Read,
+string_enum! {
+    /// A type of feedback.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum FeedbackType {
+        /// Sent when a message is received.
+        Delivered => "delivered",
+
+        /// Sent when a message has been observed by the end user.
+        Read => "read",
+    }
 }
@@ -9,7 +9,7 @@ use crate::{InvalidInput, StateEvent};
 pub type NameEvent = StateEvent<NameEventContent>;
 
 /// The payload for `NameEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, StateEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize, StateEventContent)]
 #[ruma_event(type = "m.room.name")]
 pub struct NameEventContent {
     /// The name of the room. This MUST NOT exceed 255 bytes.
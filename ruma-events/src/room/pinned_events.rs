@@ -10,7 +10,7 @@ use crate::StateEvent;
 pub type PinnedEventsEvent = StateEvent<PinnedEventsEventContent>;
 
 /// The payload for `PinnedEventsEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, StateEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize, StateEventContent)]
 #[non_exhaustive]
 #[ruma_event(type = "m.room.pinned_events")]
 pub struct PinnedEventsEventContent {
@@ -13,10 +13,11 @@ use crate::{EventType, StateEvent};
 pub type PowerLevelsEvent = StateEvent<PowerLevelsEventContent>;
 
 /// The payload for `PowerLevelsEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, StateEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, StateEventContent)]
 #[ruma_event(type = "m.room.power_levels")]
 pub struct PowerLevelsEventContent {
     /// The level required to ban a user.
+    #[cfg_attr(feature = "compat", serde(deserialize_with = "crate::compat::int_or_string"))]
     #[serde(default = "default_power_level", skip_serializing_if = "is_default_power_level")]
     #[ruma_event(skip_redaction)]
     pub ban: Int,
@@ -24,30 +25,39 @@ pub struct PowerLevelsEventContent {
     /// The level required to send specific event types.
     ///
     /// This is a mapping from event type to power level required.
+    #[cfg_attr(
+        feature = "compat",
+        serde(deserialize_with = "crate::compat::btreemap_int_or_string")
+    )]
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     #[ruma_event(skip_redaction)]
     pub events: BTreeMap<EventType, Int>,
 
     /// The default level required to send message events.
+    #[cfg_attr(feature = "compat", serde(deserialize_with = "crate::compat::int_or_string"))]
     #[serde(default, skip_serializing_if = "ruma_serde::is_default")]
     #[ruma_event(skip_redaction)]
     pub events_default: Int,
 
     /// The level required to invite a user.
+    #[cfg_attr(feature = "compat", serde(deserialize_with = "crate::compat::int_or_string"))]
     #[serde(default = "default_power_level", skip_serializing_if = "is_default_power_level")]
     pub invite: Int,
 
     /// The level required to kick a user.
+    #[cfg_attr(feature = "compat", serde(deserialize_with = "crate::compat::int_or_string"))]
     #[serde(default = "default_power_level", skip_serializing_if = "is_default_power_level")]
     #[ruma_event(skip_redaction)]
     pub kick: Int,
 
     /// The level required to redact an event.
+    #[cfg_attr(feature = "compat", serde(deserialize_with = "crate::compat::int_or_string"))]
     #[serde(default = "default_power_level", skip_serializing_if = "is_default_power_level")]
     #[ruma_event(skip_redaction)]
     pub redact: Int,
 
     /// The default level required to send state events.
+    #[cfg_attr(feature = "compat", serde(deserialize_with = "crate::compat::int_or_string"))]
     #[serde(default = "default_power_level", skip_serializing_if = "is_default_power_level")]
     #[ruma_event(skip_redaction)]
     pub state_default: Int,
@@ -55,11 +65,16 @@ pub struct PowerLevelsEventContent {
     /// The power levels for specific users.
     ///
     /// This is a mapping from `user_id` to power level for that user.
+    #[cfg_attr(
+        feature = "compat",
+        serde(deserialize_with = "crate::compat::btreemap_int_or_string")
+    )]
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     #[ruma_event(skip_redaction)]
     pub users: BTreeMap<UserId, Int>,
 
     /// The default power level for every user in the room.
+    #[cfg_attr(feature = "compat", serde(deserialize_with = "crate::compat::int_or_string"))]
     #[serde(default, skip_serializing_if = "ruma_serde::is_default")]
     #[ruma_event(skip_redaction)]
     pub users_default: Int,
@@ -90,10 +105,82 @@ impl Default for PowerLevelsEventContent {
     }
 }
 
+impl PowerLevelsEventContent {
+    /// The power level of the given user, falling back to `users_default`.
+    pub fn user_power_level(&self, user_id: &UserId) -> Int {
+        self.users.get(user_id).copied().unwrap_or(self.users_default)
+    }
+
+    /// Whether the given user is allowed to send message events of the given type.
+    ///
+    /// An entry in `events` takes precedence over `events_default`.
+    pub fn user_can_send_message(&self, user_id: &UserId, event_type: &EventType) -> bool {
+        let required = self.events.get(event_type).copied().unwrap_or(self.events_default);
+        self.user_power_level(user_id) >= required
+    }
+
+    /// Whether the given user is allowed to send state events of the given type.
+    ///
+    /// An entry in `events` takes precedence over `state_default`.
+    pub fn user_can_send_state(&self, user_id: &UserId, event_type: &EventType) -> bool {
+        let required = self.events.get(event_type).copied().unwrap_or(self.state_default);
+        self.user_power_level(user_id) >= required
+    }
+
+    /// Whether `sender` is allowed to ban `target`.
+    ///
+    /// This requires the `ban` level and a higher power level than the target.
+    pub fn user_can_ban(&self, sender: &UserId, target: &UserId) -> bool {
+        let sender_level = self.user_power_level(sender);
+        sender_level >= self.ban && sender_level > self.user_power_level(target)
+    }
+
+    /// Whether `sender` is allowed to kick `target`.
+    ///
+    /// This requires the `kick` level and a higher power level than the target.
+    pub fn user_can_kick(&self, sender: &UserId, target: &UserId) -> bool {
+        let sender_level = self.user_power_level(sender);
+        sender_level >= self.kick && sender_level > self.user_power_level(target)
+    }
+
+    /// Whether `sender` is allowed to redact an event sent by `original_sender`.
+    ///
+    /// Users can always redact their own events, as long as they are allowed to send redaction
+    /// events. Redacting events of other users requires the `redact` level.
+    pub fn user_can_redact_event_of(&self, sender: &UserId, original_sender: &UserId) -> bool {
+        self.user_can_send_message(sender, &EventType::RoomRedaction)
+            && (sender == original_sender || self.user_power_level(sender) >= self.redact)
+    }
+
+    /// Whether the given user is allowed to invite other users.
+    pub fn user_can_invite(&self, user_id: &UserId) -> bool {
+        self.user_power_level(user_id) >= self.invite
+    }
+
+    /// Whether `sender` is allowed to change the power level of `target` to `new_level`.
+    ///
+    /// This requires being allowed to send `m.room.power_levels` events, and `new_level` can't be
+    /// higher than the sender's own level. Users can change their own level, e.g. to demote
+    /// themselves, but only the levels of users with a lower level than their own.
+    pub fn user_can_change_power_level(
+        &self,
+        sender: &UserId,
+        target: &UserId,
+        new_level: Int,
+    ) -> bool {
+        let sender_level = self.user_power_level(sender);
+
+        self.user_can_send_state(sender, &EventType::RoomPowerLevels)
+            && new_level <= sender_level
+            && (sender == target || self.user_power_level(target) < sender_level)
+    }
+}
+
 /// The power level requirements for specific notification types.
-#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct NotificationPowerLevels {
     /// The level required to trigger an `@room` notification.
+    #[cfg_attr(feature = "compat", serde(deserialize_with = "crate::compat::int_or_string"))]
     #[serde(default = "default_power_level")]
     pub room: Int,
 }
@@ -119,17 +206,178 @@ fn is_default_power_level(l: &Int) -> bool {
 mod tests {
     use std::{
         collections::BTreeMap,
+        convert::TryFrom,
         time::{Duration, UNIX_EPOCH},
     };
 
     use js_int::Int;
     use maplit::btreemap;
-    use ruma_identifiers::{event_id, room_id, user_id};
-    use serde_json::{json, to_value as to_json_value};
+    use ruma_identifiers::{event_id, room_id, user_id, UserId};
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
     use super::{default_power_level, NotificationPowerLevels, PowerLevelsEventContent};
     use crate::{EventType, StateEvent, Unsigned};
 
+    /// Power levels with two admins (100 and 99), a moderator (50), a user (0, the default) and a
+    /// muted user (-1).
+    fn levels() -> PowerLevelsEventContent {
+        PowerLevelsEventContent {
+            events: btreemap! {
+                EventType::RoomName => Int::from(100),
+                EventType::RoomMessage => Int::from(0),
+                EventType::Sticker => Int::from(60),
+            },
+            users: btreemap! {
+                user_id!("@admin:example.com") => Int::from(100),
+                user_id!("@admin2:example.com") => Int::from(99),
+                user_id!("@mod:example.com") => Int::from(50),
+                user_id!("@muted:example.com") => Int::from(-1),
+            },
+            ..PowerLevelsEventContent::default()
+        }
+    }
+
+    #[test]
+    fn user_power_level_defaults() {
+        let levels = levels();
+
+        assert_eq!(levels.user_power_level(&user_id!("@admin:example.com")), Int::from(100));
+        assert_eq!(levels.user_power_level(&user_id!("@user:example.com")), Int::from(0));
+    }
+
+    #[test]
+    fn send_event_precedence() {
+        let levels = levels();
+        let cases = [
+            // `events` overrides `events_default` (0).
+            ("@user:example.com", EventType::RoomMessage, true, true),
+            ("@mod:example.com", EventType::Sticker, false, false),
+            ("@admin:example.com", EventType::Sticker, true, true),
+            // Falls back to `events_default` for messages and `state_default` (50) for state.
+            ("@user:example.com", EventType::RoomTopic, true, false),
+            ("@mod:example.com", EventType::RoomTopic, true, true),
+            ("@muted:example.com", EventType::CallInvite, false, false),
+            // `events` overrides `state_default`.
+            ("@mod:example.com", EventType::RoomName, false, false),
+            ("@admin:example.com", EventType::RoomName, true, true),
+        ];
+
+        for (user, event_type, message, state) in &cases {
+            let user = UserId::try_from(*user).unwrap();
+            assert_eq!(
+                levels.user_can_send_message(&user, event_type),
+                *message,
+                "{} sending {} message",
+                user,
+                event_type
+            );
+            assert_eq!(
+                levels.user_can_send_state(&user, event_type),
+                *state,
+                "{} sending {} state",
+                user,
+                event_type
+            );
+        }
+    }
+
+    #[test]
+    fn membership_actions() {
+        let levels = levels();
+        let cases = [
+            // (sender, target, can ban, can kick)
+            ("@admin:example.com", "@mod:example.com", true, true),
+            ("@mod:example.com", "@user:example.com", true, true),
+            // Can't ban or kick users with the same or a higher level.
+            ("@mod:example.com", "@mod:example.com", false, false),
+            ("@mod:example.com", "@admin:example.com", false, false),
+            // Having a higher level than the target isn't enough without the `ban` / `kick` level.
+            ("@user:example.com", "@muted:example.com", false, false),
+        ];
+
+        for (sender, target, ban, kick) in &cases {
+            let sender = UserId::try_from(*sender).unwrap();
+            let target = UserId::try_from(*target).unwrap();
+            assert_eq!(
+                levels.user_can_ban(&sender, &target),
+                *ban,
+                "{} banning {}",
+                sender,
+                target
+            );
+            assert_eq!(
+                levels.user_can_kick(&sender, &target),
+                *kick,
+                "{} kicking {}",
+                sender,
+                target
+            );
+        }
+
+        assert!(levels.user_can_invite(&user_id!("@mod:example.com")));
+        assert!(!levels.user_can_invite(&user_id!("@user:example.com")));
+    }
+
+    #[test]
+    fn redaction() {
+        let levels = levels();
+        let cases = [
+            // (sender, original sender, can redact)
+            ("@user:example.com", "@user:example.com", true),
+            ("@user:example.com", "@mod:example.com", false),
+            ("@mod:example.com", "@user:example.com", true),
+            ("@mod:example.com", "@admin:example.com", true),
+            // Can't send redaction events at all.
+            ("@muted:example.com", "@muted:example.com", false),
+        ];
+
+        for (sender, original_sender, can_redact) in &cases {
+            let sender = UserId::try_from(*sender).unwrap();
+            let original_sender = UserId::try_from(*original_sender).unwrap();
+            assert_eq!(
+                levels.user_can_redact_event_of(&sender, &original_sender),
+                *can_redact,
+                "{} redacting an event of {}",
+                sender,
+                original_sender
+            );
+        }
+    }
+
+    #[test]
+    fn change_power_level() {
+        let levels = levels();
+        let cases = [
+            // (sender, target, new level, allowed)
+            ("@admin:example.com", "@mod:example.com", 100, true),
+            ("@admin:example.com", "@user:example.com", 50, true),
+            ("@mod:example.com", "@user:example.com", 50, true),
+            // Can't grant a higher level than one's own.
+            ("@mod:example.com", "@user:example.com", 51, false),
+            // Self-demotion is allowed, self-promotion isn't.
+            ("@mod:example.com", "@mod:example.com", 0, true),
+            ("@mod:example.com", "@mod:example.com", 100, false),
+            // Only the levels of users with a lower level than the sender can be changed.
+            ("@admin:example.com", "@admin2:example.com", 0, true),
+            ("@mod:example.com", "@admin:example.com", 0, false),
+            // Sending power levels requires `state_default`.
+            ("@user:example.com", "@muted:example.com", 0, false),
+        ];
+
+        for (sender, target, new_level, allowed) in &cases {
+            let sender = UserId::try_from(*sender).unwrap();
+            let target = UserId::try_from(*target).unwrap();
+            assert_eq!(
+                levels.user_can_change_power_level(&sender, &target, Int::from(*new_level)),
+                *allowed,
+                "{} setting the level of {} to {}",
+                sender,
+                target,
+                new_level
+            );
+        }
+    }
+
     #[test]
     fn serialization_with_optional_fields_as_none() {
         let default = default_power_level();
@@ -266,4 +514,54 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn events_map_roundtrip() {
+        let json = json!({
+            "events": {
+                "m.room.name": 50,
+                "m.room.power_levels": 100,
+                "org.example.custom": 25
+            }
+        });
+
+        let content = from_json_value::<PowerLevelsEventContent>(json.clone()).unwrap();
+        assert_eq!(
+            content.events,
+            btreemap! {
+                EventType::RoomName => Int::from(50),
+                EventType::RoomPowerLevels => Int::from(100),
+                EventType::Custom("org.example.custom".into()) => Int::from(25),
+            }
+        );
+        assert_eq!(to_json_value(&content).unwrap(), json);
+    }
+
+    fn string_encoded_power_levels() -> serde_json::Value {
+        json!({
+            "ban": "100",
+            "events": { "m.room.name": "75" },
+            "users": { "@carl:example.com": "100" },
+            "notifications": { "room": "20" }
+        })
+    }
+
+    #[cfg(feature = "compat")]
+    #[test]
+    fn compat_string_encoded_integers() {
+        let content =
+            from_json_value::<PowerLevelsEventContent>(string_encoded_power_levels()).unwrap();
+
+        assert_eq!(content.ban, Int::from(100));
+        assert_eq!(content.events[&EventType::RoomName], Int::from(75));
+        assert_eq!(content.users[&user_id!("@carl:example.com")], Int::from(100));
+        assert_eq!(content.notifications.room, Int::from(20));
+        assert_eq!(content.kick, default_power_level());
+    }
+
+    #[cfg(not(feature = "compat"))]
+    #[test]
+    fn string_encoded_integers_rejected() {
+        assert!(from_json_value::<PowerLevelsEventContent>(string_encoded_power_levels()).is_err());
+    }
 }
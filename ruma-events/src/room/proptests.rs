@@ -0,0 +1,584 @@
+//! Round-trip property tests for the content of every event in the *m.room* namespace.
+//!
+//! Each test generates arbitrary content, serializes it, and checks that deserializing the JSON
+//! gives back the same content. Content types that implement `StrictEventContent` are also
+//! deserialized strictly, which fails if their serialization emits a field that the strict
+//! deserialization doesn't know about.
+
+use std::{convert::TryFrom, fmt::Debug};
+
+use js_int::{Int, UInt};
+use proptest::{
+    collection::{btree_map, vec},
+    option,
+    prelude::*,
+};
+use ruma_identifiers::{
+    EventId, RoomAliasId, RoomId, RoomVersionId, ServerKeyId, ServerName, UserId,
+};
+use serde::de::DeserializeOwned;
+use serde_json::value::to_raw_value as to_raw_json_value;
+
+use super::{
+    aliases::AliasesEventContent,
+    avatar::AvatarEventContent,
+    canonical_alias::CanonicalAliasEventContent,
+    create::{CreateEventContent, PreviousRoom},
+    encrypted::{
+        CiphertextInfo, EncryptedEventContent, MegolmV1AesSha2ContentInit,
+        OlmV1Curve25519AesSha2Content,
+    },
+    encryption::EncryptionEventContent,
+    guest_access::{GuestAccess, GuestAccessEventContent},
+    history_visibility::{HistoryVisibility, HistoryVisibilityEventContent},
+    join_rules::{JoinRule, JoinRulesEventContent},
+    member::{MemberEventContent, MembershipState, SignedContent, ThirdPartyInvite},
+    message::{
+        feedback::{FeedbackEventContent, FeedbackType},
+        AudioInfo, AudioMessageEventContent, EmoteMessageEventContent, FileInfo,
+        FileMessageEventContent, FormattedBody, ImageMessageEventContent, InReplyTo, LimitType,
+        LocationInfo, LocationMessageEventContent, MessageEventContent, NoticeMessageEventContent,
+        RelatesTo, Replacement, ServerNoticeMessageEventContent, ServerNoticeType,
+        TextMessageEventContent, VideoInfo, VideoMessageEventContent,
+    },
+    name::NameEventContent,
+    pinned_events::PinnedEventsEventContent,
+    power_levels::{NotificationPowerLevels, PowerLevelsEventContent},
+    redaction::RedactionEventContent,
+    server_acl::ServerAclEventContent,
+    third_party_invite::{PublicKey, ThirdPartyInviteEventContent},
+    tombstone::TombstoneEventContent,
+    topic::TopicEventContent,
+    EncryptedFile, ImageInfo, JsonWebKey, ThumbnailInfo,
+};
+use crate::{
+    from_str_strict, EventContent, EventEncryptionAlgorithm, EventType, StrictEventContent,
+};
+
+/// Keeps the whole harness fast enough to run with the regular test suite.
+const CASES: u32 = 64;
+
+fn assert_round_trip<T>(content: &T)
+where
+    T: EventContent + DeserializeOwned + PartialEq + Debug,
+{
+    let json = serde_json::to_string(content).unwrap();
+    assert_eq!(&serde_json::from_str::<T>(&json).unwrap(), content, "json: {}", json);
+
+    let raw = to_raw_json_value(content).unwrap();
+    assert_eq!(&T::from_parts(content.event_type(), raw).unwrap(), content, "json: {}", json);
+}
+
+fn assert_strict_round_trip<T>(content: &T)
+where
+    T: StrictEventContent + PartialEq + Debug,
+{
+    assert_round_trip(content);
+
+    let json = serde_json::to_string(content).unwrap();
+    assert_eq!(&from_str_strict::<T>(&json).unwrap(), content, "json: {}", json);
+}
+
+fn server_name() -> impl Strategy<Value = String> {
+    "[a-z0-9]{1,12}\\.(org|com)"
+}
+
+fn user_id() -> impl Strategy<Value = UserId> {
+    ("[a-z0-9._=-]{1,16}", server_name()).prop_map(|(localpart, server)| {
+        UserId::try_from(format!("@{}:{}", localpart, server)).unwrap()
+    })
+}
+
+fn event_id() -> impl Strategy<Value = EventId> {
+    ("[a-zA-Z0-9]{1,20}", server_name())
+        .prop_map(|(opaque, server)| EventId::try_from(format!("${}:{}", opaque, server)).unwrap())
+}
+
+fn room_id() -> impl Strategy<Value = RoomId> {
+    ("[a-zA-Z0-9]{1,18}", server_name())
+        .prop_map(|(opaque, server)| RoomId::try_from(format!("!{}:{}", opaque, server)).unwrap())
+}
+
+fn room_alias_id() -> impl Strategy<Value = RoomAliasId> {
+    ("[a-z0-9_-]{1,16}", server_name()).prop_map(|(alias, server)| {
+        RoomAliasId::try_from(format!("#{}:{}", alias, server)).unwrap()
+    })
+}
+
+fn uint() -> impl Strategy<Value = UInt> {
+    (0..=u64::from(UInt::MAX)).prop_map(|n| UInt::new(n).unwrap())
+}
+
+fn int() -> impl Strategy<Value = Int> {
+    (i64::from(Int::MIN)..=i64::from(Int::MAX)).prop_map(|n| Int::new(n).unwrap())
+}
+
+fn text() -> impl Strategy<Value = String> {
+    any::<String>()
+}
+
+prop_compose! {
+    fn encrypted_file()(
+        url in text(),
+        k in text(),
+        ext in any::<bool>(),
+        iv in text(),
+        hashes in btree_map(text(), text(), 0..3)
+    ) -> Box<EncryptedFile> {
+        let key = JsonWebKey {
+            kty: "oct".into(),
+            key_ops: vec!["encrypt".into(), "decrypt".into()],
+            alg: "A256CTR".into(),
+            k,
+            ext,
+        };
+
+        Box::new(EncryptedFile { url, key, iv, hashes, v: "v2".into() })
+    }
+}
+
+prop_compose! {
+    fn thumbnail_info()(
+        height in option::of(uint()),
+        width in option::of(uint()),
+        mimetype in option::of(text()),
+        size in option::of(uint())
+    ) -> Box<ThumbnailInfo> {
+        Box::new(ThumbnailInfo { height, width, mimetype, size })
+    }
+}
+
+prop_compose! {
+    fn image_info()(
+        height in option::of(uint()),
+        width in option::of(uint()),
+        mimetype in option::of(text()),
+        size in option::of(uint()),
+        thumbnail_info in option::of(thumbnail_info()),
+        thumbnail_url in option::of(text()),
+        thumbnail_file in option::of(encrypted_file())
+    ) -> Box<ImageInfo> {
+        Box::new(ImageInfo {
+            height,
+            width,
+            mimetype,
+            size,
+            thumbnail_info,
+            thumbnail_url,
+            thumbnail_file,
+        })
+    }
+}
+
+prop_compose! {
+    fn audio_info()(
+        duration in option::of(uint()),
+        mimetype in option::of(text()),
+        size in option::of(uint())
+    ) -> Box<AudioInfo> {
+        Box::new(AudioInfo { duration, mimetype, size })
+    }
+}
+
+prop_compose! {
+    fn file_info()(
+        mimetype in option::of(text()),
+        size in option::of(uint()),
+        thumbnail_info in option::of(thumbnail_info()),
+        thumbnail_url in option::of(text()),
+        thumbnail_file in option::of(encrypted_file())
+    ) -> Box<FileInfo> {
+        Box::new(FileInfo { mimetype, size, thumbnail_info, thumbnail_url, thumbnail_file })
+    }
+}
+
+prop_compose! {
+    fn location_info()(
+        thumbnail_info in option::of(thumbnail_info()),
+        thumbnail_url in option::of(text()),
+        thumbnail_file in option::of(encrypted_file())
+    ) -> Box<LocationInfo> {
+        Box::new(LocationInfo { thumbnail_info, thumbnail_url, thumbnail_file })
+    }
+}
+
+prop_compose! {
+    fn video_info()(
+        duration in option::of(uint()),
+        height in option::of(uint()),
+        width in option::of(uint()),
+        mimetype in option::of(text()),
+        size in option::of(uint()),
+        thumbnail_info in option::of(thumbnail_info()),
+        thumbnail_url in option::of(text()),
+        thumbnail_file in option::of(encrypted_file())
+    ) -> Box<VideoInfo> {
+        Box::new(VideoInfo {
+            duration,
+            height,
+            width,
+            mimetype,
+            size,
+            thumbnail_info,
+            thumbnail_url,
+            thumbnail_file,
+        })
+    }
+}
+
+fn formatted_body() -> impl Strategy<Value = Option<FormattedBody>> {
+    option::of(text().prop_map(FormattedBody::html))
+}
+
+fn relates_to() -> impl Strategy<Value = Option<RelatesTo>> {
+    option::of((option::of(event_id()), option::of(event_id())).prop_map(
+        |(in_reply_to, replaces)| RelatesTo {
+            in_reply_to: in_reply_to.map(|event_id| InReplyTo { event_id }),
+            replacement: replaces.map(|event_id| Replacement { event_id }),
+        },
+    ))
+}
+
+fn message_content() -> impl Strategy<Value = MessageEventContent> {
+    prop_oneof![
+        (text(), option::of(audio_info()), option::of(text()), option::of(encrypted_file()))
+            .prop_map(|(body, info, url, file)| MessageEventContent::Audio(
+                AudioMessageEventContent { body, info, url, file }
+            )),
+        (text(), formatted_body(), relates_to()).prop_map(|(body, formatted, relates_to)| {
+            MessageEventContent::Emote(EmoteMessageEventContent {
+                body,
+                formatted,
+                relates_to,
+                new_content: None,
+            })
+        }),
+        (
+            text(),
+            option::of(text()),
+            option::of(file_info()),
+            option::of(text()),
+            option::of(encrypted_file())
+        )
+            .prop_map(|(body, filename, info, url, file)| MessageEventContent::File(
+                FileMessageEventContent { body, filename, info, url, file }
+            )),
+        (text(), option::of(image_info()), option::of(text()), option::of(encrypted_file()))
+            .prop_map(|(body, info, url, file)| MessageEventContent::Image(
+                ImageMessageEventContent { body, info, url, file }
+            )),
+        (text(), text(), option::of(location_info())).prop_map(|(body, geo_uri, info)| {
+            MessageEventContent::Location(LocationMessageEventContent { body, geo_uri, info })
+        }),
+        (text(), formatted_body(), relates_to()).prop_map(|(body, formatted, relates_to)| {
+            MessageEventContent::Notice(NoticeMessageEventContent {
+                body,
+                formatted,
+                relates_to,
+                new_content: None,
+            })
+        }),
+        (text(), option::of(text()), option::of(Just(LimitType::MonthlyActiveUser))).prop_map(
+            |(body, admin_contact, limit_type)| MessageEventContent::ServerNotice(
+                ServerNoticeMessageEventContent {
+                    body,
+                    server_notice_type: ServerNoticeType::UsageLimitReached,
+                    admin_contact,
+                    limit_type,
+                }
+            )
+        ),
+        (text(), formatted_body(), relates_to()).prop_map(|(body, formatted, relates_to)| {
+            MessageEventContent::Text(TextMessageEventContent {
+                body,
+                formatted,
+                relates_to,
+                new_content: None,
+            })
+        }),
+        (text(), option::of(video_info()), option::of(text()), option::of(encrypted_file()))
+            .prop_map(|(body, info, url, file)| MessageEventContent::Video(
+                VideoMessageEventContent { body, info, url, file }
+            )),
+    ]
+}
+
+fn encrypted_content() -> impl Strategy<Value = EncryptedEventContent> {
+    prop_oneof![
+        (btree_map(text(), (text(), uint()), 0..3), text()).prop_map(|(ciphertext, sender_key)| {
+            let ciphertext = ciphertext
+                .into_iter()
+                .map(|(key, (body, message_type))| (key, CiphertextInfo::new(body, message_type)))
+                .collect();
+
+            EncryptedEventContent::OlmV1Curve25519AesSha2(OlmV1Curve25519AesSha2Content::new(
+                ciphertext, sender_key,
+            ))
+        }),
+        (text(), text(), "[A-Z]{1,10}", text()).prop_map(
+            |(ciphertext, sender_key, device_id, session_id)| {
+                EncryptedEventContent::MegolmV1AesSha2(
+                    MegolmV1AesSha2ContentInit {
+                        ciphertext,
+                        sender_key,
+                        device_id: device_id.into(),
+                        session_id,
+                    }
+                    .into(),
+                )
+            }
+        ),
+    ]
+}
+
+fn member_third_party_invite() -> impl Strategy<Value = ThirdPartyInvite> {
+    let server_key_id = "[a-zA-Z0-9_]{1,8}"
+        .prop_map(|version| ServerKeyId::try_from(format!("ed25519:{}", version)).unwrap());
+    let signatures = btree_map(
+        server_name().prop_map(|server| Box::<ServerName>::try_from(server).unwrap()),
+        btree_map(server_key_id, text(), 1..3),
+        0..3,
+    );
+
+    (text(), user_id(), signatures, text()).prop_map(|(display_name, mxid, signatures, token)| {
+        ThirdPartyInvite { display_name, signed: SignedContent { mxid, signatures, token } }
+    })
+}
+
+prop_compose! {
+    fn member_content()(
+        avatar_url in option::of(text()),
+        displayname in option::of(text()),
+        is_direct in option::of(any::<bool>()),
+        membership in prop_oneof![
+            Just(MembershipState::Ban),
+            Just(MembershipState::Invite),
+            Just(MembershipState::Join),
+            Just(MembershipState::Knock),
+            Just(MembershipState::Leave),
+        ],
+        reason in option::of(text()),
+        third_party_invite in option::of(member_third_party_invite()),
+        join_authorised_via_users_server in option::of(user_id())
+    ) -> MemberEventContent {
+        MemberEventContent {
+            avatar_url,
+            displayname,
+            is_direct,
+            membership,
+            reason,
+            third_party_invite,
+            join_authorised_via_users_server,
+        }
+    }
+}
+
+fn event_type() -> impl Strategy<Value = EventType> {
+    prop_oneof![
+        Just(EventType::RoomName),
+        Just(EventType::RoomPowerLevels),
+        Just(EventType::RoomMessage),
+        "dev\\.ruma\\.[a-z_]{1,10}".prop_map(EventType::from),
+    ]
+}
+
+prop_compose! {
+    fn power_levels_content()(
+        ban in int(),
+        events in btree_map(event_type(), int(), 0..4),
+        events_default in int(),
+        invite in int(),
+        kick in int(),
+        redact in int(),
+        state_default in int(),
+        users in btree_map(user_id(), int(), 0..4),
+        users_default in int(),
+        room in int()
+    ) -> PowerLevelsEventContent {
+        PowerLevelsEventContent {
+            ban,
+            events,
+            events_default,
+            invite,
+            kick,
+            redact,
+            state_default,
+            users,
+            users_default,
+            notifications: NotificationPowerLevels { room },
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(CASES))]
+
+    #[test]
+    fn aliases(aliases in vec(room_alias_id(), 0..4)) {
+        assert_strict_round_trip(&AliasesEventContent::new(aliases));
+    }
+
+    #[test]
+    fn avatar(info in option::of(image_info()), url in text()) {
+        assert_strict_round_trip(&AvatarEventContent { info, url });
+    }
+
+    #[test]
+    fn canonical_alias(
+        alias in option::of(room_alias_id()),
+        alt_aliases in vec(room_alias_id(), 0..4)
+    ) {
+        assert_strict_round_trip(&CanonicalAliasEventContent { alias, alt_aliases });
+    }
+
+    #[test]
+    fn create(
+        creator in user_id(),
+        federate in any::<bool>(),
+        room_version in prop_oneof![
+            Just(RoomVersionId::Version1),
+            Just(RoomVersionId::Version2),
+            Just(RoomVersionId::Version3),
+            Just(RoomVersionId::Version4),
+            Just(RoomVersionId::Version5),
+            Just(RoomVersionId::Version6),
+        ],
+        predecessor in option::of(
+            (room_id(), event_id()).prop_map(|(room_id, event_id)| PreviousRoom::new(room_id, event_id))
+        )
+    ) {
+        assert_strict_round_trip(&CreateEventContent { creator, federate, room_version, predecessor });
+    }
+
+    #[test]
+    fn encrypted(content in encrypted_content()) {
+        assert_round_trip(&content);
+    }
+
+    #[test]
+    fn encryption(
+        algorithm in prop_oneof![
+            Just(EventEncryptionAlgorithm::OlmV1Curve25519AesSha2),
+            Just(EventEncryptionAlgorithm::MegolmV1AesSha2),
+            "[a-z.]{1,16}".prop_map(EventEncryptionAlgorithm::Custom),
+        ],
+        rotation_period_ms in option::of(uint()),
+        rotation_period_msgs in option::of(uint())
+    ) {
+        assert_strict_round_trip(&EncryptionEventContent {
+            algorithm,
+            rotation_period_ms,
+            rotation_period_msgs,
+        });
+    }
+
+    #[test]
+    fn guest_access(
+        guest_access in prop_oneof![Just(GuestAccess::CanJoin), Just(GuestAccess::Forbidden)]
+    ) {
+        assert_strict_round_trip(&GuestAccessEventContent::new(guest_access));
+    }
+
+    #[test]
+    fn history_visibility(
+        history_visibility in prop_oneof![
+            Just(HistoryVisibility::Invited),
+            Just(HistoryVisibility::Joined),
+            Just(HistoryVisibility::Shared),
+            Just(HistoryVisibility::WorldReadable),
+        ]
+    ) {
+        assert_strict_round_trip(&HistoryVisibilityEventContent::new(history_visibility));
+    }
+
+    #[test]
+    fn join_rules(
+        join_rule in prop_oneof![
+            Just(JoinRule::Invite),
+            Just(JoinRule::Knock),
+            Just(JoinRule::Private),
+            Just(JoinRule::Public),
+        ]
+    ) {
+        assert_strict_round_trip(&JoinRulesEventContent::new(join_rule));
+    }
+
+    #[test]
+    fn member(content in member_content()) {
+        assert_strict_round_trip(&content);
+    }
+
+    #[test]
+    fn message(content in message_content()) {
+        assert_strict_round_trip(&content);
+    }
+
+    #[test]
+    fn message_feedback(
+        target_event_id in event_id(),
+        feedback_type in prop_oneof![Just(FeedbackType::Delivered), Just(FeedbackType::Read)]
+    ) {
+        assert_strict_round_trip(&FeedbackEventContent::new(target_event_id, feedback_type));
+    }
+
+    // Up to 63 characters of up to 4 bytes each stay within the 255 byte limit.
+    #[test]
+    fn name(name in "\\PC{0,63}") {
+        assert_strict_round_trip(&NameEventContent::new(name).unwrap());
+    }
+
+    #[test]
+    fn pinned_events(pinned in vec(event_id(), 0..4)) {
+        assert_strict_round_trip(&PinnedEventsEventContent::new(pinned));
+    }
+
+    #[test]
+    fn power_levels(content in power_levels_content()) {
+        assert_strict_round_trip(&content);
+    }
+
+    #[test]
+    fn redaction(reason in option::of(text()), redacts in option::of(event_id())) {
+        assert_strict_round_trip(&RedactionEventContent { reason, redacts });
+    }
+
+    #[test]
+    fn server_acl(
+        allow_ip_literals in any::<bool>(),
+        allow in vec(text(), 0..4),
+        deny in vec(text(), 0..4)
+    ) {
+        assert_strict_round_trip(&ServerAclEventContent { allow_ip_literals, allow, deny });
+    }
+
+    #[test]
+    fn third_party_invite(
+        display_name in text(),
+        key_validity_url in text(),
+        public_key in text(),
+        public_keys in option::of(vec(
+            (option::of(text()), text()).prop_map(|(key_validity_url, public_key)| PublicKey {
+                key_validity_url,
+                public_key,
+            }),
+            0..3,
+        ))
+    ) {
+        assert_strict_round_trip(&ThirdPartyInviteEventContent {
+            display_name,
+            key_validity_url,
+            public_key,
+            public_keys,
+        });
+    }
+
+    #[test]
+    fn tombstone(body in text(), replacement_room in room_id()) {
+        assert_strict_round_trip(&TombstoneEventContent { body, replacement_room });
+    }
+
+    #[test]
+    fn topic(topic in text()) {
+        assert_strict_round_trip(&TopicEventContent { topic });
+    }
+}
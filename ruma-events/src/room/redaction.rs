@@ -3,8 +3,9 @@
 use std::time::SystemTime;
 
 use ruma_events_macros::{Event, EventContent};
-use ruma_identifiers::{EventId, RoomId, UserId};
+use ruma_identifiers::{EventId, RoomId, RoomVersionId, UserId};
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 
 use crate::{
     MessageEventContent, RedactedMessageEventContent, RedactedStateEventContent, RoomEventContent,
@@ -12,12 +13,15 @@ use crate::{
 };
 
 /// Redaction event.
-#[derive(Clone, Debug, Event)]
+#[derive(Clone, Debug, PartialEq, Eq, Event)]
 pub struct RedactionEvent {
     /// Data specific to the event type.
     pub content: RedactionEventContent,
 
     /// The ID of the event that was redacted.
+    ///
+    /// When deserializing, this is taken from `content.redacts` if the event doesn't have a
+    /// top-level `redacts` field.
     pub redacts: EventId,
 
     /// The globally unique event identifier for the user who sent the event.
@@ -37,12 +41,15 @@ pub struct RedactionEvent {
 }
 
 /// Redaction event without a `room_id`.
-#[derive(Clone, Debug, Event)]
+#[derive(Clone, Debug, PartialEq, Eq, Event)]
 pub struct SyncRedactionEvent {
     /// Data specific to the event type.
     pub content: RedactionEventContent,
 
     /// The ID of the event that was redacted.
+    ///
+    /// When deserializing, this is taken from `content.redacts` if the event doesn't have a
+    /// top-level `redacts` field.
     pub redacts: EventId,
 
     /// The globally unique event identifier for the user who sent the event.
@@ -59,12 +66,83 @@ pub struct SyncRedactionEvent {
 }
 
 /// A redaction of an event.
-#[derive(Clone, Debug, Deserialize, Serialize, EventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, EventContent)]
 #[ruma_event(type = "m.room.redaction")]
 pub struct RedactionEventContent {
     /// The reason for the redaction, if any.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reason: Option<String>,
+
+    /// The ID of the event that was redacted.
+    ///
+    /// Only used by room versions that place `redacts` inside of `content` (version 11 and
+    /// later). Use `RedactionEvent::redacts` to get the ID regardless of room version.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redacts: Option<EventId>,
+}
+
+impl RedactionEvent {
+    /// The ID of the event that was redacted.
+    pub fn redacts(&self) -> &EventId {
+        &self.redacts
+    }
+
+    /// Serializes this event to JSON, placing `redacts` where the given room version expects it.
+    ///
+    /// Room versions 11 and later place `redacts` inside of `content`, older room versions use a
+    /// top-level `redacts` field.
+    pub fn to_json_for_room_version(
+        &self,
+        room_version: &RoomVersionId,
+    ) -> serde_json::Result<JsonValue> {
+        place_redacts(serde_json::to_value(self)?, &self.redacts, room_version)
+    }
+}
+
+impl SyncRedactionEvent {
+    /// The ID of the event that was redacted.
+    pub fn redacts(&self) -> &EventId {
+        &self.redacts
+    }
+
+    /// Serializes this event to JSON, placing `redacts` where the given room version expects it.
+    ///
+    /// Room versions 11 and later place `redacts` inside of `content`, older room versions use a
+    /// top-level `redacts` field.
+    pub fn to_json_for_room_version(
+        &self,
+        room_version: &RoomVersionId,
+    ) -> serde_json::Result<JsonValue> {
+        place_redacts(serde_json::to_value(self)?, &self.redacts, room_version)
+    }
+}
+
+/// Whether the given room version places `redacts` inside of the event content.
+fn redacts_in_content(room_version: &RoomVersionId) -> bool {
+    room_version.as_str().parse::<u32>().map_or(false, |version| version >= 11)
+}
+
+fn place_redacts(
+    mut json: JsonValue,
+    redacts: &EventId,
+    room_version: &RoomVersionId,
+) -> serde_json::Result<JsonValue> {
+    let redacts = serde_json::to_value(redacts)?;
+    let object = json.as_object_mut().expect("events serialize to JSON objects");
+
+    if redacts_in_content(room_version) {
+        object.remove("redacts");
+        if let Some(content) = object.get_mut("content").and_then(JsonValue::as_object_mut) {
+            content.insert("redacts".into(), redacts);
+        }
+    } else {
+        object.insert("redacts".into(), redacts);
+        if let Some(content) = object.get_mut("content").and_then(JsonValue::as_object_mut) {
+            content.remove("redacts");
+        }
+    }
+
+    Ok(json)
 }
 
 impl RoomEventContent for RedactionEventContent {}
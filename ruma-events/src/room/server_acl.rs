@@ -9,7 +9,7 @@ use crate::StateEvent;
 pub type ServerAclEvent = StateEvent<ServerAclEventContent>;
 
 /// The payload for `ServerAclEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, StateEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, StateEventContent)]
 #[ruma_event(type = "m.room.server_acl")]
 pub struct ServerAclEventContent {
     /// True to allow server names that are IP address literals. False to deny.
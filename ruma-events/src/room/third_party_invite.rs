@@ -13,7 +13,7 @@ use crate::StateEvent;
 pub type ThirdPartyInviteEvent = StateEvent<ThirdPartyInviteEventContent>;
 
 /// The payload for `ThirdPartyInviteEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, StateEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, StateEventContent)]
 #[ruma_event(type = "m.room.third_party_invite")]
 pub struct ThirdPartyInviteEventContent {
     /// A user-readable string which represents the user who has been invited.
@@ -31,7 +31,7 @@ pub struct ThirdPartyInviteEventContent {
 }
 
 /// A public key for signing a third party invite token.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct PublicKey {
     /// An optional URL which can be fetched to validate whether the key has been revoked.
     ///
@@ -11,7 +11,7 @@ use crate::StateEvent;
 pub type TombstoneEvent = StateEvent<TombstoneEventContent>;
 
 /// The payload for `TombstoneEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, StateEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize, StateEventContent)]
 #[ruma_event(type = "m.room.tombstone")]
 pub struct TombstoneEventContent {
     /// A server-defined message.
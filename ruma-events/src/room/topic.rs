@@ -9,7 +9,7 @@ use crate::StateEvent;
 pub type TopicEvent = StateEvent<TopicEventContent>;
 
 /// The payload for `TopicEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, StateEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize, StateEventContent)]
 #[ruma_event(type = "m.room.topic")]
 pub struct TopicEventContent {
     /// The topic text.
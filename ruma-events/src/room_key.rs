@@ -12,7 +12,7 @@ use crate::BasicEvent;
 pub type RoomKeyEvent = BasicEvent<RoomKeyEventContent>;
 
 /// The payload for `RoomKeyEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, BasicEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, BasicEventContent)]
 #[ruma_event(type = "m.room_key")]
 pub struct RoomKeyEventContent {
     /// The encryption algorithm the key in this event is to be used with.
@@ -2,8 +2,8 @@
 
 use ruma_events_macros::BasicEventContent;
 use ruma_identifiers::{DeviceId, EventEncryptionAlgorithm, RoomId};
+use ruma_serde::string_enum;
 use serde::{Deserialize, Serialize};
-use strum::{Display, EnumString};
 
 use crate::BasicEvent;
 
@@ -13,7 +13,7 @@ use crate::BasicEvent;
 pub type RoomKeyRequestEvent = BasicEvent<RoomKeyRequestEventContent>;
 
 /// The payload for `RoomKeyRequestEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, BasicEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, BasicEventContent)]
 #[ruma_event(type = "m.room_key_request")]
 pub struct RoomKeyRequestEventContent {
     /// Whether this is a new key request or a cancellation of a previous request.
@@ -22,6 +22,7 @@ pub struct RoomKeyRequestEventContent {
     /// Information about the requested key.
     ///
     /// Required when action is `request`.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<RequestedKeyInfo>,
 
     /// ID of the device requesting the key.
@@ -34,23 +35,51 @@ pub struct RoomKeyRequestEventContent {
     pub request_id: String,
 }
 
-/// A new key request or a cancellation of a previous request.
-#[derive(Clone, Copy, Debug, PartialEq, Display, EnumString, Deserialize, Serialize)]
-#[non_exhaustive]
-#[serde(rename_all = "snake_case")]
-#[strum(serialize_all = "snake_case")]
-pub enum Action {
-    /// Request a key.
-    Request,
-
-    /// Cancel a request for a key.
-    #[serde(rename = "request_cancellation")]
-    #[strum(serialize = "request_cancellation")]
-    CancelRequest,
+impl RoomKeyRequestEventContent {
+    /// Creates a new key request for the given key, device and request ID.
+    pub fn new_request(
+        body: RequestedKeyInfo,
+        requesting_device_id: Box<DeviceId>,
+        request_id: String,
+    ) -> Self {
+        Self { action: Action::Request, body: Some(body), requesting_device_id, request_id }
+    }
+
+    /// Creates a cancellation of the key request with the given device and request ID.
+    pub fn new_cancellation(requesting_device_id: Box<DeviceId>, request_id: String) -> Self {
+        Self { action: Action::CancelRequest, body: None, requesting_device_id, request_id }
+    }
+
+    /// Whether this is a cancellation of a previous request.
+    pub fn is_cancellation(&self) -> bool {
+        self.action == Action::CancelRequest
+    }
+
+    /// Whether this event refers to the same key request as `other`, i.e. whether it was sent
+    /// by the same device with the same request ID.
+    ///
+    /// This can be used to find the request that a cancellation cancels.
+    pub fn is_same_request_as(&self, other: &Self) -> bool {
+        self.requesting_device_id == other.requesting_device_id
+            && self.request_id == other.request_id
+    }
+}
+
+string_enum! {
+    /// A new key request or a cancellation of a previous request.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum Action {
+        /// Request a key.
+        Request => "request",
+
+        /// Cancel a request for a key.
+        CancelRequest => "request_cancellation",
+    }
 }
 
 /// Information about a requested key.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct RequestedKeyInfo {
     /// The encryption algorithm the requested key in this event is to be used with.
     pub algorithm: EventEncryptionAlgorithm,
@@ -64,3 +93,52 @@ pub struct RequestedKeyInfo {
     /// The ID of the session that the key is for.
     pub session_id: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use matches::assert_matches;
+    use ruma_identifiers::{room_id, EventEncryptionAlgorithm};
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::{Action, RequestedKeyInfo, RoomKeyRequestEventContent};
+
+    #[test]
+    fn serialize_cancellation_without_body() {
+        let content = RoomKeyRequestEventContent::new_cancellation("ABCDEFG".into(), "1".into());
+
+        assert_eq!(
+            to_json_value(&content).unwrap(),
+            json!({
+                "action": "request_cancellation",
+                "requesting_device_id": "ABCDEFG",
+                "request_id": "1",
+            })
+        );
+    }
+
+    #[test]
+    fn cancellation_matches_request() {
+        let request = RoomKeyRequestEventContent::new_request(
+            RequestedKeyInfo {
+                algorithm: EventEncryptionAlgorithm::MegolmV1AesSha2,
+                room_id: room_id!("!room:example.org"),
+                sender_key: "key".into(),
+                session_id: "session".into(),
+            },
+            "ABCDEFG".into(),
+            "1".into(),
+        );
+        let cancellation: RoomKeyRequestEventContent = from_json_value(json!({
+            "action": "request_cancellation",
+            "requesting_device_id": "ABCDEFG",
+            "request_id": "1",
+        }))
+        .unwrap();
+
+        assert!(!request.is_cancellation());
+        assert!(cancellation.is_cancellation());
+        assert_matches!(cancellation.body, None);
+        assert!(cancellation.is_same_request_as(&request));
+        assert_eq!(request.action, Action::Request);
+    }
+}
@@ -0,0 +1,4 @@
+//! Modules for events in the *m.secret_storage* namespace.
+
+pub mod default_key;
+pub mod key;
@@ -0,0 +1,28 @@
+//! Types for the *m.secret_storage.default_key* event.
+
+use ruma_events_macros::BasicEventContent;
+use serde::{Deserialize, Serialize};
+
+use crate::BasicEvent;
+
+/// The key that clients should use to encrypt new secrets by default.
+///
+/// This event appears in the user's global account data.
+pub type DefaultKeyEvent = BasicEvent<DefaultKeyEventContent>;
+
+/// The payload for `DefaultKeyEvent`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, BasicEventContent)]
+#[ruma_event(type = "m.secret_storage.default_key")]
+pub struct DefaultKeyEventContent {
+    /// The ID of the default key.
+    ///
+    /// Its description is stored in the `m.secret_storage.key.<key id>` account data event.
+    pub key: String,
+}
+
+impl DefaultKeyEventContent {
+    /// Creates a new `DefaultKeyEventContent` with the given key ID.
+    pub fn new(key: String) -> Self {
+        Self { key }
+    }
+}
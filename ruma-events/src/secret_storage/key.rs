@@ -0,0 +1,251 @@
+//! Types for the *m.secret_storage.key.<key id>* events.
+
+use js_int::{uint, UInt};
+use ruma_serde::string_enum;
+use serde::{de::Error as _, Deserialize, Serialize};
+use serde_json::value::RawValue as RawJsonValue;
+
+use crate::{BasicEvent, BasicEventContent, EventContent};
+
+/// The prefix of the event type of `KeyEvent`, which is followed by the ID of the key.
+const EVENT_TYPE_PREFIX: &str = "m.secret_storage.key.";
+
+/// The description of a key that can be used to encrypt secrets in secret storage.
+///
+/// This event appears in the user's global account data. Its type includes the ID of the key, as
+/// in `m.secret_storage.key.<key id>`.
+pub type KeyEvent = BasicEvent<KeyEventContent>;
+
+/// The payload for `KeyEvent`.
+///
+/// The ID of the key isn't part of the JSON content, it is taken from the event type when the
+/// event is deserialized.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct KeyEventContent {
+    /// The full event type, including the key ID.
+    #[serde(skip)]
+    event_type: String,
+
+    /// The name of the key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// The algorithm the key is used with.
+    pub algorithm: SecretEncryptionAlgorithm,
+
+    /// Information for deriving the key from a passphrase.
+    ///
+    /// If this is `None`, the key can't be generated from a passphrase.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passphrase: Option<PassPhrase>,
+
+    /// The 16-byte initialization vector used to check the key, encoded as base64.
+    ///
+    /// Used with `SecretEncryptionAlgorithm::V1AesHmacSha2`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iv: Option<String>,
+
+    /// The MAC of 32 encrypted zero bytes, encoded as base64.
+    ///
+    /// Used with `SecretEncryptionAlgorithm::V1AesHmacSha2`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mac: Option<String>,
+}
+
+impl KeyEventContent {
+    /// Creates a new `KeyEventContent` for the key with the given ID and algorithm.
+    pub fn new(key_id: &str, algorithm: SecretEncryptionAlgorithm) -> Self {
+        Self {
+            event_type: format!("{}{}", EVENT_TYPE_PREFIX, key_id),
+            name: None,
+            algorithm,
+            passphrase: None,
+            iv: None,
+            mac: None,
+        }
+    }
+
+    /// The ID of the key.
+    ///
+    /// This is empty if the content was deserialized on its own, without an event type.
+    pub fn key_id(&self) -> &str {
+        if self.event_type.starts_with(EVENT_TYPE_PREFIX) {
+            &self.event_type[EVENT_TYPE_PREFIX.len()..]
+        } else {
+            ""
+        }
+    }
+}
+
+impl EventContent for KeyEventContent {
+    fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    fn from_parts(event_type: &str, content: Box<RawJsonValue>) -> Result<Self, serde_json::Error> {
+        if !event_type.starts_with(EVENT_TYPE_PREFIX) || event_type.len() == EVENT_TYPE_PREFIX.len()
+        {
+            return Err(serde_json::Error::custom(format!(
+                "expected event type `{}<key id>`, found `{}`",
+                EVENT_TYPE_PREFIX, event_type
+            )));
+        }
+
+        let mut content: Self = serde_json::from_str(content.get())?;
+        content.event_type = event_type.to_owned();
+
+        Ok(content)
+    }
+}
+
+impl BasicEventContent for KeyEventContent {}
+
+/// Information for deriving a secret storage key from a passphrase.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PassPhrase {
+    /// The algorithm to use to generate the key from the passphrase.
+    pub algorithm: KeyDerivationAlgorithm,
+
+    /// The salt used in the derivation.
+    pub salt: String,
+
+    /// The number of iterations used in the derivation.
+    pub iterations: UInt,
+
+    /// The number of bits to generate for the key.
+    #[serde(default = "default_bits")]
+    pub bits: UInt,
+}
+
+impl PassPhrase {
+    /// Creates a new `PassPhrase` with the given algorithm, salt and number of iterations, for a
+    /// key of 256 bits.
+    pub fn new(algorithm: KeyDerivationAlgorithm, salt: String, iterations: UInt) -> Self {
+        Self { algorithm, salt, iterations, bits: default_bits() }
+    }
+}
+
+fn default_bits() -> UInt {
+    uint!(256)
+}
+
+string_enum! {
+    /// An algorithm for encrypting secrets in secret storage.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum SecretEncryptionAlgorithm {
+        /// Encryption using AES-CTR-256 and authentication using HMAC-SHA-256.
+        V1AesHmacSha2 => "m.secret_storage.v1.aes-hmac-sha2",
+    }
+}
+
+string_enum! {
+    /// An algorithm for deriving a secret storage key from a passphrase.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum KeyDerivationAlgorithm {
+        /// PBKDF2 using SHA-512.
+        Pbkdf2 => "m.pbkdf2",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::uint;
+    use matches::assert_matches;
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::{
+        KeyDerivationAlgorithm, KeyEvent, KeyEventContent, PassPhrase, SecretEncryptionAlgorithm,
+    };
+    use crate::{AnyBasicEvent, BasicEvent, EventContent};
+
+    #[test]
+    fn roundtrip_key_description() {
+        let json = json!({
+            "type": "m.secret_storage.key.bmur2d9ypPUH1msSwCxQOJkuKRmJI55e",
+            "content": {
+                "name": "Recovery key",
+                "algorithm": "m.secret_storage.v1.aes-hmac-sha2",
+                "passphrase": {
+                    "algorithm": "m.pbkdf2",
+                    "salt": "MmMsAlty",
+                    "iterations": 100_000
+                },
+                "iv": "gH2iNpiETFhApvW6/FFEJQ",
+                "mac": "9lw12m5SKDipNghdQXKjgpfdj1/K7HFI2brO+UWAGoM"
+            }
+        });
+
+        let event = from_json_value::<KeyEvent>(json.clone()).unwrap();
+        assert_eq!(event.content.key_id(), "bmur2d9ypPUH1msSwCxQOJkuKRmJI55e");
+        assert_eq!(event.content.name.as_deref(), Some("Recovery key"));
+        assert_eq!(event.content.algorithm, SecretEncryptionAlgorithm::V1AesHmacSha2);
+
+        let passphrase = event.content.passphrase.as_ref().unwrap();
+        assert_eq!(passphrase.algorithm, KeyDerivationAlgorithm::Pbkdf2);
+        assert_eq!(passphrase.iterations, uint!(100_000));
+        assert_eq!(passphrase.bits, uint!(256));
+
+        let mut expected = json;
+        expected["content"]["passphrase"]["bits"] = json!(256);
+        assert_eq!(to_json_value(&event).unwrap(), expected);
+    }
+
+    #[test]
+    fn serialize_new_key_description() {
+        let mut content = KeyEventContent::new("abc", SecretEncryptionAlgorithm::V1AesHmacSha2);
+        content.passphrase =
+            Some(PassPhrase::new(KeyDerivationAlgorithm::Pbkdf2, "salt".into(), uint!(10)));
+        assert_eq!(content.event_type(), "m.secret_storage.key.abc");
+
+        assert_eq!(
+            to_json_value(&BasicEvent { content }).unwrap(),
+            json!({
+                "type": "m.secret_storage.key.abc",
+                "content": {
+                    "algorithm": "m.secret_storage.v1.aes-hmac-sha2",
+                    "passphrase": {
+                        "algorithm": "m.pbkdf2",
+                        "salt": "salt",
+                        "iterations": 10,
+                        "bits": 256
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn reject_other_event_types() {
+        let content = json!({ "algorithm": "m.secret_storage.v1.aes-hmac-sha2" });
+
+        for event_type in &["m.secret_storage.key.", "m.secret_storage.default_key"] {
+            let json = json!({ "type": event_type, "content": content });
+            assert!(from_json_value::<KeyEvent>(json).is_err());
+        }
+    }
+
+    #[test]
+    fn deserialize_content_without_event_type() {
+        let json = json!({ "algorithm": "m.secret_storage.v1.aes-hmac-sha2" });
+
+        let content = from_json_value::<KeyEventContent>(json).unwrap();
+        assert_eq!(content.key_id(), "");
+        assert_eq!(content.algorithm, SecretEncryptionAlgorithm::V1AesHmacSha2);
+    }
+
+    #[test]
+    fn key_events_are_custom_basic_events() {
+        let json = json!({
+            "type": "m.secret_storage.key.abc",
+            "content": { "algorithm": "m.secret_storage.v1.aes-hmac-sha2" }
+        });
+
+        assert_matches!(
+            from_json_value::<AnyBasicEvent>(json).unwrap(),
+            AnyBasicEvent::Custom(event)
+                if event.content.event_type == "m.secret_storage.key.abc"
+        );
+    }
+}
@@ -0,0 +1,146 @@
+//! Types for the *m.space.child* event.
+
+use ruma_events_macros::StateEventContent;
+use ruma_identifiers::ServerName;
+use serde::{Deserialize, Serialize};
+
+use crate::{InvalidInput, StateEvent};
+
+/// An event that declares a room as a child of a space.
+///
+/// The `state_key` is the ID of the child room.
+pub type ChildEvent = StateEvent<ChildEventContent>;
+
+/// The payload for `ChildEvent`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, StateEventContent)]
+#[ruma_event(type = "m.space.child")]
+pub struct ChildEventContent {
+    /// Servers to try to join the child room through.
+    ///
+    /// If this is empty, the child room is not part of the space anymore.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub via: Vec<Box<ServerName>>,
+
+    /// A string used to order the children of the space lexicographically.
+    ///
+    /// Invalid values are ignored when deserializing.
+    #[serde(
+        default,
+        deserialize_with = "valid_order_or_none",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub(crate) order: Option<String>,
+
+    /// Whether the child room is suggested to members of the space.
+    #[serde(default, skip_serializing_if = "ruma_serde::is_default")]
+    pub suggested: bool,
+}
+
+impl ChildEventContent {
+    /// Creates a new `ChildEventContent` with the given servers to join the child room through.
+    pub fn new(via: Vec<Box<ServerName>>) -> Self {
+        Self { via, order: None, suggested: false }
+    }
+
+    /// The string used to order the children of the space, if any.
+    pub fn order(&self) -> Option<&str> {
+        self.order.as_deref()
+    }
+
+    /// Sets the string used to order the children of the space.
+    ///
+    /// # Errors
+    ///
+    /// `InvalidInput` will be returned if the order is more than 50 characters long or contains
+    /// characters other than printable ASCII characters.
+    pub fn set_order(&mut self, order: Option<String>) -> Result<(), InvalidInput> {
+        if let Some(order) = &order {
+            if !is_valid_order(order) {
+                return Err(InvalidInput(
+                    "an order must be at most 50 printable ASCII characters".into(),
+                ));
+            }
+        }
+
+        self.order = order;
+        Ok(())
+    }
+}
+
+fn is_valid_order(order: &str) -> bool {
+    order.len() <= 50 && order.bytes().all(|b| (0x20..=0x7E).contains(&b))
+}
+
+fn valid_order_or_none<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    // Orders that aren't strings or aren't valid are ignored rather than rejected.
+    Ok(match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(order) if is_valid_order(&order) => Some(order),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use matches::assert_matches;
+    use ruma_identifiers::server_name;
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::ChildEventContent;
+
+    #[test]
+    fn serialize_content() {
+        let mut content = ChildEventContent::new(vec![server_name!("example.org")]);
+        content.set_order(Some("aaa".into())).unwrap();
+        content.suggested = true;
+
+        assert_eq!(
+            to_json_value(&content).unwrap(),
+            json!({ "via": ["example.org"], "order": "aaa", "suggested": true })
+        );
+    }
+
+    #[test]
+    fn serialize_removed_child() {
+        assert_eq!(to_json_value(ChildEventContent::new(Vec::new())).unwrap(), json!({}));
+    }
+
+    #[test]
+    fn reject_invalid_order() {
+        let mut content = ChildEventContent::new(vec![server_name!("example.org")]);
+
+        assert!(content.set_order(Some("ordre ü".into())).is_err());
+        assert!(content.set_order(Some("a".repeat(51))).is_err());
+        assert_eq!(content.order(), None);
+
+        content.set_order(Some("a".repeat(50))).unwrap();
+        assert_eq!(content.order(), Some(&*"a".repeat(50)));
+    }
+
+    #[test]
+    fn deserialize_ignores_invalid_order() {
+        assert_matches!(
+            from_json_value::<ChildEventContent>(json!({
+                "via": ["example.org"],
+                "order": "\u{1F600}",
+            }))
+            .unwrap(),
+            ChildEventContent { via, order: None, suggested: false }
+                if via == vec![server_name!("example.org")]
+        );
+        assert_eq!(
+            from_json_value::<ChildEventContent>(json!({ "via": ["example.org"], "order": 5 }))
+                .unwrap()
+                .order(),
+            None
+        );
+        assert_eq!(
+            from_json_value::<ChildEventContent>(json!({ "via": ["example.org"], "order": "b" }))
+                .unwrap()
+                .order(),
+            Some("b")
+        );
+    }
+}
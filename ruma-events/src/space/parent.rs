@@ -0,0 +1,34 @@
+//! Types for the *m.space.parent* event.
+
+use ruma_events_macros::StateEventContent;
+use ruma_identifiers::ServerName;
+use serde::{Deserialize, Serialize};
+
+use crate::StateEvent;
+
+/// An event that declares a space as a parent of a room.
+///
+/// The `state_key` is the ID of the parent space.
+pub type ParentEvent = StateEvent<ParentEventContent>;
+
+/// The payload for `ParentEvent`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, StateEventContent)]
+#[ruma_event(type = "m.space.parent")]
+pub struct ParentEventContent {
+    /// Servers to try to join the parent space through.
+    ///
+    /// If this is empty, the space is not a parent of the room anymore.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub via: Vec<Box<ServerName>>,
+
+    /// Whether this is the main parent of the room.
+    #[serde(default, skip_serializing_if = "ruma_serde::is_default")]
+    pub canonical: bool,
+}
+
+impl ParentEventContent {
+    /// Creates a new `ParentEventContent` with the given servers to join the parent space through.
+    pub fn new(via: Vec<Box<ServerName>>) -> Self {
+        Self { via, canonical: false }
+    }
+}
@@ -9,7 +9,7 @@ use crate::{room::ImageInfo, MessageEvent};
 pub type StickerEvent = MessageEvent<StickerEventContent>;
 
 /// The payload for `StickerEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, MessageEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, MessageEventContent)]
 #[ruma_event(type = "m.sticker")]
 pub struct StickerEventContent {
     /// A textual representation or associated description of the sticker image. This could
@@ -14,7 +14,9 @@ pub type TagEvent = BasicEvent<TagEventContent>;
 pub type Tags = BTreeMap<String, TagInfo>;
 
 /// The payload for `TagEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, BasicEventContent)]
+///
+/// Doesn't implement `Eq` or `Hash` since `TagInfo` contains a floating point number.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, BasicEventContent)]
 #[ruma_event(type = "m.tag")]
 pub struct TagEventContent {
     /// A map of tag names to tag info.
@@ -22,7 +24,9 @@ pub struct TagEventContent {
 }
 
 /// Information about a tag.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+///
+/// Doesn't implement `Eq` or `Hash` since `order` is a floating point number.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct TagInfo {
     /// Value to use for lexicographically ordering rooms with this tag.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -10,7 +10,7 @@ use crate::EphemeralRoomEvent;
 pub type TypingEvent = EphemeralRoomEvent<TypingEventContent>;
 
 /// The payload for `TypingEvent`.
-#[derive(Clone, Debug, Deserialize, Serialize, EphemeralRoomEventContent)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize, EphemeralRoomEventContent)]
 #[ruma_event(type = "m.typing")]
 pub struct TypingEventContent {
     /// The list of user IDs typing in this room, if any.
@@ -3,11 +3,14 @@ use std::time::{Duration, UNIX_EPOCH};
 use matches::assert_matches;
 use ruma_common::Raw;
 use ruma_events::{
-    custom::CustomEventContent, AnyMessageEvent, AnyStateEvent, AnyStateEventContent,
+    custom::{CustomEvent, CustomEventContent},
+    macros::MessageEventContent,
+    AnyMessageEvent, AnyMessageEventContent, AnyStateEvent, AnyStateEventContent,
     AnySyncMessageEvent, AnySyncRoomEvent, MessageEvent, StateEvent, SyncMessageEvent,
     SyncStateEvent, Unsigned,
 };
-use ruma_identifiers::{event_id, room_id, user_id};
+use ruma_identifiers::{event_id, room_id, user_id, UserId};
+use serde::{Deserialize, Serialize};
 use serde_json::{
     from_value as from_json_value, json, to_value as to_json_value, Value as JsonValue,
 };
@@ -35,7 +38,7 @@ fn custom_state_event() -> JsonValue {
 
 #[test]
 fn serialize_custom_message_event() {
-    let aliases_event = AnyMessageEvent::Custom(MessageEvent {
+    let aliases_event = AnyMessageEvent::Custom(CustomEvent::new(MessageEvent {
         content: CustomEventContent {
             json: json!({
                 "body": " * edited message",
@@ -56,7 +59,7 @@ fn serialize_custom_message_event() {
         room_id: room_id!("!room:room.com"),
         sender: user_id!("@carl:example.com"),
         unsigned: Unsigned::default(),
-    });
+    }));
 
     let actual = to_json_value(&aliases_event).unwrap();
     let expected = json!({
@@ -84,7 +87,7 @@ fn serialize_custom_message_event() {
 
 #[test]
 fn serialize_custom_state_event() {
-    let aliases_event = AnyStateEvent::Custom(StateEvent {
+    let aliases_event = AnyStateEvent::Custom(CustomEvent::new(StateEvent {
         content: CustomEventContent {
             json: json!({
                 "custom": 10
@@ -98,7 +101,7 @@ fn serialize_custom_state_event() {
         sender: user_id!("@carl:example.com"),
         state_key: "".into(),
         unsigned: Unsigned::default(),
-    });
+    }));
 
     let actual = to_json_value(&aliases_event).unwrap();
     let expected = json!({
@@ -128,12 +131,14 @@ fn deserialize_custom_state_event() {
         }
     });
 
+    let event = match from_json_value::<Raw<AnyStateEvent>>(json_data).unwrap().deserialize() {
+        Ok(AnyStateEvent::Custom(event)) => event.into_event(),
+        other => panic!("unexpected result: {:?}", other),
+    };
+
     assert_matches!(
-        from_json_value::<Raw<AnyStateEvent>>(json_data)
-            .unwrap()
-            .deserialize()
-            .unwrap(),
-        AnyStateEvent::Custom(StateEvent {
+        event,
+        StateEvent {
             content: CustomEventContent {
                 json, event_type,
             },
@@ -144,7 +149,7 @@ fn deserialize_custom_state_event() {
             prev_content: None,
             state_key,
             unsigned,
-        }) if json == expected_content && event_type == "m.reaction"
+        } if json == expected_content && event_type == "m.reaction"
             && event_id == event_id!("$h29iv0s8:example.com")
             && origin_server_ts == UNIX_EPOCH + Duration::from_millis(10)
             && sender == user_id!("@carl:example.com")
@@ -216,10 +221,14 @@ fn deserialize_custom_message_sync_event() {
         }
     });
 
+    let event = match from_json_value::<AnySyncRoomEvent>(json_data) {
+        Ok(AnySyncRoomEvent::Message(AnySyncMessageEvent::Custom(event))) => event.into_event(),
+        other => panic!("unexpected result: {:?}", other),
+    };
+
     assert_matches!(
-        from_json_value::<AnySyncRoomEvent>(json_data)
-            .unwrap(),
-        AnySyncRoomEvent::Message(AnySyncMessageEvent::Custom(SyncMessageEvent {
+        event,
+        SyncMessageEvent {
             content: CustomEventContent {
                 json, event_type,
             },
@@ -227,10 +236,73 @@ fn deserialize_custom_message_sync_event() {
             origin_server_ts,
             sender,
             unsigned,
-        })) if json == expected_content && event_type == "m.reaction"
+        } if json == expected_content && event_type == "m.reaction"
             && event_id == event_id!("$h29iv0s8:example.com")
             && origin_server_ts == UNIX_EPOCH + Duration::from_millis(10)
             && sender == user_id!("@carl:example.com")
             && !unsigned.is_empty()
     );
 }
+
+#[test]
+fn custom_event_round_trip_preserves_unknown_keys() {
+    let json = r#"{"type":"org.example.poll","content":{"question":"?"},"event_id":"$h29iv0s8:example.com","origin_server_ts":10,"room_id":"!room:room.com","sender":"@carl:example.com","org.example.extra":{"nested":[1,2,3]}}"#;
+
+    let event = serde_json::from_str::<AnyMessageEvent>(json).unwrap();
+    let custom = match &event {
+        AnyMessageEvent::Custom(custom) => custom,
+        other => panic!("unexpected event: {:?}", other),
+    };
+    assert_eq!(custom.json().unwrap().get(), json);
+    assert_matches!(
+        event.content(),
+        AnyMessageEventContent::Custom(CustomEventContent { event_type, .. })
+            if event_type == "org.example.poll"
+    );
+
+    assert_eq!(serde_json::to_string(&event).unwrap(), json);
+}
+
+#[test]
+fn custom_event_sync_conversion_preserves_unknown_keys() {
+    let json = json!({
+        "type": "org.example.poll",
+        "content": { "question": "?" },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 10,
+        "sender": "@carl:example.com",
+        "org.example.extra": true
+    });
+
+    let sync_event = from_json_value::<AnySyncMessageEvent>(json.clone()).unwrap();
+    let event = sync_event.into_full_event(room_id!("!room:room.com"));
+
+    let mut expected = json;
+    expected["room_id"] = "!room:room.com".into();
+    assert_eq!(to_json_value(&event).unwrap(), expected);
+}
+
+/// An event content type defined outside of ruma-events, the way a downstream crate would.
+#[derive(Clone, Debug, Deserialize, Serialize, MessageEventContent)]
+#[ruma_event(type = "org.example.poke")]
+struct PokeEventContent {
+    target: UserId,
+}
+
+#[test]
+fn downstream_message_event_content() {
+    let json_data = json!({
+        "content": { "target": "@alice:example.com" },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 10,
+        "room_id": "!room:room.com",
+        "sender": "@carl:example.com",
+        "type": "org.example.poke"
+    });
+
+    let event = from_json_value::<MessageEvent<PokeEventContent>>(json_data.clone()).unwrap();
+    assert_eq!(event.content.target, user_id!("@alice:example.com"));
+    assert_eq!(event.sender, user_id!("@carl:example.com"));
+
+    assert_eq!(to_json_value(&event).unwrap(), json_data);
+}
@@ -0,0 +1,190 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use ruma_events::{
+    room::message::{
+        resolve_edits, EmoteMessageEventContent, InReplyTo, MessageEvent, MessageEventContent,
+        RelatesTo, Replacement, TextMessageEventContent,
+    },
+    Unsigned,
+};
+use ruma_identifiers::{event_id, room_id, user_id, EventId, UserId};
+use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+fn message(
+    event_id: EventId,
+    sender: UserId,
+    ts: u64,
+    content: TextMessageEventContent,
+) -> MessageEvent {
+    MessageEvent {
+        content: MessageEventContent::Text(content),
+        event_id,
+        origin_server_ts: UNIX_EPOCH + Duration::from_millis(ts),
+        room_id: room_id!("!room:example.org"),
+        sender,
+        unsigned: Unsigned::default(),
+    }
+}
+
+fn original() -> MessageEvent {
+    message(
+        event_id!("$original:example.org"),
+        user_id!("@alice:example.org"),
+        1,
+        TextMessageEventContent::plain("hello wrold"),
+    )
+}
+
+fn edit(event_id: EventId, sender: UserId, ts: u64, replaces: EventId, body: &str) -> MessageEvent {
+    let mut content = TextMessageEventContent::plain(format!("* {}", body));
+    content.relates_to = Some(RelatesTo {
+        in_reply_to: None,
+        replacement: Some(Replacement { event_id: replaces }),
+    });
+    content.new_content =
+        Some(Box::new(MessageEventContent::Text(TextMessageEventContent::plain(body))));
+
+    message(event_id, sender, ts, content)
+}
+
+#[test]
+fn serialize_edit() {
+    let edit = edit(
+        event_id!("$edit:example.org"),
+        user_id!("@alice:example.org"),
+        2,
+        event_id!("$original:example.org"),
+        "hello world",
+    );
+
+    let json = json!({
+        "msgtype": "m.text",
+        "body": "* hello world",
+        "m.new_content": {
+            "msgtype": "m.text",
+            "body": "hello world"
+        },
+        "m.relates_to": {
+            "rel_type": "m.replace",
+            "event_id": "$original:example.org"
+        }
+    });
+
+    assert_eq!(to_json_value(&edit.content).unwrap(), json);
+    assert_eq!(from_json_value::<MessageEventContent>(json).unwrap(), edit.content);
+}
+
+#[test]
+fn latest_edit_wins() {
+    let original = original();
+    let alice = user_id!("@alice:example.org");
+    let edits = vec![
+        edit(event_id!("$b:example.org"), alice.clone(), 5, original.event_id.clone(), "second"),
+        edit(event_id!("$a:example.org"), alice.clone(), 3, original.event_id.clone(), "first"),
+        // Same timestamp as `$b`, but a larger event ID.
+        edit(event_id!("$c:example.org"), alice, 5, original.event_id.clone(), "third"),
+    ];
+
+    assert_eq!(resolve_edits(&original, &edits).body(), "third");
+    assert_eq!(resolve_edits(&original, Vec::new()).body(), "hello wrold");
+}
+
+#[test]
+fn edit_keeps_reply_relation() {
+    let mut content = TextMessageEventContent::plain("> <@bob:example.org> hi\n\nhello wrold");
+    content.relates_to = Some(RelatesTo {
+        in_reply_to: Some(InReplyTo { event_id: event_id!("$question:example.org") }),
+        replacement: None,
+    });
+    let original =
+        message(event_id!("$original:example.org"), user_id!("@alice:example.org"), 1, content);
+    let edit = edit(
+        event_id!("$edit:example.org"),
+        user_id!("@alice:example.org"),
+        2,
+        original.event_id.clone(),
+        "hello world",
+    );
+
+    let resolved = resolve_edits(&original, vec![&edit]);
+    assert_eq!(resolved.body(), "hello world");
+    assert_eq!(
+        resolved.relates_to(),
+        Some(&RelatesTo {
+            in_reply_to: Some(InReplyTo { event_id: event_id!("$question:example.org") }),
+            replacement: None,
+        })
+    );
+    assert!(resolved.new_content().is_none());
+}
+
+#[test]
+fn emote_edit() {
+    let mut original = original();
+    original.content = MessageEventContent::Emote(EmoteMessageEventContent::plain("waves"));
+
+    let mut content = EmoteMessageEventContent::plain("* waves back");
+    content.relates_to = Some(RelatesTo {
+        in_reply_to: None,
+        replacement: Some(Replacement { event_id: original.event_id.clone() }),
+    });
+    content.new_content =
+        Some(Box::new(MessageEventContent::Emote(EmoteMessageEventContent::plain("waves back"))));
+    let mut edit = original.clone();
+    edit.event_id = event_id!("$edit:example.org");
+    edit.content = MessageEventContent::Emote(content);
+
+    let json = to_json_value(&edit.content).unwrap();
+    assert_eq!(json["m.relates_to"]["rel_type"], "m.replace");
+    assert_eq!(from_json_value::<MessageEventContent>(json).unwrap(), edit.content);
+
+    let resolved = resolve_edits(&original, vec![&edit]);
+    assert_eq!(resolved, MessageEventContent::Emote(EmoteMessageEventContent::plain("waves back")));
+}
+
+#[test]
+fn edit_from_other_sender_is_ignored() {
+    let original = original();
+    let edit = edit(
+        event_id!("$edit:example.org"),
+        user_id!("@mallory:example.org"),
+        2,
+        original.event_id.clone(),
+        "hello mallory",
+    );
+
+    assert_eq!(resolve_edits(&original, vec![&edit]).body(), "hello wrold");
+}
+
+#[test]
+fn edits_of_edits_are_ignored() {
+    let original = original();
+    let alice = user_id!("@alice:example.org");
+    let first = edit(
+        event_id!("$first:example.org"),
+        alice.clone(),
+        2,
+        original.event_id.clone(),
+        "hello world",
+    );
+    // Later edits have to replace the original event as well, not the previous edit.
+    let second =
+        edit(event_id!("$second:example.org"), alice, 3, first.event_id.clone(), "hello, world");
+
+    assert_eq!(resolve_edits(&original, vec![&first, &second]).body(), "hello world");
+}
+
+#[test]
+fn edit_without_new_content_is_skipped() {
+    let original = original();
+    let alice = user_id!("@alice:example.org");
+    let valid =
+        edit(event_id!("$valid:example.org"), alice.clone(), 2, original.event_id.clone(), "valid");
+    let mut malformed =
+        edit(event_id!("$malformed:example.org"), alice, 3, original.event_id.clone(), "");
+    if let MessageEventContent::Text(content) = &mut malformed.content {
+        content.new_content = None;
+    }
+
+    assert_eq!(resolve_edits(&original, vec![&valid, &malformed]).body(), "valid");
+}
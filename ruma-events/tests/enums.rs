@@ -1,16 +1,19 @@
+use std::convert::TryFrom;
+
 use matches::assert_matches;
 use ruma_identifiers::{event_id, room_alias_id, room_id, user_id};
 use serde_json::{from_value as from_json_value, json, Value as JsonValue};
 
 use ruma_events::{
+    ignored_user_list::IgnoredUserListEventContent,
     room::{
         aliases::AliasesEventContent,
         message::{MessageEventContent, TextMessageEventContent},
         power_levels::PowerLevelsEventContent,
     },
-    AnyEvent, AnyMessageEvent, AnyRoomEvent, AnyStateEvent, AnyStateEventContent,
-    AnySyncMessageEvent, AnySyncRoomEvent, AnySyncStateEvent, MessageEvent, StateEvent,
-    SyncMessageEvent, SyncStateEvent,
+    AnyEvent, AnyMessageEvent, AnyRedactedSyncMessageEvent, AnyRoomEvent, AnyStateEvent,
+    AnyStateEventContent, AnySyncMessageEvent, AnySyncRoomEvent, AnySyncStateEvent, BasicEvent,
+    MessageEvent, StateEvent, SyncMessageEvent, SyncStateEvent,
 };
 
 fn message_event() -> JsonValue {
@@ -141,6 +144,7 @@ fn message_event_sync_deserialization() {
                     body,
                     formatted: Some(formatted),
                     relates_to: None,
+                    new_content: None,
                 }),
                 ..
             })
@@ -180,6 +184,7 @@ fn message_room_event_deserialization() {
                     body,
                     formatted: Some(formatted),
                     relates_to: None,
+                    new_content: None,
                 }),
                 ..
             })
@@ -219,6 +224,7 @@ fn message_event_deserialization() {
                     body,
                     formatted: Some(formatted),
                     relates_to: None,
+                    new_content: None,
                 }),
                 ..
             })
@@ -267,3 +273,93 @@ fn alias_event_field_access() {
         panic!("the `Any*Event` enum's accessor methods may have been altered")
     }
 }
+
+#[test]
+fn room_event_conversions() {
+    let state_event = from_json_value::<AnyStateEvent>(aliases_event()).unwrap();
+    let room_event = AnyRoomEvent::from(state_event);
+    assert_matches!(&room_event, AnyRoomEvent::State(_));
+
+    let event = AnyEvent::from(room_event.clone());
+    assert_matches!(AnyRoomEvent::try_from(event), Ok(AnyRoomEvent::State(_)));
+
+    assert_matches!(AnyStateEvent::try_from(room_event.clone()), Ok(AnyStateEvent::RoomAliases(_)));
+    assert_matches!(AnyMessageEvent::try_from(room_event), Err(AnyRoomEvent::State(_)));
+
+    let sync_event = from_json_value::<AnySyncMessageEvent>(message_event_sync()).unwrap();
+    let sync_room_event = AnySyncRoomEvent::from(sync_event);
+    assert_matches!(
+        AnySyncMessageEvent::try_from(sync_room_event),
+        Ok(AnySyncMessageEvent::RoomMessage(_))
+    );
+}
+
+#[test]
+fn basic_event_from_content() {
+    let content =
+        IgnoredUserListEventContent { ignored_users: vec![user_id!("@carl:example.com")] };
+    let event = BasicEvent::from(content.clone());
+    assert_eq!(event.content, content);
+
+    let any_event =
+        AnyEvent::Basic(from_json_value(json!({ "content": {}, "type": "m.dummy" })).unwrap());
+    assert_matches!(AnyRoomEvent::try_from(any_event), Err(AnyEvent::Basic(_)));
+}
+
+#[test]
+fn room_event_dispatch() {
+    let redacted_message = json!({
+        "content": {},
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "sender": "@example:localhost",
+        "type": "m.room.message",
+        "unsigned": {
+            "redacted_because": {
+                "content": {},
+                "event_id": "$h29iv0s9:example.com",
+                "origin_server_ts": 2,
+                "redacts": "$h29iv0s8:example.com",
+                "sender": "@example:localhost",
+                "type": "m.room.redaction"
+            }
+        }
+    });
+    let custom_state = json!({
+        "content": { "key": "value" },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "sender": "@example:localhost",
+        "state_key": "",
+        "type": "dev.ruma.custom"
+    });
+
+    assert_matches!(
+        from_json_value::<AnySyncRoomEvent>(redacted_message),
+        Ok(AnySyncRoomEvent::RedactedMessage(AnyRedactedSyncMessageEvent::RoomMessage(_)))
+    );
+    assert_matches!(
+        from_json_value::<AnySyncRoomEvent>(custom_state),
+        Ok(AnySyncRoomEvent::State(AnySyncStateEvent::Custom(event)))
+            if event.event().content.event_type == "dev.ruma.custom"
+    );
+}
+
+#[test]
+fn large_room_event_array_deserialization() {
+    let events: Vec<_> = (0..1_000)
+        .map(|i| if i % 2 == 0 { message_event_sync() } else { aliases_event_sync() })
+        .collect();
+    let json = serde_json::to_string(&events).unwrap();
+
+    let events = serde_json::from_str::<Vec<AnySyncRoomEvent>>(&json).unwrap();
+
+    assert_eq!(events.len(), 1_000);
+    for (i, event) in events.iter().enumerate() {
+        if i % 2 == 0 {
+            assert_matches!(event, AnySyncRoomEvent::Message(AnySyncMessageEvent::RoomMessage(_)));
+        } else {
+            assert_matches!(event, AnySyncRoomEvent::State(AnySyncStateEvent::RoomAliases(_)));
+        }
+    }
+}
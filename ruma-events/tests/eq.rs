@@ -0,0 +1,65 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use js_int::int;
+use ruma_events::{
+    room::{
+        member::{MemberEvent, MemberEventContent, MembershipState},
+        message::MessageEventContent,
+    },
+    MessageEvent, Unsigned,
+};
+use ruma_identifiers::{event_id, room_id, user_id};
+use serde_json::{from_value as from_json_value, json};
+
+fn member_event() -> MemberEvent {
+    MemberEvent {
+        content: MemberEventContent {
+            avatar_url: None,
+            displayname: Some("Alice".into()),
+            is_direct: None,
+            membership: MembershipState::Join,
+            reason: None,
+            third_party_invite: None,
+            join_authorised_via_users_server: None,
+        },
+        event_id: event_id!("$h29iv0s8:example.com"),
+        origin_server_ts: UNIX_EPOCH + Duration::from_millis(1),
+        prev_content: None,
+        room_id: room_id!("!roomid:example.com"),
+        sender: user_id!("@alice:example.com"),
+        state_key: "@alice:example.com".into(),
+        unsigned: Unsigned { age: Some(int!(10)), transaction_id: None },
+    }
+}
+
+#[test]
+fn identical_member_events_are_equal() {
+    assert_eq!(member_event(), member_event());
+}
+
+#[test]
+fn member_events_with_different_content_are_not_equal() {
+    let mut other = member_event();
+    other.content.membership = MembershipState::Leave;
+
+    assert_ne!(member_event(), other);
+}
+
+#[test]
+fn parsed_message_events() {
+    let message_event = |body: &str| {
+        from_json_value::<MessageEvent<MessageEventContent>>(json!({
+            "content": { "body": body, "msgtype": "m.text" },
+            "event_id": "$h29iv0s8:example.com",
+            "origin_server_ts": 1,
+            "room_id": "!roomid:example.com",
+            "sender": "@alice:example.com",
+            "type": "m.room.message"
+        }))
+        .unwrap()
+    };
+
+    assert_eq!(message_event("Hello"), message_event("Hello"));
+    // Events with the same ID but different content are not equal.
+    assert_ne!(message_event("Hello"), message_event("Goodbye"));
+}
@@ -2,14 +2,20 @@ use std::time::{Duration, UNIX_EPOCH};
 
 use js_int::UInt;
 use matches::assert_matches;
-use ruma_identifiers::{event_id, room_id, user_id};
+use ruma_identifiers::{event_id, room_id, user_id, RoomVersionId};
 use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
 use ruma_events::{
-    call::{answer::AnswerEventContent, SessionDescription, SessionDescriptionType},
-    room::{ImageInfo, ThumbnailInfo},
+    call::{answer::AnswerEventContent, SessionDescription, SessionDescriptionType, VoipVersion},
+    room::{
+        member::MembershipState,
+        redaction::{RedactionEvent, RedactionEventContent, SyncRedactionEvent},
+        ImageInfo, ThumbnailInfo,
+    },
     sticker::StickerEventContent,
-    AnyMessageEvent, MessageEvent, Unsigned,
+    AnyBasicEvent, AnyEphemeralRoomEvent, AnyMessageEvent, AnyStateEvent, AnyStrippedStateEvent,
+    AnySyncEphemeralRoomEvent, AnySyncMessageEvent, AnySyncStateEvent, AnyToDeviceEvent,
+    MessageEvent, Unsigned,
 };
 
 #[test]
@@ -29,7 +35,7 @@ fn deserialize_message_event() {
                 "sdp": "Hello"
             },
             "call_id": "foofoo",
-            "version": 1
+            "version": 0
         },
         "event_id": "$h29iv0s8:example.com",
         "origin_server_ts": 1,
@@ -48,6 +54,7 @@ fn deserialize_message_event() {
                     sdp,
                 },
                 call_id,
+                party_id: None,
                 version,
             },
             event_id,
@@ -55,7 +62,7 @@ fn deserialize_message_event() {
             room_id,
             sender,
             unsigned,
-        }) if sdp == "Hello" && call_id == "foofoo" && version == UInt::new(1).unwrap()
+        }) if sdp == "Hello" && call_id == "foofoo" && version == VoipVersion::V0
             && event_id == event_id!("$h29iv0s8:example.com")
             && origin_server_ts == UNIX_EPOCH + Duration::from_millis(1)
             && room_id == room_id!("!roomid:room.com")
@@ -120,3 +127,249 @@ fn serialize_message_event() {
 
     assert_eq!(actual, expected);
 }
+
+#[test]
+fn message_event_downcast() {
+    let json_data = json!({
+        "content": {
+            "answer": {
+                "type": "answer",
+                "sdp": "Hello"
+            },
+            "call_id": "foofoo",
+            "version": 0
+        },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "room_id": "!roomid:room.com",
+        "sender": "@carl:example.com",
+        "type": "m.call.answer"
+    });
+    let event = from_json_value::<AnyMessageEvent>(json_data).unwrap();
+
+    assert!(event.as_room_message().is_none());
+    assert!(event.as_sticker().is_none());
+    assert_eq!(event.as_call_answer().unwrap().content.call_id, "foofoo");
+    assert!(event.clone().into_room_redaction().is_none());
+    assert_eq!(event.into_call_answer().unwrap().sender, user_id!("@carl:example.com"));
+}
+
+#[test]
+fn sync_state_event_downcast() {
+    let json_data = json!({
+        "content": {
+            "membership": "join"
+        },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "sender": "@carl:example.com",
+        "state_key": "@carl:example.com",
+        "type": "m.room.member"
+    });
+    let event = from_json_value::<AnySyncStateEvent>(json_data).unwrap();
+
+    assert!(event.as_room_name().is_none());
+    assert_eq!(event.as_room_member().unwrap().content.membership, MembershipState::Join);
+    assert!(event.clone().into_room_topic().is_none());
+    assert_eq!(event.into_room_member().unwrap().state_key, "@carl:example.com");
+}
+
+/// Calls every `as_*` and `into_*` method of an event enum on `$event`, which must be the variant
+/// that the first pair of methods downcasts to.
+macro_rules! assert_downcasts {
+    ($event:expr, ($as:ident, $into:ident), [$(($other_as:ident, $other_into:ident)),* $(,)?]) => {{
+        let event = $event;
+        assert!(event.$as().is_some());
+        $(
+            assert!(event.$other_as().is_none());
+            assert!(event.clone().$other_into().is_none());
+        )*
+        assert!(event.$into().is_some());
+    }};
+}
+
+macro_rules! assert_message_downcasts {
+    ($event:expr) => {
+        assert_downcasts!(
+            $event,
+            (as_call_answer, into_call_answer),
+            [
+                (as_call_invite, into_call_invite),
+                (as_call_hangup, into_call_hangup),
+                (as_call_candidates, into_call_candidates),
+                (as_call_negotiate, into_call_negotiate),
+                (as_call_reject, into_call_reject),
+                (as_call_select_answer, into_call_select_answer),
+                (as_room_encrypted, into_room_encrypted),
+                (as_room_message, into_room_message),
+                (as_room_message_feedback, into_room_message_feedback),
+                (as_room_redaction, into_room_redaction),
+                (as_sticker, into_sticker),
+            ]
+        )
+    };
+}
+
+macro_rules! assert_state_downcasts {
+    ($event:expr) => {
+        assert_downcasts!(
+            $event,
+            (as_room_member, into_room_member),
+            [
+                (as_room_aliases, into_room_aliases),
+                (as_room_avatar, into_room_avatar),
+                (as_room_canonical_alias, into_room_canonical_alias),
+                (as_room_create, into_room_create),
+                (as_room_encryption, into_room_encryption),
+                (as_room_guest_access, into_room_guest_access),
+                (as_room_history_visibility, into_room_history_visibility),
+                (as_room_join_rules, into_room_join_rules),
+                (as_room_name, into_room_name),
+                (as_room_pinned_events, into_room_pinned_events),
+                (as_room_power_levels, into_room_power_levels),
+                (as_room_server_acl, into_room_server_acl),
+                (as_room_third_party_invite, into_room_third_party_invite),
+                (as_room_tombstone, into_room_tombstone),
+                (as_room_topic, into_room_topic),
+                (as_space_child, into_space_child),
+                (as_space_parent, into_space_parent),
+            ]
+        )
+    };
+}
+
+fn redaction() -> RedactionEvent {
+    RedactionEvent {
+        content: RedactionEventContent { reason: None, redacts: None },
+        redacts: event_id!("$h29iv0s8:example.com"),
+        event_id: event_id!("$redaction:example.com"),
+        origin_server_ts: UNIX_EPOCH + Duration::from_millis(2),
+        room_id: room_id!("!roomid:room.com"),
+        sender: user_id!("@carl:example.com"),
+        unsigned: Unsigned::default(),
+    }
+}
+
+fn sync_redaction() -> SyncRedactionEvent {
+    let RedactionEvent { content, redacts, event_id, origin_server_ts, sender, unsigned, .. } =
+        redaction();
+    SyncRedactionEvent { content, redacts, event_id, origin_server_ts, sender, unsigned }
+}
+
+#[test]
+fn basic_and_to_device_event_downcasts() {
+    let basic = json!({ "content": {}, "type": "m.dummy" });
+    assert_downcasts!(
+        from_json_value::<AnyBasicEvent>(basic).unwrap(),
+        (as_dummy, into_dummy),
+        [
+            (as_direct, into_direct),
+            (as_ignored_user_list, into_ignored_user_list),
+            (as_presence, into_presence),
+            (as_push_rules, into_push_rules),
+            (as_room_key, into_room_key),
+            (as_secret_storage_default_key, into_secret_storage_default_key),
+            (as_tag, into_tag),
+        ]
+    );
+
+    let to_device = json!({ "content": {}, "sender": "@carl:example.com", "type": "m.dummy" });
+    assert_downcasts!(
+        from_json_value::<AnyToDeviceEvent>(to_device).unwrap(),
+        (as_dummy, into_dummy),
+        [
+            (as_room_key, into_room_key),
+            (as_room_key_request, into_room_key_request),
+            (as_forwarded_room_key, into_forwarded_room_key),
+            (as_key_verification_request, into_key_verification_request),
+            (as_key_verification_start, into_key_verification_start),
+            (as_key_verification_cancel, into_key_verification_cancel),
+            (as_key_verification_accept, into_key_verification_accept),
+            (as_key_verification_key, into_key_verification_key),
+            (as_key_verification_mac, into_key_verification_mac),
+            (as_room_encrypted, into_room_encrypted),
+        ]
+    );
+}
+
+#[test]
+fn ephemeral_event_downcasts() {
+    let mut json_data = json!({
+        "content": { "user_ids": ["@carl:example.com"] },
+        "room_id": "!roomid:room.com",
+        "type": "m.typing"
+    });
+
+    assert_downcasts!(
+        from_json_value::<AnyEphemeralRoomEvent>(json_data.clone()).unwrap(),
+        (as_typing, into_typing),
+        [(as_fully_read, into_fully_read), (as_receipt, into_receipt)]
+    );
+
+    json_data.as_object_mut().unwrap().remove("room_id");
+    assert_downcasts!(
+        from_json_value::<AnySyncEphemeralRoomEvent>(json_data).unwrap(),
+        (as_typing, into_typing),
+        [(as_fully_read, into_fully_read), (as_receipt, into_receipt)]
+    );
+}
+
+#[test]
+fn message_event_downcasts() {
+    let mut json_data = json!({
+        "content": {
+            "answer": {
+                "type": "answer",
+                "sdp": "Hello"
+            },
+            "call_id": "foofoo",
+            "version": 0
+        },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "room_id": "!roomid:room.com",
+        "sender": "@carl:example.com",
+        "type": "m.call.answer"
+    });
+
+    let event = from_json_value::<AnyMessageEvent>(json_data.clone()).unwrap();
+    assert_message_downcasts!(event.clone());
+    assert_message_downcasts!(event.redact(redaction(), RoomVersionId::Version6));
+
+    json_data.as_object_mut().unwrap().remove("room_id");
+    let event = from_json_value::<AnySyncMessageEvent>(json_data).unwrap();
+    assert_message_downcasts!(event.clone());
+    assert_message_downcasts!(event.redact(sync_redaction(), RoomVersionId::Version6));
+}
+
+#[test]
+fn state_event_downcasts() {
+    let mut json_data = json!({
+        "content": {
+            "membership": "join"
+        },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "room_id": "!roomid:room.com",
+        "sender": "@carl:example.com",
+        "state_key": "@carl:example.com",
+        "type": "m.room.member"
+    });
+
+    let event = from_json_value::<AnyStateEvent>(json_data.clone()).unwrap();
+    assert_state_downcasts!(event.clone());
+    assert_state_downcasts!(event.redact(redaction(), RoomVersionId::Version6));
+
+    let object = json_data.as_object_mut().unwrap();
+    object.remove("room_id");
+    let event = from_json_value::<AnySyncStateEvent>(json_data.clone()).unwrap();
+    assert_state_downcasts!(event.clone());
+    assert_state_downcasts!(event.redact(sync_redaction(), RoomVersionId::Version6));
+
+    let object = json_data.as_object_mut().unwrap();
+    object.remove("event_id");
+    object.remove("origin_server_ts");
+    let event = from_json_value::<AnyStrippedStateEvent>(json_data).unwrap();
+    assert_state_downcasts!(event.clone());
+    assert_state_downcasts!(event.redact(sync_redaction(), RoomVersionId::Version6));
+}
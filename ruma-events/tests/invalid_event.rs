@@ -0,0 +1,70 @@
+use ruma_common::Raw;
+use ruma_events::{deserialize_events, AnyStateEvent, RawEventExt};
+use serde_json::{from_value as from_json_value, json, Value as JsonValue};
+
+fn state_events() -> JsonValue {
+    json!([
+        {
+            "content": { "name": "Room" },
+            "event_id": "$h29iv0s8:example.com",
+            "origin_server_ts": 1,
+            "room_id": "!n8f893n9:example.com",
+            "sender": "@carl:example.com",
+            "state_key": "",
+            "type": "m.room.name"
+        },
+        {
+            "content": { "membership": true },
+            "event_id": "$h29iv0s9:example.com",
+            "origin_server_ts": 2,
+            "room_id": "!n8f893n9:example.com",
+            "sender": "@carl:example.com",
+            "state_key": "@carl:example.com",
+            "type": "m.room.member"
+        },
+        {
+            "content": { "topic": "Test" },
+            "event_id": "$h29iv0s0:example.com",
+            "origin_server_ts": 3,
+            "room_id": "!n8f893n9:example.com",
+            "sender": "@carl:example.com",
+            "state_key": "",
+            "type": "m.room.topic"
+        }
+    ])
+}
+
+#[test]
+fn invalid_event_in_list() {
+    let events = from_json_value::<Vec<Raw<AnyStateEvent>>>(state_events()).unwrap();
+
+    let err = deserialize_events(&events).unwrap_err();
+    assert_eq!(err.index(), Some(1));
+    assert_eq!(err.event_type(), Some("m.room.member"));
+    assert!(err.message().contains("true"));
+    assert_eq!(serde_json::from_str::<JsonValue>(err.json().get()).unwrap(), state_events()[1]);
+
+    let message = err.to_string();
+    assert!(message.starts_with("invalid event of type `m.room.member` at index 1: "));
+    assert!(message.contains("true"));
+    assert!(!message.contains('\n'));
+}
+
+#[test]
+fn invalid_single_event() {
+    let event = from_json_value::<Raw<AnyStateEvent>>(state_events()[1].clone()).unwrap();
+
+    let err = event.deserialize_event().unwrap_err();
+    assert_eq!(err.index(), None);
+    assert_eq!(err.event_type(), Some("m.room.member"));
+    assert!(err.to_string().starts_with("invalid event of type `m.room.member`: "));
+}
+
+#[test]
+fn valid_events_in_list() {
+    let mut json = state_events();
+    json.as_array_mut().unwrap().remove(1);
+    let events = from_json_value::<Vec<Raw<AnyStateEvent>>>(json).unwrap();
+
+    assert_eq!(deserialize_events(&events).unwrap().len(), 2);
+}
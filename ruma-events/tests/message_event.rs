@@ -1,16 +1,24 @@
 use std::time::{Duration, UNIX_EPOCH};
 
-use js_int::{uint, UInt};
+use js_int::{int, UInt};
 use matches::assert_matches;
 use ruma_common::Raw;
 use ruma_events::{
-    call::{answer::AnswerEventContent, SessionDescription, SessionDescriptionType},
-    room::{ImageInfo, ThumbnailInfo},
+    call::{
+        answer::AnswerEventContent, reject::RejectEventContent, SessionDescription,
+        SessionDescriptionType, VoipVersion,
+    },
+    room::{
+        message::{MessageEventContent, TextMessageEventContent},
+        ImageInfo, ThumbnailInfo,
+    },
     sticker::StickerEventContent,
-    AnyMessageEventContent, AnySyncMessageEvent, MessageEvent, RawExt, Unsigned,
+    AnyMessageEventContent, AnySyncMessageEvent, MessageEvent, RawExt, SyncMessageEvent, Unsigned,
 };
 use ruma_identifiers::{event_id, room_id, user_id};
-use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+use serde_json::{
+    from_value as from_json_value, json, to_value as to_json_value, Value as JsonValue,
+};
 
 #[test]
 fn message_serialize_sticker() {
@@ -77,7 +85,7 @@ fn deserialize_message_call_answer_content() {
             "sdp": "Hello"
         },
         "call_id": "foofoo",
-        "version": 1
+        "version": 0
     });
 
     assert_matches!(
@@ -91,8 +99,9 @@ fn deserialize_message_call_answer_content() {
                 sdp,
             },
             call_id,
+            party_id: None,
             version,
-        }) if sdp == "Hello" && call_id == "foofoo" && version == UInt::new(1).unwrap()
+        }) if sdp == "Hello" && call_id == "foofoo" && version == VoipVersion::V0
     );
 }
 
@@ -105,7 +114,7 @@ fn deserialize_message_call_answer() {
                 "sdp": "Hello"
             },
             "call_id": "foofoo",
-            "version": 1
+            "version": 0
         },
         "event_id": "$h29iv0s8:example.com",
         "origin_server_ts": 1,
@@ -126,6 +135,7 @@ fn deserialize_message_call_answer() {
                     sdp,
                 },
                 call_id,
+                party_id: None,
                 version,
             }),
             event_id,
@@ -133,7 +143,7 @@ fn deserialize_message_call_answer() {
             room_id,
             sender,
             unsigned,
-        } if sdp == "Hello" && call_id == "foofoo" && version == UInt::new(1).unwrap()
+        } if sdp == "Hello" && call_id == "foofoo" && version == VoipVersion::V0
             && event_id == event_id!("$h29iv0s8:example.com")
             && origin_server_ts == UNIX_EPOCH + Duration::from_millis(1)
             && room_id == room_id!("!roomid:room.com")
@@ -142,6 +152,54 @@ fn deserialize_message_call_answer() {
     );
 }
 
+#[test]
+fn message_event_roundtrip_preserves_origin_server_ts() {
+    let json_data = json!({
+        "content": {
+            "answer": {
+                "type": "answer",
+                "sdp": "Hello"
+            },
+            "call_id": "foofoo",
+            "version": 0
+        },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1_432_804_485_886_u64,
+        "room_id": "!roomid:room.com",
+        "sender": "@carl:example.com",
+        "type": "m.call.answer"
+    });
+
+    let event = from_json_value::<Raw<MessageEvent<AnyMessageEventContent>>>(json_data.clone())
+        .unwrap()
+        .deserialize()
+        .unwrap();
+    assert_eq!(event.origin_server_ts, UNIX_EPOCH + Duration::from_millis(1_432_804_485_886));
+    assert_eq!(to_json_value(&event).unwrap(), json_data);
+}
+
+#[test]
+fn message_event_origin_server_ts_before_epoch() {
+    let event = MessageEvent {
+        content: AnyMessageEventContent::CallAnswer(AnswerEventContent {
+            answer: SessionDescription {
+                session_type: SessionDescriptionType::Answer,
+                sdp: "Hello".into(),
+            },
+            call_id: "foofoo".into(),
+            party_id: None,
+            version: VoipVersion::V0,
+        }),
+        event_id: event_id!("$h29iv0s8:example.com"),
+        origin_server_ts: UNIX_EPOCH - Duration::from_millis(1),
+        room_id: room_id!("!roomid:room.com"),
+        sender: user_id!("@carl:example.com"),
+        unsigned: Unsigned::default(),
+    };
+
+    assert!(to_json_value(&event).is_err());
+}
+
 #[test]
 fn deserialize_message_sticker() {
     let json_data = json!({
@@ -230,7 +288,7 @@ fn deserialize_message_then_convert_to_full() {
                 "sdp": "Hello"
             },
             "call_id": "foofoo",
-            "version": 1
+            "version": 0
         },
         "event_id": "$h29iv0s8:example.com",
         "origin_server_ts": 1,
@@ -257,6 +315,7 @@ fn deserialize_message_then_convert_to_full() {
                     sdp,
                 },
                 call_id,
+                party_id: None,
                 version,
             }),
             event_id,
@@ -266,7 +325,7 @@ fn deserialize_message_then_convert_to_full() {
             unsigned,
         } if sdp == "Hello"
             && call_id == "foofoo"
-            && version == uint!(1)
+            && version == VoipVersion::V0
             && event_id == "$h29iv0s8:example.com"
             && origin_server_ts == UNIX_EPOCH + Duration::from_millis(1)
             && room_id == "!roomid:room.com"
@@ -274,3 +333,99 @@ fn deserialize_message_then_convert_to_full() {
             && unsigned.is_empty()
     );
 }
+
+fn sync_message_event_with_unsigned(
+    unsigned: JsonValue,
+) -> SyncMessageEvent<AnyMessageEventContent> {
+    from_json_value(json!({
+        "content": { "body": "Hello", "msgtype": "m.text" },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1_000_000,
+        "sender": "@carl:example.com",
+        "type": "m.room.message",
+        "unsigned": unsigned
+    }))
+    .unwrap()
+}
+
+#[test]
+fn transaction_id_for_local_echo() {
+    let event = sync_message_event_with_unsigned(json!({ "transaction_id": "m1234.0" }));
+    assert_eq!(event.transaction_id(), Some("m1234.0"));
+
+    let event = sync_message_event_with_unsigned(json!({}));
+    assert_eq!(event.transaction_id(), None);
+}
+
+#[test]
+fn timestamp_adjusted_by_age() {
+    let now = UNIX_EPOCH + Duration::from_secs(5_000);
+
+    let event = sync_message_event_with_unsigned(json!({ "age": 1_500 }));
+    assert_eq!(event.unsigned.age, Some(int!(1_500)));
+    assert_eq!(event.timestamp_adjusted(now), now - Duration::from_millis(1_500));
+
+    // Without an age, the origin server's timestamp is used.
+    let event = sync_message_event_with_unsigned(json!({}));
+    assert_eq!(event.timestamp_adjusted(now), UNIX_EPOCH + Duration::from_millis(1_000_000));
+
+    // Negative ages are clamped to zero.
+    let event = sync_message_event_with_unsigned(json!({ "age": -250 }));
+    assert_eq!(event.timestamp_adjusted(now), now);
+}
+
+#[test]
+fn content_json() {
+    let event = SyncMessageEvent::new(
+        MessageEventContent::Text(TextMessageEventContent::html("Hello", "<b>Hello</b>")),
+        event_id!("$h29iv0s8:example.com"),
+        user_id!("@carl:example.com"),
+        UNIX_EPOCH + Duration::from_millis(1),
+    );
+
+    assert_eq!(
+        event.content_json().unwrap(),
+        json!({
+            "body": "Hello",
+            "format": "org.matrix.custom.html",
+            "formatted_body": "<b>Hello</b>",
+            "msgtype": "m.text"
+        })
+    );
+}
+
+#[test]
+fn clone_message_event() {
+    let event = MessageEvent::new(
+        MessageEventContent::Text(TextMessageEventContent::plain("Hello")),
+        event_id!("$h29iv0s8:example.com"),
+        user_id!("@carl:example.com"),
+        UNIX_EPOCH + Duration::from_millis(1),
+        room_id!("!roomid:room.com"),
+    );
+
+    assert_eq!(event.clone(), event);
+}
+
+#[test]
+fn call_reject_roundtrip() {
+    let event = MessageEvent::new(
+        RejectEventContent::new("foofoo".into(), "party".into()),
+        event_id!("$h29iv0s8:example.com"),
+        user_id!("@carl:example.com"),
+        UNIX_EPOCH + Duration::from_millis(1),
+        room_id!("!roomid:room.com"),
+    );
+
+    let json_data = json!({
+        "content": { "call_id": "foofoo", "party_id": "party", "version": "1" },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "room_id": "!roomid:room.com",
+        "sender": "@carl:example.com",
+        "type": "m.call.reject"
+    });
+
+    assert_eq!(to_json_value(&event).unwrap(), json_data);
+    assert_eq!(from_json_value::<MessageEvent<RejectEventContent>>(json_data).unwrap(), event);
+}
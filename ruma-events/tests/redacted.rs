@@ -24,7 +24,7 @@ fn sync_unsigned() -> RedactedSyncUnsigned {
     // with `RedactedContent` instead of failing to deserialize according
     // to the event type string.
     unsigned.redacted_because = Some(Box::new(SyncRedactionEvent {
-        content: RedactionEventContent { reason: Some("redacted because".into()) },
+        content: RedactionEventContent { reason: Some("redacted because".into()), redacts: None },
         redacts: event_id!("$h29iv0s8:example.com"),
         event_id: event_id!("$h29iv0s8:example.com"),
         origin_server_ts: UNIX_EPOCH + Duration::from_millis(1),
@@ -38,7 +38,7 @@ fn sync_unsigned() -> RedactedSyncUnsigned {
 fn full_unsigned() -> RedactedUnsigned {
     let mut unsigned = RedactedUnsigned::default();
     unsigned.redacted_because = Some(Box::new(RedactionEvent {
-        content: RedactionEventContent { reason: Some("redacted because".into()) },
+        content: RedactionEventContent { reason: Some("redacted because".into()), redacts: None },
         room_id: room_id!("!roomid:room.com"),
         redacts: event_id!("$h29iv0s8:example.com"),
         event_id: event_id!("$h29iv0s8:example.com"),
@@ -186,7 +186,7 @@ fn redacted_deserialize_any_room_sync() {
     // to return early with `RedactedContent` instead of failing to deserialize according
     // to the event type string.
     unsigned.redacted_because = Some(Box::new(RedactionEvent {
-        content: RedactionEventContent { reason: Some("redacted because".into()) },
+        content: RedactionEventContent { reason: Some("redacted because".into()), redacts: None },
         redacts: event_id!("$h29iv0s8:example.com"),
         event_id: event_id!("$h29iv0s8:example.com"),
         origin_server_ts: UNIX_EPOCH + Duration::from_millis(1),
@@ -220,6 +220,42 @@ fn redacted_deserialize_any_room_sync() {
     )
 }
 
+#[test]
+fn redacted_because_accessor() {
+    let redacted = json!({
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "sender": "@alice:example.com",
+        "unsigned": {
+            "redacted_because": {
+                "content": { "reason": "spam" },
+                "event_id": "$redaction:example.com",
+                "origin_server_ts": 2,
+                "redacts": "$h29iv0s8:example.com",
+                "sender": "@carl:example.com",
+                "type": "m.room.redaction"
+            }
+        },
+        "type": "m.room.message"
+    });
+
+    let event = from_json_value::<AnySyncRoomEvent>(redacted).unwrap();
+    let redaction = event.redacted_because().unwrap();
+    assert_eq!(redaction.sender, user_id!("@carl:example.com"));
+    assert_eq!(redaction.content.reason.as_deref(), Some("spam"));
+    assert_eq!(redaction.redacts, event_id!("$h29iv0s8:example.com"));
+
+    let message = json!({
+        "content": { "body": "hello", "msgtype": "m.text" },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "room_id": "!roomid:room.com",
+        "sender": "@alice:example.com",
+        "type": "m.room.message"
+    });
+    assert!(from_json_value::<AnyRoomEvent>(message).unwrap().redacted_because().is_none());
+}
+
 #[test]
 fn redacted_state_event_deserialize() {
     let unsigned = sync_unsigned();
@@ -334,7 +370,7 @@ fn redact_method_properly_redacts() {
     });
 
     let redaction = RedactionEvent {
-        content: RedactionEventContent { reason: Some("redacted because".into()) },
+        content: RedactionEventContent { reason: Some("redacted because".into()), redacts: None },
         redacts: event_id!("$143273582443PhrSn:example.com"),
         event_id: event_id!("$h29iv0s8:example.com"),
         origin_server_ts: UNIX_EPOCH + Duration::from_millis(1),
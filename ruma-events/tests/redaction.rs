@@ -1,12 +1,15 @@
-use std::time::{Duration, UNIX_EPOCH};
+use std::{
+    convert::TryFrom,
+    time::{Duration, UNIX_EPOCH},
+};
 
 use matches::assert_matches;
 use ruma_common::Raw;
 use ruma_events::{
-    room::redaction::{RedactionEvent, RedactionEventContent},
+    room::redaction::{RedactionEvent, RedactionEventContent, SyncRedactionEvent},
     AnyMessageEvent, Unsigned,
 };
-use ruma_identifiers::{event_id, room_id, user_id};
+use ruma_identifiers::{event_id, room_id, user_id, RoomVersionId};
 use serde_json::{
     from_value as from_json_value, json, to_value as to_json_value, Value as JsonValue,
 };
@@ -28,7 +31,7 @@ fn redaction() -> JsonValue {
 #[test]
 fn serialize_redaction() {
     let aliases_event = RedactionEvent {
-        content: RedactionEventContent { reason: Some("being a turd".into()) },
+        content: RedactionEventContent { reason: Some("being a turd".into()), redacts: None },
         redacts: event_id!("$nomore:example.com"),
         event_id: event_id!("$h29iv0s8:example.com"),
         origin_server_ts: UNIX_EPOCH + Duration::from_millis(1),
@@ -53,7 +56,7 @@ fn deserialize_redaction() {
             .deserialize()
             .unwrap(),
         AnyMessageEvent::RoomRedaction(RedactionEvent {
-            content: RedactionEventContent { reason: Some(reas) },
+            content: RedactionEventContent { reason: Some(reas), .. },
             redacts,
             event_id,
             origin_server_ts,
@@ -69,3 +72,97 @@ fn deserialize_redaction() {
             && unsigned.is_empty()
     );
 }
+
+#[test]
+fn deserialize_redaction_with_redacts_in_content() {
+    let json_data = json!({
+        "content": {
+            "reason": "being a turd",
+            "redacts": "$nomore:example.com"
+        },
+        "event_id": "$h29iv0s8:example.com",
+        "sender": "@carl:example.com",
+        "origin_server_ts": 1,
+        "room_id": "!roomid:room.com",
+        "type": "m.room.redaction"
+    });
+
+    let event = from_json_value::<RedactionEvent>(json_data).unwrap();
+    assert_eq!(event.redacts(), &event_id!("$nomore:example.com"));
+    assert_eq!(event.content.redacts, Some(event_id!("$nomore:example.com")));
+    assert_eq!(event.content.reason.as_deref(), Some("being a turd"));
+}
+
+#[test]
+fn deserialize_sync_redaction_in_both_layouts() {
+    let top_level = json!({
+        "content": { "reason": "being a turd" },
+        "redacts": "$nomore:example.com",
+        "event_id": "$h29iv0s8:example.com",
+        "sender": "@carl:example.com",
+        "origin_server_ts": 1,
+        "type": "m.room.redaction"
+    });
+    let in_content = json!({
+        "content": { "reason": "being a turd", "redacts": "$nomore:example.com" },
+        "event_id": "$h29iv0s8:example.com",
+        "sender": "@carl:example.com",
+        "origin_server_ts": 1,
+        "type": "m.room.redaction"
+    });
+
+    let event = from_json_value::<SyncRedactionEvent>(top_level).unwrap();
+    assert_eq!(event.redacts(), &event_id!("$nomore:example.com"));
+    assert_eq!(event.content.redacts, None);
+
+    let event = from_json_value::<SyncRedactionEvent>(in_content.clone()).unwrap();
+    assert_eq!(event.redacts(), &event_id!("$nomore:example.com"));
+    assert_eq!(
+        event.to_json_for_room_version(&RoomVersionId::try_from("11").unwrap()).unwrap(),
+        in_content
+    );
+}
+
+#[test]
+fn deserialize_redaction_top_level_redacts() {
+    let event = from_json_value::<RedactionEvent>(redaction()).unwrap();
+    assert_eq!(event.redacts(), &event_id!("$nomore:example.com"));
+    assert_eq!(event.content.redacts, None);
+}
+
+#[test]
+fn deserialize_redaction_without_redacts() {
+    let json_data = json!({
+        "content": {
+            "reason": "being a turd"
+        },
+        "event_id": "$h29iv0s8:example.com",
+        "sender": "@carl:example.com",
+        "origin_server_ts": 1,
+        "room_id": "!roomid:room.com",
+        "type": "m.room.redaction"
+    });
+
+    assert!(from_json_value::<RedactionEvent>(json_data).is_err());
+}
+
+#[test]
+fn serialize_redaction_for_room_version() {
+    let event = from_json_value::<RedactionEvent>(redaction()).unwrap();
+
+    assert_eq!(event.to_json_for_room_version(&RoomVersionId::Version6).unwrap(), redaction());
+    assert_eq!(
+        event.to_json_for_room_version(&RoomVersionId::try_from("11").unwrap()).unwrap(),
+        json!({
+            "content": {
+                "reason": "being a turd",
+                "redacts": "$nomore:example.com"
+            },
+            "event_id": "$h29iv0s8:example.com",
+            "sender": "@carl:example.com",
+            "origin_server_ts": 1,
+            "room_id": "!roomid:room.com",
+            "type": "m.room.redaction"
+        })
+    );
+}
@@ -0,0 +1,401 @@
+//! Wire format regression tests: one JSON fixture per event module is deserialized and serialized
+//! again, and has to come out unchanged.
+
+use std::fmt::Debug;
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{
+    from_value as from_json_value, json, to_value as to_json_value, Value as JsonValue,
+};
+
+use ruma_events::{
+    presence::PresenceEvent, AnyBasicEvent, AnyEphemeralRoomEvent, AnyMessageEvent, AnyStateEvent,
+    AnyToDeviceEvent,
+};
+
+fn assert_roundtrip<T>(json: JsonValue)
+where
+    T: Debug + DeserializeOwned + Serialize,
+{
+    let event = from_json_value::<T>(json.clone()).unwrap();
+    assert_eq!(to_json_value(&event).unwrap(), json, "{:?}", event);
+}
+
+fn message_event(event_type: &str, content: JsonValue) -> JsonValue {
+    json!({
+        "content": content,
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "room_id": "!roomid:room.com",
+        "sender": "@carl:example.com",
+        "type": event_type
+    })
+}
+
+fn state_event(event_type: &str, state_key: &str, content: JsonValue) -> JsonValue {
+    let mut json = message_event(event_type, content);
+    json["state_key"] = state_key.into();
+    json
+}
+
+fn to_device_event(event_type: &str, content: JsonValue) -> JsonValue {
+    json!({
+        "content": content,
+        "sender": "@carl:example.com",
+        "type": event_type
+    })
+}
+
+#[test]
+fn basic_events() {
+    assert_roundtrip::<AnyBasicEvent>(json!({
+        "content": { "@bob:example.com": ["!roomid:room.com"] },
+        "type": "m.direct"
+    }));
+    assert_roundtrip::<AnyBasicEvent>(json!({
+        "content": {},
+        "type": "m.dummy"
+    }));
+    assert_roundtrip::<AnyBasicEvent>(json!({
+        "content": { "ignored_users": { "@bob:example.com": {} } },
+        "type": "m.ignored_user_list"
+    }));
+    assert_roundtrip::<AnyBasicEvent>(json!({
+        "content": {
+            "global": {
+                "content": [],
+                "override": [],
+                "room": [],
+                "sender": [],
+                "underride": []
+            }
+        },
+        "type": "m.push_rules"
+    }));
+    assert_roundtrip::<AnyBasicEvent>(json!({
+        "content": {
+            "algorithm": "m.megolm.v1.aes-sha2",
+            "room_id": "!roomid:room.com",
+            "session_id": "SessId",
+            "session_key": "SessKey"
+        },
+        "type": "m.room_key"
+    }));
+    assert_roundtrip::<AnyBasicEvent>(json!({
+        "content": { "key": "bmur2d9ypPUH1msSwCxQOJkuKRmJI55e" },
+        "type": "m.secret_storage.default_key"
+    }));
+    assert_roundtrip::<AnyBasicEvent>(json!({
+        "content": { "algorithm": "m.secret_storage.v1.aes-hmac-sha2", "name": "Recovery key" },
+        "type": "m.secret_storage.key.bmur2d9ypPUH1msSwCxQOJkuKRmJI55e"
+    }));
+    assert_roundtrip::<AnyBasicEvent>(json!({
+        "content": { "tags": { "u.work": { "order": 0.25 } } },
+        "type": "m.tag"
+    }));
+}
+
+#[test]
+fn presence_event() {
+    assert_roundtrip::<PresenceEvent>(json!({
+        "content": {
+            "avatar_url": "mxc://localhost:wefuiwegh8742w",
+            "currently_active": false,
+            "displayname": "Carl",
+            "last_active_ago": 2_478_593,
+            "presence": "online"
+        },
+        "sender": "@carl:example.com",
+        "type": "m.presence"
+    }));
+}
+
+#[test]
+fn ephemeral_room_events() {
+    assert_roundtrip::<AnyEphemeralRoomEvent>(json!({
+        "content": { "event_id": "$h29iv0s8:example.com" },
+        "room_id": "!roomid:room.com",
+        "type": "m.fully_read"
+    }));
+    assert_roundtrip::<AnyEphemeralRoomEvent>(json!({
+        "content": {
+            "$h29iv0s8:example.com": {
+                "m.read": { "@carl:example.com": { "ts": 1 } }
+            }
+        },
+        "room_id": "!roomid:room.com",
+        "type": "m.receipt"
+    }));
+    assert_roundtrip::<AnyEphemeralRoomEvent>(json!({
+        "content": { "user_ids": ["@carl:example.com"] },
+        "room_id": "!roomid:room.com",
+        "type": "m.typing"
+    }));
+}
+
+#[test]
+fn message_events() {
+    assert_roundtrip::<AnyMessageEvent>(message_event(
+        "m.call.answer",
+        json!({
+            "answer": { "type": "answer", "sdp": "Hello" },
+            "call_id": "foofoo",
+            "version": 0
+        }),
+    ));
+    assert_roundtrip::<AnyMessageEvent>(message_event(
+        "m.call.candidates",
+        json!({
+            "call_id": "foofoo",
+            "candidates": [{ "candidate": "candidate:1", "sdpMid": "audio", "sdpMLineIndex": 0 }],
+            "version": 0
+        }),
+    ));
+    assert_roundtrip::<AnyMessageEvent>(message_event(
+        "m.call.hangup",
+        json!({ "call_id": "foofoo", "party_id": "party", "version": "1", "reason": "ice_failed" }),
+    ));
+    assert_roundtrip::<AnyMessageEvent>(message_event(
+        "m.call.invite",
+        json!({
+            "call_id": "foofoo",
+            "lifetime": 30000,
+            "offer": { "type": "offer", "sdp": "Hello" },
+            "version": 0
+        }),
+    ));
+    assert_roundtrip::<AnyMessageEvent>(message_event(
+        "m.call.negotiate",
+        json!({
+            "call_id": "foofoo",
+            "party_id": "party",
+            "lifetime": 10000,
+            "description": { "type": "offer", "sdp": "Hello" },
+            "version": "1"
+        }),
+    ));
+    assert_roundtrip::<AnyMessageEvent>(message_event(
+        "m.call.reject",
+        json!({ "call_id": "foofoo", "party_id": "party", "version": "1" }),
+    ));
+    assert_roundtrip::<AnyMessageEvent>(message_event(
+        "m.call.select_answer",
+        json!({
+            "call_id": "foofoo",
+            "party_id": "party",
+            "selected_party_id": "other_party",
+            "version": "1"
+        }),
+    ));
+    assert_roundtrip::<AnyMessageEvent>(message_event(
+        "m.room.encrypted",
+        json!({
+            "algorithm": "m.megolm.v1.aes-sha2",
+            "ciphertext": "ciphertext",
+            "sender_key": "sender_key",
+            "device_id": "device_id",
+            "session_id": "session_id"
+        }),
+    ));
+    assert_roundtrip::<AnyMessageEvent>(message_event(
+        "m.room.message",
+        json!({ "body": "Hello", "msgtype": "m.text" }),
+    ));
+    assert_roundtrip::<AnyMessageEvent>(message_event(
+        "m.room.message.feedback",
+        json!({ "target_event_id": "$h29iv0s8:example.com", "type": "read" }),
+    ));
+    let mut redaction = message_event("m.room.redaction", json!({ "reason": "Spamming" }));
+    redaction["redacts"] = "$nomore:example.com".into();
+    assert_roundtrip::<AnyMessageEvent>(redaction);
+    assert_roundtrip::<AnyMessageEvent>(message_event(
+        "m.sticker",
+        json!({
+            "body": "Hello",
+            "info": { "h": 423, "mimetype": "image/png", "size": 84242, "w": 1011 },
+            "url": "mxc://matrix.org/rnsldl8srs98IRrs"
+        }),
+    ));
+}
+
+#[test]
+fn state_events() {
+    assert_roundtrip::<AnyStateEvent>(state_event(
+        "m.room.aliases",
+        "room.com",
+        json!({ "aliases": ["#somewhere:room.com"] }),
+    ));
+    assert_roundtrip::<AnyStateEvent>(state_event(
+        "m.room.avatar",
+        "",
+        json!({
+            "info": { "h": 398, "mimetype": "image/jpeg", "size": 31037, "w": 394 },
+            "url": "mxc://matrix.org/JWEIFJgwEIhweiWJE"
+        }),
+    ));
+    assert_roundtrip::<AnyStateEvent>(state_event(
+        "m.room.canonical_alias",
+        "",
+        json!({ "alias": "#somewhere:room.com" }),
+    ));
+    assert_roundtrip::<AnyStateEvent>(state_event(
+        "m.room.create",
+        "",
+        json!({
+            "creator": "@carl:example.com",
+            "m.federate": false,
+            "room_version": "4",
+            "predecessor": {
+                "room_id": "!oldroom:room.com",
+                "event_id": "$h29iv0s8:example.com"
+            }
+        }),
+    ));
+    assert_roundtrip::<AnyStateEvent>(state_event(
+        "m.room.encryption",
+        "",
+        json!({ "algorithm": "m.megolm.v1.aes-sha2", "rotation_period_ms": 604_800_000 }),
+    ));
+    assert_roundtrip::<AnyStateEvent>(state_event(
+        "m.room.guest_access",
+        "",
+        json!({ "guest_access": "can_join" }),
+    ));
+    assert_roundtrip::<AnyStateEvent>(state_event(
+        "m.room.history_visibility",
+        "",
+        json!({ "history_visibility": "shared" }),
+    ));
+    assert_roundtrip::<AnyStateEvent>(state_event(
+        "m.room.join_rules",
+        "",
+        json!({ "join_rule": "invite" }),
+    ));
+    assert_roundtrip::<AnyStateEvent>(state_event(
+        "m.room.member",
+        "@carl:example.com",
+        json!({ "displayname": "Carl", "is_direct": true, "membership": "join" }),
+    ));
+    assert_roundtrip::<AnyStateEvent>(state_event(
+        "m.room.name",
+        "",
+        json!({ "name": "The Room" }),
+    ));
+    assert_roundtrip::<AnyStateEvent>(state_event(
+        "m.room.pinned_events",
+        "",
+        json!({ "pinned": ["$h29iv0s8:example.com"] }),
+    ));
+    assert_roundtrip::<AnyStateEvent>(state_event(
+        "m.room.power_levels",
+        "",
+        json!({ "ban": 75, "users": { "@carl:example.com": 100 } }),
+    ));
+    assert_roundtrip::<AnyStateEvent>(state_event(
+        "m.room.server_acl",
+        "",
+        json!({ "allow_ip_literals": false, "allow": ["*"], "deny": ["evil.com"] }),
+    ));
+    assert_roundtrip::<AnyStateEvent>(state_event(
+        "m.room.third_party_invite",
+        "token",
+        json!({
+            "display_name": "Carl",
+            "key_validity_url": "https://example.com/isvalid",
+            "public_key": "abc123"
+        }),
+    ));
+    assert_roundtrip::<AnyStateEvent>(state_event(
+        "m.room.tombstone",
+        "",
+        json!({ "body": "This room has moved", "replacement_room": "!newroom:room.com" }),
+    ));
+    assert_roundtrip::<AnyStateEvent>(state_event("m.room.topic", "", json!({ "topic": "Hello" })));
+    assert_roundtrip::<AnyStateEvent>(state_event(
+        "m.space.child",
+        "!child:example.com",
+        json!({ "via": ["example.com"], "order": "aaa", "suggested": true }),
+    ));
+    assert_roundtrip::<AnyStateEvent>(state_event(
+        "m.space.parent",
+        "!space:example.com",
+        json!({ "via": ["example.com"], "canonical": true }),
+    ));
+}
+
+#[test]
+fn to_device_events() {
+    assert_roundtrip::<AnyToDeviceEvent>(to_device_event(
+        "m.room_key_request",
+        json!({
+            "action": "request_cancellation",
+            "requesting_device_id": "ABCDEFG",
+            "request_id": "1"
+        }),
+    ));
+    assert_roundtrip::<AnyToDeviceEvent>(to_device_event(
+        "m.forwarded_room_key",
+        json!({
+            "algorithm": "m.megolm.v1.aes-sha2",
+            "room_id": "!roomid:room.com",
+            "sender_key": "SenderKey",
+            "session_id": "SessId",
+            "session_key": "SessKey",
+            "sender_claimed_ed25519_key": "ClaimedKey",
+            "forwarding_curve25519_key_chain": []
+        }),
+    ));
+    assert_roundtrip::<AnyToDeviceEvent>(to_device_event(
+        "m.key.verification.request",
+        json!({
+            "from_device": "ABCDEFG",
+            "transaction_id": "S0meUniqueAndOpaqueString",
+            "methods": ["m.sas.v1"],
+            "timestamp": 1_559_598_944_869_u64
+        }),
+    ));
+    assert_roundtrip::<AnyToDeviceEvent>(to_device_event(
+        "m.key.verification.start",
+        json!({
+            "from_device": "ABCDEFG",
+            "transaction_id": "S0meUniqueAndOpaqueString",
+            "method": "m.sas.v1",
+            "key_agreement_protocols": ["curve25519"],
+            "hashes": ["sha256"],
+            "message_authentication_codes": ["hkdf-hmac-sha256"],
+            "short_authentication_string": ["decimal", "emoji"]
+        }),
+    ));
+    assert_roundtrip::<AnyToDeviceEvent>(to_device_event(
+        "m.key.verification.accept",
+        json!({
+            "transaction_id": "S0meUniqueAndOpaqueString",
+            "method": "m.sas.v1",
+            "commitment": "fQpGIW1Snz+pwLZu6sTy2aHy",
+            "key_agreement_protocol": "curve25519",
+            "hash": "sha256",
+            "message_authentication_code": "hkdf-hmac-sha256",
+            "short_authentication_string": ["decimal", "emoji"]
+        }),
+    ));
+    assert_roundtrip::<AnyToDeviceEvent>(to_device_event(
+        "m.key.verification.cancel",
+        json!({
+            "transaction_id": "S0meUniqueAndOpaqueString",
+            "reason": "User rejected the key verification request",
+            "code": "m.user"
+        }),
+    ));
+    assert_roundtrip::<AnyToDeviceEvent>(to_device_event(
+        "m.key.verification.key",
+        json!({ "transaction_id": "S0meUniqueAndOpaqueString", "key": "fQpGIW1Snz+pwLZu6sTy2aHy" }),
+    ));
+    assert_roundtrip::<AnyToDeviceEvent>(to_device_event(
+        "m.key.verification.mac",
+        json!({
+            "transaction_id": "S0meUniqueAndOpaqueString",
+            "mac": { "ed25519:ABCDEFG": "mac" },
+            "keys": "keys"
+        }),
+    ));
+}
@@ -1,16 +1,21 @@
 use std::time::{Duration, UNIX_EPOCH};
 
-use js_int::UInt;
+use js_int::{int, UInt};
 use matches::assert_matches;
 use ruma_common::Raw;
 use ruma_events::{
-    room::{aliases::AliasesEventContent, avatar::AvatarEventContent, ImageInfo, ThumbnailInfo},
+    from_str_strict,
+    room::{
+        aliases::AliasesEventContent, avatar::AvatarEventContent, member::MembershipState,
+        power_levels::PowerLevelsEventContent, ImageInfo, ThumbnailInfo,
+    },
     AnyRoomEvent, AnyStateEvent, AnyStateEventContent, AnySyncStateEvent, RawExt, StateEvent,
     SyncStateEvent, Unsigned,
 };
 use ruma_identifiers::{event_id, room_alias_id, room_id, user_id};
 use serde_json::{
-    from_value as from_json_value, json, to_value as to_json_value, Value as JsonValue,
+    from_value as from_json_value, json, to_value as to_json_value,
+    value::to_raw_value as to_raw_json_value, Value as JsonValue,
 };
 
 fn aliases_event_with_prev_content() -> JsonValue {
@@ -272,7 +277,7 @@ fn deserialize_member_event_with_top_level_membership_field() {
                 content,
                 event_id,
                 origin_server_ts,
-                prev_content: None,
+                prev_content: Some(prev_content),
                 sender,
                 ..
             }
@@ -280,6 +285,7 @@ fn deserialize_member_event_with_top_level_membership_field() {
             && origin_server_ts == UNIX_EPOCH + Duration::from_millis(1)
             && sender == user_id!("@example:localhost")
             && content.displayname == Some("example".into())
+            && prev_content.membership == MembershipState::Invite
     );
 }
 
@@ -315,3 +321,238 @@ fn deserialize_full_event_convert_to_sync() {
             && unsigned.is_empty()
     );
 }
+
+#[test]
+fn origin_server_ts_accessor() {
+    let mut json_data = aliases_event_with_prev_content();
+    json_data["origin_server_ts"] = 1_432_804_485_886_u64.into();
+
+    let event = from_json_value::<AnyStateEvent>(json_data.clone()).unwrap();
+    assert_eq!(*event.origin_server_ts(), UNIX_EPOCH + Duration::from_millis(1_432_804_485_886));
+
+    let sync_event: AnySyncStateEvent = event.into();
+    assert_eq!(
+        *sync_event.origin_server_ts(),
+        UNIX_EPOCH + Duration::from_millis(1_432_804_485_886)
+    );
+
+    // A zero timestamp is the UNIX epoch, a missing one is an error.
+    json_data["origin_server_ts"] = 0.into();
+    let event = from_json_value::<AnyStateEvent>(json_data.clone()).unwrap();
+    assert_eq!(*event.origin_server_ts(), UNIX_EPOCH);
+
+    json_data.as_object_mut().unwrap().remove("origin_server_ts");
+    assert!(from_json_value::<AnyStateEvent>(json_data).is_err());
+}
+
+fn aliases_event_with_prev_content_in_unsigned(top_level: bool) -> JsonValue {
+    let mut json_data = json!({
+        "content": {
+            "aliases": [ "#somewhere:localhost" ]
+        },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "room_id": "!roomid:room.com",
+        "sender": "@carl:example.com",
+        "state_key": "",
+        "type": "m.room.aliases",
+        "unsigned": {
+            "age": 100,
+            "prev_content": {
+                "aliases": [ "#unsigned:localhost" ]
+            }
+        }
+    });
+
+    if top_level {
+        json_data["prev_content"] = json!({ "aliases": [ "#inner:localhost" ] });
+    }
+
+    json_data
+}
+
+#[test]
+fn deserialize_aliases_with_prev_content_in_unsigned() {
+    let json_data = aliases_event_with_prev_content_in_unsigned(false);
+
+    let ev = from_json_value::<AnySyncStateEvent>(json_data.clone()).unwrap();
+    assert_matches!(
+        ev,
+        AnySyncStateEvent::RoomAliases(SyncStateEvent {
+            prev_content: Some(prev_content),
+            unsigned,
+            ..
+        }) if prev_content.aliases == vec![room_alias_id!("#unsigned:localhost")]
+            && unsigned.age == Some(int!(100))
+    );
+
+    assert_matches!(
+        from_json_value::<StateEvent<AnyStateEventContent>>(json_data).unwrap(),
+        StateEvent { prev_content: Some(AnyStateEventContent::RoomAliases(prev_content)), .. }
+            if prev_content.aliases == vec![room_alias_id!("#unsigned:localhost")]
+    );
+}
+
+#[test]
+fn deserialize_aliases_with_prev_content_in_both_places() {
+    // The top-level `prev_content` wins.
+    let json_data = aliases_event_with_prev_content_in_unsigned(true);
+
+    assert_matches!(
+        from_json_value::<StateEvent<AnyStateEventContent>>(json_data).unwrap(),
+        StateEvent {
+            prev_content: Some(AnyStateEventContent::RoomAliases(prev_content)),
+            unsigned,
+            ..
+        } if prev_content.aliases == vec![room_alias_id!("#inner:localhost")]
+            && unsigned.age == Some(int!(100))
+    );
+}
+
+#[test]
+fn serialize_aliases_with_prev_content_from_unsigned() {
+    // No matter where `prev_content` was received, it is sent at the top level only.
+    let expected = json!({
+        "content": {
+            "aliases": [ "#somewhere:localhost" ]
+        },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "prev_content": {
+            "aliases": [ "#unsigned:localhost" ]
+        },
+        "room_id": "!roomid:room.com",
+        "sender": "@carl:example.com",
+        "state_key": "",
+        "type": "m.room.aliases",
+        "unsigned": {
+            "age": 100
+        }
+    });
+
+    let ev = from_json_value::<AnyStateEvent>(aliases_event_with_prev_content_in_unsigned(false))
+        .unwrap();
+    assert_eq!(to_json_value(ev).unwrap(), expected);
+
+    let ev = from_json_value::<AnyStateEvent>(expected.clone()).unwrap();
+    assert_eq!(to_json_value(ev).unwrap(), expected);
+}
+
+#[test]
+fn deserialize_strict_rejects_unknown_field() {
+    let mut json_data = aliases_event_with_prev_content();
+    json_data["bogus"] = json!(true);
+    let json = to_raw_json_value(&json_data).unwrap();
+
+    let err = StateEvent::<AnyStateEventContent>::deserialize_strict(&json).unwrap_err();
+    assert!(err.to_string().contains("unknown field `bogus`"));
+
+    assert_matches!(
+        from_json_value::<StateEvent<AnyStateEventContent>>(json_data).unwrap().content,
+        AnyStateEventContent::RoomAliases(AliasesEventContent { aliases, .. })
+            if aliases == vec![room_alias_id!("#somewhere:localhost")]
+    );
+}
+
+#[test]
+fn deserialize_strict_accepts_known_fields() {
+    let json = to_raw_json_value(&aliases_event_with_prev_content()).unwrap();
+
+    assert_matches!(
+        StateEvent::<AnyStateEventContent>::deserialize_strict(&json).unwrap(),
+        StateEvent { content: AnyStateEventContent::RoomAliases(_), prev_content: Some(_), .. }
+    );
+}
+
+#[test]
+fn strict_content_deserialization() {
+    let json = r#"{ "ban": 100, "users": { "@carl:example.com": 100 } }"#;
+    let content = from_str_strict::<PowerLevelsEventContent>(json).unwrap();
+    assert_eq!(content.ban, int!(100));
+    assert_eq!(content.kick, int!(50));
+
+    let json = r#"{ "ban": 100, "user_default": 10 }"#;
+    let err = from_str_strict::<PowerLevelsEventContent>(json).unwrap_err();
+    assert!(err.to_string().contains("unknown field `user_default`"));
+    assert_eq!(serde_json::from_str::<PowerLevelsEventContent>(json).unwrap().ban, int!(100));
+}
+
+#[test]
+fn new_and_with_methods() {
+    let content = AliasesEventContent::new(vec![room_alias_id!("#somewhere:localhost")]);
+    let prev_content = AliasesEventContent::new(vec![room_alias_id!("#inner:localhost")]);
+
+    let event = StateEvent::new(
+        AnyStateEventContent::RoomAliases(content),
+        event_id!("$h29iv0s8:example.com"),
+        user_id!("@carl:example.com"),
+        UNIX_EPOCH + Duration::from_millis(1),
+        room_id!("!roomid:room.com"),
+        "".into(),
+    )
+    .with_prev_content(AnyStateEventContent::RoomAliases(prev_content))
+    .with_unsigned(Unsigned::default());
+
+    assert_eq!(to_json_value(&event).unwrap(), aliases_event_with_prev_content());
+}
+
+#[test]
+fn deserialize_strict_rejects_invalid_state_key() {
+    let mut json_data = json!({
+        "content": { "name": "The room" },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "room_id": "!roomid:room.com",
+        "sender": "@carl:example.com",
+        "state_key": "",
+        "type": "m.room.name"
+    });
+    let json = to_raw_json_value(&json_data).unwrap();
+    assert!(StateEvent::<AnyStateEventContent>::deserialize_strict(&json).is_ok());
+
+    json_data["state_key"] = "bogus".into();
+    let json = to_raw_json_value(&json_data).unwrap();
+    let err = StateEvent::<AnyStateEventContent>::deserialize_strict(&json).unwrap_err();
+    assert!(err.to_string().contains("expected an empty `state_key` for `m.room.name` events"));
+
+    json_data["content"] = json!({ "membership": "join" });
+    json_data["type"] = "m.room.member".into();
+    let json = to_raw_json_value(&json_data).unwrap();
+    let err = StateEvent::<AnyStateEventContent>::deserialize_strict(&json).unwrap_err();
+    assert!(err.to_string().contains("expected a user ID as the `state_key`"));
+
+    json_data["state_key"] = "@carl:example.com".into();
+    let json = to_raw_json_value(&json_data).unwrap();
+    assert!(StateEvent::<AnyStateEventContent>::deserialize_strict(&json).is_ok());
+}
+
+#[test]
+fn is_state_change() {
+    let mut json_data = aliases_event_with_prev_content();
+    let event = from_json_value::<StateEvent<AnyStateEventContent>>(json_data.clone()).unwrap();
+    assert!(event.is_state_change());
+
+    json_data["prev_content"] = json_data["content"].clone();
+    let event = from_json_value::<StateEvent<AnyStateEventContent>>(json_data.clone()).unwrap();
+    assert!(!event.is_state_change());
+
+    json_data.as_object_mut().unwrap().remove("prev_content");
+    let event = from_json_value::<StateEvent<AnyStateEventContent>>(json_data).unwrap();
+    assert!(event.is_state_change());
+}
+
+#[test]
+fn is_state_change_ignores_key_order() {
+    let json = r#"{
+        "content": { "a": 1, "b": { "c": 2, "d": 3 } },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "prev_content": { "b": { "d": 3, "c": 2 }, "a": 1 },
+        "sender": "@carl:example.com",
+        "state_key": "",
+        "type": "org.example.custom"
+    }"#;
+
+    let event = serde_json::from_str::<SyncStateEvent<AnyStateEventContent>>(json).unwrap();
+    assert!(!event.is_state_change());
+}
@@ -0,0 +1,36 @@
+use ruma_events::{
+    call::{hangup::Reason, SessionDescriptionType},
+    key::verification::{
+        HashAlgorithm, KeyAgreementProtocol, MessageAuthenticationCode, ShortAuthenticationString,
+        VerificationMethod,
+    },
+    room::{
+        guest_access::GuestAccess,
+        history_visibility::HistoryVisibility,
+        join_rules::JoinRule,
+        member::MembershipState,
+        message::{feedback::FeedbackType, LimitType, MessageFormat, ServerNoticeType},
+    },
+    room_key_request::Action,
+};
+use ruma_serde::test::assert_string_enum_roundtrip;
+
+#[test]
+fn string_enums_roundtrip() {
+    assert_string_enum_roundtrip::<Reason>();
+    assert_string_enum_roundtrip::<SessionDescriptionType>();
+    assert_string_enum_roundtrip::<HashAlgorithm>();
+    assert_string_enum_roundtrip::<KeyAgreementProtocol>();
+    assert_string_enum_roundtrip::<MessageAuthenticationCode>();
+    assert_string_enum_roundtrip::<ShortAuthenticationString>();
+    assert_string_enum_roundtrip::<VerificationMethod>();
+    assert_string_enum_roundtrip::<GuestAccess>();
+    assert_string_enum_roundtrip::<HistoryVisibility>();
+    assert_string_enum_roundtrip::<JoinRule>();
+    assert_string_enum_roundtrip::<MembershipState>();
+    assert_string_enum_roundtrip::<FeedbackType>();
+    assert_string_enum_roundtrip::<LimitType>();
+    assert_string_enum_roundtrip::<MessageFormat>();
+    assert_string_enum_roundtrip::<ServerNoticeType>();
+    assert_string_enum_roundtrip::<Action>();
+}
@@ -14,6 +14,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The room ID to get the auth chain for.
         #[ruma_api(path)]
@@ -24,6 +25,7 @@ ruma_api! {
         pub event_id: EventId,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The full set of authorization events that make up the state of the room,
         /// and their authorization events, recursively.
@@ -17,6 +17,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The room ID to backfill.
         #[ruma_api(path)]
@@ -31,6 +32,7 @@ ruma_api! {
         pub limit: UInt,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The `server_name` of the homeserver sending this transaction.
         pub origin: Box<ServerName>,
@@ -16,6 +16,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The user ID to retrieve devices for. Must be a user local to the receiving homeserver.
         #[ruma_api(query)]
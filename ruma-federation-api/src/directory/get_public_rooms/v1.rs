@@ -21,6 +21,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The maximum number of rooms to return. Default is no limit.
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -12,11 +12,56 @@ ruma_api! {
         requires_authentication: false,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {}
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The server name to delegate server-server communciations to, with optional port.
-        #[serde(rename = "m.homeserver")]
-        pub homeserver: String,
+        #[serde(rename = "m.server")]
+        pub server: String,
+    }
+}
+
+impl Request {
+    /// Creates an empty `Request`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given delegated server name.
+    pub fn new(server: String) -> Self {
+        Self { server }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::{IncomingResponse as _, OutgoingRequest as _, OutgoingResponse as _};
+    use serde_json::{
+        from_slice as from_json_slice, json, to_vec as to_json_vec, Value as JsonValue,
+    };
+
+    use super::{Request, Response};
+
+    #[test]
+    fn request_path() {
+        let req =
+            Request::new().try_into_http_request::<Vec<u8>>("https://example.org", None).unwrap();
+        assert_eq!(req.uri(), "https://example.org/.well-known/matrix/server");
+    }
+
+    #[test]
+    fn response_roundtrip() {
+        let body = json!({ "m.server": "delegated.example.org:8448" });
+        let res =
+            Response::try_from_http_response(http::Response::new(to_json_vec(&body).unwrap()))
+                .unwrap();
+        assert_eq!(res.server, "delegated.example.org:8448");
+
+        let http_res = Response::new(res.server).try_into_http_response::<Vec<u8>>().unwrap();
+        assert_eq!(from_json_slice::<JsonValue>(http_res.body()).unwrap(), body);
     }
 }
@@ -16,6 +16,7 @@ ruma_api! {
         requires_authentication: false,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The server's DNS name to query
         #[ruma_api(path)]
@@ -4,8 +4,9 @@
 use std::collections::BTreeMap;
 
 use ruma_api::ruma_api;
-use ruma_identifiers::{DeviceId, DeviceKeyAlgorithm, DeviceKeyId, UserId};
-use serde::{Deserialize, Serialize};
+use ruma_identifiers::{DeviceId, DeviceKeyAlgorithm, UserId};
+
+pub use ruma_common::encryption::{OneTimeKey, SignedKey};
 
 ruma_api! {
     metadata: {
@@ -17,14 +18,15 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The keys to be claimed.
-        one_time_keys: OneTimeKeyClaims,
+        pub one_time_keys: OneTimeKeyClaims,
     }
 
     response: {
         /// One-time keys for the queried devices
-        one_time_keys: OneTimeKeys,
+        pub one_time_keys: OneTimeKeys,
     }
 }
 
@@ -32,17 +34,7 @@ ruma_api! {
 pub type OneTimeKeyClaims = BTreeMap<UserId, BTreeMap<Box<DeviceId>, DeviceKeyAlgorithm>>;
 
 /// One time keys for use in pre-key messages
-pub type OneTimeKeys = BTreeMap<UserId, BTreeMap<Box<DeviceId>, BTreeMap<DeviceKeyId, KeyObject>>>;
-
-/// A key and its signature
-#[non_exhaustive]
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct KeyObject {
-    /// The key, encoded using unpadded base64.
-    key: String,
-    /// Signature of the key object.
-    signatures: BTreeMap<UserId, BTreeMap<DeviceKeyId, String>>,
-}
+pub type OneTimeKeys = BTreeMap<UserId, ruma_common::encryption::OneTimeKeys>;
 
 impl Request {
     /// Creates a new `Request` with the given one time key claims.
@@ -57,3 +49,74 @@ impl Response {
         Self { one_time_keys }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use matches::assert_matches;
+    use ruma_api::{IncomingResponse as _, OutgoingRequest as _};
+    use ruma_identifiers::{user_id, DeviceId, DeviceKeyAlgorithm, DeviceKeyId};
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::{OneTimeKey, Request, Response};
+
+    #[test]
+    fn serialize_request() {
+        let mut devices = BTreeMap::new();
+        devices.insert("JLAFKJWSCS".into(), DeviceKeyAlgorithm::SignedCurve25519);
+        let mut one_time_keys = BTreeMap::new();
+        one_time_keys.insert(user_id!("@alice:example.org"), devices);
+
+        let req = Request::new(one_time_keys)
+            .try_into_http_request::<Vec<u8>>("https://example.com", Some("auth_tok"))
+            .unwrap();
+
+        assert_eq!(req.uri().path(), "/_matrix/federation/v1/user/keys/claim");
+        assert_eq!(
+            from_json_slice::<JsonValue>(req.body()).unwrap(),
+            json!({
+                "one_time_keys": {
+                    "@alice:example.org": {
+                        "JLAFKJWSCS": "signed_curve25519"
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_response() {
+        let body = json!({
+            "one_time_keys": {
+                "@alice:example.org": {
+                    "JLAFKJWSCS": {
+                        "signed_curve25519:AAAAHg": {
+                            "key": "zKbLg+NrIjpnagy+pIY6uPL4ZwEG2v+8F9lmgsnlZzs",
+                            "signatures": {
+                                "@alice:example.org": {
+                                    "ed25519:JLAFKJWSCS": "FLWxXqGbwrb8SM3Y795eB6OA8bwBcoMZFXBqnTn58AYWZSqiD45tlBVcDa2L7RwdKXebW/VzDlnfVJ+9jok1Bw"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        let response = Response::try_from_http_response(http::Response::new(
+            serde_json::to_vec(&body).unwrap(),
+        ))
+        .unwrap();
+
+        let keys = &response.one_time_keys[&user_id!("@alice:example.org")]
+            [<&DeviceId>::from("JLAFKJWSCS")];
+        let key_id = DeviceKeyId::from_parts(DeviceKeyAlgorithm::SignedCurve25519, "AAAAHg".into());
+        assert_matches!(
+            &keys[&key_id],
+            OneTimeKey::SignedKey(signed)
+                if signed.key == "zKbLg+NrIjpnagy+pIY6uPL4ZwEG2v+8F9lmgsnlZzs"
+                    && signed.signatures[&user_id!("@alice:example.org")].len() == 1
+        );
+    }
+}
@@ -1,8 +1,10 @@
-//! [POST /_matrix/federation/v1/user/keys/query](https://matrix.org/docs/spec/server_server/r0.1.4#post-matrix-federation-v1-user-keys-claim)
+//! [POST /_matrix/federation/v1/user/keys/query](https://matrix.org/docs/spec/server_server/r0.1.4#post-matrix-federation-v1-user-keys-query)
 
 use std::collections::BTreeMap;
 
 use ruma_api::ruma_api;
+#[cfg(feature = "unstable-pre-spec")]
+use ruma_common::encryption::CrossSigningKey;
 use ruma_common::encryption::DeviceKeys;
 use ruma_identifiers::{DeviceId, UserId};
 
@@ -16,15 +18,26 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The keys to be downloaded. Gives all keys for a given user if the list of device ids is
         /// empty.
-        device_keys: BTreeMap<UserId, Vec<Box<DeviceId>>>,
+        pub device_keys: BTreeMap<UserId, Vec<Box<DeviceId>>>,
     }
 
     response: {
         /// Keys from the queried devices.
-        device_keys: BTreeMap<UserId, BTreeMap<Box<DeviceId>, DeviceKeys>>,
+        pub device_keys: BTreeMap<UserId, BTreeMap<Box<DeviceId>, DeviceKeys>>,
+
+        /// Information on the master cross-signing keys of the queried users.
+        #[cfg(feature = "unstable-pre-spec")]
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        pub master_keys: BTreeMap<UserId, CrossSigningKey>,
+
+        /// Information on the self-signing keys of the queried users.
+        #[cfg(feature = "unstable-pre-spec")]
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        pub self_signing_keys: BTreeMap<UserId, CrossSigningKey>,
     }
 }
 
@@ -38,6 +51,116 @@ impl Request {
 impl Response {
     /// Creates a new `Response` with the given device keys.
     pub fn new(device_keys: BTreeMap<UserId, BTreeMap<Box<DeviceId>, DeviceKeys>>) -> Self {
-        Self { device_keys }
+        Self {
+            device_keys,
+            #[cfg(feature = "unstable-pre-spec")]
+            master_keys: BTreeMap::new(),
+            #[cfg(feature = "unstable-pre-spec")]
+            self_signing_keys: BTreeMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use ruma_api::{IncomingResponse as _, OutgoingRequest as _};
+    use ruma_identifiers::{
+        user_id, DeviceId, DeviceKeyAlgorithm, DeviceKeyId, EventEncryptionAlgorithm,
+    };
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::{Request, Response};
+
+    #[test]
+    fn serialize_request() {
+        let mut device_keys = BTreeMap::new();
+        device_keys.insert(user_id!("@alice:example.org"), vec!["JLAFKJWSCS".into()]);
+        device_keys.insert(user_id!("@bob:example.com"), Vec::new());
+
+        let req = Request::new(device_keys)
+            .try_into_http_request::<Vec<u8>>("https://example.com", Some("auth_tok"))
+            .unwrap();
+
+        assert_eq!(req.uri().path(), "/_matrix/federation/v1/user/keys/query");
+        assert_eq!(
+            from_json_slice::<JsonValue>(req.body()).unwrap(),
+            json!({
+                "device_keys": {
+                    "@alice:example.org": ["JLAFKJWSCS"],
+                    "@bob:example.com": []
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_two_user_response() {
+        let body = json!({
+            "device_keys": {
+                "@alice:example.org": {
+                    "JLAFKJWSCS": {
+                        "user_id": "@alice:example.org",
+                        "device_id": "JLAFKJWSCS",
+                        "algorithms": ["m.olm.v1.curve25519-aes-sha2", "m.megolm.v1.aes-sha2"],
+                        "keys": {
+                            "curve25519:JLAFKJWSCS": "3C5BFWi2Y8MaVvjM8M22DBmh24PmgR0nPvJOIArzgyI",
+                            "ed25519:JLAFKJWSCS": "lEuiRJBit0IG6nUf5pUzWTUEsRVVe/HJkoKuEww9ULI"
+                        },
+                        "signatures": {
+                            "@alice:example.org": {
+                                "ed25519:JLAFKJWSCS": "dSO80A01XiigH3uBiDVx/EjzaoycHcjq9lfQX0uWsqxl2giMIiSPR8a4d291W1ihKJL/a+myXS367WT6NAIcBA"
+                            }
+                        },
+                        "unsigned": {
+                            "device_display_name": "Alice's mobile phone"
+                        }
+                    }
+                },
+                "@bob:example.com": {
+                    "BOBDEVICE": {
+                        "user_id": "@bob:example.com",
+                        "device_id": "BOBDEVICE",
+                        "algorithms": ["m.olm.v1.curve25519-aes-sha2"],
+                        "keys": {
+                            "curve25519:BOBDEVICE": "bobcurvekey"
+                        },
+                        "signatures": {}
+                    }
+                }
+            }
+        });
+
+        let response = Response::try_from_http_response(http::Response::new(
+            serde_json::to_vec(&body).unwrap(),
+        ))
+        .unwrap();
+        assert_eq!(response.device_keys.len(), 2);
+
+        let alice =
+            &response.device_keys[&user_id!("@alice:example.org")][<&DeviceId>::from("JLAFKJWSCS")];
+        assert_eq!(alice.user_id, user_id!("@alice:example.org"));
+        assert_eq!(
+            alice.algorithms,
+            vec![
+                EventEncryptionAlgorithm::OlmV1Curve25519AesSha2,
+                EventEncryptionAlgorithm::MegolmV1AesSha2
+            ]
+        );
+        assert_eq!(
+            alice.keys[&DeviceKeyId::from_parts(DeviceKeyAlgorithm::Ed25519, "JLAFKJWSCS".into())],
+            "lEuiRJBit0IG6nUf5pUzWTUEsRVVe/HJkoKuEww9ULI"
+        );
+        assert_eq!(
+            alice.unsigned.as_ref().unwrap().device_display_name.as_deref(),
+            Some("Alice's mobile phone")
+        );
+
+        let bob =
+            &response.device_keys[&user_id!("@bob:example.com")][<&DeviceId>::from("BOBDEVICE")];
+        assert_eq!(&*bob.device_id, "BOBDEVICE");
+        assert!(bob.signatures.is_empty());
+        assert!(bob.unsigned.is_none());
     }
 }
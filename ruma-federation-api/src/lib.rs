@@ -1,6 +1,7 @@
 //! (De)serializable types for the Matrix Federation API.
 
 #![warn(missing_docs)]
+#![allow(clippy::new_without_default)]
 
 mod serde;
 
@@ -1,4 +1,9 @@
 //! Room membership endpoints.
 
+pub mod create_invite;
 pub mod create_join_event;
 pub mod create_join_event_template;
+#[cfg(feature = "unstable-pre-spec")]
+pub mod create_knock_event;
+#[cfg(feature = "unstable-pre-spec")]
+pub mod create_knock_event_template;
@@ -0,0 +1,4 @@
+//! Endpoint for inviting a remote user to a room.
+
+pub mod v1;
+pub mod v2;
@@ -0,0 +1,112 @@
+//! [PUT /_matrix/federation/v1/invite/{roomId}/{eventId}](https://matrix.org/docs/spec/server_server/r0.1.4#put-matrix-federation-v1-invite-roomid-eventid)
+
+use ruma_api::ruma_api;
+use ruma_common::Raw;
+use ruma_events::pdu::Pdu;
+use ruma_identifiers::{EventId, RoomId};
+
+ruma_api! {
+    metadata: {
+        description: "Invites a remote user to a room.",
+        name: "create_invite",
+        method: PUT,
+        path: "/_matrix/federation/v1/invite/:room_id/:event_id",
+        rate_limited: false,
+        requires_authentication: true,
+    }
+
+    request: {
+        /// The room ID that the user is being invited to.
+        #[ruma_api(path)]
+        pub room_id: RoomId,
+
+        /// The event ID for the invite event, generated by the inviting server.
+        #[ruma_api(path)]
+        pub event_id: EventId,
+
+        /// The invite event which needs to be signed.
+        #[ruma_api(body)]
+        pub event: Raw<Pdu>,
+    }
+
+    response: {
+        /// The signed invite event.
+        ///
+        /// Kept as `Raw` so the signatures can be verified against the exact JSON sent by the
+        /// invited server.
+        #[ruma_api(body)]
+        #[serde(with = "crate::serde::invite_response")]
+        pub event: Raw<Pdu>,
+    }
+}
+
+impl Request {
+    /// Creates a new `Request` with the given room ID, event ID and invite event.
+    pub fn new(room_id: RoomId, event_id: EventId, event: Raw<Pdu>) -> Self {
+        Self { room_id, event_id, event }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given signed invite event.
+    pub fn new(event: Raw<Pdu>) -> Self {
+        Self { event }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::{IncomingResponse as _, OutgoingRequest as _, OutgoingResponse as _};
+    use ruma_common::Raw;
+    use ruma_identifiers::{event_id, room_id};
+    use serde_json::{
+        from_slice as from_json_slice, json, value::to_raw_value as to_raw_json_value,
+        Value as JsonValue,
+    };
+
+    use super::{Request, Response};
+
+    #[test]
+    fn serialize_request_body_as_event() {
+        let event = json!({
+            "sender": "@someone:example.org",
+            "origin": "matrix.org",
+            "origin_server_ts": 1_234_567_890,
+            "type": "m.room.member",
+            "state_key": "@joe:elsewhere.com",
+            "content": {
+                "membership": "invite"
+            },
+            "signatures": {
+                "example.com": {
+                    "ed25519:key_version": "SomeSignatureHere"
+                }
+            }
+        });
+        let req = Request::new(
+            room_id!("!abc123:matrix.org"),
+            event_id!("$abc123:example.org"),
+            Raw::from_json(to_raw_json_value(&event).unwrap()),
+        );
+
+        let http_req = req
+            .try_into_http_request::<Vec<u8>>("https://elsewhere.com", Some("auth_tok"))
+            .unwrap();
+        assert_eq!(from_json_slice::<JsonValue>(http_req.body()).unwrap(), event);
+    }
+
+    #[test]
+    fn response_keeps_signed_event() {
+        let signed_event = r#"{"sender":"@someone:example.org","origin":"matrix.org","origin_server_ts":1234567890,"type":"m.room.member","state_key":"@joe:elsewhere.com","content":{"membership":"invite"},"signatures":{"example.com":{"ed25519:key_version":"SomeSignatureHere"},"elsewhere.com":{"ed25519:k3y_versi0n":"SomeOtherSignatureHere"}}}"#;
+        let body = format!(r#"[200, {{"event": {}}}]"#, signed_event);
+
+        let res = Response::try_from_http_response(http::Response::new(body.into_bytes())).unwrap();
+        assert_eq!(res.event.json().get(), signed_event);
+
+        let http_res = Response::new(res.event).try_into_http_response::<Vec<u8>>().unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_res.body()).unwrap(),
+            json!([200, { "event": from_json_slice::<JsonValue>(signed_event.as_bytes()).unwrap() }])
+        );
+    }
+}
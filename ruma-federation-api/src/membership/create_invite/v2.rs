@@ -0,0 +1,154 @@
+//! [PUT /_matrix/federation/v2/invite/{roomId}/{eventId}](https://matrix.org/docs/spec/server_server/r0.1.4#put-matrix-federation-v2-invite-roomid-eventid)
+
+use ruma_api::ruma_api;
+use ruma_common::Raw;
+use ruma_events::{pdu::Pdu, AnyStrippedStateEvent};
+use ruma_identifiers::{EventId, RoomId, RoomVersionId};
+
+ruma_api! {
+    metadata: {
+        description: "Invites a remote user to a room.",
+        name: "create_invite",
+        method: PUT,
+        path: "/_matrix/federation/v2/invite/:room_id/:event_id",
+        rate_limited: false,
+        requires_authentication: true,
+    }
+
+    request: {
+        /// The room ID that the user is being invited to.
+        #[ruma_api(path)]
+        pub room_id: RoomId,
+
+        /// The event ID for the invite event, generated by the inviting server.
+        #[ruma_api(path)]
+        pub event_id: EventId,
+
+        /// The version of the room where the user is being invited to.
+        pub room_version: RoomVersionId,
+
+        /// The invite event which needs to be signed.
+        pub event: Raw<Pdu>,
+
+        /// An optional list of simplified events to help the receiver of the invite identify the
+        /// room.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub invite_room_state: Vec<Raw<AnyStrippedStateEvent>>,
+    }
+
+    response: {
+        /// The signed invite event.
+        ///
+        /// Kept as `Raw` so the signatures can be verified against the exact JSON sent by the
+        /// invited server.
+        pub event: Raw<Pdu>,
+    }
+}
+
+impl Request {
+    /// Creates a new `Request` with the given room ID, event ID, room version and invite event.
+    pub fn new(
+        room_id: RoomId,
+        event_id: EventId,
+        room_version: RoomVersionId,
+        event: Raw<Pdu>,
+    ) -> Self {
+        Self { room_id, event_id, room_version, event, invite_room_state: Vec::new() }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given signed invite event.
+    pub fn new(event: Raw<Pdu>) -> Self {
+        Self { event }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::{IncomingRequest as _, IncomingResponse as _, OutgoingResponse as _};
+    use ruma_events::AnyStrippedStateEvent;
+    use ruma_identifiers::{event_id, room_id, RoomVersionId};
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::{Request, Response};
+
+    #[test]
+    fn deserialize_request() {
+        let body = json!({
+            "room_version": "2",
+            "event": {
+                "sender": "@someone:example.org",
+                "origin": "matrix.org",
+                "origin_server_ts": 1_234_567_890,
+                "type": "m.room.member",
+                "state_key": "@joe:elsewhere.com",
+                "content": {
+                    "membership": "invite"
+                },
+                "signatures": {
+                    "example.com": {
+                        "ed25519:key_version": "SomeSignatureHere"
+                    }
+                }
+            },
+            "invite_room_state": [
+                {
+                    "type": "m.room.name",
+                    "sender": "@bob:example.org",
+                    "state_key": "",
+                    "content": {
+                        "name": "Example Room"
+                    }
+                },
+                {
+                    "type": "m.room.join_rules",
+                    "sender": "@bob:example.org",
+                    "state_key": "",
+                    "content": {
+                        "join_rule": "invite"
+                    }
+                }
+            ]
+        });
+        let http_req = http::Request::builder()
+            .method(http::Method::PUT)
+            .uri("https://elsewhere.com/_matrix/federation/v2/invite/!abc123:matrix.org/$abc123:example.org")
+            .body(serde_json::to_vec(&body).unwrap())
+            .unwrap();
+
+        let req = Request::try_from_http_request(http_req).unwrap();
+        assert_eq!(req.room_id, room_id!("!abc123:matrix.org"));
+        assert_eq!(req.event_id, event_id!("$abc123:example.org"));
+        assert_eq!(req.room_version, RoomVersionId::Version2);
+        assert_eq!(
+            from_json_slice::<JsonValue>(req.event.json().get().as_bytes()).unwrap(),
+            body["event"]
+        );
+
+        assert_eq!(req.invite_room_state.len(), 2);
+        match req.invite_room_state[0].deserialize().unwrap() {
+            AnyStrippedStateEvent::RoomName(ev) => {
+                assert_eq!(ev.content.name(), Some("Example Room"));
+            }
+            ev => panic!("unexpected stripped state event: {:?}", ev),
+        }
+        match req.invite_room_state[1].deserialize().unwrap() {
+            AnyStrippedStateEvent::RoomJoinRules(_) => {}
+            ev => panic!("unexpected stripped state event: {:?}", ev),
+        }
+    }
+
+    #[test]
+    fn response_keeps_signed_event() {
+        let signed_event = r#"{"sender":"@someone:example.org","origin":"matrix.org","origin_server_ts":1234567890,"type":"m.room.member","state_key":"@joe:elsewhere.com","content":{"membership":"invite"},"signatures":{"example.com":{"ed25519:key_version":"SomeSignatureHere"},"elsewhere.com":{"ed25519:k3y_versi0n":"SomeOtherSignatureHere"}}}"#;
+        let body = format!(r#"{{"event": {}}}"#, signed_event);
+
+        let res = Response::try_from_http_response(http::Response::new(body.into_bytes())).unwrap();
+        assert_eq!(res.event.json().get(), signed_event);
+
+        let http_res = Response::new(res.event).try_into_http_response::<Vec<u8>>().unwrap();
+        let res = Response::try_from_http_response(http_res).unwrap();
+        assert_eq!(res.event.json().get(), signed_event);
+    }
+}
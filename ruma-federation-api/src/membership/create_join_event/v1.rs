@@ -16,6 +16,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The room ID that is about to be joined.
         #[ruma_api(path)]
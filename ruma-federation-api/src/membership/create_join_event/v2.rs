@@ -16,6 +16,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The room ID that is about to be joined.
         #[ruma_api(path)]
@@ -29,6 +30,7 @@ ruma_api! {
         pub pdu_stub: PduStub,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// Full state of the room.
         pub room_state: RoomState,
@@ -0,0 +1,3 @@
+//! Endpoint to send knock events to remote homeservers.
+
+pub mod v1;
@@ -0,0 +1,36 @@
+//! [PUT /_matrix/federation/v1/send_knock/{roomId}/{eventId}](https://github.com/matrix-org/matrix-doc/pull/2403)
+
+use ruma_api::ruma_api;
+use ruma_common::Raw;
+use ruma_events::{pdu::PduStub, AnyStrippedStateEvent};
+use ruma_identifiers::{EventId, RoomId};
+
+ruma_api! {
+    metadata: {
+        description: "Send a knock event to a resident server.",
+        name: "create_knock_event",
+        method: PUT,
+        path: "/_matrix/federation/v1/send_knock/:room_id/:event_id",
+        rate_limited: false,
+        requires_authentication: true,
+    }
+
+    #[derive(PartialEq, Eq)]
+    request: {
+        /// The room ID that should receive the knock.
+        #[ruma_api(path)]
+        pub room_id: RoomId,
+        /// The event ID for the knock event.
+        #[ruma_api(path)]
+        pub event_id: EventId,
+
+        /// PDU type without event and room IDs.
+        #[ruma_api(body)]
+        pub pdu_stub: PduStub,
+    }
+
+    response: {
+        /// A list of stripped state events to help the initiator of the knock identify the room.
+        pub knock_room_state: Vec<Raw<AnyStrippedStateEvent>>,
+    }
+}
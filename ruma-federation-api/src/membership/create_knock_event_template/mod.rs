@@ -0,0 +1,3 @@
+//! Endpoint to request a template for knock events.
+
+pub mod v1;
@@ -0,0 +1,37 @@
+//! [GET /_matrix/federation/v1/make_knock/{roomId}/{userId}](https://github.com/matrix-org/matrix-doc/pull/2403)
+
+use ruma_api::ruma_api;
+use ruma_common::Raw;
+use ruma_events::pdu::Pdu;
+use ruma_identifiers::{RoomId, RoomVersionId, UserId};
+
+ruma_api! {
+    metadata: {
+        description: "Send a request for a knock event template to a resident server.",
+        name: "create_knock_event_template",
+        method: GET,
+        path: "/_matrix/federation/v1/make_knock/:room_id/:user_id",
+        rate_limited: false,
+        requires_authentication: true,
+    }
+
+    #[derive(PartialEq, Eq)]
+    request: {
+        /// The room ID that should receive the knock.
+        #[ruma_api(path)]
+        pub room_id: RoomId,
+        /// The user ID the knock event will be for.
+        #[ruma_api(path)]
+        pub user_id: UserId,
+        /// The room versions the sending server has support for.
+        #[ruma_api(query)]
+        pub ver: Vec<RoomVersionId>,
+    }
+
+    response: {
+        /// The version of the room where the server is trying to knock.
+        pub room_version: RoomVersionId,
+        /// An unsigned template event.
+        pub event: Raw<Pdu>,
+    }
+}
@@ -13,12 +13,14 @@ ruma_api! {
         requires_authentication: false,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// The OpenID access token to get information about the owner for.
         #[ruma_api(query)]
         pub access_token: String,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// The Matrix User ID who generated the token.
         pub sub: UserId,
@@ -14,6 +14,7 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// User ID to query.
         #[ruma_api(query)]
@@ -25,6 +26,7 @@ ruma_api! {
         pub field: Option<ProfileField>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// Display name of the user.
         #[serde(skip_serializing_if = "Option::is_none")]
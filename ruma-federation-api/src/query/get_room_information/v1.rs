@@ -13,12 +13,14 @@ ruma_api! {
         requires_authentication: true,
     }
 
+    #[derive(PartialEq, Eq)]
     request: {
         /// Room alias to query.
         #[ruma_api(query)]
         pub room_alias: String,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// Room ID mapped to queried alias.
         pub room_id: RoomId,
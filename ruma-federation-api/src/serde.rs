@@ -1,4 +1,5 @@
 //! Modules for custom serde de/-serialization implementations.
 
+pub mod invite_response;
 pub mod pdu_process_response;
 pub mod room_state;
@@ -0,0 +1,99 @@
+//! A module to (de)serialize the event of the v1 invite endpoint's response, which is wrapped in
+//! a `[200, { "event": ... }]` array like the response of the v1 send_join endpoint.
+
+use std::fmt;
+
+use ruma_common::Raw;
+use ruma_events::pdu::Pdu;
+use serde::{
+    de::{Deserializer, Error, IgnoredAny, SeqAccess, Visitor},
+    ser::{SerializeSeq, Serializer},
+    Deserialize, Serialize,
+};
+
+pub fn serialize<S>(event: &Raw<Pdu>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut seq = serializer.serialize_seq(Some(2))?;
+    seq.serialize_element(&200)?;
+    seq.serialize_element(&WrappedEvent { event: event.clone() })?;
+    seq.end()
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Raw<Pdu>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_seq(InviteResponseVisitor)
+}
+
+#[derive(Deserialize, Serialize)]
+struct WrappedEvent {
+    event: Raw<Pdu>,
+}
+
+struct InviteResponseVisitor;
+
+impl<'de> Visitor<'de> for InviteResponseVisitor {
+    type Value = Raw<Pdu>;
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Invite response wrapped in an array.")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let expected = "a two-element list in the response";
+        if seq.next_element::<IgnoredAny>()?.is_none() {
+            return Err(A::Error::invalid_length(0, &expected));
+        }
+
+        let WrappedEvent { event } =
+            seq.next_element()?.ok_or_else(|| A::Error::invalid_length(1, &expected))?;
+
+        while let Some(IgnoredAny) = seq.next_element()? {
+            // ignore extra elements
+        }
+
+        Ok(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_common::Raw;
+    use serde_json::{json, value::to_raw_value as to_raw_json_value};
+
+    use super::{deserialize, serialize};
+
+    #[test]
+    fn test_deserialize_response() {
+        let response = r#"[200, { "event": { "type": "m.room.member" } }]"#;
+
+        let mut deserializer = serde_json::Deserializer::from_str(response);
+        let event = deserialize(&mut deserializer).unwrap();
+
+        assert_eq!(event.json().get(), r#"{ "type": "m.room.member" }"#);
+    }
+
+    #[test]
+    fn test_serialize_response() {
+        let event = Raw::from_json(to_raw_json_value(&json!({ "type": "m.room.member" })).unwrap());
+
+        let serialized = serialize(&event, serde_json::value::Serializer).unwrap();
+        let expected = json!([200, { "event": { "type": "m.room.member" } }]);
+
+        assert_eq!(serialized, expected);
+    }
+
+    #[test]
+    fn test_too_short_array() {
+        let mut deserializer = serde_json::Deserializer::from_str("[200]");
+        assert!(deserialize(&mut deserializer)
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid length 1, expected a two-element list in the response"));
+    }
+}
@@ -1,3 +1,4 @@
 //! Endpoints for exchanging transaction messages between homeservers.
 
+pub mod edu;
 pub mod send_transaction_message;
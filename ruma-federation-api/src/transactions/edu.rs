@@ -0,0 +1,283 @@
+//! Content types of ephemeral data units (EDUs) sent in transactions, and conversions to and from
+//! the corresponding client-facing events.
+
+use std::collections::{btree_map::Entry, BTreeMap};
+
+use js_int::UInt;
+use ruma_common::presence::PresenceState;
+use ruma_events::{
+    presence::{PresenceEvent, PresenceEventContent},
+    receipt::{Receipt, ReceiptEventContent, Receipts},
+};
+use ruma_identifiers::{EventId, RoomId, UserId};
+use serde::{Deserialize, Serialize};
+
+/// The content of an *m.receipt* EDU.
+///
+/// A mapping of room ID to the receipts for that room.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ReceiptContent(pub BTreeMap<RoomId, ReceiptMap>);
+
+impl ReceiptContent {
+    /// Converts the receipts of every room into the content of a client-facing *m.receipt*
+    /// event.
+    pub fn into_event_contents(self) -> BTreeMap<RoomId, ReceiptEventContent> {
+        self.0.into_iter().map(|(room_id, receipts)| (room_id, receipts.into())).collect()
+    }
+}
+
+/// The receipts of a single room in an *m.receipt* EDU.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ReceiptMap {
+    /// Read receipts for users in the room.
+    #[serde(rename = "m.read")]
+    pub read: BTreeMap<UserId, ReceiptData>,
+}
+
+impl ReceiptMap {
+    /// Creates a new `ReceiptMap` with the given read receipts.
+    pub fn new(read: BTreeMap<UserId, ReceiptData>) -> Self {
+        Self { read }
+    }
+}
+
+impl From<ReceiptMap> for ReceiptEventContent {
+    fn from(receipts: ReceiptMap) -> Self {
+        let mut content = BTreeMap::new();
+
+        for (user_id, ReceiptData { data, event_ids }) in receipts.read {
+            for event_id in event_ids {
+                content
+                    .entry(event_id)
+                    .or_insert_with(|| Receipts { read: None })
+                    .read
+                    .get_or_insert_with(BTreeMap::new)
+                    .insert(user_id.clone(), data.clone());
+            }
+        }
+
+        ReceiptEventContent(content)
+    }
+}
+
+/// Collects the receipts of each user into a single `ReceiptData`.
+///
+/// If a user has receipts for several events, the receipt data of the first of these events (by
+/// event ID) is kept.
+impl From<ReceiptEventContent> for ReceiptMap {
+    fn from(content: ReceiptEventContent) -> Self {
+        let mut read = BTreeMap::<UserId, ReceiptData>::new();
+
+        for (event_id, receipts) in content.0 {
+            for (user_id, receipt) in receipts.read.unwrap_or_default() {
+                match read.entry(user_id) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(ReceiptData::new(receipt, vec![event_id.clone()]));
+                    }
+                    Entry::Occupied(mut entry) => entry.get_mut().event_ids.push(event_id.clone()),
+                }
+            }
+        }
+
+        Self { read }
+    }
+}
+
+/// A read receipt of a single user in an *m.receipt* EDU.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ReceiptData {
+    /// Metadata for the read receipt.
+    pub data: Receipt,
+
+    /// The extremity event IDs that the user has read up to.
+    pub event_ids: Vec<EventId>,
+}
+
+impl ReceiptData {
+    /// Creates a new `ReceiptData` with the given receipt metadata and event IDs.
+    pub fn new(data: Receipt, event_ids: Vec<EventId>) -> Self {
+        Self { data, event_ids }
+    }
+}
+
+/// The content of an *m.presence* EDU.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PresenceContent {
+    /// A list of presence updates that the receiving server is likely to be interested in.
+    pub push: Vec<PresenceUpdate>,
+}
+
+impl PresenceContent {
+    /// Creates a new `PresenceContent` with the given presence updates.
+    pub fn new(push: Vec<PresenceUpdate>) -> Self {
+        Self { push }
+    }
+}
+
+/// An update to the presence of a user in an *m.presence* EDU.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PresenceUpdate {
+    /// The user ID this presence EDU is for.
+    pub user_id: UserId,
+
+    /// The presence of the user.
+    pub presence: PresenceState,
+
+    /// An optional description to accompany the presence.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_msg: Option<String>,
+
+    /// The number of milliseconds that have elapsed since the user last did something.
+    pub last_active_ago: UInt,
+
+    /// Whether or not the user is currently active.
+    ///
+    /// Defaults to false.
+    #[serde(default, skip_serializing_if = "ruma_serde::is_default")]
+    pub currently_active: bool,
+}
+
+impl PresenceUpdate {
+    /// Creates a new `PresenceUpdate` with the given user ID, presence and last activity.
+    pub fn new(user_id: UserId, presence: PresenceState, last_active_ago: UInt) -> Self {
+        Self { user_id, presence, last_active_ago, status_msg: None, currently_active: false }
+    }
+}
+
+impl From<PresenceUpdate> for PresenceEvent {
+    fn from(update: PresenceUpdate) -> Self {
+        Self {
+            content: PresenceEventContent {
+                avatar_url: None,
+                currently_active: Some(update.currently_active),
+                displayname: None,
+                last_active_ago: Some(update.last_active_ago),
+                presence: update.presence,
+                status_msg: update.status_msg,
+            },
+            sender: update.user_id,
+        }
+    }
+}
+
+/// Converts a client-facing presence event to a presence update.
+///
+/// Avatar URL and display name aren't sent over federation and are dropped. A missing
+/// `last_active_ago` is treated as zero.
+impl From<PresenceEvent> for PresenceUpdate {
+    fn from(event: PresenceEvent) -> Self {
+        Self {
+            user_id: event.sender,
+            presence: event.content.presence,
+            status_msg: event.content.status_msg,
+            last_active_ago: event.content.last_active_ago.unwrap_or_default(),
+            currently_active: event.content.currently_active.unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::uint;
+    use matches::assert_matches;
+    use ruma_common::presence::PresenceState;
+    use ruma_events::presence::PresenceEvent;
+    use ruma_identifiers::{event_id, room_id, user_id};
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
+
+    use super::{PresenceContent, PresenceUpdate, ReceiptContent, ReceiptMap};
+    use crate::transactions::send_transaction_message::v1::Edu;
+
+    fn transaction_edus() -> Vec<Edu> {
+        from_json_value(json!([
+            {
+                "edu_type": "m.receipt",
+                "content": {
+                    "!some_room:example.org": {
+                        "m.read": {
+                            "@john:matrix.org": {
+                                "data": { "ts": 1_533_358_089_009_u64 },
+                                "event_ids": ["$read_this_event:matrix.org"]
+                            }
+                        }
+                    }
+                }
+            },
+            {
+                "edu_type": "m.presence",
+                "content": {
+                    "push": [
+                        {
+                            "currently_active": true,
+                            "last_active_ago": 5000,
+                            "presence": "online",
+                            "status_msg": "Making cupcakes",
+                            "user_id": "@john:matrix.org"
+                        }
+                    ]
+                }
+            }
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn receipt_roundtrip() {
+        let edu = transaction_edus().remove(0);
+        assert_eq!(edu.edu_type, "m.receipt");
+
+        let contents =
+            from_json_value::<ReceiptContent>(edu.content.clone()).unwrap().into_event_contents();
+        let content = &contents[&room_id!("!some_room:example.org")];
+        let receipts = content[&event_id!("$read_this_event:matrix.org")].read.as_ref().unwrap();
+        assert!(receipts[&user_id!("@john:matrix.org")].ts.is_some());
+
+        let back = ReceiptContent(
+            contents.into_iter().map(|(room_id, content)| (room_id, content.into())).collect(),
+        );
+        assert_eq!(to_json_value(back).unwrap(), edu.content);
+    }
+
+    #[test]
+    fn receipt_for_several_events() {
+        let receipts = from_json_value::<ReceiptMap>(json!({
+            "m.read": {
+                "@john:matrix.org": {
+                    "data": {},
+                    "event_ids": ["$first:matrix.org", "$second:matrix.org"]
+                }
+            }
+        }))
+        .unwrap();
+
+        let content = ruma_events::receipt::ReceiptEventContent::from(receipts);
+        assert_eq!(content.len(), 2);
+
+        let receipts = ReceiptMap::from(content);
+        assert_eq!(
+            receipts.read[&user_id!("@john:matrix.org")].event_ids,
+            vec![event_id!("$first:matrix.org"), event_id!("$second:matrix.org")]
+        );
+    }
+
+    #[test]
+    fn presence_roundtrip() {
+        let edu = transaction_edus().remove(1);
+        assert_eq!(edu.edu_type, "m.presence");
+
+        let content = from_json_value::<PresenceContent>(edu.content.clone()).unwrap();
+        let events: Vec<PresenceEvent> = content.push.into_iter().map(Into::into).collect();
+        assert_matches!(
+            events.as_slice(),
+            [PresenceEvent { content, sender }]
+                if sender == &user_id!("@john:matrix.org")
+                    && content.presence == PresenceState::Online
+                    && content.last_active_ago == Some(uint!(5000))
+                    && content.currently_active == Some(true)
+                    && content.status_msg.as_deref() == Some("Making cupcakes")
+        );
+
+        let back = PresenceContent::new(events.into_iter().map(PresenceUpdate::from).collect());
+        assert_eq!(to_json_value(back).unwrap(), edu.content);
+    }
+}
@@ -43,6 +43,7 @@ ruma_api! {
         pub edus: Vec<Edu>,
     }
 
+    #[derive(PartialEq, Eq)]
     response: {
         /// Map of event IDs and response for each PDU given in the request.
         #[serde(with = "crate::serde::pdu_process_response")]
@@ -13,6 +13,8 @@ pub enum Error {
     InvalidKeyVersion,
     /// The localpart of the ID string is not valid (because it is empty).
     InvalidLocalPart,
+    /// The `mxc://` URI is missing the `mxc://` scheme, the server name or a valid media ID.
+    InvalidMxcUri,
     /// The server name part of the the ID string is not a valid server name.
     InvalidServerName,
     /// The ID exceeds 255 bytes (or 32 codepoints for a room version ID.)
@@ -37,6 +39,7 @@ impl Display for Error {
             Error::InvalidCharacters => "localpart contains invalid characters",
             Error::InvalidKeyVersion => "key id version contains invalid characters",
             Error::InvalidLocalPart => "localpart is empty",
+            Error::InvalidMxcUri => "mxc URI is not of the form mxc://<server-name>/<media-id>",
             Error::InvalidServerName => "server name is not a valid IP address or domain name",
             Error::MaximumLengthExceeded => "ID exceeds 255 bytes",
             Error::MinimumLengthNotSatisfied => "ID must be at least 4 characters",
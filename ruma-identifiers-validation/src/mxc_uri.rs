@@ -0,0 +1,30 @@
+use crate::{error::Error, server_name};
+
+/// The scheme and authority separator every `mxc://` URI starts with.
+pub const PROTOCOL: &str = "mxc://";
+
+/// Validates an `mxc://` URI and returns the index of the slash between the server name and the
+/// media ID.
+pub fn validate(uri: &str) -> Result<usize, Error> {
+    if !uri.starts_with(PROTOCOL) {
+        return Err(Error::InvalidMxcUri);
+    }
+
+    let slash_idx = match uri[PROTOCOL.len()..].find('/') {
+        Some(idx) => PROTOCOL.len() + idx,
+        None => return Err(Error::InvalidMxcUri),
+    };
+
+    server_name::validate(&uri[PROTOCOL.len()..slash_idx])?;
+
+    let media_id = &uri[slash_idx + 1..];
+    if media_id.is_empty()
+        || media_id
+            .bytes()
+            .any(|byte| !(byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_'))
+    {
+        return Err(Error::InvalidMxcUri);
+    }
+
+    Ok(slash_idx)
+}
@@ -16,9 +16,10 @@ use serde1::de::{self, Deserialize as _, Deserializer, Unexpected};
 
 #[doc(inline)]
 pub use crate::{
-    device_id::DeviceId, device_key_id::DeviceKeyId, event_id::EventId, room_alias_id::RoomAliasId,
-    room_id::RoomId, room_id_or_room_alias_id::RoomIdOrAliasId, room_version_id::RoomVersionId,
-    server_key_id::ServerKeyId, server_name::ServerName, user_id::UserId,
+    device_id::DeviceId, device_key_id::DeviceKeyId, event_id::EventId, mxc_uri::MxcUri,
+    room_alias_id::RoomAliasId, room_id::RoomId, room_id_or_room_alias_id::RoomIdOrAliasId,
+    room_version_id::RoomVersionId, server_key_id::ServerKeyId, server_name::ServerName,
+    user_id::UserId,
 };
 #[doc(inline)]
 pub use ruma_identifiers_validation::{
@@ -34,6 +35,7 @@ pub mod user_id;
 
 mod device_key_id;
 mod event_id;
+mod mxc_uri;
 mod room_alias_id;
 mod room_id;
 mod room_id_or_room_alias_id;
@@ -0,0 +1,293 @@
+//! Matrix content (`mxc://`) URIs.
+
+use std::{
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+#[cfg(feature = "serde")]
+use serde1::{Deserialize, Deserializer, Serialize, Serializer};
+
+use ruma_identifiers_validation::mxc_uri::PROTOCOL;
+
+use crate::{server_name::ServerName, Error};
+
+/// A URI that should be a Matrix-spec compliant MXC URI.
+///
+/// An `MxcUri` can be created in two ways:
+///
+/// * Through its `TryFrom` and `FromStr` implementations, which reject URIs that are not of the
+///   form `mxc://<server-name>/<media-id>`.
+/// * Through `MxcUri::new_lenient` or deserialization, which accept any string because historical
+///   events contain invalid `mxc://` URIs. Use `MxcUri::is_valid` to find out whether such an URI
+///   is valid.
+///
+/// ```
+/// # use std::convert::TryFrom;
+/// # use ruma_identifiers::MxcUri;
+/// let uri = MxcUri::try_from("mxc://example.com/AQwafuaFswefuhsfAFAgsw").unwrap();
+/// assert_eq!(uri.server_name().unwrap().as_str(), "example.com");
+/// assert_eq!(uri.media_id(), Some("AQwafuaFswefuhsfAFAgsw"));
+///
+/// let invalid = MxcUri::new_lenient("mxc://example.com");
+/// assert!(!invalid.is_valid());
+/// assert_eq!(invalid.media_id(), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MxcUri {
+    full_uri: Box<str>,
+    slash_idx: Option<usize>,
+}
+
+impl MxcUri {
+    /// Creates an `MxcUri` from the given string without rejecting invalid URIs.
+    ///
+    /// The accessors for the server name and media ID return `None` if the URI is invalid.
+    pub fn new_lenient(uri: impl Into<Box<str>>) -> Self {
+        let full_uri = uri.into();
+        let slash_idx = ruma_identifiers_validation::mxc_uri::validate(&full_uri).ok();
+
+        Self { full_uri, slash_idx }
+    }
+
+    /// Creates a string slice from this `MxcUri`.
+    pub fn as_str(&self) -> &str {
+        &self.full_uri
+    }
+
+    /// Creates a byte slice from this `MxcUri`.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.full_uri.as_bytes()
+    }
+
+    /// Whether this is a valid `mxc://<server-name>/<media-id>` URI.
+    pub fn is_valid(&self) -> bool {
+        self.slash_idx.is_some()
+    }
+
+    /// Returns the server name of this URI, if it is valid.
+    pub fn server_name(&self) -> Option<&ServerName> {
+        self.slash_idx
+            .map(|idx| <&ServerName>::try_from(&self.full_uri[PROTOCOL.len()..idx]).unwrap())
+    }
+
+    /// Returns the media ID of this URI, if it is valid.
+    pub fn media_id(&self) -> Option<&str> {
+        self.slash_idx.map(|idx| &self.full_uri[idx + 1..])
+    }
+}
+
+/// Attempts to create a new `MxcUri` from a string representation, rejecting invalid URIs.
+fn try_from<S>(uri: S) -> Result<MxcUri, Error>
+where
+    S: AsRef<str> + Into<Box<str>>,
+{
+    let slash_idx = ruma_identifiers_validation::mxc_uri::validate(uri.as_ref())?;
+    Ok(MxcUri { full_uri: uri.into(), slash_idx: Some(slash_idx) })
+}
+
+impl AsRef<str> for MxcUri {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<MxcUri> for String {
+    fn from(uri: MxcUri) -> Self {
+        uri.full_uri.into()
+    }
+}
+
+impl FromStr for MxcUri {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        try_from(s)
+    }
+}
+
+impl TryFrom<&str> for MxcUri {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        try_from(s)
+    }
+}
+
+impl TryFrom<String> for MxcUri {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        try_from(s)
+    }
+}
+
+impl Display for MxcUri {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl PartialEq for MxcUri {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for MxcUri {}
+
+impl PartialOrd for MxcUri {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MxcUri {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl std::hash::Hash for MxcUri {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for MxcUri {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Deserialization never fails for strings, since historical events may contain invalid URIs.
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for MxcUri {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Self::new_lenient(String::deserialize(deserializer)?))
+    }
+}
+
+partial_eq_string!(MxcUri);
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use proptest::prelude::*;
+    #[cfg(feature = "serde")]
+    use serde_json::{from_str, to_string};
+
+    use super::MxcUri;
+    use crate::Error;
+
+    const VALID: &[(&str, &str, &str)] = &[
+        ("mxc://example.com/AQwafuaFswefuhsfAFAgsw", "example.com", "AQwafuaFswefuhsfAFAgsw"),
+        ("mxc://localhost:8448/abc-def_123", "localhost:8448", "abc-def_123"),
+        ("mxc://1.1.1.1/media", "1.1.1.1", "media"),
+        ("mxc://[::1]:80/m", "[::1]:80", "m"),
+    ];
+
+    const INVALID: &[&str] = &[
+        "",
+        "mxc://",
+        "mxc://example.com",
+        "mxc://example.com/",
+        "mxc:///media",
+        "mxc://example.com/media/extra",
+        "mxc://exa mple.com/media",
+        "mxc://example.com/me dia",
+        "https://example.com/media",
+        "MXC://example.com/media",
+    ];
+
+    #[test]
+    fn valid_mxc_uris() {
+        for &(uri, server_name, media_id) in VALID {
+            let parsed = MxcUri::try_from(uri).expect(uri);
+            assert!(parsed.is_valid());
+            assert_eq!(parsed.as_str(), uri);
+            assert_eq!(parsed.server_name().unwrap().as_str(), server_name);
+            assert_eq!(parsed.media_id(), Some(media_id));
+            assert_eq!(MxcUri::new_lenient(uri), parsed);
+        }
+    }
+
+    #[test]
+    fn invalid_mxc_uris() {
+        for &uri in INVALID {
+            assert!(MxcUri::try_from(uri).is_err(), "{} should be invalid", uri);
+
+            let lenient = MxcUri::new_lenient(uri);
+            assert!(!lenient.is_valid());
+            assert_eq!(lenient.as_str(), uri);
+            assert_eq!(lenient.server_name(), None);
+            assert_eq!(lenient.media_id(), None);
+        }
+    }
+
+    #[test]
+    fn invalid_server_name() {
+        assert_eq!(
+            MxcUri::try_from("mxc://exa mple.com/media").unwrap_err(),
+            Error::InvalidServerName
+        );
+        assert_eq!(MxcUri::try_from("mxc://example.com").unwrap_err(), Error::InvalidMxcUri);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        for &(uri, ..) in VALID {
+            let parsed: MxcUri = from_str(&format!("\"{}\"", uri)).unwrap();
+            assert!(parsed.is_valid());
+            assert_eq!(to_string(&parsed).unwrap(), format!("\"{}\"", uri));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_invalid_is_lenient() {
+        for &uri in INVALID {
+            let parsed: MxcUri = from_str(&format!("\"{}\"", uri)).unwrap();
+            assert!(!parsed.is_valid());
+            assert_eq!(to_string(&parsed).unwrap(), format!("\"{}\"", uri));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn generated_valid_uris(
+            server_name in "[a-z0-9]{1,12}(\\.[a-z]{2,6})?(:[1-9][0-9]{0,3})?",
+            media_id in "[a-zA-Z0-9_-]{1,32}",
+        ) {
+            let uri = format!("mxc://{}/{}", server_name, media_id);
+            let parsed = MxcUri::try_from(uri.as_str()).unwrap();
+
+            prop_assert_eq!(parsed.server_name().unwrap().as_str(), server_name.as_str());
+            prop_assert_eq!(parsed.media_id(), Some(media_id.as_str()));
+            prop_assert_eq!(MxcUri::new_lenient(uri.as_str()), parsed);
+        }
+
+        #[test]
+        fn lenient_agrees_with_strict(uri in "(mxc://)?[a-z0-9.:/ _-]{0,24}") {
+            let lenient = MxcUri::new_lenient(uri.as_str());
+
+            prop_assert_eq!(lenient.as_str(), uri.as_str());
+            prop_assert_eq!(lenient.is_valid(), MxcUri::try_from(uri.as_str()).is_ok());
+
+            if let Some(server_name) = lenient.server_name() {
+                let media_id = lenient.media_id().unwrap();
+                prop_assert_eq!(format!("mxc://{}/{}", server_name, media_id), uri);
+            }
+        }
+    }
+}
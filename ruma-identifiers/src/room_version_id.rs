@@ -112,6 +112,25 @@ impl RoomVersionId {
         matches!(self, Self::Custom(_))
     }
 
+    /// Whether or not this is the official room version with the given number.
+    ///
+    /// ```
+    /// # use ruma_identifiers::RoomVersionId;
+    /// assert!(RoomVersionId::Version5.is_version(5));
+    /// assert!(!RoomVersionId::Version5.is_version(6));
+    /// ```
+    pub fn is_version(&self, version: u8) -> bool {
+        match self {
+            Self::Version1 => version == 1,
+            Self::Version2 => version == 2,
+            Self::Version3 => version == 3,
+            Self::Version4 => version == 4,
+            Self::Version5 => version == 5,
+            Self::Version6 => version == 6,
+            Self::Custom(_) => false,
+        }
+    }
+
     /// Whether or not this is a version 1 room.
     #[deprecated = "compare to RoomVersionId::Version1 instead"]
     pub fn is_version_1(&self) -> bool {
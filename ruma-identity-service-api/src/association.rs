@@ -0,0 +1,4 @@
+//! Endpoints to create and validate associations between third party identifiers and Matrix
+//! user IDs.
+
+pub mod email;
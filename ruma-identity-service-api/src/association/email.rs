@@ -0,0 +1,4 @@
+//! Endpoints to validate ownership of an email address.
+
+pub mod create_email_validation_session;
+pub mod validate_email;
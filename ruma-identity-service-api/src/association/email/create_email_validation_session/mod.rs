@@ -0,0 +1,3 @@
+//! Endpoint to create a session for validating an email address.
+
+pub mod v2;
@@ -0,0 +1,83 @@
+//! [POST /_matrix/identity/v2/validate/email/requestToken](https://matrix.org/docs/spec/identity_service/r0.3.0#post-matrix-identity-v2-validate-email-requesttoken)
+
+use js_int::UInt;
+use ruma_api::ruma_api;
+
+ruma_api! {
+    metadata: {
+        description: "Creates a session for validating an email address.",
+        method: POST,
+        name: "create_email_validation_session",
+        path: "/_matrix/identity/v2/validate/email/requestToken",
+        rate_limited: false,
+        requires_authentication: true,
+    }
+
+    #[derive(PartialEq, Eq)]
+    request: {
+        /// A unique string generated by the client, and used to identify the validation attempt.
+        pub client_secret: String,
+
+        /// The email address to validate.
+        pub email: String,
+
+        /// Used to distinguish protocol level retries from requests to re-send the email.
+        pub send_attempt: UInt,
+
+        /// URL the identity server should redirect the user to after validation, if the user
+        /// follows the link in the email.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub next_link: Option<String>,
+    }
+
+    #[derive(PartialEq, Eq)]
+    response: {
+        /// The session ID.
+        ///
+        /// Session IDs consist of alphanumeric characters only and are at most 255 characters
+        /// long.
+        pub sid: String,
+    }
+}
+
+impl Request {
+    /// Creates a new `Request` with the given client secret, email address and send attempt
+    /// counter.
+    pub fn new(client_secret: String, email: String, send_attempt: UInt) -> Self {
+        Self { client_secret, email, send_attempt, next_link: None }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given session ID.
+    pub fn new(sid: String) -> Self {
+        Self { sid }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use js_int::uint;
+    use ruma_api::OutgoingRequest as _;
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Request;
+
+    #[test]
+    fn serialize_request_with_next_link() {
+        let mut req = Request::new("secret".into(), "alice@example.org".into(), uint!(1));
+        req.next_link = Some("https://example.org/validated".into());
+
+        let http_req =
+            req.try_into_http_request::<Vec<u8>>("https://id.example.org", Some("tok")).unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({
+                "client_secret": "secret",
+                "email": "alice@example.org",
+                "send_attempt": 1,
+                "next_link": "https://example.org/validated",
+            })
+        );
+    }
+}
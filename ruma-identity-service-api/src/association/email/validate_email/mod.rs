@@ -0,0 +1,3 @@
+//! Endpoint to validate ownership of an email address with a token sent to it.
+
+pub mod v2;
@@ -0,0 +1,46 @@
+//! [POST /_matrix/identity/v2/validate/email/submitToken](https://matrix.org/docs/spec/identity_service/r0.3.0#post-matrix-identity-v2-validate-email-submittoken)
+
+use ruma_api::ruma_api;
+
+ruma_api! {
+    metadata: {
+        description: "Validates ownership of an email address.",
+        method: POST,
+        name: "validate_email",
+        path: "/_matrix/identity/v2/validate/email/submitToken",
+        rate_limited: false,
+        requires_authentication: true,
+    }
+
+    #[derive(PartialEq, Eq)]
+    request: {
+        /// The session ID, generated by the `requestToken` call.
+        pub sid: String,
+
+        /// The client secret that was supplied to the `requestToken` call.
+        pub client_secret: String,
+
+        /// The token generated by the `requestToken` call and emailed to the user.
+        pub token: String,
+    }
+
+    #[derive(PartialEq, Eq)]
+    response: {
+        /// Whether the validation was successful or not.
+        pub success: bool,
+    }
+}
+
+impl Request {
+    /// Creates a new `Request` with the given session ID, client secret and token.
+    pub fn new(sid: String, client_secret: String, token: String) -> Self {
+        Self { sid, client_secret, token }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given success flag.
+    pub fn new(success: bool) -> Self {
+        Self { success }
+    }
+}
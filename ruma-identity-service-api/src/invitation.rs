@@ -0,0 +1,3 @@
+//! Endpoints to invite third party identifiers that aren't bound to a Matrix user ID yet.
+
+pub mod store_invitation;
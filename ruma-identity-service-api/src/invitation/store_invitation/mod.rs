@@ -0,0 +1,3 @@
+//! Endpoint to store pending invitations to a room for a third party identifier.
+
+pub mod v2;
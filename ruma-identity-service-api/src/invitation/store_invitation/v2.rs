@@ -0,0 +1,184 @@
+//! [POST /_matrix/identity/v2/store-invite](https://matrix.org/docs/spec/identity_service/r0.3.0#post-matrix-identity-v2-store-invite)
+
+use ruma_api::ruma_api;
+use ruma_common::thirdparty::Medium;
+use ruma_events::room::third_party_invite::PublicKey;
+use ruma_identifiers::{RoomAliasId, RoomId, UserId};
+
+ruma_api! {
+    metadata: {
+        description: "Stores pending invitations to a room for a third party identifier.",
+        method: POST,
+        name: "store_invitation",
+        path: "/_matrix/identity/v2/store-invite",
+        rate_limited: false,
+        requires_authentication: true,
+    }
+
+    #[derive(PartialEq, Eq)]
+    request: {
+        /// The type of the third party identifier for the invited user.
+        ///
+        /// Currently, only `Medium::Email` is supported.
+        pub medium: Medium,
+
+        /// The email address of the invited user.
+        pub address: String,
+
+        /// The Matrix room ID to which the user is invited.
+        pub room_id: RoomId,
+
+        /// The Matrix user ID of the inviting user.
+        pub sender: UserId,
+
+        /// The Matrix room alias for the room to which the user is invited.
+        ///
+        /// This should be retrieved from the `m.room.canonical_alias` state event.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub room_alias: Option<RoomAliasId>,
+
+        /// The content URI for the room to which the user is invited.
+        ///
+        /// This should be retrieved from the `m.room.avatar` state event.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub room_avatar_url: Option<String>,
+
+        /// The `join_rule` for the room to which the user is invited.
+        ///
+        /// This should be retrieved from the `m.room.join_rules` state event.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub room_join_rules: Option<String>,
+
+        /// The name of the room to which the user is invited.
+        ///
+        /// This should be retrieved from the `m.room.name` state event.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub room_name: Option<String>,
+
+        /// The display name of the user ID initiating the invite.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub sender_display_name: Option<String>,
+
+        /// The content URI for the avatar of the user ID initiating the invite.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub sender_avatar_url: Option<String>,
+    }
+
+    #[derive(PartialEq, Eq)]
+    response: {
+        /// The generated token.
+        ///
+        /// Must be a string consisting of the characters `[0-9a-zA-Z.=_-]`. Its length must not
+        /// exceed 255 characters and it must not be empty.
+        pub token: String,
+
+        /// The public keys the invitation can be verified with, for the `public_keys` field of
+        /// the `m.room.third_party_invite` event.
+        pub public_keys: Vec<PublicKey>,
+
+        /// The generated (redacted) display name, for the `display_name` field of the
+        /// `m.room.third_party_invite` event.
+        ///
+        /// An example is `f...@b...`.
+        pub display_name: String,
+    }
+}
+
+impl Request {
+    /// Creates a new `Request` inviting the given email address to a room.
+    pub fn email(address: String, room_id: RoomId, sender: UserId) -> Self {
+        Self {
+            medium: Medium::Email,
+            address,
+            room_id,
+            sender,
+            room_alias: None,
+            room_avatar_url: None,
+            room_join_rules: None,
+            room_name: None,
+            sender_display_name: None,
+            sender_avatar_url: None,
+        }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given token, public keys and display name.
+    pub fn new(token: String, public_keys: Vec<PublicKey>, display_name: String) -> Self {
+        Self { token, public_keys, display_name }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::{OutgoingRequest as _, OutgoingResponse as _};
+    use ruma_events::room::third_party_invite::PublicKey;
+    use ruma_identifiers::{room_alias_id, room_id, user_id};
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::{Request, Response};
+
+    #[test]
+    fn serialize_email_request() {
+        let mut req = Request::email(
+            "alice@example.org".into(),
+            room_id!("!room:example.org"),
+            user_id!("@bob:example.org"),
+        );
+        req.room_alias = Some(room_alias_id!("#room:example.org"));
+        req.room_name = Some("Example Room".into());
+
+        let http_req =
+            req.try_into_http_request::<Vec<u8>>("https://id.example.org", Some("tok")).unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({
+                "medium": "email",
+                "address": "alice@example.org",
+                "room_id": "!room:example.org",
+                "sender": "@bob:example.org",
+                "room_alias": "#room:example.org",
+                "room_name": "Example Room",
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_response() {
+        let public_keys = vec![
+            PublicKey {
+                key_validity_url: Some(
+                    "https://id.example.org/_matrix/identity/v2/pubkey/isvalid".into(),
+                ),
+                public_key: "serverPublicKeyBase64".into(),
+            },
+            PublicKey {
+                key_validity_url: Some(
+                    "https://id.example.org/_matrix/identity/v2/pubkey/ephemeral/isvalid".into(),
+                ),
+                public_key: "ephemeralPublicKeyBase64".into(),
+            },
+        ];
+
+        let http_res = Response::new("sometoken".into(), public_keys, "a...@e...".into())
+            .try_into_http_response::<Vec<u8>>()
+            .unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_res.body()).unwrap(),
+            json!({
+                "token": "sometoken",
+                "public_keys": [
+                    {
+                        "key_validity_url": "https://id.example.org/_matrix/identity/v2/pubkey/isvalid",
+                        "public_key": "serverPublicKeyBase64",
+                    },
+                    {
+                        "key_validity_url": "https://id.example.org/_matrix/identity/v2/pubkey/ephemeral/isvalid",
+                        "public_key": "ephemeralPublicKeyBase64",
+                    },
+                ],
+                "display_name": "a...@e...",
+            })
+        );
+    }
+}
@@ -1 +1,8 @@
+//! (De)serializable types for the Matrix Identity Service API.
 
+#![warn(missing_docs)]
+#![allow(clippy::new_without_default)]
+
+pub mod association;
+pub mod invitation;
+pub mod lookup;
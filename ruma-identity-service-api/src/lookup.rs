@@ -0,0 +1,20 @@
+//! Endpoints to look up the Matrix user IDs bound to third party identifiers.
+
+use ruma_serde::string_enum;
+
+pub mod get_hash_parameters;
+pub mod lookup_3pid;
+
+string_enum! {
+    /// The algorithm used for hashing the addresses of a lookup.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum IdentifierHashingAlgorithm {
+        /// The SHA-256 hash of `"<address> <medium> <pepper>"`, encoded as unpadded URL-safe
+        /// base64.
+        Sha256 => "sha256",
+
+        /// No hashing: the addresses are sent as `"<address> <medium>"` in plain text.
+        None => "none",
+    }
+}
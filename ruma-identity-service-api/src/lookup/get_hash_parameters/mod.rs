@@ -0,0 +1,3 @@
+//! Endpoint to get the parameters for hashing third party identifiers before a lookup.
+
+pub mod v2;
@@ -0,0 +1,70 @@
+//! [GET /_matrix/identity/v2/hash_details](https://matrix.org/docs/spec/identity_service/r0.3.0#get-matrix-identity-v2-hash-details)
+
+use ruma_api::ruma_api;
+
+use crate::lookup::IdentifierHashingAlgorithm;
+
+ruma_api! {
+    metadata: {
+        description: "Gets parameters for hashing identifiers from the server.",
+        method: GET,
+        name: "get_hash_parameters",
+        path: "/_matrix/identity/v2/hash_details",
+        rate_limited: false,
+        requires_authentication: true,
+    }
+
+    #[derive(PartialEq, Eq)]
+    request: {}
+
+    #[derive(PartialEq, Eq)]
+    response: {
+        /// The pepper the client must use in hashing identifiers, and must supply to the
+        /// `lookup` endpoint when performing lookups.
+        ///
+        /// Servers should rotate this string often.
+        pub lookup_pepper: String,
+
+        /// The algorithms the server supports.
+        ///
+        /// Must contain at least `sha256`.
+        pub algorithms: Vec<IdentifierHashingAlgorithm>,
+    }
+}
+
+impl Request {
+    /// Creates an empty `Request`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given pepper and supported algorithms.
+    pub fn new(lookup_pepper: String, algorithms: Vec<IdentifierHashingAlgorithm>) -> Self {
+        Self { lookup_pepper, algorithms }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_api::OutgoingResponse as _;
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::Response;
+    use crate::lookup::IdentifierHashingAlgorithm;
+
+    #[test]
+    fn serialize_response() {
+        let res = Response::new(
+            "matrixrocks".into(),
+            vec![IdentifierHashingAlgorithm::None, IdentifierHashingAlgorithm::Sha256],
+        );
+
+        let http_res = res.try_into_http_response::<Vec<u8>>().unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_res.body()).unwrap(),
+            json!({ "lookup_pepper": "matrixrocks", "algorithms": ["none", "sha256"] })
+        );
+    }
+}
@@ -0,0 +1,3 @@
+//! Endpoint to look up the Matrix user IDs bound to a set of third party identifiers.
+
+pub mod v2;
@@ -0,0 +1,130 @@
+//! [POST /_matrix/identity/v2/lookup](https://matrix.org/docs/spec/identity_service/r0.3.0#post-matrix-identity-v2-lookup)
+
+use std::collections::BTreeMap;
+
+use ruma_api::ruma_api;
+use ruma_identifiers::UserId;
+
+use crate::lookup::IdentifierHashingAlgorithm;
+
+ruma_api! {
+    metadata: {
+        description: "Looks up the Matrix user IDs which have bound the given 3PIDs.",
+        method: POST,
+        name: "lookup_3pid",
+        path: "/_matrix/identity/v2/lookup",
+        rate_limited: false,
+        requires_authentication: true,
+    }
+
+    #[derive(PartialEq, Eq)]
+    request: {
+        /// The algorithm the client is using to encode the `addresses`.
+        ///
+        /// This should be one of the available options from the `hash_details` endpoint.
+        pub algorithm: IdentifierHashingAlgorithm,
+
+        /// The pepper from the `hash_details` endpoint.
+        pub pepper: String,
+
+        /// The addresses to look up, encoded according to the `algorithm` and `pepper`.
+        pub addresses: Vec<String>,
+    }
+
+    #[derive(PartialEq, Eq)]
+    response: {
+        /// Any applicable mappings of `addresses` to Matrix user IDs.
+        ///
+        /// Addresses which do not have associations are not included.
+        pub mappings: BTreeMap<String, UserId>,
+    }
+}
+
+impl Request {
+    /// Creates a new `Request` with the given algorithm, pepper and encoded addresses.
+    pub fn new(
+        algorithm: IdentifierHashingAlgorithm,
+        pepper: String,
+        addresses: Vec<String>,
+    ) -> Self {
+        Self { algorithm, pepper, addresses }
+    }
+}
+
+impl Response {
+    /// Creates a new `Response` with the given mappings.
+    pub fn new(mappings: BTreeMap<String, UserId>) -> Self {
+        Self { mappings }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use ruma_api::{OutgoingRequest as _, OutgoingResponse as _};
+    use ruma_identifiers::user_id;
+    use serde_json::{from_slice as from_json_slice, json, Value as JsonValue};
+
+    use super::{Request, Response};
+    use crate::lookup::IdentifierHashingAlgorithm;
+
+    #[test]
+    fn serialize_hashed_request() {
+        let req = Request::new(
+            IdentifierHashingAlgorithm::Sha256,
+            "matrixrocks".into(),
+            vec!["4kenr7N9drpCJ4AfalmlGQVsOn3o2RHjkADUpXJWZUc".into()],
+        );
+
+        let http_req =
+            req.try_into_http_request::<Vec<u8>>("https://id.example.org", Some("tok")).unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({
+                "algorithm": "sha256",
+                "pepper": "matrixrocks",
+                "addresses": ["4kenr7N9drpCJ4AfalmlGQVsOn3o2RHjkADUpXJWZUc"],
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_plaintext_request() {
+        let req = Request::new(
+            IdentifierHashingAlgorithm::None,
+            "matrixrocks".into(),
+            vec!["alice@example.org email".into()],
+        );
+
+        let http_req =
+            req.try_into_http_request::<Vec<u8>>("https://id.example.org", Some("tok")).unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_req.body()).unwrap(),
+            json!({
+                "algorithm": "none",
+                "pepper": "matrixrocks",
+                "addresses": ["alice@example.org email"],
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_response() {
+        let mut mappings = BTreeMap::new();
+        mappings.insert(
+            "4kenr7N9drpCJ4AfalmlGQVsOn3o2RHjkADUpXJWZUc".to_owned(),
+            user_id!("@alice:example.org"),
+        );
+
+        let http_res = Response::new(mappings).try_into_http_response::<Vec<u8>>().unwrap();
+        assert_eq!(
+            from_json_slice::<JsonValue>(http_res.body()).unwrap(),
+            json!({
+                "mappings": {
+                    "4kenr7N9drpCJ4AfalmlGQVsOn3o2RHjkADUpXJWZUc": "@alice:example.org",
+                },
+            })
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::fmt::{self, Formatter};
+use core::fmt::{self, Formatter};
 
 use serde::{
     de::{Deserialize, Deserializer, MapAccess, Visitor},
@@ -9,7 +9,7 @@ use serde::{
 ///
 /// This type is used in a few places where the Matrix specification requires an empty JSON object,
 /// but it's wasteful to represent it as a `BTreeMap` in Rust code.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Empty;
 
 impl Serialize for Empty {
@@ -57,7 +57,7 @@ impl<'de> Deserialize<'de> for Empty {
 ///
 /// To be used as `#[serde(with = "vec_as_map_of_empty")]`.
 pub mod vec_as_map_of_empty {
-    use std::collections::BTreeMap;
+    use alloc::{collections::BTreeMap, vec::Vec};
 
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -1,6 +1,8 @@
 //! De-/serialization functions to and from json strings, allows the type to be used as a query
 //! string.
 
+use alloc::string::String;
+
 use serde::{
     de::{Deserialize, DeserializeOwned, Deserializer, Error as _},
     ser::{Error as _, Serialize, Serializer},
@@ -1,15 +1,34 @@
 //! De-/serialization helpers for other ruma crates
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::string::String;
+
 use serde::de::{Deserialize, IntoDeserializer};
 
 pub mod duration;
 pub mod empty;
+#[cfg(feature = "serde_json")]
 pub mod json_string;
+pub mod string_enum;
+#[cfg(feature = "serde_json")]
 pub mod test;
+#[cfg(feature = "std")]
 pub mod time;
+#[cfg(feature = "std")]
 pub mod urlencoded;
 
 pub use empty::vec_as_map_of_empty;
+pub use string_enum::StringEnum;
+
+// Re-exports used by macro-generated code.
+#[doc(hidden)]
+pub mod exports {
+    pub use alloc::{string::String, vec, vec::Vec};
+    pub use serde;
+}
 
 /// Check whether a value is equal to its default value.
 pub fn is_default<T: Default + PartialEq>(val: &T) -> bool {
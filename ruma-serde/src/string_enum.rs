@@ -0,0 +1,183 @@
+//! Helpers for string-backed enums.
+
+use alloc::{string::String, vec::Vec};
+use core::{
+    convert::Infallible,
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Deserializer};
+
+/// An enum whose variants are each represented by a single string on the wire.
+///
+/// Implemented by the `string_enum!` macro. Every value of such an enum roundtrips through its
+/// `Display` and `FromStr` implementations, as well as through serialization.
+pub trait StringEnum:
+    AsRef<str> + Display + FromStr<Err = Infallible> + From<String> + Debug + PartialEq + Sized
+{
+    /// All variants known to the enum, i.e. everything except the custom variant.
+    fn known_variants() -> Vec<Self>;
+}
+
+/// Deserialize a string-backed enum from a string.
+///
+/// Used by the code generated by the `string_enum!` macro.
+pub fn deserialize_from_string<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: From<String>,
+{
+    String::deserialize(deserializer).map(T::from)
+}
+
+/// Generate a string-backed enum along with its `as_str` method and implementations of
+/// `AsRef<str>`, `Display`, `FromStr`, `From<&str>`, `From<String>`, `Serialize`, `Deserialize`
+/// and `StringEnum`.
+///
+/// Every variant is paired with its wire representation, optionally followed by aliases that are
+/// accepted when parsing. A hidden `_Custom(String)` variant is added to hold any other string,
+/// so parsing never fails.
+///
+/// ```
+/// ruma_serde::string_enum! {
+///     /// The state of a door.
+///     #[derive(Clone, Debug, PartialEq, Eq)]
+///     pub enum DoorState {
+///         /// The door is open.
+///         Open => "open",
+///
+///         /// The door is closed.
+///         Closed => "closed" | "shut",
+///     }
+/// }
+///
+/// assert_eq!(DoorState::Closed.as_str(), "closed");
+/// assert_eq!("shut".parse::<DoorState>().unwrap(), DoorState::Closed);
+/// assert_eq!(DoorState::from("ajar").to_string(), "ajar");
+/// ```
+#[macro_export]
+macro_rules! string_enum {
+    (
+        $( #[$enum_attr:meta] )*
+        $vis:vis enum $name:ident {
+            $(
+                $( #[$variant_attr:meta] )*
+                $variant:ident => $string:literal $( | $alias:literal )*
+            ),* $(,)?
+        }
+    ) => {
+        $( #[$enum_attr] )*
+        $vis enum $name {
+            $(
+                $( #[$variant_attr] )*
+                $variant,
+            )*
+
+            #[doc(hidden)]
+            _Custom($crate::exports::String),
+        }
+
+        impl $name {
+            /// Creates a string slice from this value.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $( Self::$variant => $string, )*
+                    Self::_Custom(s) => s,
+                }
+            }
+        }
+
+        impl ::core::convert::AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                self.as_str()
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl ::core::convert::From<&str> for $name {
+            fn from(s: &str) -> Self {
+                match s {
+                    $( $string $( | $alias )* => Self::$variant, )*
+                    _ => Self::_Custom(s.into()),
+                }
+            }
+        }
+
+        impl ::core::convert::From<$crate::exports::String> for $name {
+            fn from(s: $crate::exports::String) -> Self {
+                match s.as_str() {
+                    $( $string $( | $alias )* => Self::$variant, )*
+                    _ => Self::_Custom(s),
+                }
+            }
+        }
+
+        impl ::core::str::FromStr for $name {
+            type Err = ::core::convert::Infallible;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(Self::from(s))
+            }
+        }
+
+        impl $crate::exports::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: $crate::exports::serde::Serializer,
+            {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> $crate::exports::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: $crate::exports::serde::Deserializer<'de>,
+            {
+                $crate::string_enum::deserialize_from_string(deserializer)
+            }
+        }
+
+        impl $crate::string_enum::StringEnum for $name {
+            fn known_variants() -> $crate::exports::Vec<Self> {
+                $crate::exports::vec![ $( Self::$variant ),* ]
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::assert_string_enum_roundtrip;
+
+    crate::string_enum! {
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        enum Fruit {
+            Apple => "apple",
+            BloodOrange => "blood_orange" | "blood-orange",
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        assert_string_enum_roundtrip::<Fruit>();
+    }
+
+    #[test]
+    fn aliases() {
+        assert_eq!("blood-orange".parse::<Fruit>().unwrap(), Fruit::BloodOrange);
+        assert_eq!(Fruit::BloodOrange.to_string(), "blood_orange");
+    }
+
+    #[test]
+    fn custom() {
+        assert_eq!(Fruit::from("pear"), Fruit::_Custom("pear".into()));
+        assert_eq!(serde_json::to_value(Fruit::from("pear")).unwrap(), "pear");
+    }
+}
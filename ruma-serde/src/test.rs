@@ -1,9 +1,12 @@
 //! Helpers for tests
 
-use std::fmt::Debug;
+use alloc::{borrow::ToOwned, string::ToString};
+use core::fmt::Debug;
 
 use serde::{de::DeserializeOwned, Serialize};
 
+use crate::string_enum::StringEnum;
+
 pub fn serde_json_eq<T>(de: T, se: serde_json::Value)
 where
     T: Clone + Debug + PartialEq + Serialize + DeserializeOwned,
@@ -11,3 +14,26 @@ where
     assert_eq!(se, serde_json::to_value(de.clone()).unwrap());
     assert_eq!(de, serde_json::from_value(se).unwrap());
 }
+
+/// Assert that every known variant of the given string-backed enum roundtrips through its
+/// `Display` and `FromStr` implementations as well as through JSON, and that unknown strings are
+/// preserved as-is.
+pub fn assert_string_enum_roundtrip<T>()
+where
+    T: StringEnum + Serialize + DeserializeOwned,
+{
+    for variant in T::known_variants() {
+        let string = variant.to_string();
+        assert_eq!(string, variant.as_ref());
+        assert_eq!(string.parse::<T>().unwrap(), variant);
+
+        let json = serde_json::to_value(&variant).unwrap();
+        assert_eq!(json, serde_json::Value::String(string));
+        assert_eq!(serde_json::from_value::<T>(json).unwrap(), variant);
+    }
+
+    let custom = T::from("org.example.custom".to_owned());
+    assert!(!T::known_variants().contains(&custom));
+    assert_eq!(custom.to_string(), "org.example.custom");
+    assert_eq!("org.example.custom".parse::<T>().unwrap(), custom);
+}
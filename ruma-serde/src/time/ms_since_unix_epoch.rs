@@ -14,14 +14,15 @@ use serde::{
 
 /// Serialize a SystemTime.
 ///
-/// Will fail if integer is greater than the maximum integer that can be unambiguously represented
-/// by an f64.
+/// Will fail if the time is before the UNIX epoch or if integer is greater than the maximum integer
+/// that can be unambiguously represented by an f64.
 pub fn serialize<S>(time: &SystemTime, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    // If this unwrap fails, the system this is executed is completely broken.
-    let time_since_epoch = time.duration_since(UNIX_EPOCH).unwrap();
+    let time_since_epoch = time
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| S::Error::custom("time is before the UNIX epoch"))?;
     match UInt::try_from(time_since_epoch.as_millis()) {
         Ok(uint) => uint.serialize(serializer),
         Err(err) => Err(S::Error::custom(err)),
@@ -68,4 +69,40 @@ mod tests {
         let request = SystemTimeTest { timestamp: UNIX_EPOCH + Duration::new(2, 0) };
         assert_eq!(serde_json::to_value(&request).unwrap(), json!({ "timestamp": 2000 }));
     }
+
+    #[test]
+    fn test_roundtrip_preserves_millis() {
+        let json = json!({ "timestamp": 1_432_804_485_886_u64 });
+
+        let parsed = serde_json::from_value::<SystemTimeTest>(json.clone()).unwrap();
+        assert_eq!(parsed.timestamp, UNIX_EPOCH + Duration::from_millis(1_432_804_485_886));
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), json);
+    }
+
+    #[test]
+    fn test_deserialize_max_safe_integer() {
+        let json = json!({ "timestamp": 9_007_199_254_740_991_u64 });
+        assert!(serde_json::from_value::<SystemTimeTest>(json).is_ok());
+
+        let json = json!({ "timestamp": 9_007_199_254_740_992_u64 });
+        assert!(serde_json::from_value::<SystemTimeTest>(json).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_negative() {
+        let json = json!({ "timestamp": -1 });
+        assert!(serde_json::from_value::<SystemTimeTest>(json).is_err());
+    }
+
+    #[test]
+    fn test_serialize_before_epoch() {
+        let request = SystemTimeTest { timestamp: UNIX_EPOCH - Duration::from_millis(1) };
+        assert!(serde_json::to_value(&request).is_err());
+    }
+
+    #[test]
+    fn test_serialize_too_large() {
+        let request = SystemTimeTest { timestamp: UNIX_EPOCH + Duration::from_millis(1 << 53) };
+        assert!(serde_json::to_value(&request).is_err());
+    }
 }
@@ -7,9 +7,21 @@
 //! Depending on which parts of Matrix are relevant to you, activate the
 //! following features:
 //!
+//! * `events` for the event types (`ruma::events`)
+//! * `signatures` for signing and verification of JSON and events (`ruma::signatures`)
 //! * `client-api` for the client-server API
 //! * `federation-api` for the server-server (federation) API
 //! * `appservice-api` for the application service API
+//! * `identity-service-api` for the identity service API
+//! * `push-gateway-api` for the push gateway API
+//!
+//! The API features imply the `events` and / or `signatures` features where they need them, so
+//! types like `EventType`, `Raw` or the identifier types are always the very same items no matter
+//! whether they are accessed through `ruma::events`, `ruma::api::client` or the crate root.
+//!
+//! Features of the API crates that are not part of the Matrix specification yet can be enabled
+//! through `client-api-unstable-pre-spec` and `federation-api-unstable-pre-spec`. Each of them
+//! only implies the API feature it belongs to. The older `unstable-pre-spec` feature enables both.
 
 #![deny(missing_docs)]
 
@@ -19,8 +31,8 @@ pub use ruma_identifiers as identifiers;
 
 pub use ruma_identifiers::{
     device_id, device_key_id, event_id, room_alias_id, room_id, room_version_id, server_key_id,
-    server_name, user_id, DeviceId, DeviceKeyAlgorithm, DeviceKeyId, EventId, RoomAliasId, RoomId,
-    RoomIdOrAliasId, RoomVersionId, ServerKeyAlgorithm, ServerKeyId, ServerName, UserId,
+    server_name, user_id, DeviceId, DeviceKeyAlgorithm, DeviceKeyId, EventId, MxcUri, RoomAliasId,
+    RoomId, RoomIdOrAliasId, RoomVersionId, ServerKeyAlgorithm, ServerKeyId, ServerName, UserId,
 };
 
 #[cfg(feature = "ruma-events")]
@@ -45,10 +57,10 @@ pub mod api {
     #[cfg(feature = "ruma-federation-api")]
     #[doc(inline)]
     pub use ruma_federation_api as federation;
-    #[cfg(feature = "identity-service-api")]
+    #[cfg(feature = "ruma-identity-service-api")]
     #[doc(inline)]
     pub use ruma_identity_service_api as identity_service;
-    #[cfg(feature = "identity-service-api")]
+    #[cfg(feature = "ruma-push-gateway-api")]
     #[doc(inline)]
     pub use ruma_push_gateway_api as push_gateway;
 }
@@ -0,0 +1,36 @@
+//! Makes sure that the types shared between the re-exported crates are the same items, so they
+//! can be passed from one part of the facade to another without conversions.
+
+#![cfg(feature = "client-api")]
+
+use ruma::{
+    api::client::r0::{message::send_message_event, sync::sync_events::Timeline},
+    events::{AnySyncRoomEvent, EventType},
+    room_id, Raw, RoomId,
+};
+use serde_json::value::to_raw_value as to_raw_json_value;
+
+#[test]
+fn client_api_uses_reexported_event_type_and_identifiers() {
+    let room_id: RoomId = room_id!("!room:example.org");
+    let event_type: ruma::events::EventType = EventType::RoomMessage;
+
+    let request = send_message_event::Request {
+        room_id: &room_id,
+        event_type,
+        txn_id: "1",
+        data: to_raw_json_value(&serde_json::json!({})).unwrap(),
+    };
+
+    let _: &ruma::identifiers::RoomId = request.room_id;
+}
+
+#[test]
+fn client_api_uses_reexported_raw() {
+    fn events(timeline: Timeline) -> Vec<Raw<AnySyncRoomEvent>> {
+        timeline.events
+    }
+
+    let timeline: Timeline = serde_json::from_value(serde_json::json!({ "events": [] })).unwrap();
+    assert!(events(timeline).is_empty());
+}